@@ -1,5 +1,5 @@
 use rash_core::jinja::lookup::LOOKUPS;
-use rash_core::modules::MODULES;
+use rash_core::modules::{Capabilities, CheckModeSupport, MODULES};
 
 use std::sync::LazyLock;
 
@@ -187,6 +187,54 @@ fn format_schema(schema: &Schema) -> String {
     format!("{table}")
 }
 
+fn format_capabilities(capabilities: &Capabilities) -> String {
+    let support = |supported: bool| if supported { "full" } else { "none" };
+
+    let mut table = Table::new();
+    table.set_format(*FORMAT);
+    table.set_titles(row!["Attribute", "Support", "Description"]);
+    table.add_row(row![
+        "check_mode",
+        capabilities.check_mode,
+        match capabilities.check_mode {
+            CheckModeSupport::Full => "Reports what would change without changing anything.",
+            CheckModeSupport::Partial =>
+                "Reports what would change just for some params, others are executed as usual.",
+            CheckModeSupport::Always => "Executed as usual, it never changes anything.",
+            CheckModeSupport::None => "Skipped in check mode.",
+        }
+    ]);
+    table.add_row(row![
+        "diff_mode",
+        support(capabilities.diff),
+        if capabilities.diff {
+            "Shows the changes with `--diff`."
+        } else {
+            "Does not show changes with `--diff`."
+        }
+    ]);
+    table.add_row(row![
+        "become",
+        support(capabilities.r#become),
+        if capabilities.r#become {
+            "Executed as `become_user`."
+        } else {
+            "Executed as the current user, its tasks are executed as `become_user`."
+        }
+    ]);
+    table.add_row(row![
+        "async",
+        support(capabilities.r#async),
+        if capabilities.r#async {
+            "Can be executed in background with `async`."
+        } else {
+            "Cannot be executed with `async`."
+        }
+    ]);
+
+    format!("{table}")
+}
+
 fn replace_matches(captures: Vec<(Match, Option<String>, String)>, ch: &mut Chapter) {
     for capture in captures.iter() {
         if capture.2 == "include_module_index" {
@@ -217,6 +265,9 @@ indent: true
 
 {{$include_doc {{{{#include ../../rash_core/src/modules/{name}.rs:module}}}}}}
 
+## Attributes
+
+{attributes}
 ## Parameters
 
 {parameters}
@@ -226,6 +277,7 @@ indent: true
                     name = name,
                     weight = new_section_number.first().unwrap() * 1000
                         + (ch.sub_items.len() + 1) as u32,
+                    attributes = format_capabilities(&module.1.get_capabilities()),
                     parameters = parameters,
                 )
                 .to_owned();
@@ -631,6 +683,34 @@ mod weight_overflow_test {
     }
 }
 
+#[cfg(test)]
+mod capabilities_test {
+    use super::*;
+
+    #[test]
+    fn test_format_capabilities() {
+        let output = format_capabilities(&Capabilities {
+            check_mode: CheckModeSupport::None,
+            diff: true,
+            ..Default::default()
+        });
+
+        assert!(output.starts_with("| Attribute "));
+        assert!(output.contains("| check_mode | none    | Skipped in check mode."));
+        assert!(output.contains("| diff_mode  | full    | Shows the changes with `--diff`."));
+        assert!(output.contains("| become     | full    | Executed as `become_user`."));
+        assert!(output.contains("| async      | none    | Cannot be executed with `async`."));
+    }
+
+    #[test]
+    fn test_format_capabilities_control_flow() {
+        let output = format_capabilities(&MODULES.get("block").unwrap().get_capabilities());
+
+        assert!(output.contains("| check_mode | full    |"));
+        assert!(output.contains("| become     | none    |"));
+    }
+}
+
 #[cfg(test)]
 mod schema_debug_test {
     use super::*;
@@ -1,24 +1,37 @@
-use rash_core::context::{BecomeMethod, Context, GlobalParams};
+use rash_core::connection::Connection;
+use rash_core::context::{BecomeMethod, Context, ExecControls, GlobalParams};
 use rash_core::docopt;
 use rash_core::error::{Error, ErrorKind};
+use rash_core::events;
+use rash_core::job::{JobId, JobInfo, JobStore, get_ttl};
+use rash_core::journal::Journal;
 use rash_core::logger;
 use rash_core::modules::add_module_search_path;
+use rash_core::push::{self, Inventory, PushOptions};
+use rash_core::stats;
 use rash_core::task::{
-    InternalTaskData, get_internal_result_path, parse_file, parse_file_with_handlers,
+    Debugger, InternalTaskData, StartAtTask, TaskExecResult, get_internal_result_path, lint,
+    list_tags, parse_file, parse_file_with_handlers, parse_header,
 };
 use rash_core::vars::builtin::Builtins;
 use rash_core::vars::env;
+use rash_core::vault;
 
 use rpassword::read_password;
 use std::error::Error as StdError;
-use std::fs::{File, read_to_string};
+use std::ffi::OsString;
+use std::fs::{self, File, read_to_string};
 use std::io::Write;
 use std::path::{Path, PathBuf};
-use std::process::exit;
+use std::process::{self, exit};
 
 use clap::error::ErrorKind as ClapErrorKind;
-use clap::{ArgAction, CommandFactory, Parser, crate_authors, crate_description, crate_version};
+use clap::{
+    ArgAction, CommandFactory, Parser, Subcommand, crate_authors, crate_description, crate_version,
+};
+use glob::Pattern;
 use minijinja::{Value, context};
+use serde_norway::Value as YamlValue;
 
 #[macro_use]
 extern crate log;
@@ -40,12 +53,17 @@ where
     Ok((s[..pos].parse()?, s[pos + 1..].parse()?))
 }
 
+fn parse_connection(s: &str) -> Result<String, Error> {
+    s.parse::<Connection>().map(|_| s.to_owned())
+}
+
 #[derive(Parser, Debug)]
 #[command(
     name="rash",
     about = crate_description!(),
     version = crate_version!(),
     author = crate_authors!("\n"),
+    args_conflicts_with_subcommands = true,
 )]
 struct Cli {
     /// run operations with become (does not imply password prompting)
@@ -79,12 +97,63 @@ struct Cli {
     /// Verbose mode (-vv for more)
     #[arg(short, long, action = ArgAction::Count)]
     verbose: u8,
+    /// Only run tasks tagged with these values (comma separated)
+    #[arg(short, long, action = ArgAction::Append, value_delimiter = ',')]
+    tags: Vec<String>,
+    /// Only run tasks whose tags do not match these values (comma separated)
+    #[arg(long, action = ArgAction::Append, value_delimiter = ',')]
+    skip_tags: Vec<String>,
+    /// List all available tags and exit
+    #[arg(long)]
+    list_tags: bool,
+    /// List tasks that would be executed with their index and location, and exit
+    #[arg(long)]
+    list_tasks: bool,
+    /// Skip tasks until the first one whose name matches this glob pattern
+    #[arg(long, value_name = "NAME")]
+    start_at_task: Option<String>,
+    /// Ask for confirmation before executing each task: (N)o/(y)es/(c)ontinue
+    #[arg(long)]
+    step: bool,
+    /// Open an interactive debugger when a task fails, unless it sets `debugger: never`
+    #[arg(long)]
+    debugger: bool,
+    /// Continue the last failed run of the script from the task that failed
+    #[arg(long, conflicts_with_all = ["start_at_task", "list_tasks", "inventory"])]
+    resume: bool,
+    /// Check the script without executing it and exit
+    #[arg(long)]
+    syntax_check: bool,
+    /// Print a recap with the number of tasks by result at the end of the run
+    #[arg(long)]
+    recap: bool,
+    /// Print the recap and the slowest tasks with their durations
+    #[arg(long)]
+    profile: bool,
+    /// Execute tasks in this target: local, chroot:<PATH>, docker:<CONTAINER>,
+    /// podman:<CONTAINER>, nsenter:<PID> or systemd-nspawn:<DIRECTORY>
+    #[arg(long, value_parser = parse_connection)]
+    connection: Option<String>,
+    /// File with the password to decrypt vault encrypted files
+    #[arg(long, value_name = "PATH")]
+    vault_password_file: Option<PathBuf>,
+    /// Inventory file with the hosts where the script is executed over SSH
+    #[arg(short, long, value_name = "PATH", conflicts_with_all = ["ask_become_pass", "step", "debugger"])]
+    inventory: Option<PathBuf>,
+    /// Only run in hosts whose name matches these glob patterns (comma separated)
+    #[arg(long, value_delimiter = ',', requires = "inventory")]
+    hosts: Vec<String>,
+    /// Max number of hosts where the script is executed at the same time
+    #[arg(long, default_value_t = 5, requires = "inventory")]
+    forks: usize,
     /// Inline script to be executed.
     /// If provided, <SCRIPT_FILE> will be used as filename in `rash.path` builtin.
     #[arg(short, long)]
     script: Option<String>,
     /// Path to the script file to be executed.
     /// If provided, this file will be read and used as the script content.
+    /// Scripts named as a command, like `lint`, are executed just if the command arguments are
+    /// not valid, use `./lint` instead.
     script_file: Option<String>,
     /// Additional args to be accessible rash scripts.
     ///
@@ -95,6 +164,80 @@ struct Cli {
     /// Internal task file for sudo become execution (hidden, not for direct use)
     #[arg(long, hide = true)]
     internal_task: Option<PathBuf>,
+    /// Vars file for push mode execution (hidden, not for direct use)
+    #[arg(long, hide = true)]
+    internal_vars: Option<PathBuf>,
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Check script files without executing them.
+    ///
+    /// Problems are reported with their file and line, exiting with error if any is found.
+    Lint {
+        /// Paths to the script files to be checked.
+        #[arg(required = true)]
+        script_files: Vec<PathBuf>,
+    },
+    /// Manage files encrypted at rest.
+    ///
+    /// The password is read from `--vault-password-file`, `RASH_VAULT_PASSWORD`,
+    /// `RASH_VAULT_PASSWORD_FILE` or prompted, in that order.
+    Vault {
+        /// File with the vault password
+        #[arg(long, value_name = "PATH", global = true)]
+        vault_password_file: Option<PathBuf>,
+        #[command(subcommand)]
+        action: VaultCommand,
+    },
+    /// Manage background jobs started by `async` tasks.
+    ///
+    /// Finished jobs are removed after `RASH_JOBS_TTL` seconds, 7 days by default.
+    Jobs {
+        #[command(subcommand)]
+        action: JobsCommand,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum JobsCommand {
+    /// List jobs with their ID, status, elapsed seconds and command.
+    List,
+    /// Print job status, output and exit code as JSON.
+    Status { jid: JobId },
+    /// Kill a running job and its child processes.
+    Kill { jid: JobId },
+}
+
+#[derive(Subcommand, Debug)]
+enum VaultCommand {
+    /// Encrypt files in place.
+    Encrypt {
+        #[arg(required = true)]
+        files: Vec<PathBuf>,
+    },
+    /// Decrypt files in place.
+    Decrypt {
+        #[arg(required = true)]
+        files: Vec<PathBuf>,
+    },
+    /// Open a decrypted copy of the file in `$EDITOR` and encrypt the changes.
+    Edit { file: PathBuf },
+    /// Encrypt files with a new password.
+    Rekey {
+        /// File with the new vault password
+        #[arg(long, value_name = "PATH")]
+        new_vault_password_file: Option<PathBuf>,
+        #[arg(required = true)]
+        files: Vec<PathBuf>,
+    },
+    /// Print decrypted content of files.
+    View {
+        #[arg(required = true)]
+        files: Vec<PathBuf>,
+    },
 }
 
 fn log_inner_errors(e: &dyn StdError) {
@@ -152,6 +295,219 @@ fn setup_module_search_paths(script_path: &Path) {
     }
 }
 
+/// Log problems found in the script without executing it, returning true if there are any.
+fn check_script(
+    script_path: &Path,
+    script: &str,
+    args: Vec<String>,
+    environment: Vec<(String, String)>,
+) -> bool {
+    let builtins = match Builtins::new(args, script_path, false) {
+        Ok(builtins) => builtins,
+        Err(e) => crash_error(e),
+    };
+    let vars = context! {rash => &builtins, ..env::load(environment)};
+    trace!("Vars: {vars}");
+
+    let problems = lint(script_path, script, &vars);
+    problems.iter().for_each(|problem| error!("{problem}"));
+    !problems.is_empty()
+}
+
+fn lint_script_files(script_files: &[PathBuf], environment: Vec<(String, String)>) -> ! {
+    let mut has_problems = false;
+    for script_path in script_files {
+        setup_module_search_paths(script_path);
+        has_problems |= match read_to_string(script_path) {
+            Ok(script) => check_script(script_path, &script, vec![], environment.clone()),
+            Err(e) => {
+                error!("{}: {e}", script_path.display());
+                true
+            }
+        };
+    }
+    exit(has_problems as i32)
+}
+
+fn prompt_vault_password(prompt: &str, confirm: bool) -> Result<String, Error> {
+    eprint!("{prompt}: ");
+    let password = read_password()?;
+    if confirm {
+        eprint!("Confirm {}: ", prompt.to_lowercase());
+        if read_password()? != password {
+            return Err(Error::new(ErrorKind::InvalidData, "Passwords do not match"));
+        }
+    }
+    Ok(password)
+}
+
+/// Return vault password from file, environment or prompt, asking for confirmation if a new
+/// password is going to be set.
+fn get_vault_password(password_file: Option<&Path>, confirm: bool) -> Result<String, Error> {
+    match password_file {
+        Some(path) => vault::read_password_file(path),
+        None => vault::get_password().or_else(|_| prompt_vault_password("Vault password", confirm)),
+    }
+}
+
+fn read_vault_file(path: &Path, password: &str) -> Result<String, Error> {
+    let content = read_to_string(path)?;
+    vault::decrypt(&content, password)
+        .map_err(|e| Error::new(e.kind(), format!("{}: {e}", path.display())))
+}
+
+/// Write the file through a temporary file in the same directory renamed over it, so it is never
+/// left truncated. Permissions of the existing file are kept.
+fn write_vault_file(path: &Path, content: &str) -> Result<(), Error> {
+    let dir = path
+        .parent()
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    let mut temp_file = tempfile::NamedTempFile::new_in(dir)?;
+    temp_file.write_all(content.as_bytes())?;
+    temp_file.as_file().sync_all()?;
+    if let Ok(metadata) = fs::metadata(path) {
+        temp_file
+            .as_file()
+            .set_permissions(metadata.permissions())?;
+    }
+    temp_file.persist(path).map_err(|e| e.error)?;
+    Ok(())
+}
+
+fn edit_vault_file(path: &Path, password: &str) -> Result<(), Error> {
+    let content = match path.exists() {
+        true => read_vault_file(path, password)?,
+        false => String::new(),
+    };
+    let mut temp_file = tempfile::Builder::new()
+        .prefix("rash-vault-")
+        .suffix(
+            path.extension()
+                .map(|ext| format!(".{}", ext.to_string_lossy()))
+                .as_deref()
+                .unwrap_or(""),
+        )
+        .tempfile()?;
+    temp_file.write_all(content.as_bytes())?;
+    temp_file.flush()?;
+
+    let editor = std::env::var("EDITOR").unwrap_or_else(|_| "vi".to_owned());
+    let status = process::Command::new("sh")
+        .arg("-c")
+        .arg(format!("{editor} \"$1\""))
+        .arg("sh")
+        .arg(temp_file.path())
+        .status()?;
+    if !status.success() {
+        return Err(Error::new(
+            ErrorKind::SubprocessFail,
+            format!("Editor '{editor}' exited with {status}"),
+        ));
+    }
+
+    let new_content = read_to_string(temp_file.path())?;
+    if new_content != content || !path.exists() {
+        write_vault_file(path, &vault::encrypt(&new_content, password)?)?;
+    }
+    Ok(())
+}
+
+fn exec_vault_command(password_file: Option<&Path>, action: &VaultCommand) -> Result<(), Error> {
+    match action {
+        VaultCommand::Encrypt { files } => {
+            let password = get_vault_password(password_file, true)?;
+            files.iter().try_for_each(|path| {
+                let content = read_to_string(path)?;
+                if vault::is_encrypted(&content) {
+                    return Err(Error::new(
+                        ErrorKind::InvalidData,
+                        format!("{}: file is already encrypted", path.display()),
+                    ));
+                }
+                write_vault_file(path, &vault::encrypt(&content, &password)?)?;
+                Ok(())
+            })
+        }
+        VaultCommand::Decrypt { files } => {
+            let password = get_vault_password(password_file, false)?;
+            files.iter().try_for_each(|path| {
+                let content = read_vault_file(path, &password)?;
+                write_vault_file(path, &content)?;
+                Ok(())
+            })
+        }
+        VaultCommand::Edit { file } => {
+            let password = get_vault_password(password_file, !file.exists())?;
+            edit_vault_file(file, &password)
+        }
+        VaultCommand::Rekey {
+            new_vault_password_file,
+            files,
+        } => {
+            let password = get_vault_password(password_file, false)?;
+            let new_password = match new_vault_password_file {
+                Some(path) => vault::read_password_file(path)?,
+                None => prompt_vault_password("New vault password", true)?,
+            };
+            files.iter().try_for_each(|path| {
+                let content = read_vault_file(path, &password)?;
+                write_vault_file(path, &vault::encrypt(&content, &new_password)?)?;
+                Ok(())
+            })
+        }
+        VaultCommand::View { files } => {
+            let password = get_vault_password(password_file, false)?;
+            files.iter().try_for_each(|path| {
+                print!("{}", read_vault_file(path, &password)?);
+                Ok(())
+            })
+        }
+    }
+}
+
+fn get_job_json(info: &JobInfo) -> serde_json::Value {
+    serde_json::json!({
+        "jid": info.id,
+        "pid": info.pid,
+        "command": info.command,
+        "status": info.status.to_string(),
+        "finished": info.is_finished(),
+        "rc": info.rc,
+        "stdout": info.stdout,
+        "stderr": info.stderr,
+        "error": info.error,
+        "elapsed": info.elapsed.as_secs(),
+    })
+}
+
+fn exec_jobs_command(action: &JobsCommand) -> Result<(), Error> {
+    let store = JobStore::open()?;
+    match action {
+        JobsCommand::List => {
+            store.cleanup(get_ttl())?;
+            store.list()?.into_iter().try_for_each(|id| {
+                let info = store.get_info(id)?;
+                println!(
+                    "{id}\t{}\t{}s\t{}",
+                    info.status,
+                    info.elapsed.as_secs(),
+                    info.command
+                );
+                Ok(())
+            })
+        }
+        JobsCommand::Status { jid } => {
+            println!("{:#}", get_job_json(&store.get_info(*jid)?));
+            Ok(())
+        }
+        JobsCommand::Kill { jid } => {
+            println!("{:#}", get_job_json(&store.kill(*jid)?));
+            Ok(())
+        }
+    }
+}
+
 fn execute_internal_task(task_path: &Path) {
     trace!("Internal task execution from: {:?}", task_path);
 
@@ -171,14 +527,25 @@ fn execute_internal_task(task_path: &Path) {
         }
     };
 
+    internal_data
+        .secrets
+        .iter()
+        .for_each(|secret| logger::add_secret(secret));
+
     let global_params = GlobalParams::default();
 
     let task_yaml =
         serde_yaml::to_string(std::slice::from_ref(&internal_data.task)).unwrap_or_default();
 
-    let (tasks, _) = match parse_file_with_handlers(&task_yaml, &global_params) {
+    let script_path = internal_data
+        .original_path
+        .as_deref()
+        .map(Path::new)
+        .unwrap_or_else(|| Path::new("internal_task"));
+
+    let (tasks, _) = match parse_file_with_handlers(&task_yaml, script_path, &global_params) {
         Ok(parsed) => (parsed.tasks, parsed.handlers),
-        Err(_) => match parse_file(&task_yaml, &global_params) {
+        Err(_) => match parse_file(&task_yaml, script_path, &global_params) {
             Ok(tasks) => (tasks, None),
             Err(e) => {
                 error!("Failed to parse internal task: {}", e);
@@ -187,17 +554,14 @@ fn execute_internal_task(task_path: &Path) {
         },
     };
 
-    let script_path = internal_data
-        .original_path
-        .as_deref()
-        .map(Path::new)
-        .unwrap_or_else(|| Path::new("internal_task"));
-
     let result = match Builtins::new(internal_data.args.unwrap_or_default(), script_path, false) {
         Ok(builtins) => {
             let vars = context! {rash => &builtins, ..internal_data.vars};
             trace!("Internal task vars: {:?}", vars);
-            Context::new(tasks, vars, None).exec()
+            tasks
+                .first()
+                .map(|task| task.exec(vars))
+                .unwrap_or_else(|| Ok(TaskExecResult::default()))
         }
         Err(e) => {
             error!("Failed to create builtins: {}", e);
@@ -214,8 +578,7 @@ fn execute_internal_task(task_path: &Path) {
     };
 
     match result {
-        Ok(_context) => {
-            let exec_result = rash_core::task::TaskExecResult::new(false, None);
+        Ok(exec_result) => {
             let result_json = serde_json::to_string(&exec_result).unwrap_or_default();
             if let Err(e) =
                 File::create(&result_path).and_then(|mut f| f.write_all(result_json.as_bytes()))
@@ -231,8 +594,84 @@ fn execute_internal_task(task_path: &Path) {
     }
 }
 
+fn read_internal_vars(path: &Path) -> Result<Value, Error> {
+    let content = read_to_string(path)?;
+    let vars: YamlValue = serde_norway::from_str(&content)?;
+    Ok(Value::from_serialize(vars))
+}
+
+/// Return options to be passed to the remote executions in push mode.
+fn get_push_args(cli: &Cli) -> Vec<String> {
+    let flags = [
+        (cli.r#become, "--become"),
+        (cli.check, "--check"),
+        (cli.diff, "--diff"),
+    ];
+    let options = [
+        ("--become-user", Some(cli.become_user.clone())),
+        ("--become-method", Some(cli.become_method.to_string())),
+        ("--become-exe", Some(cli.become_exe.clone())),
+        ("--connection", cli.connection.clone()),
+        ("--start-at-task", cli.start_at_task.clone()),
+    ];
+    flags
+        .into_iter()
+        .filter(|(enabled, _)| *enabled)
+        .map(|(_, flag)| flag.to_owned())
+        .chain((0..cli.verbose).map(|_| "--verbose".to_owned()))
+        .chain(
+            options
+                .into_iter()
+                .filter_map(|(option, value)| value.map(|v| format!("{option}={v}"))),
+        )
+        .chain(cli.tags.iter().map(|tag| format!("--tags={tag}")))
+        .chain(cli.skip_tags.iter().map(|tag| format!("--skip-tags={tag}")))
+        .chain(
+            cli.environment
+                .iter()
+                .map(|(key, value)| format!("--environment={key}={value}")),
+        )
+        .collect()
+}
+
+/// Run script in the inventory hosts, returning true if it failed in any of them.
+fn push_script(
+    inventory_path: &Path,
+    hosts: &[String],
+    options: &PushOptions,
+    profile: bool,
+) -> Result<bool, Error> {
+    let patterns = hosts
+        .iter()
+        .map(|host| Pattern::new(host).map_err(|e| Error::new(ErrorKind::InvalidData, e)))
+        .collect::<Result<Vec<_>, _>>()?;
+    let inventory = Inventory::from_file(inventory_path)?;
+    let hosts = inventory.select(&patterns)?;
+
+    let results = push::push(&inventory, &hosts, options)?;
+    push::log_recap(&results, profile);
+    Ok(push::is_failed(&results))
+}
+
+/// Parse CLI arguments. When they are not valid and an argument is both a command name and a
+/// file, it is parsed again as the script file, as it was before commands were added.
+fn parse_cli(args: Vec<OsString>) -> Cli {
+    Cli::try_parse_from(&args).unwrap_or_else(|e| {
+        let command = Cli::command();
+        args.iter()
+            .skip(1)
+            .position(|arg| command.find_subcommand(arg).is_some() && Path::new(arg).is_file())
+            .and_then(|index| {
+                let mut script_args = args.clone();
+                script_args[index + 1] = Path::new(".").join(&args[index + 1]).into_os_string();
+                Cli::try_parse_from(script_args).ok()
+            })
+            .unwrap_or_else(|| e.exit())
+    })
+}
+
 fn main() {
-    let cli: Cli = Cli::parse();
+    let cli = parse_cli(std::env::args_os().collect());
 
     let verbose = if cli.verbose == 0 {
         match std::env::var("RASH_LOG_LEVEL") {
@@ -258,6 +697,7 @@ fn main() {
                     logger::Output::Ansible => "ansible",
                     logger::Output::Raw => "raw",
                     logger::Output::Json => "json",
+                    logger::Output::Jsonl => "jsonl",
                 },
             );
         }
@@ -272,6 +712,7 @@ fn main() {
         match rash_core::task::get_internal_output().as_deref() {
             Some("raw") => logger::Output::Raw,
             Some("json") => logger::Output::Json,
+            Some("jsonl") => logger::Output::Jsonl,
             _ => logger::Output::Ansible,
         }
     } else {
@@ -286,6 +727,35 @@ fn main() {
         return;
     }
 
+    if let Some(Command::Lint { script_files }) = &cli.command {
+        lint_script_files(script_files, cli.environment);
+    }
+
+    if let Some(Command::Vault {
+        vault_password_file,
+        action,
+    }) = &cli.command
+    {
+        match exec_vault_command(vault_password_file.as_deref(), action) {
+            Ok(()) => return,
+            Err(e) => crash_error(e),
+        }
+    }
+
+    if let Some(Command::Jobs { action }) = &cli.command {
+        match exec_jobs_command(action) {
+            Ok(()) => return,
+            Err(e) => crash_error(e),
+        }
+    }
+
+    if let Some(vault_password_file) = &cli.vault_password_file {
+        match vault::read_password_file(vault_password_file) {
+            Ok(password) => vault::set_password(password),
+            Err(e) => crash_error(e),
+        }
+    }
+
     if cli.script.is_none() && cli.script_file.is_none() {
         let mut cmd = Cli::command();
         cmd.error(
@@ -296,12 +766,15 @@ fn main() {
     };
     trace!("start logger");
     trace!("{:?}", cli);
-    let script_path_string = cli.script_file.unwrap_or_else(|| "rash".to_string());
+    let script_path_string = cli
+        .script_file
+        .clone()
+        .unwrap_or_else(|| "rash".to_string());
     let script_path = Path::new(&script_path_string);
 
     setup_module_search_paths(script_path);
 
-    let main_file = if let Some(s) = cli.script {
+    let main_file = if let Some(s) = cli.script.clone() {
         s
     } else {
         trace!("reading tasks from: {script_path:?}");
@@ -311,6 +784,11 @@ fn main() {
         }
     };
 
+    if cli.syntax_check {
+        let has_problems = check_script(script_path, &main_file, cli.script_args, cli.environment);
+        exit(has_problems as i32);
+    }
+
     let script_args: Vec<&str> = cli.script_args.iter().map(|s| &**s).collect();
     let mut new_vars = match docopt::parse(&main_file, &script_args) {
         Ok(v) => Value::from_serialize(v),
@@ -323,8 +801,20 @@ fn main() {
         },
     };
 
+    let header = match parse_header(&main_file) {
+        Ok(header) => header,
+        Err(e) => crash_error(e),
+    };
+    // CLI flags can enable become and check mode, but not disable them
+    let check_mode = cli.check || header.check_mode.unwrap_or(false);
+
+    let connection = cli.connection.clone().map(YamlValue::String);
+    let start_at_task = match cli.start_at_task.as_deref().map(Pattern::new).transpose() {
+        Ok(pattern) => pattern.map(StartAtTask::new),
+        Err(e) => crash_error(Error::new(ErrorKind::InvalidData, e)),
+    };
     let global_params = GlobalParams {
-        r#become: cli.r#become,
+        r#become: cli.r#become || header.r#become.unwrap_or(false),
         become_user: &cli.become_user,
         become_method: cli.become_method,
         become_exe: &cli.become_exe,
@@ -336,35 +826,163 @@ fn main() {
         } else {
             None
         },
-        check_mode: cli.check,
+        check_mode,
+        tags: &cli.tags,
+        skip_tags: &cli.skip_tags,
+        inherited_tags: &[],
+        connection: connection.as_ref(),
+        throttle: None,
+        debugger: match cli.debugger {
+            true => Debugger::OnFailed,
+            false => Debugger::Never,
+        },
+        start_at_task: start_at_task.as_ref(),
+        in_parallel: false,
     };
 
-    let (tasks, handlers) = match parse_file_with_handlers(&main_file, &global_params) {
+    let (tasks, handlers) = match parse_file_with_handlers(&main_file, script_path, &global_params)
+    {
         Ok(parsed) => (parsed.tasks, parsed.handlers),
-        Err(e1) => match parse_file(&main_file, &global_params) {
+        Err(e1) => match parse_file(&main_file, script_path, &global_params) {
             Ok(tasks) => (tasks, None),
+            // report the error of the tasks when the script is just a list of tasks
+            Err(e2)
+                if serde_norway::from_str::<YamlValue>(&main_file)
+                    .is_ok_and(|yaml| yaml.is_sequence()) =>
+            {
+                crash_error(e2)
+            }
             Err(_) => {
                 crash_error(e1);
             }
         },
     };
 
-    let env_vars = env::load(cli.environment);
+    let header_environment = match header.get_environment() {
+        Ok(environment) => environment,
+        Err(e) => crash_error(e),
+    };
+    let env_vars = env::load([header_environment, cli.environment.clone()].concat());
     new_vars = context! {..new_vars, ..env_vars};
+    if let Some(internal_vars_path) = &cli.internal_vars {
+        match read_internal_vars(internal_vars_path) {
+            Ok(internal_vars) => new_vars = context! {..new_vars, ..internal_vars},
+            Err(e) => crash_error(e),
+        }
+    }
     match Builtins::new(
         script_args.into_iter().map(String::from).collect(),
         script_path,
-        cli.check,
+        check_mode,
     ) {
         Ok(builtins) => new_vars = context! {rash => &builtins, ..new_vars},
         Err(e) => crash_error(e),
     };
+    match header.get_vars(script_path, &new_vars) {
+        Ok(header_vars) => new_vars = context! {..new_vars, ..header_vars},
+        Err(e) => crash_error(e),
+    };
     trace!("Vars: {new_vars}");
-    match Context::with_handlers(tasks, new_vars, None, handlers).exec() {
+
+    if cli.list_tags {
+        match list_tags(&tasks, &new_vars) {
+            Ok(tags) => tags.iter().for_each(|tag| println!("{tag}")),
+            Err(e) => crash_error(e),
+        };
+        return;
+    }
+
+    // resuming with other arguments would restore vars registered from different values
+    let journal_args = cli
+        .environment
+        .iter()
+        .map(|(key, value)| format!("-e{key}={value}"))
+        .chain(cli.script_args.iter().cloned())
+        .collect::<Vec<_>>();
+    // check mode and push mode runs are not resumable
+    let is_resumable = !cli.list_tasks && !check_mode && cli.internal_vars.is_none();
+    let journal = match (is_resumable || cli.resume).then(|| {
+        Journal::open(script_path, &main_file, &journal_args)
+            .and_then(|journal| journal.try_lock().map(|lock| (journal, lock)))
+    }) {
+        Some(Ok(journal)) => Some(journal),
+        Some(Err(e)) if cli.resume => crash_error(e),
+        // concurrent runs of the same script are expected, only the first one is resumable
+        Some(Err(e)) if e.kind() == ErrorKind::Other => {
+            debug!("progress is not saved: {e}");
+            None
+        }
+        Some(Err(e)) => {
+            warn!("Progress is not saved: {e}");
+            None
+        }
+        None => None,
+    };
+    // the lock is held until the run finishes
+    let (journal, _journal_lock) = journal.unzip();
+    let is_journaled = is_resumable && journal.is_some();
+    let resume = match journal
+        .as_ref()
+        .filter(|_| cli.resume)
+        .map(Journal::read)
+        .transpose()
+    {
+        Ok(entry) => entry,
+        Err(e) => crash_error(e),
+    };
+    let context =
+        Context::with_handlers(tasks, new_vars, None, handlers).with_controls(ExecControls {
+            list_tasks: cli.list_tasks,
+            step: cli.step,
+            journal: journal.clone().filter(|_| is_journaled),
+            resume,
+        });
+
+    // tasks are skipped until the task to start at is found, in any block or included file
+    let check_started = || match &start_at_task {
+        Some(start_at_task) => start_at_task.check_started(),
+        None => Ok(()),
+    };
+
+    if cli.list_tasks {
+        if let Err(e) = context.exec().and_then(|_| check_started()) {
+            crash_error(e);
+        }
+        return;
+    }
+
+    if let Some(inventory_path) = &cli.inventory {
+        let options = PushOptions {
+            script_path: script_path.to_path_buf(),
+            script: cli.script.clone(),
+            args: get_push_args(&cli),
+            script_args: cli.script_args.clone(),
+            vault_password: vault::get_password().ok(),
+            forks: cli.forks,
+        };
+        match push_script(inventory_path, &cli.hosts, &options, cli.profile) {
+            Ok(true) => exit(1),
+            Ok(false) => return,
+            Err(e) => crash_error(e),
+        }
+    }
+
+    events::run_start(&script_path_string);
+    let result = context.exec().and_then(|_| check_started());
+    events::run_end();
+    if cli.recap || cli.profile {
+        stats::log_recap(cli.profile);
+    }
+    match result {
         Ok(_) => (),
         Err(context_error) => match context_error.kind() {
             ErrorKind::EmptyTaskStack => (),
-            _ => crash_error(context_error),
+            _ => {
+                if is_journaled && journal.as_ref().is_some_and(Journal::exists) {
+                    info!("to resume from the failed task, use: --resume");
+                }
+                crash_error(context_error)
+            }
         },
     };
 }
@@ -0,0 +1,574 @@
+//! Connection
+//!
+//! Targets where modules are executed: the local host or inside a chroot, a container or the
+//! namespaces of a process.
+//!
+//! Tasks with a non local connection are serialized as [`InternalTaskData`] and executed by a
+//! copy of the current `rash` binary inside the target, like `become_method: sudo` does. The
+//! binary is copied once per target and run, named by its checksum.
+//!
+//! [`InternalTaskData`]: ../task/struct.InternalTaskData.html
+
+use crate::error::{Error, ErrorKind, Result};
+use crate::task::{
+    InternalTaskData, RASH_INTERNAL_OUTPUT_ENV, RASH_INTERNAL_RESULT_ENV, RASH_INTERNAL_TASK_FLAG,
+    TaskExecResult,
+};
+
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::Command as StdCommand;
+use std::sync::{LazyLock, Mutex};
+
+use serde::Deserialize;
+use serde_norway::Value as YamlValue;
+use sha2::{Digest, Sha256};
+
+/// Directory of the target where the binary, task and result files are copied.
+const TARGET_TMP_DIR: &str = "/tmp";
+
+/// Target paths of the binaries copied in this run, by connection.
+static TARGET_BINARIES: LazyLock<Mutex<HashMap<String, String>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Target files removed when dropped, so they are removed even if the task fails.
+struct TargetFiles<'a> {
+    connection: &'a Connection,
+    paths: Vec<String>,
+}
+
+impl Drop for TargetFiles<'_> {
+    fn drop(&mut self) {
+        let paths = self.paths.iter().map(String::as_str).collect::<Vec<_>>();
+        self.connection.remove_files(&paths);
+    }
+}
+
+// ANCHOR: connection
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "type", rename_all = "kebab-case", deny_unknown_fields)]
+pub enum Connection {
+    /// Execute in the current host.
+    Local,
+    /// Execute inside a chroot.
+    Chroot { path: PathBuf },
+    /// Execute inside a running docker container.
+    Docker {
+        container: String,
+        user: Option<String>,
+    },
+    /// Execute inside a running podman container.
+    Podman {
+        container: String,
+        user: Option<String>,
+    },
+    /// Execute inside the mount, UTS, IPC, network and PID namespaces of a process.
+    Nsenter { pid: u32 },
+    /// Execute inside a systemd-nspawn container booted from a directory.
+    SystemdNspawn { directory: PathBuf },
+}
+// ANCHOR_END: connection
+
+impl std::str::FromStr for Connection {
+    type Err = Error;
+
+    /// Parse `<type>[:<target>]` format, e.g.: `local`, `docker:my-container` or `nsenter:1234`.
+    fn from_str(s: &str) -> Result<Self> {
+        let (connection_type, target) = match s.split_once(':') {
+            Some((connection_type, target)) => (connection_type, Some(target)),
+            None => (s, None),
+        };
+        let missing_target = || {
+            Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "Connection '{connection_type}' requires a target: {connection_type}:<target>"
+                ),
+            )
+        };
+        let get_target = || target.filter(|t| !t.is_empty()).ok_or_else(missing_target);
+
+        match connection_type {
+            "local" => Ok(Connection::Local),
+            "chroot" => Ok(Connection::Chroot {
+                path: PathBuf::from(get_target()?),
+            }),
+            "docker" => Ok(Connection::Docker {
+                container: get_target()?.to_owned(),
+                user: None,
+            }),
+            "podman" => Ok(Connection::Podman {
+                container: get_target()?.to_owned(),
+                user: None,
+            }),
+            "nsenter" => Ok(Connection::Nsenter {
+                pid: get_target()?.parse().map_err(|e| {
+                    Error::new(
+                        ErrorKind::InvalidData,
+                        format!("Invalid nsenter pid '{}': {e}", target.unwrap_or_default()),
+                    )
+                })?,
+            }),
+            "systemd-nspawn" => Ok(Connection::SystemdNspawn {
+                directory: PathBuf::from(get_target()?),
+            }),
+            _ => Err(Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "Invalid connection '{connection_type}'. Valid options: local, chroot, docker, podman, nsenter, systemd-nspawn"
+                ),
+            )),
+        }
+    }
+}
+
+impl Connection {
+    /// Create connection from a string (`<type>[:<target>]`) or a mapping with its `type`.
+    pub fn from_yaml(yaml: &YamlValue) -> Result<Self> {
+        match yaml {
+            YamlValue::String(s) => s.parse(),
+            _ => serde_norway::from_value(yaml.clone()).map_err(|e| {
+                Error::new(ErrorKind::InvalidData, format!("Invalid connection: {e}"))
+            }),
+        }
+    }
+
+    pub fn is_local(&self) -> bool {
+        *self == Connection::Local
+    }
+
+    /// Host path of the target root filesystem, if it is reachable from the host.
+    fn get_root(&self) -> Option<PathBuf> {
+        match self {
+            Connection::Chroot { path } => Some(path.clone()),
+            Connection::Nsenter { pid } => Some(PathBuf::from(format!("/proc/{pid}/root"))),
+            Connection::SystemdNspawn { directory } => Some(directory.clone()),
+            Connection::Local | Connection::Docker { .. } | Connection::Podman { .. } => None,
+        }
+    }
+
+    fn get_container_exe(&self) -> Option<(&str, &str, Option<&str>)> {
+        match self {
+            Connection::Docker { container, user } => Some(("docker", container, user.as_deref())),
+            Connection::Podman { container, user } => Some(("podman", container, user.as_deref())),
+            _ => None,
+        }
+    }
+
+    fn run(command: &mut StdCommand) -> Result<()> {
+        trace!("connection command: {command:?}");
+        let output = command.output().map_err(|e| {
+            Error::new(
+                ErrorKind::SubprocessFail,
+                format!("Failed to execute {:?}: {e}", command.get_program()),
+            )
+        })?;
+        match output.status.success() {
+            true => Ok(()),
+            false => Err(Error::new(
+                ErrorKind::SubprocessFail,
+                format!(
+                    "{:?} failed: {}",
+                    command.get_program(),
+                    String::from_utf8_lossy(&output.stderr).trim()
+                ),
+            )),
+        }
+    }
+
+    /// Copy local file to the target path.
+    fn put_file(&self, src: &Path, dest: &str) -> Result<()> {
+        match (self.get_root(), self.get_container_exe()) {
+            (Some(root), _) => {
+                let host_dest = root.join(dest.trim_start_matches('/'));
+                if let Some(parent) = host_dest.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                fs::copy(src, host_dest)?;
+                Ok(())
+            }
+            (None, Some((exe, container, _))) => Self::run(
+                StdCommand::new(exe)
+                    .arg("cp")
+                    .arg(src)
+                    .arg(format!("{container}:{dest}")),
+            ),
+            (None, None) => {
+                fs::copy(src, dest)?;
+                Ok(())
+            }
+        }
+    }
+
+    /// Read target file content, if it exists.
+    fn read_file(&self, path: &str) -> Result<Option<String>> {
+        let host_path = match (self.get_root(), self.get_container_exe()) {
+            (Some(root), _) => root.join(path.trim_start_matches('/')),
+            (None, Some((exe, container, _))) => {
+                let host_path = env::temp_dir().join(format!("rash_{}", uuid::Uuid::new_v4()));
+                if Self::run(
+                    StdCommand::new(exe)
+                        .arg("cp")
+                        .arg(format!("{container}:{path}"))
+                        .arg(&host_path),
+                )
+                .is_err()
+                {
+                    return Ok(None);
+                }
+                let content = fs::read_to_string(&host_path);
+                let _ = fs::remove_file(&host_path);
+                return Ok(Some(content?));
+            }
+            (None, None) => PathBuf::from(path),
+        };
+        match host_path.exists() {
+            true => Ok(Some(fs::read_to_string(host_path)?)),
+            false => Ok(None),
+        }
+    }
+
+    /// Rename target file, replacing `dest` if it exists.
+    fn rename_file(&self, src: &str, dest: &str) -> Result<()> {
+        match (self.get_root(), self.get_container_exe()) {
+            (Some(root), _) => Ok(fs::rename(
+                root.join(src.trim_start_matches('/')),
+                root.join(dest.trim_start_matches('/')),
+            )?),
+            (None, Some((exe, container, _))) => Self::run(
+                StdCommand::new(exe)
+                    .args(["exec", container, "mv", "-f"])
+                    .args([src, dest]),
+            ),
+            (None, None) => Ok(fs::rename(src, dest)?),
+        }
+    }
+
+    /// Copy `rash` binary to the target, if it was not copied in this run yet, returning its
+    /// target path. It is named by its checksum and replaced atomically, so tasks running at
+    /// the same time in the target never execute a partial copy.
+    fn put_binary(&self) -> Result<String> {
+        // hold the lock while copying, so it is copied once
+        let mut target_binaries = TARGET_BINARIES.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(target_exe) = target_binaries.get(&self.to_string()) {
+            trace!("binary already copied to {self}: {target_exe}");
+            return Ok(target_exe.clone());
+        }
+
+        let rash_path = env::current_exe().map_err(|e| {
+            Error::new(
+                ErrorKind::Other,
+                format!("Failed to get current exe path: {e}"),
+            )
+        })?;
+        let checksum = Sha256::digest(fs::read(&rash_path)?)
+            .iter()
+            .map(|b| format!("{b:02x}"))
+            .collect::<String>();
+        let target_exe = format!("{TARGET_TMP_DIR}/rash_{checksum}");
+
+        let tmp_target_exe = format!("{target_exe}.{}", uuid::Uuid::new_v4());
+        let result = self
+            .put_file(&rash_path, &tmp_target_exe)
+            .and_then(|_| self.rename_file(&tmp_target_exe, &target_exe));
+        if result.is_err() {
+            self.remove_files(&[&tmp_target_exe]);
+        }
+        result?;
+        target_binaries.insert(self.to_string(), target_exe.clone());
+        Ok(target_exe)
+    }
+
+    /// Remove target files, ignoring errors.
+    fn remove_files(&self, paths: &[&str]) {
+        match (self.get_root(), self.get_container_exe()) {
+            (Some(root), _) => paths.iter().for_each(|path| {
+                let _ = fs::remove_file(root.join(path.trim_start_matches('/')));
+            }),
+            (None, Some((exe, container, _))) => {
+                let _ = Self::run(
+                    StdCommand::new(exe)
+                        .args(["exec", container, "rm", "-f"])
+                        .args(paths),
+                );
+            }
+            (None, None) => paths.iter().for_each(|path| {
+                let _ = fs::remove_file(path);
+            }),
+        }
+    }
+
+    /// Command to execute `program` inside the target with the environment variables.
+    fn get_command(&self, program: &str, envs: &[(&str, String)]) -> StdCommand {
+        let mut command = match self {
+            Connection::Local => {
+                let mut command = StdCommand::new(program);
+                command.envs(envs.iter().map(|(k, v)| (k, v)));
+                return command;
+            }
+            Connection::Chroot { path } => {
+                let mut command = StdCommand::new("chroot");
+                command.arg(path);
+                command
+            }
+            Connection::Nsenter { pid } => {
+                let mut command = StdCommand::new("nsenter");
+                command
+                    .arg("--target")
+                    .arg(pid.to_string())
+                    .args(["--mount", "--uts", "--ipc", "--net", "--pid", "--"]);
+                command
+            }
+            Connection::SystemdNspawn { directory } => {
+                let mut command = StdCommand::new("systemd-nspawn");
+                command.arg("--quiet").arg("--directory").arg(directory);
+                envs.iter().for_each(|(k, v)| {
+                    command.arg(format!("--setenv={k}={v}"));
+                });
+                command
+            }
+            Connection::Docker { .. } | Connection::Podman { .. } => {
+                // safe unwrap: it is a container connection
+                let (exe, container, user) = self.get_container_exe().unwrap();
+                let mut command = StdCommand::new(exe);
+                command.arg("exec");
+                envs.iter().for_each(|(k, v)| {
+                    command.arg("--env").arg(format!("{k}={v}"));
+                });
+                if let Some(user) = user {
+                    command.arg("--user").arg(user);
+                }
+                command.arg(container);
+                command
+            }
+        };
+        command.envs(envs.iter().map(|(k, v)| (k, v)));
+        command.arg(program);
+        command
+    }
+
+    /// Copy `rash` binary and task to the target, execute it there and return its result.
+    pub fn exec_internal_task(&self, internal_data: &InternalTaskData) -> Result<TaskExecResult> {
+        let id = uuid::Uuid::new_v4();
+        let target_task_file = format!("{TARGET_TMP_DIR}/rash_task_{id}.yaml");
+        let target_result_file = format!("{TARGET_TMP_DIR}/rash_result_{id}.json");
+
+        let mut task_file = tempfile::NamedTempFile::new()?;
+        let task_content =
+            serde_yaml::to_string(internal_data).map_err(|e| Error::new(ErrorKind::Other, e))?;
+        task_file.write_all(task_content.as_bytes())?;
+
+        let target_exe = self.put_binary()?;
+        // the task file could contain secrets
+        let _target_files = TargetFiles {
+            connection: self,
+            paths: vec![target_task_file.clone(), target_result_file.clone()],
+        };
+
+        self.put_file(task_file.path(), &target_task_file)?;
+        let envs = [
+            (RASH_INTERNAL_RESULT_ENV, target_result_file.clone()),
+            (RASH_INTERNAL_TASK_FLAG, "1".to_owned()),
+            (
+                RASH_INTERNAL_OUTPUT_ENV,
+                env::var(RASH_INTERNAL_OUTPUT_ENV).unwrap_or_default(),
+            ),
+        ];
+        let mut command = self.get_command(&target_exe, &envs);
+        command.arg("--internal-task").arg(&target_task_file);
+        trace!("exec internal task: {command:?}");
+        let status = command.status().map_err(|e| {
+            Error::new(
+                ErrorKind::SubprocessFail,
+                format!("Failed to execute {:?}: {e}", command.get_program()),
+            )
+        })?;
+
+        match self.read_file(&target_result_file)? {
+            Some(content) if status.success() => serde_json::from_str(&content).map_err(|e| {
+                Error::new(
+                    ErrorKind::Other,
+                    format!("Failed to parse result JSON: {e}"),
+                )
+            }),
+            _ => Err(Error::new(
+                ErrorKind::SubprocessFail,
+                format!(
+                    "Task failed in {self} with exit code {}",
+                    status.code().unwrap_or(-1)
+                ),
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for Connection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Connection::Local => write!(f, "local"),
+            Connection::Chroot { path } => write!(f, "chroot:{}", path.display()),
+            Connection::Docker { container, .. } => write!(f, "docker:{container}"),
+            Connection::Podman { container, .. } => write!(f, "podman:{container}"),
+            Connection::Nsenter { pid } => write!(f, "nsenter:{pid}"),
+            Connection::SystemdNspawn { directory } => {
+                write!(f, "systemd-nspawn:{}", directory.display())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::process::Command;
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!("local".parse::<Connection>().unwrap(), Connection::Local);
+        assert_eq!(
+            "chroot:/mnt/root".parse::<Connection>().unwrap(),
+            Connection::Chroot {
+                path: PathBuf::from("/mnt/root")
+            }
+        );
+        assert_eq!(
+            "docker:web".parse::<Connection>().unwrap(),
+            Connection::Docker {
+                container: "web".to_owned(),
+                user: None
+            }
+        );
+        assert_eq!(
+            "nsenter:1234".parse::<Connection>().unwrap(),
+            Connection::Nsenter { pid: 1234 }
+        );
+        assert_eq!(
+            "systemd-nspawn:/var/lib/machines/foo"
+                .parse::<Connection>()
+                .unwrap(),
+            Connection::SystemdNspawn {
+                directory: PathBuf::from("/var/lib/machines/foo")
+            }
+        );
+    }
+
+    #[test]
+    fn test_from_str_invalid() {
+        assert!("docker".parse::<Connection>().is_err());
+        assert!("docker:".parse::<Connection>().is_err());
+        assert!("nsenter:foo".parse::<Connection>().is_err());
+        assert!("ssh:host".parse::<Connection>().is_err());
+    }
+
+    #[test]
+    fn test_from_yaml() {
+        let yaml: YamlValue = serde_norway::from_str(
+            r#"
+type: podman
+container: db
+user: postgres
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            Connection::from_yaml(&yaml).unwrap(),
+            Connection::Podman {
+                container: "db".to_owned(),
+                user: Some("postgres".to_owned())
+            }
+        );
+
+        let yaml: YamlValue = serde_norway::from_str("docker:web").unwrap();
+        assert_eq!(
+            Connection::from_yaml(&yaml).unwrap(),
+            Connection::Docker {
+                container: "web".to_owned(),
+                user: None
+            }
+        );
+
+        let yaml: YamlValue = serde_norway::from_str("{type: chroot, foo: bar}").unwrap();
+        assert!(Connection::from_yaml(&yaml).is_err());
+    }
+
+    #[test]
+    fn test_display() {
+        for s in ["local", "chroot:/mnt", "docker:web", "nsenter:1"] {
+            assert_eq!(s.parse::<Connection>().unwrap().to_string(), s);
+        }
+    }
+
+    #[test]
+    fn test_get_command_docker() {
+        let connection: Connection = "docker:web".parse().unwrap();
+        let command: Command =
+            connection.get_command("/tmp/rash", &[("RASH_INTERNAL", "1".to_owned())]);
+        assert_eq!(command.get_program(), "docker");
+        assert_eq!(
+            command.get_args().collect::<Vec<_>>(),
+            vec!["exec", "--env", "RASH_INTERNAL=1", "web", "/tmp/rash"]
+        );
+    }
+
+    #[test]
+    fn test_put_and_read_file_chroot() {
+        let dir = tempfile::tempdir().unwrap();
+        let connection = Connection::Chroot {
+            path: dir.path().to_path_buf(),
+        };
+        let src = dir.path().join("src");
+        fs::write(&src, "foo").unwrap();
+
+        connection.put_file(&src, "/tmp/dest").unwrap();
+        assert_eq!(
+            connection.read_file("/tmp/dest").unwrap(),
+            Some("foo".to_owned())
+        );
+
+        connection.remove_files(&["/tmp/dest"]);
+        assert_eq!(connection.read_file("/tmp/dest").unwrap(), None);
+    }
+
+    #[test]
+    fn test_put_binary_chroot() {
+        let dir = tempfile::tempdir().unwrap();
+        let connection = Connection::Chroot {
+            path: dir.path().to_path_buf(),
+        };
+
+        let target_exe = connection.put_binary().unwrap();
+        assert!(target_exe.starts_with("/tmp/rash_"));
+        let host_exe = dir.path().join(target_exe.trim_start_matches('/'));
+        assert_eq!(
+            fs::read(&host_exe).unwrap(),
+            fs::read(env::current_exe().unwrap()).unwrap()
+        );
+
+        // copied once per run
+        fs::remove_file(&host_exe).unwrap();
+        assert_eq!(connection.put_binary().unwrap(), target_exe);
+        assert!(!host_exe.exists());
+        assert_eq!(fs::read_dir(dir.path().join("tmp")).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn test_target_files_removed_on_drop() {
+        let dir = tempfile::tempdir().unwrap();
+        let connection = Connection::Chroot {
+            path: dir.path().to_path_buf(),
+        };
+        let src = dir.path().join("src");
+        fs::write(&src, "secret").unwrap();
+        connection.put_file(&src, "/tmp/task").unwrap();
+
+        drop(TargetFiles {
+            connection: &connection,
+            paths: vec!["/tmp/task".to_owned()],
+        });
+        assert_eq!(connection.read_file("/tmp/task").unwrap(), None);
+    }
+}
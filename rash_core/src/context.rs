@@ -1,10 +1,69 @@
-use crate::task::{Handlers, PendingHandlers, Tasks};
 /// Context
 ///
 /// Preserve state between executions
-use crate::{error::Result, jinja::merge_option};
+use crate::error::Result;
+use crate::events::TaskInfo;
+use crate::journal::{self, Journal};
+use crate::task::{Debugger, Handlers, PendingHandlers, StartAtTask, Task, Tasks};
+use crate::{jinja::merge_option, stats};
+
+use std::io::{self, BufRead, Write};
+
 use clap::ValueEnum;
 use minijinja::{Value, context};
+use serde_norway::Value as YamlValue;
+
+/// Controls of [`Context::exec`] to run just part of the tasks.
+#[derive(Debug, Clone, Default)]
+pub struct ExecControls {
+    /// Print tasks instead of executing them.
+    pub list_tasks: bool,
+    /// Ask for confirmation before executing each task.
+    pub step: bool,
+    /// Save progress before executing each task, removing it when all tasks succeed.
+    pub journal: Option<Journal>,
+    /// Restore the state of a previous run and skip the tasks it already executed.
+    pub resume: Option<journal::Entry>,
+}
+
+/// Answer to the confirmation asked with `--step`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum StepAnswer {
+    Yes,
+    No,
+    /// Execute this and all remaining tasks without asking again.
+    Continue,
+}
+
+impl StepAnswer {
+    /// Parse answer, `no` by default.
+    fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "y" | "yes" => Some(StepAnswer::Yes),
+            "" | "n" | "no" => Some(StepAnswer::No),
+            "c" | "continue" => Some(StepAnswer::Continue),
+            _ => None,
+        }
+    }
+
+    /// Ask in stderr until a valid answer is read from stdin. On EOF, the answer is `no`.
+    fn ask(task_name: &str) -> Result<Self> {
+        let stdin = io::stdin();
+        loop {
+            eprint!("Perform task: {task_name} (N)o/(y)es/(c)ontinue: ");
+            io::stderr().flush()?;
+            let mut line = String::new();
+            if stdin.lock().read_line(&mut line)? == 0 {
+                eprintln!();
+                return Ok(StepAnswer::No);
+            }
+            match StepAnswer::parse(&line) {
+                Some(answer) => return Ok(answer),
+                None => eprintln!("Invalid answer: {}", line.trim()),
+            }
+        }
+    }
+}
 
 /// Main data structure in `rash`.
 /// It contents all [`task::Tasks`] with their [`vars::Vars`] to be executed
@@ -19,6 +78,7 @@ pub struct Context<'a> {
     scoped_vars: Option<Value>,
     handlers: Option<Handlers<'a>>,
     pending_handlers: PendingHandlers,
+    controls: ExecControls,
 }
 
 impl<'a> Context<'a> {
@@ -33,6 +93,7 @@ impl<'a> Context<'a> {
             scoped_vars: scope_vars,
             handlers: None,
             pending_handlers: PendingHandlers::new(),
+            controls: ExecControls::default(),
         }
     }
 
@@ -48,7 +109,45 @@ impl<'a> Context<'a> {
             scoped_vars: scope_vars,
             handlers,
             pending_handlers: PendingHandlers::new(),
+            controls: ExecControls::default(),
+        }
+    }
+
+    /// Set [`ExecControls`] of the execution.
+    pub fn with_controls(self, controls: ExecControls) -> Self {
+        Context { controls, ..self }
+    }
+
+    /// Return task name to list: rendered name or raw name if the rendering fails.
+    fn get_task_name(task: &Task, vars: &Value) -> Option<String> {
+        task.get_rendered_name(vars.clone())
+            .ok()
+            .or_else(|| task.get_name())
+    }
+
+    fn print_task(index: &str, task_info: &TaskInfo, name: &str) {
+        let location = match (&task_info.file, task_info.line) {
+            (Some(file), Some(line)) => format!("{file}:{line}"),
+            (Some(file), None) => file.clone(),
+            (None, _) => "-".to_owned(),
+        };
+        println!("{index}\t{location}\t{name}");
+    }
+
+    /// Print the task and the tasks created from it, numbered by their position in each level:
+    /// `2.1` is the first task in the block of the second task.
+    fn print_tasks(task: &Task, vars: &Value, index: &str) -> Result<()> {
+        if !task.is_selected_by_tags() || task.is_before_start_at_task(vars)? {
+            return Ok(());
         }
+        let task_info = task.get_info(vars);
+        let name = Self::get_task_name(task, vars).unwrap_or_else(|| task_info.module.clone());
+        Self::print_task(index, &task_info, &name);
+        let mut child_index = 0;
+        task.for_each_child(vars, &mut |child, vars| {
+            child_index += 1;
+            Self::print_tasks(child, vars, &format!("{index}.{child_index}"))
+        })
     }
 
     fn execute_pending_handlers(&mut self) -> Result<()> {
@@ -61,12 +160,20 @@ impl<'a> Context<'a> {
 
         for handler_name in &pending {
             if let Some(handler) = handlers.get(handler_name) {
-                info!(target: "task",
-                    "[handler:{}] - ",
-                    handler.get_task().get_rendered_name(self.vars.clone())
-                        .unwrap_or_else(|_| handler_name.to_string()),
-                );
-                let _ = handler.get_task().exec(self.vars.clone())?;
+                let task_info = TaskInfo {
+                    task: format!(
+                        "handler:{}",
+                        handler
+                            .get_task()
+                            .get_rendered_name(self.vars.clone())
+                            .unwrap_or_else(|_| handler_name.to_string()),
+                    ),
+                    handler: true,
+                    ..handler.get_task().get_info(&self.vars)
+                };
+                info!(target: "task", "[{}] - ", task_info.task);
+                let _ =
+                    stats::track_task(&task_info, || handler.get_task().exec(self.vars.clone()))?;
             } else {
                 warn!("Handler '{}' not found", handler_name);
             }
@@ -83,20 +190,80 @@ impl<'a> Context<'a> {
     /// [`ErrorKind::EmptyTaskStack`]: ../error/enum.ErrorKind.html
     pub fn exec(&self) -> Result<Self> {
         let mut context = self.clone();
+        let tasks_len = context.tasks.len();
+
+        // Progress is journaled by top level task: a task failing in a block or an included
+        // file resumes from the beginning of its top level task.
+        let resume_index = match &context.controls.resume {
+            Some(entry) => {
+                if !entry.omitted_vars.is_empty() {
+                    warn!(
+                        "Vars not saved in the journal, they could contain secrets: {}",
+                        entry.omitted_vars.join(", ")
+                    );
+                }
+                let journal_vars = entry.get_vars();
+                context.vars = merge_option(context.vars, journal_vars.clone());
+                context.scoped_vars = journal_vars;
+                context.pending_handlers = entry.get_pending_handlers();
+                entry.task_index
+            }
+            None => 1,
+        };
 
         while !context.tasks.is_empty() {
             let mut next_tasks = context.tasks.clone();
             let next_task = next_tasks.remove(0);
+            let index = tasks_len - context.tasks.len() + 1;
+
+            if index < resume_index {
+                debug!("skipping already executed task: {index}");
+                context.tasks = next_tasks;
+                continue;
+            }
+
+            if !next_task.is_selected_by_tags() {
+                debug!("skipping by tags: {:?}", next_task.get_tags());
+                context.tasks = next_tasks;
+                continue;
+            }
+
+            if next_task.is_before_start_at_task(&context.vars)? {
+                debug!("skipping until start at task: {:?}", next_task.get_name());
+                context.tasks = next_tasks;
+                continue;
+            }
+
+            if context.controls.list_tasks {
+                Self::print_tasks(&next_task, &context.vars, &index.to_string())?;
+                context.tasks = next_tasks;
+                continue;
+            }
+
+            let task_info = next_task.get_info(&context.vars);
+
+            if context.controls.step {
+                match StepAnswer::ask(&task_info.task)? {
+                    StepAnswer::Yes => (),
+                    StepAnswer::No => {
+                        debug!("skipping by step: {}", task_info.task);
+                        context.tasks = next_tasks;
+                        continue;
+                    }
+                    StepAnswer::Continue => context.controls.step = false,
+                }
+            }
+
+            context.write_journal(index);
 
             info!(target: "task",
-                "[{}:{}] - {} to go - ",
-                context.vars.get_attr("rash")?.get_attr("path")?,
-                next_task.get_rendered_name(context.vars.clone())
-                    .unwrap_or_else(|_| next_task.get_module().get_name().to_owned()),
+                "[{}] - {} to go - ",
+                task_info.task,
                 context.tasks.len(),
             );
 
-            let exec_result = next_task.exec(context.vars.clone())?;
+            let exec_result =
+                stats::track_task(&task_info, || next_task.exec(context.vars.clone()))?;
 
             let changed = exec_result.get_changed();
             let flush_handlers = exec_result.is_flush_handlers();
@@ -122,6 +289,7 @@ impl<'a> Context<'a> {
                 scoped_vars,
                 handlers: context.handlers,
                 pending_handlers: context.pending_handlers,
+                controls: context.controls,
             };
 
             if flush_handlers {
@@ -129,11 +297,28 @@ impl<'a> Context<'a> {
             }
         }
 
+        context.write_journal(tasks_len + 1);
         context.execute_pending_handlers()?;
 
+        if let Some(journal) = &context.controls.journal
+            && let Err(e) = journal.remove()
+        {
+            warn!("Failed to remove journal: {e}");
+        }
+
         Ok(context)
     }
 
+    /// Save progress before executing the task with `index`. Failing to save it does not stop
+    /// the execution.
+    fn write_journal(&self, index: usize) {
+        if let Some(journal) = &self.controls.journal
+            && let Err(e) = journal.write(index, self.scoped_vars.as_ref(), &self.pending_handlers)
+        {
+            warn!("Failed to write journal: {e}");
+        }
+    }
+
     /// Get a reference to the variables
     pub fn get_vars(&self) -> &Value {
         &self.vars
@@ -180,7 +365,7 @@ impl std::fmt::Display for BecomeMethod {
 }
 
 /// [`task::Task`] parameters that can be set globally
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub struct GlobalParams<'a> {
     pub r#become: bool,
     pub become_user: &'a str,
@@ -188,6 +373,22 @@ pub struct GlobalParams<'a> {
     pub become_exe: &'a str,
     pub become_password: Option<&'a str>,
     pub check_mode: bool,
+    /// Only execute tasks tagged with any of these tags.
+    pub tags: &'a [String],
+    /// Skip tasks tagged with any of these tags.
+    pub skip_tags: &'a [String],
+    /// Tags inherited from the parent task (`block`, `include`, `rescue` or `always`).
+    pub inherited_tags: &'a [String],
+    /// Target where modules are executed, inherited from the parent task or set globally.
+    pub connection: Option<&'a YamlValue>,
+    /// Max number of tasks executed at the same time by a `parallel` task, from its `throttle`.
+    pub throttle: Option<usize>,
+    /// When to open the debugger, inherited from the parent task or set globally.
+    pub debugger: Debugger,
+    /// Skip tasks, including the ones in blocks and included files, until this one is found.
+    pub start_at_task: Option<&'a StartAtTask>,
+    /// Task is executed in a thread of a `parallel` task, inherited by its children.
+    pub in_parallel: bool,
 }
 
 impl Default for GlobalParams<'_> {
@@ -199,6 +400,14 @@ impl Default for GlobalParams<'_> {
             become_exe: "sudo",
             become_password: None,
             check_mode: Default::default(),
+            tags: &[],
+            skip_tags: &[],
+            inherited_tags: &[],
+            connection: None,
+            throttle: None,
+            debugger: Debugger::default(),
+            start_at_task: None,
+            in_parallel: false,
         }
     }
 }
@@ -0,0 +1,324 @@
+//! Events
+//!
+//! Stream of versioned JSON objects, one per line, printed with `--output jsonl`.
+
+use crate::error::Result;
+use crate::logger::{NO_LOG_MESSAGE, Output, get_output_format, is_no_log};
+use crate::stats::{self, Recap, TaskStatus};
+use crate::task::{TaskExecResult, is_internal_execution};
+
+use std::cell::RefCell;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use minijinja::Value;
+use serde::Serialize;
+
+/// Version of the events schema. It changes on breaking changes only: new events or fields
+/// can be added in the same version.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Log target used to print events.
+pub const EVENT_TARGET: &str = "event";
+
+static RUN_START: OnceLock<Instant> = OnceLock::new();
+
+thread_local! {
+    static PENDING: RefCell<Pending> = RefCell::new(Pending::default());
+}
+
+/// Module output, diff and warnings waiting to be reported by the next `loop_item` or `task_end`
+/// event.
+#[derive(Debug, Default)]
+struct Pending {
+    output: Option<String>,
+    diff: Option<String>,
+    warnings: Vec<String>,
+}
+
+/// Task identification.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TaskInfo {
+    /// Script path and rendered name (or module name) of the task.
+    pub task: String,
+    pub file: Option<String>,
+    pub line: Option<usize>,
+    pub module: String,
+    pub handler: bool,
+}
+
+/// Result of a task or a loop iteration.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Outcome {
+    pub status: TaskStatus,
+    pub changed: bool,
+    pub failed: bool,
+    pub skipped: bool,
+    /// Duration in seconds.
+    pub duration: f64,
+    pub output: Option<String>,
+    pub diff: Option<String>,
+    pub warnings: Vec<String>,
+    pub error: Option<String>,
+}
+
+impl Outcome {
+    /// Create outcome from an execution result, taking the pending module output and diff.
+    pub fn new(result: &Result<TaskExecResult>, duration: Duration) -> Self {
+        let status = match result {
+            Ok(exec_result) => exec_result.get_status(),
+            Err(_) => TaskStatus::Failed,
+        };
+        let pending = PENDING.with(|pending| pending.take());
+        Outcome {
+            status,
+            changed: result.as_ref().is_ok_and(TaskExecResult::get_changed),
+            failed: status == TaskStatus::Failed,
+            skipped: status == TaskStatus::Skipped,
+            duration: duration.as_secs_f64(),
+            output: pending.output,
+            diff: pending.diff,
+            warnings: pending.warnings,
+            error: result.as_ref().err().map(ToString::to_string),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum Event<'a> {
+    RunStart {
+        script: &'a str,
+    },
+    TaskStart(&'a TaskInfo),
+    HandlerStart(&'a TaskInfo),
+    /// `until` condition is not satisfied and the task is going to be executed again.
+    TaskRetry {
+        task: &'a str,
+        attempt: u32,
+        retries: u32,
+        /// Delay before the next attempt in seconds.
+        delay: u64,
+    },
+    LoopItem {
+        task: &'a str,
+        index: usize,
+        item: Value,
+        #[serde(flatten)]
+        outcome: Outcome,
+    },
+    TaskEnd {
+        #[serde(flatten)]
+        task: &'a TaskInfo,
+        #[serde(flatten)]
+        outcome: Outcome,
+    },
+    RunEnd {
+        recap: Recap,
+        /// Duration in seconds.
+        duration: f64,
+    },
+    /// Connection to a host failed in push mode.
+    HostUnreachable {
+        host: &'a str,
+        error: &'a str,
+    },
+}
+
+#[derive(Debug, Serialize)]
+struct Envelope<'a> {
+    version: u32,
+    /// Seconds since Unix epoch.
+    timestamp: f64,
+    #[serde(flatten)]
+    event: Event<'a>,
+}
+
+/// Return true if events must be printed.
+///
+/// Internal executions (e.g. `become_method: sudo`) don't print events, the parent process
+/// reports their tasks.
+pub fn is_enabled() -> bool {
+    get_output_format() == Output::Jsonl && !is_internal_execution()
+}
+
+/// Print event.
+pub fn emit(event: Event) {
+    if !is_enabled() {
+        return;
+    }
+
+    let envelope = Envelope {
+        version: SCHEMA_VERSION,
+        timestamp: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs_f64(),
+        event,
+    };
+    match serde_json::to_string(&envelope) {
+        Ok(line) => info!(target: EVENT_TARGET, "{line}"),
+        Err(e) => error!("Failed to serialize event: {e}"),
+    }
+}
+
+/// Emit `run_start` event and start the run clock.
+pub fn run_start(script: &str) {
+    RUN_START.get_or_init(Instant::now);
+    emit(Event::RunStart { script });
+}
+
+/// Emit `run_end` event with the recap of the run.
+pub fn run_end() {
+    emit(Event::RunEnd {
+        recap: stats::get_recap(),
+        duration: RUN_START
+            .get()
+            .map(|start| start.elapsed().as_secs_f64())
+            .unwrap_or_default(),
+    });
+}
+
+/// Emit `loop_item` event, hiding the item when task output must be hidden.
+pub fn loop_item(task: &str, index: usize, item: Value, outcome: Outcome) {
+    let item = match is_no_log() {
+        true => Value::from(NO_LOG_MESSAGE),
+        false => item,
+    };
+    emit(Event::LoopItem {
+        task,
+        index,
+        item,
+        outcome,
+    });
+}
+
+/// Keep module output to report it in the next `loop_item` or `task_end` event.
+pub fn set_output(output: Option<String>) {
+    PENDING.with(|pending| pending.borrow_mut().output = output);
+}
+
+/// Append rendered diff to report it in the next `loop_item` or `task_end` event.
+pub fn add_diff(diff: &str) {
+    PENDING.with(|pending| {
+        pending
+            .borrow_mut()
+            .diff
+            .get_or_insert_with(String::new)
+            .push_str(diff)
+    });
+}
+
+/// Keep module warning to report it in the next `loop_item` or `task_end` event.
+pub fn add_warning(warning: &str) {
+    if is_enabled() {
+        PENDING.with(|pending| pending.borrow_mut().warnings.push(warning.to_owned()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::error::{Error, ErrorKind};
+
+    fn task_info() -> TaskInfo {
+        TaskInfo {
+            task: "rash:test".to_owned(),
+            file: Some("rash".to_owned()),
+            line: Some(2),
+            module: "command".to_owned(),
+            handler: false,
+        }
+    }
+
+    #[test]
+    fn test_outcome_new() {
+        set_output(Some("foo".to_owned()));
+        add_diff("- a\n");
+        add_diff("+ b\n");
+        PENDING.with(|pending| pending.borrow_mut().warnings.push("careful".to_owned()));
+        let outcome = Outcome::new(
+            &Ok(TaskExecResult::new(true, None)),
+            Duration::from_millis(500),
+        );
+        assert_eq!(
+            outcome,
+            Outcome {
+                status: TaskStatus::Changed,
+                changed: true,
+                failed: false,
+                skipped: false,
+                duration: 0.5,
+                output: Some("foo".to_owned()),
+                diff: Some("- a\n+ b\n".to_owned()),
+                warnings: vec!["careful".to_owned()],
+                error: None,
+            }
+        );
+
+        let outcome = Outcome::new(
+            &Err(Error::new(ErrorKind::Other, "boom")),
+            Duration::from_millis(0),
+        );
+        assert_eq!(outcome.status, TaskStatus::Failed);
+        assert!(outcome.failed);
+        assert_eq!(outcome.output, None);
+        assert_eq!(outcome.error, Some("boom".to_owned()));
+    }
+
+    #[test]
+    fn test_event_serialize() {
+        let task = task_info();
+        let envelope = Envelope {
+            version: SCHEMA_VERSION,
+            timestamp: 1.5,
+            event: Event::TaskStart(&task),
+        };
+        assert_eq!(
+            serde_json::to_value(&envelope).unwrap(),
+            json!({
+                "version": 1,
+                "timestamp": 1.5,
+                "event": "task_start",
+                "task": "rash:test",
+                "file": "rash",
+                "line": 2,
+                "module": "command",
+                "handler": false,
+            })
+        );
+    }
+
+    #[test]
+    fn test_event_serialize_task_end() {
+        let task = task_info();
+        let event = Event::TaskEnd {
+            task: &task,
+            outcome: Outcome::new(
+                &Ok(TaskExecResult::new(false, None).with_skipped()),
+                Duration::from_secs(1),
+            ),
+        };
+        assert_eq!(
+            serde_json::to_value(&event).unwrap(),
+            json!({
+                "event": "task_end",
+                "task": "rash:test",
+                "file": "rash",
+                "line": 2,
+                "module": "command",
+                "handler": false,
+                "status": "skipped",
+                "changed": false,
+                "failed": false,
+                "skipped": true,
+                "duration": 1.0,
+                "output": null,
+                "diff": null,
+                "warnings": [],
+                "error": null,
+            })
+        );
+    }
+}
@@ -0,0 +1,397 @@
+use crate::jinja::filter::invalid_operation;
+
+use std::result::Result as StdResult;
+use std::str::FromStr;
+
+use minijinja::value::{Kwargs, Rest};
+use minijinja::{Error as MinijinjaError, Value};
+
+fn to_json_value(value: &Value) -> StdResult<serde_json::Value, MinijinjaError> {
+    serde_json::to_value(value).map_err(invalid_operation)
+}
+
+pub fn to_yaml(value: Value) -> StdResult<String, MinijinjaError> {
+    serde_norway::to_string(&value).map_err(invalid_operation)
+}
+
+pub fn from_yaml(value: String) -> StdResult<Value, MinijinjaError> {
+    serde_norway::from_str::<serde_norway::Value>(&value)
+        .map(Value::from_serialize)
+        .map_err(invalid_operation)
+}
+
+pub fn to_nice_json(value: Value, options: Kwargs) -> StdResult<String, MinijinjaError> {
+    let indent: usize = options.get::<Option<usize>>("indent")?.unwrap_or(4);
+    options.assert_all_used()?;
+
+    let indent_str = " ".repeat(indent);
+    let formatter = serde_json::ser::PrettyFormatter::with_indent(indent_str.as_bytes());
+    let mut buf = Vec::new();
+    let mut serializer = serde_json::Serializer::with_formatter(&mut buf, formatter);
+    serde::Serialize::serialize(&to_json_value(&value)?, &mut serializer)
+        .map_err(invalid_operation)?;
+    String::from_utf8(buf).map_err(invalid_operation)
+}
+
+pub fn from_json(value: String) -> StdResult<Value, MinijinjaError> {
+    serde_json::from_str::<serde_json::Value>(&value)
+        .map(Value::from_serialize)
+        .map_err(invalid_operation)
+}
+
+/// How `combine` merges lists found in both dictionaries, as in Ansible.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ListMerge {
+    Replace,
+    Keep,
+    Append,
+    Prepend,
+    /// Append, removing from the first list the items present in the second one.
+    AppendRp,
+    /// Prepend, removing from the first list the items present in the second one.
+    PrependRp,
+}
+
+impl FromStr for ListMerge {
+    type Err = MinijinjaError;
+
+    fn from_str(s: &str) -> StdResult<Self, Self::Err> {
+        match s {
+            "replace" => Ok(ListMerge::Replace),
+            "keep" => Ok(ListMerge::Keep),
+            "append" => Ok(ListMerge::Append),
+            "prepend" => Ok(ListMerge::Prepend),
+            "append_rp" => Ok(ListMerge::AppendRp),
+            "prepend_rp" => Ok(ListMerge::PrependRp),
+            _ => Err(invalid_operation(format!(
+                "invalid list_merge '{s}', valid options: replace, keep, append, prepend, \
+                append_rp, prepend_rp"
+            ))),
+        }
+    }
+}
+
+impl ListMerge {
+    fn merge(
+        self,
+        list: Vec<serde_json::Value>,
+        other: Vec<serde_json::Value>,
+    ) -> Vec<serde_json::Value> {
+        let without_present = || list.iter().filter(|item| !other.contains(item)).cloned();
+        match self {
+            ListMerge::Replace => other,
+            ListMerge::Keep => list,
+            ListMerge::Append => list.into_iter().chain(other).collect(),
+            ListMerge::Prepend => other.into_iter().chain(list).collect(),
+            ListMerge::AppendRp => without_present().chain(other.iter().cloned()).collect(),
+            ListMerge::PrependRp => other.iter().cloned().chain(without_present()).collect(),
+        }
+    }
+}
+
+fn merge_maps(
+    map: &mut serde_json::Map<String, serde_json::Value>,
+    other: serde_json::Map<String, serde_json::Value>,
+    recursive: bool,
+    list_merge: ListMerge,
+) {
+    for (key, other_value) in other {
+        let merged_value = match (map.remove(&key), other_value) {
+            (
+                Some(serde_json::Value::Object(mut value)),
+                serde_json::Value::Object(other_value),
+            ) if recursive => {
+                merge_maps(&mut value, other_value, recursive, list_merge);
+                serde_json::Value::Object(value)
+            }
+            (Some(serde_json::Value::Array(value)), serde_json::Value::Array(other_value)) => {
+                serde_json::Value::Array(list_merge.merge(value, other_value))
+            }
+            (_, other_value) => other_value,
+        };
+        map.insert(key, merged_value);
+    }
+}
+
+pub fn combine(
+    value: Value,
+    others: Rest<Value>,
+    options: Kwargs,
+) -> StdResult<Value, MinijinjaError> {
+    let recursive: bool = options.get::<Option<bool>>("recursive")?.unwrap_or(true);
+    let list_merge = match options.get::<Option<String>>("list_merge")? {
+        Some(list_merge) => list_merge.parse()?,
+        None => ListMerge::Replace,
+    };
+    options.assert_all_used()?;
+
+    let mut result = match to_json_value(&value)? {
+        serde_json::Value::Object(map) => map,
+        _ => {
+            return Err(invalid_operation(format!(
+                "combine expects dictionaries, got: {value}"
+            )));
+        }
+    };
+
+    for other in others.iter() {
+        match to_json_value(other)? {
+            serde_json::Value::Object(other_map) => {
+                merge_maps(&mut result, other_map, recursive, list_merge)
+            }
+            _ => {
+                return Err(invalid_operation(format!(
+                    "combine expects dictionaries, got: {other}"
+                )));
+            }
+        }
+    }
+
+    Ok(Value::from_serialize(result))
+}
+
+pub fn dict2items(value: Value, options: Kwargs) -> StdResult<Value, MinijinjaError> {
+    let key_name: String = options
+        .get::<Option<String>>("key_name")?
+        .unwrap_or_else(|| "key".to_owned());
+    let value_name: String = options
+        .get::<Option<String>>("value_name")?
+        .unwrap_or_else(|| "value".to_owned());
+    options.assert_all_used()?;
+
+    match to_json_value(&value)? {
+        serde_json::Value::Object(map) => Ok(Value::from_serialize(
+            map.into_iter()
+                .map(|(k, v)| {
+                    let mut item = serde_json::Map::new();
+                    item.insert(key_name.clone(), serde_json::Value::String(k));
+                    item.insert(value_name.clone(), v);
+                    serde_json::Value::Object(item)
+                })
+                .collect::<Vec<_>>(),
+        )),
+        _ => Err(invalid_operation(format!(
+            "dict2items expects a dictionary, got: {value}"
+        ))),
+    }
+}
+
+pub fn items2dict(value: Value, options: Kwargs) -> StdResult<Value, MinijinjaError> {
+    let key_name: String = options
+        .get::<Option<String>>("key_name")?
+        .unwrap_or_else(|| "key".to_owned());
+    let value_name: String = options
+        .get::<Option<String>>("value_name")?
+        .unwrap_or_else(|| "value".to_owned());
+    options.assert_all_used()?;
+
+    match to_json_value(&value)? {
+        serde_json::Value::Array(items) => {
+            let mut map = serde_json::Map::new();
+            for item in items {
+                let key = match item.get(&key_name) {
+                    Some(serde_json::Value::String(s)) => s.clone(),
+                    Some(other) => other.to_string(),
+                    None => {
+                        return Err(invalid_operation(format!(
+                            "items2dict: '{key_name}' not found in {item}"
+                        )));
+                    }
+                };
+                let item_value = item.get(&value_name).cloned().ok_or_else(|| {
+                    invalid_operation(format!("items2dict: '{value_name}' not found in {item}"))
+                })?;
+                map.insert(key, item_value);
+            }
+            Ok(Value::from_serialize(map))
+        }
+        _ => Err(invalid_operation(format!(
+            "items2dict expects a list, got: {value}"
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::jinja::render_string;
+
+    use minijinja::context;
+
+    #[test]
+    fn test_to_yaml() {
+        let result = render_string(
+            "{{ data | to_yaml }}",
+            &context! {data => context! {a => 1, b => vec!["x"]}},
+        )
+        .unwrap();
+        assert_eq!(result, "a: 1\nb:\n- x\n");
+    }
+
+    #[test]
+    fn test_from_yaml() {
+        let result = render_string("{{ ('a: 1\nb: foo' | from_yaml).b }}", &context! {}).unwrap();
+        assert_eq!(result, "foo");
+    }
+
+    #[test]
+    fn test_to_nice_json() {
+        let result = render_string(
+            "{{ data | to_nice_json }}",
+            &context! {data => context! {a => 1}},
+        )
+        .unwrap();
+        assert_eq!(result, "{\n    \"a\": 1\n}");
+
+        let result = render_string(
+            "{{ data | to_nice_json(indent=2) }}",
+            &context! {data => context! {a => 1}},
+        )
+        .unwrap();
+        assert_eq!(result, "{\n  \"a\": 1\n}");
+    }
+
+    #[test]
+    fn test_from_json() {
+        let result =
+            render_string(r#"{{ ('{"a": [1, 2]}' | from_json).a[1] }}"#, &context! {}).unwrap();
+        assert_eq!(result, "2");
+    }
+
+    #[test]
+    fn test_from_json_invalid() {
+        let result = render_string("{{ 'not json' | from_json }}", &context! {});
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_combine() {
+        let result = render_string(
+            "{{ a | combine(b) | tojson }}",
+            &context! {
+                a => context! {x => context! {y => 1, z => 2}},
+                b => context! {x => context! {z => 3}, w => 4},
+            },
+        )
+        .unwrap();
+        assert_eq!(result, r#"{"w":4,"x":{"y":1,"z":3}}"#);
+    }
+
+    #[test]
+    fn test_combine_not_recursive() {
+        let result = render_string(
+            "{{ a | combine(b, recursive=false) | tojson }}",
+            &context! {
+                a => context! {x => context! {y => 1, z => 2}},
+                b => context! {x => context! {z => 3}},
+            },
+        )
+        .unwrap();
+        assert_eq!(result, r#"{"x":{"z":3}}"#);
+    }
+
+    #[test]
+    fn test_combine_multiple() {
+        let result = render_string(
+            "{{ {'a': 1} | combine({'b': 2}, {'a': 3}) | tojson }}",
+            &context! {},
+        )
+        .unwrap();
+        assert_eq!(result, r#"{"a":3,"b":2}"#);
+    }
+
+    #[test]
+    fn test_combine_lists() {
+        let vars = context! {
+            a => context! {x => [1, 2], y => context! {z => [1]}},
+            b => context! {x => [2, 3], y => context! {z => [2]}},
+        };
+        let combine_with = |options: &str| {
+            render_string(
+                &format!("{{{{ a | combine(b{options}) | tojson }}}}"),
+                &vars,
+            )
+            .unwrap()
+        };
+
+        assert_eq!(combine_with(""), r#"{"x":[2,3],"y":{"z":[2]}}"#);
+        assert_eq!(
+            combine_with(", list_merge='replace'"),
+            r#"{"x":[2,3],"y":{"z":[2]}}"#
+        );
+        assert_eq!(
+            combine_with(", list_merge='keep'"),
+            r#"{"x":[1,2],"y":{"z":[1]}}"#
+        );
+        assert_eq!(
+            combine_with(", list_merge='append'"),
+            r#"{"x":[1,2,2,3],"y":{"z":[1,2]}}"#
+        );
+        assert_eq!(
+            combine_with(", list_merge='prepend'"),
+            r#"{"x":[2,3,1,2],"y":{"z":[2,1]}}"#
+        );
+        assert_eq!(
+            combine_with(", list_merge='append_rp'"),
+            r#"{"x":[1,2,3],"y":{"z":[1,2]}}"#
+        );
+        assert_eq!(
+            combine_with(", list_merge='prepend_rp'"),
+            r#"{"x":[2,3,1],"y":{"z":[2,1]}}"#
+        );
+        assert_eq!(
+            combine_with(", recursive=false, list_merge='append'"),
+            r#"{"x":[1,2,2,3],"y":{"z":[2]}}"#
+        );
+    }
+
+    #[test]
+    fn test_combine_invalid_list_merge() {
+        let result = render_string(
+            "{{ {'a': [1]} | combine({'a': [2]}, list_merge='foo') }}",
+            &context! {},
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_combine_invalid() {
+        let result = render_string("{{ {'a': 1} | combine('foo') }}", &context! {});
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_dict2items() {
+        let result = render_string("{{ {'a': 1} | dict2items | tojson }}", &context! {}).unwrap();
+        assert_eq!(result, r#"[{"key":"a","value":1}]"#);
+
+        let result = render_string(
+            "{{ {'a': 1} | dict2items(key_name='name', value_name='data') | tojson }}",
+            &context! {},
+        )
+        .unwrap();
+        assert_eq!(result, r#"[{"data":1,"name":"a"}]"#);
+    }
+
+    #[test]
+    fn test_items2dict() {
+        let result = render_string(
+            "{{ [{'key': 'a', 'value': 1}, {'key': 'b', 'value': 2}] | items2dict | tojson }}",
+            &context! {},
+        )
+        .unwrap();
+        assert_eq!(result, r#"{"a":1,"b":2}"#);
+
+        let result = render_string(
+            "{{ [{'name': 'a', 'data': 1}] | items2dict(key_name='name', value_name='data') | tojson }}",
+            &context! {},
+        )
+        .unwrap();
+        assert_eq!(result, r#"{"a":1}"#);
+    }
+
+    #[test]
+    fn test_items2dict_missing_key() {
+        let result = render_string("{{ [{'value': 1}] | items2dict }}", &context! {});
+        assert!(result.is_err());
+    }
+}
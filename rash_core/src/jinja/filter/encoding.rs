@@ -0,0 +1,363 @@
+use crate::jinja::filter::invalid_operation;
+
+use std::result::Result as StdResult;
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use md5::Md5;
+use minijinja::value::Kwargs;
+use minijinja::{Error as MinijinjaError, Value};
+use rand::RngExt;
+use sha1::Sha1;
+use sha2::{Digest, Sha256, Sha512};
+
+const CRYPT_CHARS: &[u8] = b"./0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+const CRYPT_SALT_MAX_LEN: usize = 16;
+const CRYPT_DEFAULT_ROUNDS: usize = 5000;
+const CRYPT_MIN_ROUNDS: usize = 1000;
+const CRYPT_MAX_ROUNDS: usize = 999_999_999;
+
+/// Byte order used by SHA-256 crypt when encoding the final digest.
+const SHA256_CRYPT_ORDER: &[(usize, usize, usize)] = &[
+    (0, 10, 20),
+    (21, 1, 11),
+    (12, 22, 2),
+    (3, 13, 23),
+    (24, 4, 14),
+    (15, 25, 5),
+    (6, 16, 26),
+    (27, 7, 17),
+    (18, 28, 8),
+    (9, 19, 29),
+];
+
+/// Byte order used by SHA-512 crypt when encoding the final digest.
+const SHA512_CRYPT_ORDER: &[(usize, usize, usize)] = &[
+    (0, 21, 42),
+    (22, 43, 1),
+    (44, 2, 23),
+    (3, 24, 45),
+    (25, 46, 4),
+    (47, 5, 26),
+    (6, 27, 48),
+    (28, 49, 7),
+    (50, 8, 29),
+    (9, 30, 51),
+    (31, 52, 10),
+    (53, 11, 32),
+    (12, 33, 54),
+    (34, 55, 13),
+    (56, 14, 35),
+    (15, 36, 57),
+    (37, 58, 16),
+    (59, 17, 38),
+    (18, 39, 60),
+    (40, 61, 19),
+    (62, 20, 41),
+];
+
+pub fn b64encode(value: String) -> String {
+    STANDARD.encode(value.as_bytes())
+}
+
+pub fn b64decode(value: String) -> StdResult<String, MinijinjaError> {
+    let decoded = STANDARD
+        .decode(value.trim().as_bytes())
+        .map_err(invalid_operation)?;
+    String::from_utf8(decoded).map_err(invalid_operation)
+}
+
+fn hex_digest<D: Digest>(value: &str) -> String {
+    D::digest(value.as_bytes())
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+pub fn hash(value: String, algorithm: Option<String>) -> StdResult<String, MinijinjaError> {
+    match algorithm.as_deref().unwrap_or("sha1") {
+        "md5" => Ok(hex_digest::<Md5>(&value)),
+        "sha1" => Ok(hex_digest::<Sha1>(&value)),
+        "sha256" => Ok(hex_digest::<Sha256>(&value)),
+        "sha512" => Ok(hex_digest::<Sha512>(&value)),
+        other => Err(invalid_operation(format!(
+            "Unsupported hash algorithm '{other}'. Valid options: md5, sha1, sha256, sha512"
+        ))),
+    }
+}
+
+fn generate_crypt_salt() -> String {
+    let mut rng = rand::rng();
+    (0..CRYPT_SALT_MAX_LEN)
+        .map(|_| CRYPT_CHARS[rng.random_range(0..CRYPT_CHARS.len())] as char)
+        .collect()
+}
+
+fn crypt_b64_from_24bit(b2: u8, b1: u8, b0: u8, n: usize, output: &mut String) {
+    let mut w = ((b2 as u32) << 16) | ((b1 as u32) << 8) | (b0 as u32);
+    for _ in 0..n {
+        output.push(CRYPT_CHARS[(w & 0x3f) as usize] as char);
+        w >>= 6;
+    }
+}
+
+/// Repeat `source` until `len` bytes are produced.
+fn repeat_to_len(source: &[u8], len: usize) -> Vec<u8> {
+    source.iter().copied().cycle().take(len).collect()
+}
+
+/// SHA-crypt implementation as described in <https://www.akkadia.org/drepper/SHA-crypt.txt>.
+fn sha_crypt<D: Digest>(password: &[u8], salt: &[u8], rounds: usize) -> Vec<u8> {
+    let digest_b = {
+        let mut ctx = D::new();
+        ctx.update(password);
+        ctx.update(salt);
+        ctx.update(password);
+        ctx.finalize().to_vec()
+    };
+
+    let digest_a = {
+        let mut ctx = D::new();
+        ctx.update(password);
+        ctx.update(salt);
+        ctx.update(repeat_to_len(&digest_b, password.len()));
+
+        let mut length = password.len();
+        while length > 0 {
+            if length & 1 != 0 {
+                ctx.update(&digest_b);
+            } else {
+                ctx.update(password);
+            }
+            length >>= 1;
+        }
+        ctx.finalize().to_vec()
+    };
+
+    let p_bytes = {
+        let mut ctx = D::new();
+        for _ in 0..password.len() {
+            ctx.update(password);
+        }
+        repeat_to_len(&ctx.finalize(), password.len())
+    };
+
+    let s_bytes = {
+        let mut ctx = D::new();
+        for _ in 0..(16 + digest_a[0] as usize) {
+            ctx.update(salt);
+        }
+        repeat_to_len(&ctx.finalize(), salt.len())
+    };
+
+    let mut previous = digest_a;
+    for round in 0..rounds {
+        let mut ctx = D::new();
+        if round % 2 != 0 {
+            ctx.update(&p_bytes);
+        } else {
+            ctx.update(&previous);
+        }
+        if round % 3 != 0 {
+            ctx.update(&s_bytes);
+        }
+        if round % 7 != 0 {
+            ctx.update(&p_bytes);
+        }
+        if round % 2 != 0 {
+            ctx.update(&previous);
+        } else {
+            ctx.update(&p_bytes);
+        }
+        previous = ctx.finalize().to_vec();
+    }
+    previous
+}
+
+fn sha_crypt_hash(password: &str, salt: &str, rounds: Option<usize>, scheme: &str) -> String {
+    let salt = &salt[..salt.len().min(CRYPT_SALT_MAX_LEN)];
+    let effective_rounds = rounds
+        .unwrap_or(CRYPT_DEFAULT_ROUNDS)
+        .clamp(CRYPT_MIN_ROUNDS, CRYPT_MAX_ROUNDS);
+
+    let (prefix, digest, order) = match scheme {
+        "sha256" => (
+            "$5$",
+            sha_crypt::<Sha256>(password.as_bytes(), salt.as_bytes(), effective_rounds),
+            SHA256_CRYPT_ORDER,
+        ),
+        _ => (
+            "$6$",
+            sha_crypt::<Sha512>(password.as_bytes(), salt.as_bytes(), effective_rounds),
+            SHA512_CRYPT_ORDER,
+        ),
+    };
+
+    let mut encoded = String::new();
+    for &(b2, b1, b0) in order {
+        crypt_b64_from_24bit(digest[b2], digest[b1], digest[b0], 4, &mut encoded);
+    }
+    match scheme {
+        "sha256" => crypt_b64_from_24bit(0, digest[31], digest[30], 3, &mut encoded),
+        _ => crypt_b64_from_24bit(0, 0, digest[63], 2, &mut encoded),
+    }
+
+    let rounds_str = match rounds {
+        Some(_) => format!("rounds={effective_rounds}$"),
+        None => String::new(),
+    };
+    format!("{prefix}{rounds_str}{salt}${encoded}")
+}
+
+pub fn password_hash(
+    value: String,
+    scheme: Option<String>,
+    options: Kwargs,
+) -> StdResult<String, MinijinjaError> {
+    let salt: Option<String> = options.get("salt")?;
+    let rounds: Option<usize> = options.get("rounds")?;
+    options.assert_all_used()?;
+
+    let scheme = match scheme.as_deref().unwrap_or("sha512") {
+        "sha512" | "sha512_crypt" => "sha512",
+        "sha256" | "sha256_crypt" => "sha256",
+        other => {
+            return Err(invalid_operation(format!(
+                "Unsupported password hash scheme '{other}'. Valid options: sha512, sha256"
+            )));
+        }
+    };
+
+    let salt = match salt {
+        Some(s) if s.bytes().all(|b| CRYPT_CHARS.contains(&b)) => s,
+        Some(s) => {
+            return Err(invalid_operation(format!(
+                "Invalid salt '{s}': only [./0-9A-Za-z] characters are allowed"
+            )));
+        }
+        None => generate_crypt_salt(),
+    };
+
+    Ok(sha_crypt_hash(&value, &salt, rounds, scheme))
+}
+
+pub fn quote(value: Value) -> StdResult<String, MinijinjaError> {
+    let s = value.to_string();
+    shlex::try_quote(&s)
+        .map(|quoted| quoted.into_owned())
+        .map_err(invalid_operation)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jinja::render_string;
+
+    use minijinja::context;
+
+    #[test]
+    fn test_b64encode() {
+        let result = render_string("{{ 'hello world' | b64encode }}", &context! {}).unwrap();
+        assert_eq!(result, "aGVsbG8gd29ybGQ=");
+    }
+
+    #[test]
+    fn test_b64decode() {
+        let result = render_string("{{ 'aGVsbG8gd29ybGQ=' | b64decode }}", &context! {}).unwrap();
+        assert_eq!(result, "hello world");
+    }
+
+    #[test]
+    fn test_b64decode_invalid() {
+        let result = render_string("{{ '%%%' | b64decode }}", &context! {});
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_hash() {
+        let result = render_string("{{ 'test' | hash }}", &context! {}).unwrap();
+        assert_eq!(result, "a94a8fe5ccb19ba61c4c0873d391e987982fbbd3");
+
+        let result = render_string("{{ 'test' | hash('sha256') }}", &context! {}).unwrap();
+        assert_eq!(
+            result,
+            "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08"
+        );
+
+        let result = render_string("{{ 'test' | hash('md5') }}", &context! {}).unwrap();
+        assert_eq!(result, "098f6bcd4621d373cade4e832627b4f6");
+    }
+
+    #[test]
+    fn test_hash_invalid_algorithm() {
+        let result = render_string("{{ 'test' | hash('foo') }}", &context! {});
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_password_hash_sha512() {
+        let result = sha_crypt_hash("Hello world!", "saltstring", None, "sha512");
+        assert_eq!(
+            result,
+            "$6$saltstring$svn8UoSVapNtMuq1ukKS4tPQd8iKwSMHWjl/O817G3uBnIFNjnQJuesI68u4OTLiBFdcbYEdFCoEOfaS35inz1"
+        );
+    }
+
+    #[test]
+    fn test_password_hash_sha512_rounds() {
+        let result = sha_crypt_hash(
+            "Hello world!",
+            "saltstringsaltstring",
+            Some(10000),
+            "sha512",
+        );
+        assert_eq!(
+            result,
+            "$6$rounds=10000$saltstringsaltst$OW1/O6BYHV6BcXZu8QVeXbDWra3Oeqh0sbHbbMCVNSnCM/UrjmM0Dp8vOuZeHBy/YTBmSK6H9qs/y3RnOaw5v."
+        );
+    }
+
+    #[test]
+    fn test_password_hash_sha256() {
+        let result = sha_crypt_hash("Hello world!", "saltstring", None, "sha256");
+        assert_eq!(
+            result,
+            "$5$saltstring$5B8vYYiY.CVt1RlTTf8KbXBH3hsxY/GNooZaBBGWEc5"
+        );
+    }
+
+    #[test]
+    fn test_password_hash_filter() {
+        let result = render_string(
+            "{{ 'Hello world!' | password_hash('sha512', salt='saltstring') }}",
+            &context! {},
+        )
+        .unwrap();
+        assert!(result.starts_with("$6$saltstring$"));
+
+        let result = render_string("{{ 'secret' | password_hash }}", &context! {}).unwrap();
+        assert!(result.starts_with("$6$"));
+        assert_eq!(result.split('$').count(), 4);
+    }
+
+    #[test]
+    fn test_password_hash_invalid_salt() {
+        let result = render_string(
+            "{{ 'secret' | password_hash('sha512', salt='bad$salt') }}",
+            &context! {},
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_quote() {
+        let result = render_string("{{ 'hello world' | quote }}", &context! {}).unwrap();
+        assert_eq!(result, "'hello world'");
+
+        let result = render_string("{{ 'simple' | quote }}", &context! {}).unwrap();
+        assert_eq!(result, "simple");
+
+        let result = render_string(r#"{{ "it's" | quote }}"#, &context! {}).unwrap();
+        assert_eq!(result, r#""it's""#);
+    }
+}
@@ -0,0 +1,294 @@
+use crate::jinja::filter::invalid_operation;
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::result::Result as StdResult;
+
+use minijinja::value::ValueKind;
+use minijinja::{Error as MinijinjaError, Value};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum IpVersion {
+    Any,
+    V4,
+    V6,
+}
+
+#[derive(Debug)]
+struct IpNetwork {
+    address: IpAddr,
+    prefix: u32,
+}
+
+impl IpNetwork {
+    fn parse(value: &str, version: IpVersion) -> Option<Self> {
+        let (address_str, prefix_str) = match value.split_once('/') {
+            Some((address, prefix)) => (address, Some(prefix)),
+            None => (value, None),
+        };
+        let address: IpAddr = address_str.trim().parse().ok()?;
+        match (version, address) {
+            (IpVersion::V4, IpAddr::V6(_)) | (IpVersion::V6, IpAddr::V4(_)) => return None,
+            _ => (),
+        };
+        let max_prefix = Self::max_prefix(&address);
+        let prefix = match prefix_str {
+            Some(prefix) => prefix.trim().parse::<u32>().ok()?,
+            None => max_prefix,
+        };
+        if prefix > max_prefix {
+            return None;
+        }
+        Some(IpNetwork { address, prefix })
+    }
+
+    fn max_prefix(address: &IpAddr) -> u32 {
+        match address {
+            IpAddr::V4(_) => 32,
+            IpAddr::V6(_) => 128,
+        }
+    }
+
+    fn mask(&self) -> u128 {
+        let bits = Self::max_prefix(&self.address);
+        let host_bits = bits - self.prefix;
+        let all = if bits == 128 {
+            u128::MAX
+        } else {
+            (1u128 << bits) - 1
+        };
+        all.checked_shr(host_bits)
+            .unwrap_or(0)
+            .checked_shl(host_bits)
+            .unwrap_or(0)
+            & all
+    }
+
+    fn to_ip(&self, bits: u128) -> IpAddr {
+        match self.address {
+            IpAddr::V4(_) => IpAddr::V4(Ipv4Addr::from(bits as u32)),
+            IpAddr::V6(_) => IpAddr::V6(Ipv6Addr::from(bits)),
+        }
+    }
+
+    fn address_bits(&self) -> u128 {
+        match self.address {
+            IpAddr::V4(address) => u32::from(address) as u128,
+            IpAddr::V6(address) => u128::from(address),
+        }
+    }
+
+    fn network(&self) -> IpAddr {
+        self.to_ip(self.address_bits() & self.mask())
+    }
+
+    fn netmask(&self) -> IpAddr {
+        self.to_ip(self.mask())
+    }
+
+    fn broadcast(&self) -> Option<IpAddr> {
+        match self.address {
+            IpAddr::V4(_) => {
+                Some(self.to_ip((self.address_bits() | !self.mask()) & u32::MAX as u128))
+            }
+            IpAddr::V6(_) => None,
+        }
+    }
+
+    fn size(&self) -> u128 {
+        let host_bits = Self::max_prefix(&self.address) - self.prefix;
+        1u128.checked_shl(host_bits).unwrap_or(u128::MAX)
+    }
+
+    fn is_private(&self) -> bool {
+        match self.address {
+            IpAddr::V4(address) => {
+                address.is_private() || address.is_loopback() || address.is_link_local()
+            }
+            IpAddr::V6(address) => {
+                let first_segment = address.segments()[0];
+                address.is_loopback()
+                    // unique local fc00::/7
+                    || (first_segment & 0xfe00) == 0xfc00
+                    // link local fe80::/10
+                    || (first_segment & 0xffc0) == 0xfe80
+            }
+        }
+    }
+
+    fn is_public(&self) -> bool {
+        !self.is_private() && !self.address.is_unspecified() && !self.address.is_multicast()
+    }
+}
+
+fn apply_query(
+    value: &str,
+    query: Option<&str>,
+    version: IpVersion,
+) -> StdResult<Value, MinijinjaError> {
+    let network = match IpNetwork::parse(value, version) {
+        Some(network) => network,
+        None => return Ok(Value::from(false)),
+    };
+
+    match query {
+        None | Some("") => Ok(Value::from(value)),
+        Some("address") => Ok(Value::from(network.address.to_string())),
+        Some("network") => Ok(Value::from(network.network().to_string())),
+        Some("netmask") => Ok(Value::from(network.netmask().to_string())),
+        Some("prefix") => Ok(Value::from(network.prefix)),
+        Some("broadcast") => Ok(network
+            .broadcast()
+            .map(|broadcast| Value::from(broadcast.to_string()))
+            .unwrap_or_else(|| Value::from(false))),
+        Some("size") => Ok(Value::from(network.size())),
+        Some("private") => Ok(match network.is_private() {
+            true => Value::from(value),
+            false => Value::from(false),
+        }),
+        Some("public") => Ok(match network.is_public() {
+            true => Value::from(value),
+            false => Value::from(false),
+        }),
+        Some(other) => Err(invalid_operation(format!("unknown ipaddr query: {other}"))),
+    }
+}
+
+fn filter_ip(
+    value: Value,
+    query: Option<String>,
+    version: IpVersion,
+) -> StdResult<Value, MinijinjaError> {
+    match value.kind() {
+        ValueKind::Seq => {
+            let mut result = Vec::new();
+            for item in value.try_iter()? {
+                let item_result = match item.as_str() {
+                    Some(s) => apply_query(s, query.as_deref(), version)?,
+                    None => Value::from(false),
+                };
+                if item_result.kind() != ValueKind::Bool || item_result.is_true() {
+                    result.push(item_result);
+                }
+            }
+            Ok(Value::from(result))
+        }
+        _ => match value.as_str() {
+            Some(s) => apply_query(s, query.as_deref(), version),
+            None => Ok(Value::from(false)),
+        },
+    }
+}
+
+pub fn ipaddr(value: Value, query: Option<String>) -> StdResult<Value, MinijinjaError> {
+    filter_ip(value, query, IpVersion::Any)
+}
+
+pub fn ipv4(value: Value, query: Option<String>) -> StdResult<Value, MinijinjaError> {
+    filter_ip(value, query, IpVersion::V4)
+}
+
+pub fn ipv6(value: Value, query: Option<String>) -> StdResult<Value, MinijinjaError> {
+    filter_ip(value, query, IpVersion::V6)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::jinja::render_string;
+
+    use minijinja::context;
+
+    fn render(template: &str) -> String {
+        render_string(template, &context! {}).unwrap()
+    }
+
+    #[test]
+    fn test_ipaddr_valid() {
+        assert_eq!(render("{{ '192.168.1.10' | ipaddr }}"), "192.168.1.10");
+        assert_eq!(render("{{ '10.0.0.0/8' | ipaddr }}"), "10.0.0.0/8");
+        assert_eq!(render("{{ 'fe80::1/64' | ipaddr }}"), "fe80::1/64");
+        assert_eq!(render("{{ 'foo' | ipaddr }}"), "False");
+        assert_eq!(render("{{ '10.0.0.1/33' | ipaddr }}"), "False");
+    }
+
+    #[test]
+    fn test_ipaddr_list() {
+        assert_eq!(
+            render("{{ ['10.0.0.1', 'foo', '::1'] | ipaddr | tojson }}"),
+            r#"["10.0.0.1","::1"]"#
+        );
+        assert_eq!(
+            render("{{ ['10.0.0.1', 'foo', '::1'] | ipv4 | tojson }}"),
+            r#"["10.0.0.1"]"#
+        );
+        assert_eq!(
+            render("{{ ['10.0.0.1', 'foo', '::1'] | ipv6 | tojson }}"),
+            r#"["::1"]"#
+        );
+    }
+
+    #[test]
+    fn test_ipaddr_queries() {
+        assert_eq!(
+            render("{{ '192.168.1.10/24' | ipaddr('address') }}"),
+            "192.168.1.10"
+        );
+        assert_eq!(
+            render("{{ '192.168.1.10/24' | ipaddr('network') }}"),
+            "192.168.1.0"
+        );
+        assert_eq!(
+            render("{{ '192.168.1.10/24' | ipaddr('netmask') }}"),
+            "255.255.255.0"
+        );
+        assert_eq!(render("{{ '192.168.1.10/24' | ipaddr('prefix') }}"), "24");
+        assert_eq!(
+            render("{{ '192.168.1.10/24' | ipaddr('broadcast') }}"),
+            "192.168.1.255"
+        );
+        assert_eq!(render("{{ '192.168.1.10/24' | ipaddr('size') }}"), "256");
+        assert_eq!(render("{{ '10.0.0.1' | ipaddr('prefix') }}"), "32");
+        assert_eq!(render("{{ '0.0.0.0/0' | ipaddr('netmask') }}"), "0.0.0.0");
+    }
+
+    #[test]
+    fn test_ipaddr_v6_queries() {
+        assert_eq!(
+            render("{{ '2001:db8::1/32' | ipaddr('network') }}"),
+            "2001:db8::"
+        );
+        assert_eq!(
+            render("{{ '2001:db8::1/32' | ipaddr('netmask') }}"),
+            "ffff:ffff::"
+        );
+        assert_eq!(
+            render("{{ '2001:db8::1/32' | ipaddr('broadcast') }}"),
+            "False"
+        );
+        assert_eq!(render("{{ '::1/120' | ipaddr('size') }}"), "256");
+    }
+
+    #[test]
+    fn test_ipaddr_private_public() {
+        assert_eq!(render("{{ '10.1.2.3' | ipaddr('private') }}"), "10.1.2.3");
+        assert_eq!(render("{{ '8.8.8.8' | ipaddr('private') }}"), "False");
+        assert_eq!(render("{{ '8.8.8.8' | ipaddr('public') }}"), "8.8.8.8");
+        assert_eq!(render("{{ 'fd00::1' | ipaddr('public') }}"), "False");
+        assert_eq!(
+            render("{{ ['10.0.0.1', '1.1.1.1'] | ipaddr('public') | tojson }}"),
+            r#"["1.1.1.1"]"#
+        );
+    }
+
+    #[test]
+    fn test_ipv4_ipv6() {
+        assert_eq!(render("{{ '::1' | ipv4 }}"), "False");
+        assert_eq!(render("{{ '10.0.0.1' | ipv6 }}"), "False");
+        assert_eq!(render("{{ '10.0.0.1/24' | ipv4('network') }}"), "10.0.0.0");
+    }
+
+    #[test]
+    fn test_ipaddr_unknown_query() {
+        let result = render_string("{{ '10.0.0.1' | ipaddr('foo') }}", &context! {});
+        assert!(result.is_err());
+    }
+}
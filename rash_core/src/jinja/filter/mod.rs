@@ -0,0 +1,39 @@
+mod data;
+mod encoding;
+mod ipaddr;
+mod path;
+pub(super) mod regex;
+
+use minijinja::{Error as MinijinjaError, ErrorKind as MinijinjaErrorKind};
+
+pub fn add_filters(env: &mut minijinja::Environment<'static>) {
+    env.add_filter("to_yaml", data::to_yaml);
+    env.add_filter("from_yaml", data::from_yaml);
+    env.add_filter("to_nice_json", data::to_nice_json);
+    env.add_filter("from_json", data::from_json);
+    env.add_filter("combine", data::combine);
+    env.add_filter("dict2items", data::dict2items);
+    env.add_filter("items2dict", data::items2dict);
+
+    env.add_filter("b64encode", encoding::b64encode);
+    env.add_filter("b64decode", encoding::b64decode);
+    env.add_filter("hash", encoding::hash);
+    env.add_filter("password_hash", encoding::password_hash);
+    env.add_filter("quote", encoding::quote);
+
+    env.add_filter("regex_replace", regex::regex_replace);
+    env.add_filter("regex_search", regex::regex_search);
+    env.add_filter("regex_findall", regex::regex_findall);
+
+    env.add_filter("basename", path::basename);
+    env.add_filter("dirname", path::dirname);
+    env.add_filter("realpath", path::realpath);
+
+    env.add_filter("ipaddr", ipaddr::ipaddr);
+    env.add_filter("ipv4", ipaddr::ipv4);
+    env.add_filter("ipv6", ipaddr::ipv6);
+}
+
+pub(super) fn invalid_operation<E: std::fmt::Display>(err: E) -> MinijinjaError {
+    MinijinjaError::new(MinijinjaErrorKind::InvalidOperation, err.to_string())
+}
@@ -0,0 +1,120 @@
+use std::env;
+use std::path::{Component, Path, PathBuf};
+
+/// Text after the last `/`, as Python `os.path.basename`: empty if the path ends with `/`.
+pub fn basename(value: String) -> String {
+    match value.rsplit_once('/') {
+        Some((_, name)) => name.to_owned(),
+        None => value,
+    }
+}
+
+/// Text before the last `/`, as Python `os.path.dirname`: the path without the trailing `/`
+/// if it ends with it.
+pub fn dirname(value: String) -> String {
+    match value.rsplit_once('/') {
+        Some((head, _)) if head.trim_end_matches('/').is_empty() => format!("{head}/"),
+        Some((head, _)) => head.trim_end_matches('/').to_owned(),
+        None => String::new(),
+    }
+}
+
+/// Resolve symlinks when the path exists, otherwise normalize it lexically.
+pub fn realpath(value: String) -> String {
+    let path = Path::new(&value);
+    if let Ok(canonical) = path.canonicalize() {
+        return canonical.to_string_lossy().into_owned();
+    }
+
+    let absolute = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        env::current_dir().unwrap_or_default().join(path)
+    };
+
+    absolute
+        .components()
+        .fold(PathBuf::new(), |mut acc, component| {
+            match component {
+                Component::ParentDir => {
+                    acc.pop();
+                }
+                Component::CurDir => {}
+                other => acc.push(other),
+            };
+            acc
+        })
+        .to_string_lossy()
+        .into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::jinja::render_string;
+
+    use std::fs::create_dir;
+    use std::os::unix::fs::symlink;
+
+    use minijinja::context;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_basename() {
+        let result =
+            render_string("{{ '/etc/rash/config.yml' | basename }}", &context! {}).unwrap();
+        assert_eq!(result, "config.yml");
+
+        let result = render_string("{{ '/' | basename }}", &context! {}).unwrap();
+        assert_eq!(result, "");
+
+        let result = render_string("{{ '/etc/' | basename }}", &context! {}).unwrap();
+        assert_eq!(result, "");
+
+        let result = render_string("{{ 'config.yml' | basename }}", &context! {}).unwrap();
+        assert_eq!(result, "config.yml");
+    }
+
+    #[test]
+    fn test_dirname() {
+        let result = render_string("{{ '/etc/rash/config.yml' | dirname }}", &context! {}).unwrap();
+        assert_eq!(result, "/etc/rash");
+
+        let result = render_string("{{ 'config.yml' | dirname }}", &context! {}).unwrap();
+        assert_eq!(result, "");
+
+        let result = render_string("{{ '/etc/' | dirname }}", &context! {}).unwrap();
+        assert_eq!(result, "/etc");
+
+        let result = render_string("{{ '/etc' | dirname }}", &context! {}).unwrap();
+        assert_eq!(result, "/");
+
+        let result = render_string("{{ '/etc//rash.yml' | dirname }}", &context! {}).unwrap();
+        assert_eq!(result, "/etc");
+    }
+
+    #[test]
+    fn test_realpath_symlink() {
+        let dir = tempdir().unwrap();
+        let target = dir.path().join("target");
+        create_dir(&target).unwrap();
+        let link = dir.path().join("link");
+        symlink(&target, &link).unwrap();
+
+        let result = render_string(
+            "{{ path | realpath }}",
+            &context! {path => link.to_str().unwrap()},
+        )
+        .unwrap();
+        assert_eq!(result, target.canonicalize().unwrap().to_str().unwrap());
+    }
+
+    #[test]
+    fn test_realpath_not_existing() {
+        let result = render_string(
+            "{{ '/not/existing/../path/./file' | realpath }}",
+            &context! {},
+        )
+        .unwrap();
+        assert_eq!(result, "/not/path/file");
+    }
+}
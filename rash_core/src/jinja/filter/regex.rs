@@ -0,0 +1,234 @@
+use crate::jinja::filter::invalid_operation;
+
+use std::result::Result as StdResult;
+use std::sync::LazyLock;
+
+use minijinja::value::{Kwargs, Rest};
+use minijinja::{Error as MinijinjaError, Value};
+use regex::{Captures, Regex, RegexBuilder};
+
+static PYTHON_BACKREF_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\\(?:g<(\w+)>|(\d+))").unwrap());
+
+pub(crate) fn build_regex(pattern: &str, options: &Kwargs) -> StdResult<Regex, MinijinjaError> {
+    let ignorecase: bool = options.get::<Option<bool>>("ignorecase")?.unwrap_or(false);
+    let multiline: bool = options.get::<Option<bool>>("multiline")?.unwrap_or(false);
+    options.assert_all_used()?;
+
+    RegexBuilder::new(pattern)
+        .case_insensitive(ignorecase)
+        .multi_line(multiline)
+        .build()
+        .map_err(invalid_operation)
+}
+
+/// Translate Python style backreferences (`\1`, `\g<name>`) to `regex` crate syntax.
+///
+/// `$` is literal in Python replacements, so it is escaped first.
+fn translate_backrefs(replacement: &str) -> String {
+    PYTHON_BACKREF_RE
+        .replace_all(&replacement.replace('$', "$$"), |caps: &Captures| {
+            let group = caps.get(1).or_else(|| caps.get(2)).unwrap().as_str();
+            format!("${{{group}}}")
+        })
+        .into_owned()
+}
+
+fn get_group(caps: &Captures, group: &str) -> Value {
+    let name = match PYTHON_BACKREF_RE.captures(group) {
+        Some(c) => c.get(1).or_else(|| c.get(2)).unwrap().as_str().to_owned(),
+        None => group.to_owned(),
+    };
+    let matched = match name.parse::<usize>() {
+        Ok(index) => caps.get(index),
+        Err(_) => caps.name(&name),
+    };
+    matched
+        .map(|m| Value::from(m.as_str()))
+        .unwrap_or_else(|| Value::from(()))
+}
+
+fn captures_to_value(caps: &Captures) -> Value {
+    match caps.len() {
+        1 => Value::from(caps.get(0).map(|m| m.as_str()).unwrap_or_default()),
+        2 => Value::from(caps.get(1).map(|m| m.as_str()).unwrap_or_default()),
+        _ => Value::from(
+            caps.iter()
+                .skip(1)
+                .map(|m| Value::from(m.map(|m| m.as_str()).unwrap_or_default()))
+                .collect::<Vec<_>>(),
+        ),
+    }
+}
+
+pub fn regex_replace(
+    value: String,
+    pattern: String,
+    replacement: Option<String>,
+    options: Kwargs,
+) -> StdResult<String, MinijinjaError> {
+    let re = build_regex(&pattern, &options)?;
+    let replacement = translate_backrefs(&replacement.unwrap_or_default());
+    Ok(re.replace_all(&value, replacement.as_str()).into_owned())
+}
+
+pub fn regex_search(
+    value: String,
+    pattern: String,
+    groups: Rest<String>,
+    options: Kwargs,
+) -> StdResult<Value, MinijinjaError> {
+    let re = build_regex(&pattern, &options)?;
+    match re.captures(&value) {
+        Some(caps) if groups.is_empty() => Ok(Value::from(
+            caps.get(0).map(|m| m.as_str()).unwrap_or_default(),
+        )),
+        Some(caps) => Ok(Value::from(
+            groups
+                .iter()
+                .map(|group| get_group(&caps, group))
+                .collect::<Vec<_>>(),
+        )),
+        None => Ok(Value::from(())),
+    }
+}
+
+pub fn regex_findall(
+    value: String,
+    pattern: String,
+    options: Kwargs,
+) -> StdResult<Value, MinijinjaError> {
+    let re = build_regex(&pattern, &options)?;
+    Ok(Value::from(
+        re.captures_iter(&value)
+            .map(|caps| captures_to_value(&caps))
+            .collect::<Vec<_>>(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jinja::render_string;
+
+    use minijinja::context;
+
+    #[test]
+    fn test_translate_backrefs() {
+        assert_eq!(translate_backrefs(r"\1-\2"), "${1}-${2}");
+        assert_eq!(translate_backrefs(r"\g<host>:80"), "${host}:80");
+        assert_eq!(translate_backrefs("$1"), "$$1");
+        assert_eq!(translate_backrefs(r"$\1"), "$$${1}");
+    }
+
+    #[test]
+    fn test_regex_replace() {
+        let result = render_string(
+            r#"{{ 'ansible' | regex_replace('^a.*i(.*)$', 'a\\1') }}"#,
+            &context! {},
+        )
+        .unwrap();
+        assert_eq!(result, "able");
+
+        let result = render_string(
+            "{{ 'localhost:80' | regex_replace('^(?P<host>.+):(?P<port>\\\\d+)$', '\\\\g<host>, \\\\g<port>') }}",
+            &context! {},
+        )
+        .unwrap();
+        assert_eq!(result, "localhost, 80");
+    }
+
+    #[test]
+    fn test_regex_replace_dollar() {
+        let result = render_string(
+            "{{ 'x' | regex_replace('x', '$5') }}{{ 'price: 3' | regex_replace('(\\\\d+)', '$\\\\1') }}",
+            &context! {},
+        )
+        .unwrap();
+        assert_eq!(result, "$5price: $3");
+    }
+
+    #[test]
+    fn test_regex_replace_ignorecase() {
+        let result = render_string(
+            "{{ 'Hello World' | regex_replace('world', 'rash', ignorecase=true) }}",
+            &context! {},
+        )
+        .unwrap();
+        assert_eq!(result, "Hello rash");
+    }
+
+    #[test]
+    fn test_regex_replace_remove() {
+        let result =
+            render_string("{{ 'a1b2c3' | regex_replace('[0-9]') }}", &context! {}).unwrap();
+        assert_eq!(result, "abc");
+    }
+
+    #[test]
+    fn test_regex_replace_invalid_pattern() {
+        let result = render_string("{{ 'foo' | regex_replace('(', '') }}", &context! {});
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_regex_search() {
+        let result = render_string(
+            "{{ 'server1/database42' | regex_search('database[0-9]+') }}",
+            &context! {},
+        )
+        .unwrap();
+        assert_eq!(result, "database42");
+
+        let result = render_string(
+            "{{ 'server1/database42' | regex_search('nothing') is none }}",
+            &context! {},
+        )
+        .unwrap();
+        assert_eq!(result, "True");
+    }
+
+    #[test]
+    fn test_regex_search_groups() {
+        let result = render_string(
+            r#"{{ 'server1/database42' | regex_search('server([0-9]+)/database([0-9]+)', '\\1', '\\2') | tojson }}"#,
+            &context! {},
+        )
+        .unwrap();
+        assert_eq!(result, r#"["1","42"]"#);
+
+        let result = render_string(
+            r#"{{ 'version: 1.2' | regex_search('(?P<major>\\d+)\\.(?P<minor>\\d+)', '\\g<minor>') | first }}"#,
+            &context! {},
+        )
+        .unwrap();
+        assert_eq!(result, "2");
+    }
+
+    #[test]
+    fn test_regex_search_multiline() {
+        let result = render_string(
+            "{{ 'foo\nbar' | regex_search('^bar', multiline=true) }}",
+            &context! {},
+        )
+        .unwrap();
+        assert_eq!(result, "bar");
+    }
+
+    #[test]
+    fn test_regex_findall() {
+        let result = render_string(
+            "{{ 'a1 b22 c333' | regex_findall('[0-9]+') | tojson }}",
+            &context! {},
+        )
+        .unwrap();
+        assert_eq!(result, r#"["1","22","333"]"#);
+
+        let result = render_string(
+            "{{ 'k1=v1 k2=v2' | regex_findall('(\\\\w+)=(\\\\w+)') | tojson }}",
+            &context! {},
+        )
+        .unwrap();
+        assert_eq!(result, r#"[["k1","v1"],["k2","v2"]]"#);
+    }
+}
@@ -0,0 +1,166 @@
+/// ANCHOR: lookup
+/// # csvfile
+///
+/// Read a column of the first row of a CSV file matching a key.
+///
+/// ## Parameters
+///
+/// | Parameter | Required | Type    | Values | Description                                                          |
+/// | --------- | -------- | ------- | ------ | -------------------------------------------------------------------- |
+/// | key       | yes      | string  |        | Value to match in the key column.                                    |
+/// | file      | yes      | string  |        | Path of the CSV file.                                                |
+/// | col       | no       | integer |        | Column to return, 0 indexed. **[default: `1`]**                      |
+/// | keycol    | no       | integer |        | Column to match the key, 0 indexed. **[default: `0`]**               |
+/// | delimiter | no       | string  |        | Field separator, `TAB` or `\t` for tabs. **[default: `TAB`]**        |
+/// | default   | no       | string  |        | Value returned if the key is not found. Without it, the lookup fails. |
+///
+/// ## Notes
+///
+/// - As in Ansible, fields are separated by tabs unless `delimiter` is set.
+/// - Quoted fields are supported. There is no header row, all rows are matched.
+///
+/// ANCHOR_END: lookup
+/// ANCHOR: examples
+/// ## Example
+///
+/// ```yaml
+/// - name: Get the atomic number of Lithium
+///   debug:
+///     msg: "{{ csvfile('Li', file='elements.csv', delimiter=',') }}"
+///
+/// - name: Get the atomic mass of Lithium
+///   debug:
+///     msg: "{{ csvfile('Li', file='elements.csv', delimiter=',', col=2) }}"
+/// ```
+/// ANCHOR_END: examples
+use crate::jinja::lookup::utils::to_minijinja_error;
+
+use std::result::Result as StdResult;
+
+use csv::ReaderBuilder;
+use log::trace;
+use minijinja::{Error as MinijinjaError, Value, value::Kwargs};
+
+fn parse_delimiter(delimiter: &str) -> StdResult<u8, MinijinjaError> {
+    match delimiter {
+        "TAB" | "\\t" | "\t" => Ok(b'\t'),
+        d if d.len() == 1 => Ok(d.as_bytes()[0]),
+        d => Err(to_minijinja_error(format!(
+            "Invalid delimiter '{d}', it must be a single byte character"
+        ))),
+    }
+}
+
+pub fn function(key: String, options: Kwargs) -> StdResult<Value, MinijinjaError> {
+    let file: String = options.get("file")?;
+    let col: usize = options.get::<Option<usize>>("col")?.unwrap_or(1);
+    let keycol: usize = options.get::<Option<usize>>("keycol")?.unwrap_or(0);
+    let delimiter = parse_delimiter(options.get::<Option<&str>>("delimiter")?.unwrap_or("TAB"))?;
+    let default: Option<String> = options.get("default")?;
+    options.assert_all_used()?;
+
+    trace!("csvfile lookup - reading key '{key}' from '{file}'");
+
+    let mut reader = ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .delimiter(delimiter)
+        .from_path(&file)
+        .map_err(|e| to_minijinja_error(format!("Failed to read file '{file}': {e}")))?;
+
+    for record in reader.records() {
+        let record = record
+            .map_err(|e| to_minijinja_error(format!("Failed to parse file '{file}': {e}")))?;
+        if record.get(keycol) == Some(key.as_str()) {
+            return record.get(col).map(Value::from).ok_or_else(|| {
+                to_minijinja_error(format!(
+                    "Column {col} not found for key '{key}' in '{file}'"
+                ))
+            });
+        }
+    }
+
+    default
+        .map(Value::from)
+        .ok_or_else(|| to_minijinja_error(format!("Key '{key}' not found in '{file}'")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::io::Write;
+
+    use tempfile::NamedTempFile;
+
+    fn lookup(
+        content: &str,
+        key: &str,
+        options: Vec<(&str, Value)>,
+    ) -> Result<Value, MinijinjaError> {
+        let mut temp_file = NamedTempFile::new().unwrap();
+        write!(temp_file, "{content}").unwrap();
+        let options = options.into_iter().chain([(
+            "file",
+            Value::from(temp_file.path().to_string_lossy().as_ref()),
+        )]);
+        function(key.to_owned(), Kwargs::from_iter(options))
+    }
+
+    #[test]
+    fn test_csvfile() {
+        let content = "H,1,1.008\nHe,2,4.0026\nLi,3,\"6,94\"\n";
+        let comma = ("delimiter", Value::from(","));
+
+        assert_eq!(
+            lookup(content, "He", vec![comma.clone()]).unwrap(),
+            Value::from("2")
+        );
+        assert_eq!(
+            lookup(content, "Li", vec![comma.clone(), ("col", Value::from(2))]).unwrap(),
+            Value::from("6,94")
+        );
+        assert_eq!(
+            lookup(
+                content,
+                "3",
+                vec![
+                    comma.clone(),
+                    ("keycol", Value::from(1)),
+                    ("col", Value::from(0))
+                ]
+            )
+            .unwrap(),
+            Value::from("Li")
+        );
+        assert_eq!(
+            lookup(content, "Be", vec![comma, ("default", Value::from("none"))]).unwrap(),
+            Value::from("none")
+        );
+    }
+
+    #[test]
+    fn test_csvfile_tab_delimiter() {
+        assert_eq!(
+            lookup("foo\tboo\nbar\tbaz\n", "bar", vec![]).unwrap(),
+            Value::from("baz")
+        );
+    }
+
+    #[test]
+    fn test_csvfile_errors() {
+        let error = lookup("H,1\n", "Be", vec![("delimiter", Value::from(","))]).unwrap_err();
+        assert!(error.to_string().contains("Key 'Be' not found"));
+
+        let error = lookup(
+            "H,1\n",
+            "H",
+            vec![("delimiter", Value::from(",")), ("col", Value::from(5))],
+        )
+        .unwrap_err();
+        assert!(error.to_string().contains("Column 5 not found for key 'H'"));
+
+        let error = lookup("H,1\n", "H", vec![("delimiter", Value::from(";;"))]).unwrap_err();
+        assert!(error.to_string().contains("Invalid delimiter ';;'"));
+    }
+}
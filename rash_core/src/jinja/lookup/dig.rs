@@ -0,0 +1,643 @@
+/// ANCHOR: lookup
+/// # dig
+///
+/// Resolve DNS records of a domain.
+///
+/// ## Parameters
+///
+/// | Parameter  | Required | Type    | Values                 | Description                                                                        |
+/// | ---------- | -------- | ------- | ---------------------- | ---------------------------------------------------------------------------------- |
+/// | name       | yes      | string  |                        | Domain name to resolve.                                                            |
+/// | qtype      | no       | string  | A/AAAA/MX/SRV/TXT      | Record type. **[default: `A`]**                                                    |
+/// | nameserver | no       | string  |                        | Nameserver address, with optional port, e.g.: `10.0.0.2` or `10.0.0.2:5353`. **[default: first nameserver in `/etc/resolv.conf`]** |
+/// | timeout    | no       | integer |                        | Seconds to wait for the response. **[default: `5`]**                               |
+///
+/// ## Notes
+///
+/// - Returns a list with a value per record, empty if the domain does not exist or has no records
+///   of that type.
+/// - `A`, `AAAA` and `TXT` records are returned as strings.
+/// - `MX` records are returned as dicts with `preference` and `exchange`.
+/// - `SRV` records are returned as dicts with `priority`, `weight`, `port` and `target`.
+/// - Names are returned without the trailing dot.
+/// - Queries are sent over UDP with EDNS0, and retried over TCP if the response is truncated.
+///
+/// ANCHOR_END: lookup
+/// ANCHOR: examples
+/// ## Example
+///
+/// ```yaml
+/// - debug:
+///     msg: "{{ dig('example.com') | first }}"
+///
+/// - name: Get IPv6 addresses from a specific nameserver
+///   debug:
+///     msg: "{{ dig('example.com', qtype='AAAA', nameserver='1.1.1.1') }}"
+///
+/// - name: Configure a backend per SRV record
+///   debug:
+///     msg: "server {{ item.target }}:{{ item.port }} weight={{ item.weight }}"
+///   loop: "{{ dig('_http._tcp.example.com', qtype='SRV') }}"
+/// ```
+/// ANCHOR_END: examples
+use crate::jinja::lookup::utils::to_minijinja_error;
+
+use std::fs;
+use std::io::{Read, Write};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, TcpStream, UdpSocket};
+use std::result::Result as StdResult;
+use std::time::Duration;
+
+use log::trace;
+use minijinja::{Error as MinijinjaError, Value, context, value::Kwargs};
+use rand::RngExt;
+
+const DNS_PORT: u16 = 53;
+const DEFAULT_TIMEOUT: u64 = 5;
+const RESOLV_CONF: &str = "/etc/resolv.conf";
+/// UDP payload size advertised with EDNS0, larger responses are truncated.
+const MAX_RESPONSE_SIZE: usize = 4096;
+/// Limit of compression pointers followed while reading a name, to avoid loops.
+const MAX_POINTERS: usize = 64;
+
+const CLASS_IN: u16 = 1;
+const TYPE_OPT: u16 = 41;
+const FLAG_RECURSION_DESIRED: u16 = 0x0100;
+const FLAG_TRUNCATED: u16 = 0x0200;
+const RCODE_NXDOMAIN: u16 = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum RecordType {
+    A,
+    Aaaa,
+    Mx,
+    Srv,
+    Txt,
+}
+
+impl RecordType {
+    fn from_qtype(qtype: &str) -> StdResult<Self, MinijinjaError> {
+        match qtype.to_uppercase().as_str() {
+            "A" => Ok(RecordType::A),
+            "AAAA" => Ok(RecordType::Aaaa),
+            "MX" => Ok(RecordType::Mx),
+            "SRV" => Ok(RecordType::Srv),
+            "TXT" => Ok(RecordType::Txt),
+            _ => Err(to_minijinja_error(format!(
+                "Unsupported qtype '{qtype}', expected one of: A, AAAA, MX, SRV, TXT"
+            ))),
+        }
+    }
+
+    fn code(self) -> u16 {
+        match self {
+            RecordType::A => 1,
+            RecordType::Mx => 15,
+            RecordType::Txt => 16,
+            RecordType::Aaaa => 28,
+            RecordType::Srv => 33,
+        }
+    }
+}
+
+fn parse_nameserver(nameserver: &str) -> StdResult<SocketAddr, MinijinjaError> {
+    nameserver
+        .parse::<SocketAddr>()
+        .or_else(|_| {
+            nameserver
+                .parse::<IpAddr>()
+                .map(|ip| SocketAddr::new(ip, DNS_PORT))
+        })
+        .map_err(|e| to_minijinja_error(format!("Invalid nameserver '{nameserver}': {e}")))
+}
+
+/// First nameserver of `/etc/resolv.conf`, or localhost if there is none.
+fn default_nameserver() -> SocketAddr {
+    fs::read_to_string(RESOLV_CONF)
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("nameserver"))
+        .find_map(|address| address.trim().parse::<IpAddr>().ok())
+        .map(|ip| SocketAddr::new(ip, DNS_PORT))
+        .unwrap_or_else(|| SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), DNS_PORT))
+}
+
+fn build_query(id: u16, name: &str, record_type: RecordType) -> StdResult<Vec<u8>, MinijinjaError> {
+    let mut query = Vec::with_capacity(name.len() + 18);
+    query.extend(id.to_be_bytes());
+    query.extend(FLAG_RECURSION_DESIRED.to_be_bytes());
+    // QDCOUNT, ANCOUNT, NSCOUNT and ARCOUNT
+    query.extend([0, 1, 0, 0, 0, 0, 0, 1]);
+
+    for label in name.trim_end_matches('.').split('.') {
+        if label.is_empty() || label.len() > 63 {
+            return Err(to_minijinja_error(format!("Invalid domain name '{name}'")));
+        }
+        query.push(label.len() as u8);
+        query.extend(label.as_bytes());
+    }
+    query.push(0);
+
+    query.extend(record_type.code().to_be_bytes());
+    query.extend(CLASS_IN.to_be_bytes());
+
+    // EDNS0 OPT record: root name, UDP payload size as class, no extended flags nor options
+    query.push(0);
+    query.extend(TYPE_OPT.to_be_bytes());
+    query.extend((MAX_RESPONSE_SIZE as u16).to_be_bytes());
+    query.extend([0, 0, 0, 0, 0, 0]);
+    Ok(query)
+}
+
+fn is_truncated(data: &[u8]) -> bool {
+    data.len() >= 4 && u16::from_be_bytes([data[2], data[3]]) & FLAG_TRUNCATED != 0
+}
+
+fn invalid_response(reason: &str) -> MinijinjaError {
+    to_minijinja_error(format!("Invalid DNS response: {reason}"))
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn bytes(&mut self, len: usize) -> StdResult<&'a [u8], MinijinjaError> {
+        let bytes = self
+            .data
+            .get(self.pos..self.pos + len)
+            .ok_or_else(|| invalid_response("unexpected end of message"))?;
+        self.pos += len;
+        Ok(bytes)
+    }
+
+    fn u8(&mut self) -> StdResult<u8, MinijinjaError> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    fn u16(&mut self) -> StdResult<u16, MinijinjaError> {
+        Ok(u16::from_be_bytes(self.bytes(2)?.try_into().unwrap()))
+    }
+
+    fn u32(&mut self) -> StdResult<u32, MinijinjaError> {
+        Ok(u32::from_be_bytes(self.bytes(4)?.try_into().unwrap()))
+    }
+
+    /// Read a domain name, following compression pointers.
+    fn name(&mut self) -> StdResult<String, MinijinjaError> {
+        let mut labels = Vec::new();
+        let mut pos = self.pos;
+        let mut pointers = 0;
+        let mut end = None;
+
+        loop {
+            let len = *self
+                .data
+                .get(pos)
+                .ok_or_else(|| invalid_response("unexpected end of name"))?;
+            match len {
+                0 => {
+                    end.get_or_insert(pos + 1);
+                    break;
+                }
+                len if len & 0xC0 == 0xC0 => {
+                    let low = *self
+                        .data
+                        .get(pos + 1)
+                        .ok_or_else(|| invalid_response("unexpected end of name"))?;
+                    end.get_or_insert(pos + 2);
+                    pointers += 1;
+                    if pointers > MAX_POINTERS {
+                        return Err(invalid_response("too many compression pointers"));
+                    }
+                    pos = (((len & 0x3F) as usize) << 8) | low as usize;
+                }
+                len => {
+                    let label = self
+                        .data
+                        .get(pos + 1..pos + 1 + len as usize)
+                        .ok_or_else(|| invalid_response("unexpected end of name"))?;
+                    labels.push(String::from_utf8_lossy(label).into_owned());
+                    pos += 1 + len as usize;
+                }
+            }
+        }
+
+        // safe unwrap: set before breaking the loop
+        self.pos = end.unwrap();
+        Ok(labels.join("."))
+    }
+}
+
+fn parse_record(
+    reader: &mut Reader,
+    record_type: RecordType,
+    rdlength: usize,
+) -> StdResult<Value, MinijinjaError> {
+    match record_type {
+        RecordType::A => {
+            let octets: [u8; 4] = reader
+                .bytes(rdlength)?
+                .try_into()
+                .map_err(|_| invalid_response("A record length"))?;
+            Ok(Value::from(Ipv4Addr::from(octets).to_string()))
+        }
+        RecordType::Aaaa => {
+            let octets: [u8; 16] = reader
+                .bytes(rdlength)?
+                .try_into()
+                .map_err(|_| invalid_response("AAAA record length"))?;
+            Ok(Value::from(Ipv6Addr::from(octets).to_string()))
+        }
+        RecordType::Mx => Ok(context! {
+            preference => reader.u16()?,
+            exchange => reader.name()?,
+        }),
+        RecordType::Srv => Ok(context! {
+            priority => reader.u16()?,
+            weight => reader.u16()?,
+            port => reader.u16()?,
+            target => reader.name()?,
+        }),
+        RecordType::Txt => {
+            let end = reader.pos + rdlength;
+            let mut text = String::new();
+            while reader.pos < end {
+                let len = reader.u8()? as usize;
+                text.push_str(&String::from_utf8_lossy(reader.bytes(len)?));
+            }
+            Ok(Value::from(text))
+        }
+    }
+}
+
+fn parse_response(
+    id: u16,
+    record_type: RecordType,
+    data: &[u8],
+) -> StdResult<Vec<Value>, MinijinjaError> {
+    let mut reader = Reader { data, pos: 0 };
+
+    if reader.u16()? != id {
+        return Err(invalid_response("unexpected ID"));
+    }
+    let flags = reader.u16()?;
+    if flags & FLAG_TRUNCATED != 0 {
+        return Err(invalid_response("truncated message"));
+    }
+    match flags & 0x000F {
+        0 => (),
+        RCODE_NXDOMAIN => return Ok(Vec::new()),
+        rcode => {
+            let rcode_name = match rcode {
+                1 => "FORMERR",
+                2 => "SERVFAIL",
+                4 => "NOTIMP",
+                5 => "REFUSED",
+                _ => "UNKNOWN",
+            };
+            return Err(to_minijinja_error(format!(
+                "DNS query failed with {rcode_name} ({rcode})"
+            )));
+        }
+    }
+
+    let qdcount = reader.u16()?;
+    let ancount = reader.u16()?;
+    // NSCOUNT and ARCOUNT
+    reader.bytes(4)?;
+
+    for _ in 0..qdcount {
+        reader.name()?;
+        // QTYPE and QCLASS
+        reader.bytes(4)?;
+    }
+
+    let mut records = Vec::new();
+    for _ in 0..ancount {
+        reader.name()?;
+        let rtype = reader.u16()?;
+        let _class = reader.u16()?;
+        let _ttl = reader.u32()?;
+        let rdlength = reader.u16()? as usize;
+        let rdata_end = reader.pos + rdlength;
+
+        // other types, e.g.: CNAME records followed to the requested ones, are skipped
+        if rtype == record_type.code() {
+            records.push(parse_record(&mut reader, record_type, rdlength)?);
+        }
+        reader.pos = rdata_end;
+    }
+    Ok(records)
+}
+
+fn query(
+    nameserver: SocketAddr,
+    name: &str,
+    record_type: RecordType,
+    timeout: Duration,
+) -> StdResult<Vec<Value>, MinijinjaError> {
+    let id = rand::rng().random::<u16>();
+    let query = build_query(id, name, record_type)?;
+
+    let bind_address = match nameserver {
+        SocketAddr::V4(_) => SocketAddr::new(IpAddr::V4(Ipv4Addr::UNSPECIFIED), 0),
+        SocketAddr::V6(_) => SocketAddr::new(IpAddr::V6(Ipv6Addr::UNSPECIFIED), 0),
+    };
+    let socket_error =
+        |e: std::io::Error| to_minijinja_error(format!("DNS query to {nameserver} failed: {e}"));
+    let socket = UdpSocket::bind(bind_address).map_err(socket_error)?;
+    socket
+        .set_read_timeout(Some(timeout))
+        .map_err(socket_error)?;
+    socket.connect(nameserver).map_err(socket_error)?;
+    socket.send(&query).map_err(socket_error)?;
+
+    let mut buffer = [0; MAX_RESPONSE_SIZE];
+    let len = socket.recv(&mut buffer).map_err(socket_error)?;
+    if is_truncated(&buffer[..len]) {
+        trace!("dig lookup - truncated response, retrying over TCP");
+        let response = query_tcp(nameserver, &query, timeout).map_err(socket_error)?;
+        return parse_response(id, record_type, &response);
+    }
+    parse_response(id, record_type, &buffer[..len])
+}
+
+/// Send the query over TCP, prefixed with its length, and return the response.
+fn query_tcp(nameserver: SocketAddr, query: &[u8], timeout: Duration) -> std::io::Result<Vec<u8>> {
+    let mut stream = TcpStream::connect_timeout(&nameserver, timeout)?;
+    stream.set_read_timeout(Some(timeout))?;
+    stream.set_write_timeout(Some(timeout))?;
+    stream.write_all(&[(query.len() as u16).to_be_bytes().as_slice(), query].concat())?;
+
+    let mut len = [0; 2];
+    stream.read_exact(&mut len)?;
+    let mut response = vec![0; u16::from_be_bytes(len) as usize];
+    stream.read_exact(&mut response)?;
+    Ok(response)
+}
+
+pub fn function(name: String, options: Kwargs) -> StdResult<Value, MinijinjaError> {
+    let record_type = RecordType::from_qtype(options.get::<Option<&str>>("qtype")?.unwrap_or("A"))?;
+    let nameserver = match options.get::<Option<&str>>("nameserver")? {
+        Some(nameserver) => parse_nameserver(nameserver)?,
+        None => default_nameserver(),
+    };
+    let timeout = Duration::from_secs(
+        options
+            .get::<Option<u64>>("timeout")?
+            .unwrap_or(DEFAULT_TIMEOUT),
+    );
+    options.assert_all_used()?;
+
+    trace!("dig lookup - resolving {record_type:?} records of '{name}' with {nameserver}");
+
+    query(nameserver, &name, record_type, timeout).map(Value::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::net::TcpListener;
+    use std::thread;
+
+    fn encode_name(name: &str) -> Vec<u8> {
+        name.split('.')
+            .flat_map(|label| [vec![label.len() as u8], label.as_bytes().to_vec()].concat())
+            .chain([0])
+            .collect()
+    }
+
+    /// Length of the EDNS0 OPT record at the end of the queries.
+    const OPT_LEN: usize = 11;
+
+    /// Answer the query, pointing the name of the records to the question.
+    fn build_response(query: &[u8], rcode: u8, answers: &[(u16, Vec<u8>)]) -> Vec<u8> {
+        let mut response = query[..2].to_vec();
+        response.extend([0x81, 0x80 | rcode, 0, 1]);
+        response.extend((answers.len() as u16).to_be_bytes());
+        response.extend([0, 0, 0, 0]);
+        response.extend(&query[12..query.len() - OPT_LEN]);
+        for (rtype, rdata) in answers {
+            response.extend([0xC0, 0x0C]);
+            response.extend(rtype.to_be_bytes());
+            response.extend(CLASS_IN.to_be_bytes());
+            response.extend(300_u32.to_be_bytes());
+            response.extend((rdata.len() as u16).to_be_bytes());
+            response.extend(rdata);
+        }
+        response
+    }
+
+    /// Answer a single query with `answers` and return the nameserver address.
+    fn stub_nameserver(rcode: u8, answers: Vec<(u16, Vec<u8>)>) -> String {
+        let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let address = socket.local_addr().unwrap().to_string();
+        thread::spawn(move || {
+            let mut buffer = [0; 512];
+            let (len, peer) = socket.recv_from(&mut buffer).unwrap();
+            let response = build_response(&buffer[..len], rcode, &answers);
+            socket.send_to(&response, peer).unwrap();
+        });
+        address
+    }
+
+    /// Answer a query over UDP with a truncated response and over TCP with `answers`.
+    fn stub_truncating_nameserver(answers: Vec<(u16, Vec<u8>)>) -> String {
+        let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let address = socket.local_addr().unwrap();
+        let listener = TcpListener::bind(address).unwrap();
+        thread::spawn(move || {
+            let mut buffer = [0; 512];
+            let (len, peer) = socket.recv_from(&mut buffer).unwrap();
+            let mut response = build_response(&buffer[..len], 0, &[]);
+            response[2] |= 0x02;
+            socket.send_to(&response, peer).unwrap();
+
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut len = [0; 2];
+            stream.read_exact(&mut len).unwrap();
+            let mut query = vec![0; u16::from_be_bytes(len) as usize];
+            stream.read_exact(&mut query).unwrap();
+            let response = build_response(&query, 0, &answers);
+            stream
+                .write_all(&(response.len() as u16).to_be_bytes())
+                .unwrap();
+            stream.write_all(&response).unwrap();
+        });
+        address.to_string()
+    }
+
+    fn dig(name: &str, qtype: &str, nameserver: &str) -> StdResult<Value, MinijinjaError> {
+        function(
+            name.to_owned(),
+            Kwargs::from_iter([
+                ("qtype", Value::from(qtype)),
+                ("nameserver", Value::from(nameserver)),
+                ("timeout", Value::from(1)),
+            ]),
+        )
+    }
+
+    #[test]
+    fn test_build_query() {
+        let query = build_query(0x1234, "example.com.", RecordType::Aaaa).unwrap();
+        assert_eq!(
+            query,
+            [
+                vec![0x12, 0x34, 0x01, 0x00, 0, 1, 0, 0, 0, 0, 0, 1],
+                encode_name("example.com"),
+                vec![0, 28, 0, 1],
+                vec![0, 0, 41, 0x10, 0x00, 0, 0, 0, 0, 0, 0],
+            ]
+            .concat()
+        );
+
+        assert!(build_query(1, "example..com", RecordType::A).is_err());
+    }
+
+    #[test]
+    fn test_parse_nameserver() {
+        assert_eq!(
+            parse_nameserver("10.0.0.2").unwrap(),
+            "10.0.0.2:53".parse().unwrap()
+        );
+        assert_eq!(
+            parse_nameserver("10.0.0.2:5353").unwrap(),
+            "10.0.0.2:5353".parse().unwrap()
+        );
+        assert_eq!(
+            parse_nameserver("::1").unwrap(),
+            "[::1]:53".parse().unwrap()
+        );
+        assert!(parse_nameserver("localhost").is_err());
+    }
+
+    #[test]
+    fn test_dig_a() {
+        let nameserver = stub_nameserver(
+            0,
+            vec![
+                (5, encode_name("www.example.com")),
+                (1, vec![192, 0, 2, 1]),
+                (1, vec![192, 0, 2, 2]),
+            ],
+        );
+
+        let result = dig("example.com", "a", &nameserver).unwrap();
+
+        assert_eq!(result, Value::from(vec!["192.0.2.1", "192.0.2.2"]));
+    }
+
+    #[test]
+    fn test_dig_aaaa() {
+        let ip = "2001:db8::1".parse::<Ipv6Addr>().unwrap();
+        let nameserver = stub_nameserver(0, vec![(28, ip.octets().to_vec())]);
+
+        let result = dig("example.com", "AAAA", &nameserver).unwrap();
+
+        assert_eq!(result, Value::from(vec!["2001:db8::1"]));
+    }
+
+    #[test]
+    fn test_dig_mx() {
+        // exchange compressed as `mail` plus a pointer to the question name
+        let nameserver = stub_nameserver(
+            0,
+            vec![(15, vec![0, 10, 4, b'm', b'a', b'i', b'l', 0xC0, 0x0C])],
+        );
+
+        let result = dig("example.com", "MX", &nameserver).unwrap();
+
+        assert_eq!(
+            result,
+            Value::from(vec![
+                context! {preference => 10, exchange => "mail.example.com"}
+            ])
+        );
+    }
+
+    #[test]
+    fn test_dig_srv() {
+        let rdata = [vec![0, 1, 0, 5, 0x1F, 0x90], encode_name("web.example.com")].concat();
+        let nameserver = stub_nameserver(0, vec![(33, rdata)]);
+
+        let result = dig("_http._tcp.example.com", "SRV", &nameserver).unwrap();
+
+        assert_eq!(
+            result,
+            Value::from(vec![context! {
+                priority => 1,
+                weight => 5,
+                port => 8080,
+                target => "web.example.com",
+            }])
+        );
+    }
+
+    #[test]
+    fn test_dig_txt() {
+        let rdata = [vec![3], b"foo".to_vec(), vec![4], b" boo".to_vec()].concat();
+        let nameserver = stub_nameserver(0, vec![(16, rdata)]);
+
+        let result = dig("example.com", "TXT", &nameserver).unwrap();
+
+        assert_eq!(result, Value::from(vec!["foo boo"]));
+    }
+
+    #[test]
+    fn test_dig_truncated_retries_over_tcp() {
+        let text = "v".repeat(255);
+        let answers = (0..4)
+            .map(|_| (16, [vec![255], text.as_bytes().to_vec()].concat()))
+            .collect();
+        let nameserver = stub_truncating_nameserver(answers);
+
+        let result = dig("example.com", "TXT", &nameserver).unwrap();
+
+        assert_eq!(result.len(), Some(4));
+        assert_eq!(result.get_item(&Value::from(0)).unwrap(), Value::from(text));
+    }
+
+    #[test]
+    fn test_dig_nxdomain() {
+        let nameserver = stub_nameserver(3, vec![]);
+
+        let result = dig("missing.example.com", "A", &nameserver).unwrap();
+
+        assert_eq!(result.len(), Some(0));
+    }
+
+    #[test]
+    fn test_dig_servfail() {
+        let nameserver = stub_nameserver(2, vec![]);
+
+        let error = dig("example.com", "A", &nameserver).unwrap_err();
+
+        assert!(
+            error
+                .to_string()
+                .contains("DNS query failed with SERVFAIL (2)")
+        );
+    }
+
+    #[test]
+    fn test_dig_invalid_qtype() {
+        let error = dig("example.com", "CNAME", "127.0.0.1").unwrap_err();
+
+        assert!(error.to_string().contains("Unsupported qtype 'CNAME'"));
+    }
+
+    #[test]
+    fn test_parse_response_pointer_loop() {
+        let query = build_query(1, "example.com", RecordType::Mx).unwrap();
+        // exchange pointing to itself, after the header, the question and the record fields
+        let response = build_response(&query, 0, &[(15, vec![0, 10, 0xC0, 43])]);
+
+        let error = parse_response(1, RecordType::Mx, &response).unwrap_err();
+
+        assert!(error.to_string().contains("too many compression pointers"));
+    }
+}
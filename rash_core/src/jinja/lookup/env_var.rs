@@ -0,0 +1,75 @@
+/// ANCHOR: lookup
+/// # env_var
+///
+/// Read the value of an environment variable.
+///
+/// ## Parameters
+///
+/// | Parameter | Required | Type   | Values | Description                                                       |
+/// | --------- | -------- | ------ | ------ | ----------------------------------------------------------------- |
+/// | name      | yes      | string |        | Name of the environment variable.                                 |
+/// | default   | no       | string |        | Value returned if the variable is not set. **[default: `""`]**    |
+///
+/// ## Notes
+///
+/// - Unlike the `env` builtin var, which is loaded at start, this lookup reads the environment
+///   when the template is rendered, so it sees the changes made by previous tasks.
+/// - It is not named `env` because the builtin var would shadow it.
+///
+/// ANCHOR_END: lookup
+/// ANCHOR: examples
+/// ## Example
+///
+/// ```yaml
+/// - debug:
+///     msg: "{{ env_var('HOME') }}"
+///
+/// - name: Use a default value when the variable is not set
+///   debug:
+///     msg: "{{ env_var('EDITOR', default='vi') }}"
+/// ```
+/// ANCHOR_END: examples
+use std::env;
+use std::result::Result as StdResult;
+
+use log::trace;
+use minijinja::{Error as MinijinjaError, Value, value::Kwargs};
+
+pub fn function(name: String, options: Kwargs) -> StdResult<Value, MinijinjaError> {
+    trace!("env_var lookup - reading variable: '{name}'");
+
+    let default: String = options
+        .get::<Option<String>>("default")?
+        .unwrap_or_default();
+    options.assert_all_used()?;
+
+    Ok(Value::from(env::var(&name).unwrap_or(default)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_env_var() {
+        unsafe {
+            env::set_var("RASH_TEST_ENV_VAR_LOOKUP", "foo");
+        }
+        let kwargs = Kwargs::from_iter(std::iter::empty::<(&str, Value)>());
+
+        let result = function("RASH_TEST_ENV_VAR_LOOKUP".to_owned(), kwargs).unwrap();
+
+        assert_eq!(result.as_str().unwrap(), "foo");
+    }
+
+    #[test]
+    fn test_env_var_not_set() {
+        let kwargs = Kwargs::from_iter(std::iter::empty::<(&str, Value)>());
+        let result = function("RASH_TEST_ENV_VAR_LOOKUP_UNSET".to_owned(), kwargs).unwrap();
+        assert_eq!(result.as_str().unwrap(), "");
+
+        let kwargs = Kwargs::from_iter([("default", Value::from("boo"))]);
+        let result = function("RASH_TEST_ENV_VAR_LOOKUP_UNSET".to_owned(), kwargs).unwrap();
+        assert_eq!(result.as_str().unwrap(), "boo");
+    }
+}
@@ -18,6 +18,7 @@
 /// - If read in variable context, the file can be interpreted as YAML if the content is valid to the parser.
 /// - This lookup does not understand 'globbing', use the fileglob lookup instead.
 /// - The file must be readable by the user running the script.
+/// - Files encrypted with `rash vault` are decrypted transparently.
 ///
 /// ANCHOR_END: lookup
 /// ANCHOR: examples
@@ -44,6 +45,8 @@
 ///     - "{{ file('/etc/os-release') }}"
 /// ```
 /// ANCHOR_END: examples
+use crate::vault;
+
 use std::fs;
 use std::result::Result as StdResult;
 
@@ -66,6 +69,12 @@ pub fn function(path: String, options: Kwargs) -> StdResult<Value, MinijinjaErro
             format!("Failed to read file '{path}': {e}"),
         )
     })?;
+    let content = vault::decrypt_if_encrypted(content).map_err(|e| {
+        MinijinjaError::new(
+            MinijinjaErrorKind::InvalidOperation,
+            format!("Failed to decrypt file '{path}': {e}"),
+        )
+    })?;
 
     // Apply stripping based on parameters
     let result = match (lstrip, rstrip) {
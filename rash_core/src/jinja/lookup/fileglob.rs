@@ -0,0 +1,88 @@
+/// ANCHOR: lookup
+/// # fileglob
+///
+/// Return the files matching a shell glob pattern.
+///
+/// ## Parameters
+///
+/// | Parameter | Required | Type   | Values | Description                                  |
+/// | --------- | -------- | ------ | ------ | -------------------------------------------- |
+/// | pattern   | yes      | string |        | Glob pattern, e.g.: `/etc/rash/conf.d/*.yml`. |
+///
+/// ## Notes
+///
+/// - Only files are returned, directories are ignored.
+/// - Paths are sorted alphabetically. If nothing matches, an empty list is returned.
+///
+/// ANCHOR_END: lookup
+/// ANCHOR: examples
+/// ## Example
+///
+/// ```yaml
+/// - name: Copy each config file
+///   copy:
+///     src: "{{ item }}"
+///     dest: "/etc/app/{{ item | basename }}"
+///   loop: "{{ fileglob('files/*.conf') }}"
+/// ```
+/// ANCHOR_END: examples
+use crate::jinja::lookup::utils::to_minijinja_error;
+
+use std::result::Result as StdResult;
+
+use log::trace;
+use minijinja::{Error as MinijinjaError, Value};
+
+pub fn function(pattern: String) -> StdResult<Value, MinijinjaError> {
+    trace!("fileglob lookup - pattern: '{pattern}'");
+
+    let paths = glob::glob(&pattern)
+        .map_err(|e| to_minijinja_error(format!("Invalid glob pattern '{pattern}': {e}")))?
+        .filter_map(StdResult::ok)
+        .filter(|path| path.is_file())
+        .map(|path| path.to_string_lossy().into_owned())
+        .collect::<Vec<_>>();
+
+    Ok(Value::from(paths))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::fs::{File, create_dir};
+
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_fileglob() {
+        let dir = tempdir().unwrap();
+        File::create(dir.path().join("b.conf")).unwrap();
+        File::create(dir.path().join("a.conf")).unwrap();
+        File::create(dir.path().join("c.yml")).unwrap();
+        create_dir(dir.path().join("d.conf")).unwrap();
+        let dir_path = dir.path().to_string_lossy().to_string();
+
+        let result = function(format!("{dir_path}/*.conf")).unwrap();
+
+        assert_eq!(
+            result,
+            Value::from(vec![
+                format!("{dir_path}/a.conf"),
+                format!("{dir_path}/b.conf"),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_fileglob_no_matches() {
+        let result = function("/nonexistent/*.conf".to_owned()).unwrap();
+        assert_eq!(result.len(), Some(0));
+    }
+
+    #[test]
+    fn test_fileglob_invalid_pattern() {
+        let error = function("/tmp/[".to_owned()).unwrap_err();
+        assert!(error.to_string().contains("Invalid glob pattern '/tmp/['"));
+    }
+}
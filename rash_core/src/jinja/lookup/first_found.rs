@@ -0,0 +1,171 @@
+/// ANCHOR: lookup
+/// # first_found
+///
+/// Return the first file found from a list of candidates.
+///
+/// ## Parameters
+///
+/// | Parameter | Required | Type         | Values     | Description                                                                 |
+/// | --------- | -------- | ------------ | ---------- | --------------------------------------------------------------------------- |
+/// | files     | yes      | string/array |            | Candidate files. A string is split by `,`, `;` and `:`.                     |
+/// | paths     | no       | string/array |            | Directories where to search the files. A string is split as `files`.        |
+/// | skip      | no       | boolean      | true/false | Return `none` instead of failing when no file is found. **[default: `false`]** |
+///
+/// ## Notes
+///
+/// - Candidates are checked in order: each file in every path, then the next file.
+/// - Absolute files are checked as they are, without `paths`.
+/// - Only files are returned, directories are ignored.
+///
+/// ANCHOR_END: lookup
+/// ANCHOR: examples
+/// ## Example
+///
+/// ```yaml
+/// - name: Load vars for the current distribution, or the default ones
+///   set_vars:
+///     distro: "{{ file(first_found([os_id ~ '.yml', 'default.yml'], paths=['vars'])) | from_yaml }}"
+///
+/// - name: Show the first config found
+///   debug:
+///     msg: "{{ first_found('/etc/app.conf,/usr/local/etc/app.conf', skip=true) }}"
+/// ```
+/// ANCHOR_END: examples
+use crate::jinja::lookup::utils::to_minijinja_error;
+
+use std::path::{Path, PathBuf};
+use std::result::Result as StdResult;
+
+use log::trace;
+use minijinja::value::{Kwargs, ValueKind};
+use minijinja::{Error as MinijinjaError, Value};
+
+/// Get a list of strings from a list value, or from a string split by `,`, `;` and `:`.
+fn to_string_list(value: &Value) -> StdResult<Vec<String>, MinijinjaError> {
+    match value.as_str() {
+        Some(s) => Ok(s
+            .split([',', ';', ':'])
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(str::to_owned)
+            .collect()),
+        None if value.kind() == ValueKind::Seq => value
+            .try_iter()?
+            .map(|item| {
+                item.as_str().map(str::to_owned).ok_or_else(|| {
+                    to_minijinja_error(format!("Expected a list of strings, found: {item}"))
+                })
+            })
+            .collect(),
+        None => Err(to_minijinja_error(format!(
+            "Expected a string or a list of strings, found: {value}"
+        ))),
+    }
+}
+
+fn candidates(files: &[String], paths: &[String]) -> Vec<PathBuf> {
+    files
+        .iter()
+        .flat_map(
+            |file| match paths.is_empty() || Path::new(file).is_absolute() {
+                true => vec![PathBuf::from(file)],
+                false => paths
+                    .iter()
+                    .map(|path| Path::new(path).join(file))
+                    .collect(),
+            },
+        )
+        .collect()
+}
+
+pub fn function(files: Value, options: Kwargs) -> StdResult<Value, MinijinjaError> {
+    let paths = match options.get::<Option<Value>>("paths")? {
+        Some(paths) => to_string_list(&paths)?,
+        None => Vec::new(),
+    };
+    let skip: bool = options.get::<Option<bool>>("skip")?.unwrap_or(false);
+    options.assert_all_used()?;
+
+    let files = to_string_list(&files)?;
+    let candidates = candidates(&files, &paths);
+    trace!("first_found lookup - candidates: {candidates:?}");
+
+    match candidates.into_iter().find(|candidate| candidate.is_file()) {
+        Some(found) => Ok(Value::from(found.to_string_lossy().into_owned())),
+        None if skip => Ok(Value::from(())),
+        None => Err(to_minijinja_error(format!(
+            "No file was found when using first_found: {files:?}"
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::fs::{File, create_dir};
+
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_candidates() {
+        let files = vec!["debian.yml".to_owned(), "/etc/default.yml".to_owned()];
+        let paths = vec!["vars".to_owned(), "/opt/vars".to_owned()];
+
+        assert_eq!(
+            candidates(&files, &paths),
+            vec![
+                PathBuf::from("vars/debian.yml"),
+                PathBuf::from("/opt/vars/debian.yml"),
+                PathBuf::from("/etc/default.yml"),
+            ]
+        );
+        assert_eq!(
+            candidates(&files, &[]),
+            vec![
+                PathBuf::from("debian.yml"),
+                PathBuf::from("/etc/default.yml"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_first_found() {
+        let dir = tempdir().unwrap();
+        let dir_path = dir.path().to_string_lossy().to_string();
+        create_dir(dir.path().join("debian.yml")).unwrap();
+        File::create(dir.path().join("default.yml")).unwrap();
+
+        let files = Value::from(vec!["ubuntu.yml", "debian.yml", "default.yml"]);
+        let kwargs = Kwargs::from_iter([("paths", Value::from(dir_path.as_str()))]);
+        let result = function(files, kwargs).unwrap();
+        assert_eq!(result, Value::from(format!("{dir_path}/default.yml")));
+
+        let files = Value::from(format!("{dir_path}/missing.yml, {dir_path}/default.yml"));
+        let kwargs = Kwargs::from_iter(std::iter::empty::<(&str, Value)>());
+        let result = function(files, kwargs).unwrap();
+        assert_eq!(result, Value::from(format!("{dir_path}/default.yml")));
+    }
+
+    #[test]
+    fn test_first_found_not_found() {
+        let files = Value::from(vec!["/nonexistent/file.yml"]);
+
+        let kwargs = Kwargs::from_iter([("skip", Value::from(true))]);
+        assert!(function(files.clone(), kwargs).unwrap().is_none());
+
+        let kwargs = Kwargs::from_iter(std::iter::empty::<(&str, Value)>());
+        let error = function(files, kwargs).unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("No file was found when using first_found")
+        );
+    }
+
+    #[test]
+    fn test_first_found_invalid_files() {
+        let kwargs = Kwargs::from_iter(std::iter::empty::<(&str, Value)>());
+        assert!(function(Value::from(1), kwargs).is_err());
+    }
+}
@@ -0,0 +1,171 @@
+/// ANCHOR: lookup
+/// # ini
+///
+/// Read the value of a key from an INI file.
+///
+/// ## Parameters
+///
+/// | Parameter | Required | Type   | Values | Description                                                          |
+/// | --------- | -------- | ------ | ------ | -------------------------------------------------------------------- |
+/// | key       | yes      | string |        | Key to read.                                                         |
+/// | file      | yes      | string |        | Path of the INI file.                                                |
+/// | section   | no       | string |        | Section of the key. **[default: `"global"`]**                        |
+/// | default   | no       | string |        | Value returned if the key is not found. Without it, the lookup fails. |
+///
+/// ## Notes
+///
+/// - Keys before the first section header belong to the `global` section.
+/// - Both `=` and `:` are accepted as separators. Lines starting with `#` or `;` are comments.
+/// - If a key is repeated in a section, the last value is returned.
+///
+/// ANCHOR_END: lookup
+/// ANCHOR: examples
+/// ## Example
+///
+/// ```yaml
+/// - debug:
+///     msg: "{{ ini('user', section='integration', file='users.ini') }}"
+///
+/// - debug:
+///     msg: "{{ ini('timeout', file='/etc/app.ini', default='30') }}"
+/// ```
+/// ANCHOR_END: examples
+use crate::jinja::lookup::utils::to_minijinja_error;
+
+use std::fs;
+use std::result::Result as StdResult;
+
+use log::trace;
+use minijinja::{Error as MinijinjaError, Value, value::Kwargs};
+
+const GLOBAL_SECTION: &str = "global";
+
+fn find_value(content: &str, section: &str, key: &str) -> Option<String> {
+    let mut current_section = GLOBAL_SECTION;
+
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#') && !line.starts_with(';'))
+        .filter_map(|line| {
+            if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                current_section = name.trim();
+                return None;
+            }
+            let (line_key, value) = line.split_once(['=', ':'])?;
+            (current_section == section && line_key.trim() == key).then(|| value.trim().to_owned())
+        })
+        .last()
+}
+
+pub fn function(key: String, options: Kwargs) -> StdResult<Value, MinijinjaError> {
+    let file: String = options.get("file")?;
+    let section: String = options
+        .get::<Option<String>>("section")?
+        .unwrap_or_else(|| GLOBAL_SECTION.to_owned());
+    let default: Option<String> = options.get("default")?;
+    options.assert_all_used()?;
+
+    trace!("ini lookup - reading key '{key}' of section '{section}' from '{file}'");
+
+    let content = fs::read_to_string(&file)
+        .map_err(|e| to_minijinja_error(format!("Failed to read file '{file}': {e}")))?;
+
+    find_value(&content, &section, &key)
+        .or(default)
+        .map(Value::from)
+        .ok_or_else(|| {
+            to_minijinja_error(format!(
+                "Key '{key}' not found in section '{section}' of '{file}'"
+            ))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::io::Write;
+
+    use tempfile::NamedTempFile;
+
+    const CONTENT: &str = r#"
+name = global
+; comment
+[integration]
+user = yoda
+# user = luke
+timeout: 30
+
+[production]
+user=vader
+user=palpatine
+"#;
+
+    fn ini_file() -> NamedTempFile {
+        let mut temp_file = NamedTempFile::new().unwrap();
+        write!(temp_file, "{CONTENT}").unwrap();
+        temp_file
+    }
+
+    fn lookup(file: &NamedTempFile, key: &str, options: Vec<(&str, Value)>) -> Value {
+        let options = options
+            .into_iter()
+            .chain([("file", Value::from(file.path().to_string_lossy().as_ref()))]);
+        function(key.to_owned(), Kwargs::from_iter(options)).unwrap()
+    }
+
+    #[test]
+    fn test_find_value() {
+        assert_eq!(
+            find_value(CONTENT, "global", "name"),
+            Some("global".to_owned())
+        );
+        assert_eq!(
+            find_value(CONTENT, "integration", "user"),
+            Some("yoda".to_owned())
+        );
+        assert_eq!(
+            find_value(CONTENT, "integration", "timeout"),
+            Some("30".to_owned())
+        );
+        assert_eq!(
+            find_value(CONTENT, "production", "user"),
+            Some("palpatine".to_owned())
+        );
+        assert_eq!(find_value(CONTENT, "production", "timeout"), None);
+        assert_eq!(find_value(CONTENT, "global", "user"), None);
+    }
+
+    #[test]
+    fn test_ini() {
+        let file = ini_file();
+
+        assert_eq!(lookup(&file, "name", vec![]), Value::from("global"));
+        assert_eq!(
+            lookup(&file, "user", vec![("section", Value::from("integration"))]),
+            Value::from("yoda")
+        );
+        assert_eq!(
+            lookup(&file, "missing", vec![("default", Value::from("foo"))]),
+            Value::from("foo")
+        );
+    }
+
+    #[test]
+    fn test_ini_not_found() {
+        let file = ini_file();
+        let kwargs = Kwargs::from_iter([
+            ("file", Value::from(file.path().to_string_lossy().as_ref())),
+            ("section", Value::from("production")),
+        ]);
+
+        let error = function("timeout".to_owned(), kwargs).unwrap_err();
+
+        assert!(
+            error
+                .to_string()
+                .contains("Key 'timeout' not found in section 'production'")
+        );
+    }
+}
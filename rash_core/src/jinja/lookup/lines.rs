@@ -0,0 +1,83 @@
+/// ANCHOR: lookup
+/// # lines
+///
+/// Read a file from the filesystem and return its lines as a list.
+///
+/// ## Parameters
+///
+/// | Parameter | Required | Type   | Values | Description                      |
+/// | --------- | -------- | ------ | ------ | -------------------------------- |
+/// | path      | yes      | string |        | Path of the file to read.        |
+///
+/// ## Notes
+///
+/// - Line endings are removed, `\r\n` included.
+/// - Files encrypted with `rash vault` are decrypted transparently.
+///
+/// ANCHOR_END: lookup
+/// ANCHOR: examples
+/// ## Example
+///
+/// ```yaml
+/// - name: Print each host of a file
+///   debug:
+///     msg: "{{ item }}"
+///   loop: "{{ lines('/etc/rash/hosts') }}"
+///
+/// - name: Skip comments
+///   debug:
+///     msg: "{{ lines('/etc/rash/hosts') | reject('search', '^#') | list }}"
+/// ```
+/// ANCHOR_END: examples
+use crate::jinja::lookup::utils::to_minijinja_error;
+use crate::vault;
+
+use std::fs;
+use std::result::Result as StdResult;
+
+use log::trace;
+use minijinja::{Error as MinijinjaError, Value};
+
+pub fn function(path: String) -> StdResult<Value, MinijinjaError> {
+    trace!("lines lookup - reading file: '{path}'");
+
+    let content = fs::read_to_string(&path)
+        .map_err(|e| to_minijinja_error(format!("Failed to read file '{path}': {e}")))?;
+    let content = vault::decrypt_if_encrypted(content)
+        .map_err(|e| to_minijinja_error(format!("Failed to decrypt file '{path}': {e}")))?;
+
+    Ok(Value::from(
+        content.lines().map(str::to_owned).collect::<Vec<_>>(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::io::Write;
+
+    use tempfile::NamedTempFile;
+
+    #[test]
+    fn test_lines() {
+        let mut temp_file = NamedTempFile::new().unwrap();
+        write!(temp_file, "foo\r\nboo\n\nbar").unwrap();
+
+        let result = function(temp_file.path().to_string_lossy().to_string()).unwrap();
+
+        assert_eq!(result, Value::from(vec!["foo", "boo", "", "bar"]));
+    }
+
+    #[test]
+    fn test_lines_not_found() {
+        let result = function("/nonexistent/file.txt".to_owned());
+
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("Failed to read file '/nonexistent/file.txt'")
+        );
+    }
+}
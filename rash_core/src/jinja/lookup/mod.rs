@@ -1,9 +1,18 @@
+mod csvfile;
+mod dig;
+mod env_var;
 mod file;
+mod fileglob;
 mod find;
+mod first_found;
+mod ini;
+mod lines;
 mod password;
 #[cfg(feature = "passwordstore")]
 mod passwordstore;
 mod pipe;
+mod template;
+mod url;
 mod vault;
 
 mod utils;
@@ -11,10 +20,19 @@ mod utils;
 use rash_derive::generate_lookup_functions;
 
 generate_lookup_functions!(
+    (csvfile, false),
+    (dig, false),
+    (env_var, false),
     (file, false),
+    (fileglob, false),
     (find, false),
+    (first_found, false),
+    (ini, false),
+    (lines, false),
     (password, false),
     (passwordstore, true),
     (pipe, false),
+    (template, false),
+    (url, false),
     (vault, false)
 );
@@ -1,7 +1,7 @@
 /// ANCHOR: lookup
 /// # passwordstore
 ///
-/// Lookup passwords from the passwordstore.org pass utility.
+/// Lookup passwords from the passwordstore.org pass utility. Returned passwords are masked in logs.
 ///
 /// ## Parameters
 ///
@@ -28,7 +28,7 @@
 ///     msg: "{{ passwordstore('foo/boo', subkey='username') }}"
 /// ```
 /// ANCHOR_END: examples
-use crate::jinja::lookup::utils::to_minijinja_error;
+use crate::jinja::lookup::utils::{to_minijinja_error, track_secret};
 
 use std::env;
 use std::result::Result as StdResult;
@@ -72,5 +72,7 @@ pub fn function(path: String, options: Kwargs) -> StdResult<Value, MinijinjaErro
 
     options.assert_all_used()?;
 
-    Ok(Value::from(password))
+    let result = Value::from(password);
+    track_secret(&result);
+    Ok(result)
 }
@@ -0,0 +1,103 @@
+/// ANCHOR: lookup
+/// # template
+///
+/// Render a template file with the current vars and return it as a string.
+///
+/// ## Parameters
+///
+/// | Parameter     | Required | Type   | Values | Description                                                   |
+/// | ------------- | -------- | ------ | ------ | ------------------------------------------------------------- |
+/// | path          | yes      | string |        | Path of the template file.                                    |
+/// | template_vars | no       | dict   |        | Additional vars for the template, overriding the current ones. |
+///
+/// ## Notes
+///
+/// - The file is rendered as the [template module](./module_template.html) does, but the result
+///   is returned instead of written to a file.
+/// - Files encrypted with `rash vault` are decrypted transparently.
+///
+/// ANCHOR_END: lookup
+/// ANCHOR: examples
+/// ## Example
+///
+/// ```yaml
+/// - debug:
+///     msg: "{{ template('templates/motd.j2') }}"
+///
+/// - name: Render a template with extra vars
+///   set_vars:
+///     config: "{{ template('config.yml.j2', template_vars={'port': 8080}) | from_yaml }}"
+/// ```
+/// ANCHOR_END: examples
+use crate::jinja::lookup::utils::to_minijinja_error;
+use crate::vault;
+
+use std::fs;
+use std::result::Result as StdResult;
+
+use log::trace;
+use minijinja::{Error as MinijinjaError, State, Value, context, value::Kwargs};
+
+pub fn function(state: &State, path: String, options: Kwargs) -> StdResult<Value, MinijinjaError> {
+    trace!("template lookup - rendering file: '{path}'");
+
+    let template_vars: Option<Value> = options.get("template_vars")?;
+    options.assert_all_used()?;
+
+    let content = fs::read_to_string(&path)
+        .map_err(|e| to_minijinja_error(format!("Failed to read file '{path}': {e}")))?;
+    let content = vault::decrypt_if_encrypted(content)
+        .map_err(|e| to_minijinja_error(format!("Failed to decrypt file '{path}': {e}")))?;
+
+    let vars = state
+        .known_variables()
+        .into_iter()
+        .filter_map(|name| state.lookup(&name).map(|value| (name.into_owned(), value)))
+        .collect::<Value>();
+    let vars = match template_vars {
+        Some(template_vars) => context! {..template_vars, ..vars},
+        None => vars,
+    };
+
+    state
+        .env()
+        .render_str(&content, vars)
+        .map(Value::from)
+        .map_err(|e| to_minijinja_error(format!("Failed to render template '{path}': {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::jinja::render_string;
+
+    use std::io::Write;
+
+    use minijinja::context;
+    use tempfile::NamedTempFile;
+
+    #[test]
+    fn test_template() {
+        let mut temp_file = NamedTempFile::new().unwrap();
+        write!(temp_file, "{{{{ name }}}} listens on {{{{ port }}}}").unwrap();
+        let path = temp_file.path().to_string_lossy().to_string();
+
+        let result = render_string(
+            "{{ template(path, template_vars={'port': 80}) | upper }}",
+            &context! {path => path, name => "rash", port => 8080},
+        )
+        .unwrap();
+
+        assert_eq!(result, "RASH LISTENS ON 80");
+    }
+
+    #[test]
+    fn test_template_undefined_var() {
+        let mut temp_file = NamedTempFile::new().unwrap();
+        write!(temp_file, "{{{{ missing }}}}").unwrap();
+        let path = temp_file.path().to_string_lossy().to_string();
+
+        let result = render_string("{{ template(path) }}", &context! {path => path});
+
+        assert!(result.is_err());
+    }
+}
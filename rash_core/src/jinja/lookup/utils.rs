@@ -1,5 +1,51 @@
-use minijinja::{Error as MinijinjaError, ErrorKind as MinijinjaErrorKind};
+use crate::logger::add_secret;
+
+use minijinja::value::ValueKind;
+use minijinja::{Error as MinijinjaError, ErrorKind as MinijinjaErrorKind, Value};
 
 pub fn to_minijinja_error<E: std::fmt::Display>(err: E) -> MinijinjaError {
     MinijinjaError::new(MinijinjaErrorKind::InvalidOperation, err.to_string())
 }
+
+/// Register all strings in value as secrets, to be masked in logs.
+pub fn track_secret(value: &Value) {
+    match value.kind() {
+        ValueKind::String => {
+            if let Some(s) = value.as_str() {
+                add_secret(s);
+            }
+        }
+        ValueKind::Seq => value
+            .try_iter()
+            .into_iter()
+            .flatten()
+            .for_each(|item| track_secret(&item)),
+        ValueKind::Map => value
+            .try_iter()
+            .into_iter()
+            .flatten()
+            .filter_map(|key| value.get_item(&key).ok())
+            .for_each(|item| track_secret(&item)),
+        _ => (),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logger::get_secrets;
+
+    use minijinja::context;
+
+    #[test]
+    fn test_track_secret() {
+        track_secret(&context! {
+            user => "utils-test-user",
+            tokens => vec!["utils-test-token"],
+        });
+        let secrets = get_secrets();
+        assert!(secrets.contains(&"utils-test-user".to_owned()));
+        assert!(secrets.contains(&"utils-test-token".to_owned()));
+        assert!(!secrets.contains(&"tokens".to_owned()));
+    }
+}
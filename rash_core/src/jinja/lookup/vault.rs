@@ -33,6 +33,7 @@
 ///   - `dict`: Returns key/value pairs as a dictionary (default)
 ///   - `values`: Returns only the values as a list
 ///   - `raw`: Returns the complete API response including metadata
+/// - Returned secret values are masked in logs.
 ///
 /// ANCHOR_END: lookup
 /// ANCHOR: examples
@@ -86,6 +87,8 @@
 ///     msg: "Secret: {{ vault('secret/data/myapp:password') }}"
 /// ```
 /// ANCHOR_END: examples
+use crate::jinja::lookup::utils::track_secret;
+
 use std::collections::HashMap;
 use std::env;
 use std::result::Result as StdResult;
@@ -206,6 +209,7 @@ pub fn function(secret: String, options: Kwargs) -> StdResult<Value, MinijinjaEr
     })?;
 
     options.assert_all_used()?;
+    track_secret(&result);
     Ok(result)
 }
 
@@ -1,8 +1,10 @@
 mod error_utils;
+mod filter;
 #[cfg(feature = "docs")]
 pub mod lookup;
 #[cfg(not(feature = "docs"))]
 mod lookup;
+mod test;
 
 use crate::{
     error::{Error, ErrorKind, Result},
@@ -24,6 +26,8 @@ fn init_env() -> Environment<'static> {
     env.set_undefined_behavior(UndefinedBehavior::Strict);
     env.add_global("omit", OMIT_VALUE);
     lookup::add_lookup_functions(&mut env);
+    filter::add_filters(&mut env);
+    test::add_tests(&mut env);
     env
 }
 
@@ -133,6 +137,49 @@ pub fn is_render_string(s: &str, vars: &Value) -> Result<bool> {
     }
 }
 
+/// Check template syntax without rendering it.
+#[inline(always)]
+pub fn compile_string(s: &str) -> Result<()> {
+    let mut env = MINIJINJA_ENV.clone();
+    env.add_template("t", s)
+        .map_err(|e| handle_template_error(e, s, &context! {}))
+}
+
+/// Check syntax of an expression passed directly without {{ }}, without rendering it.
+#[inline(always)]
+pub fn compile_expression(s: &str) -> Result<()> {
+    MINIJINJA_ENV
+        .compile_expression(s)
+        .map(|_| ())
+        .map_err(|e| {
+            Error::new(
+                ErrorKind::JinjaRenderError,
+                format!(
+                    "{}: {} in expression: {s}",
+                    e.kind(),
+                    e.detail().unwrap_or_default()
+                ),
+            )
+        })
+}
+
+/// Evaluate an expression passed directly without {{ }}, returning its value.
+pub fn eval_expression(s: &str, vars: &Value) -> Result<Value> {
+    MINIJINJA_ENV
+        .compile_expression(s)
+        .and_then(|expression| expression.eval(vars))
+        .map_err(|e| {
+            Error::new(
+                ErrorKind::JinjaRenderError,
+                format!(
+                    "{}: {} in expression: {s}",
+                    e.kind(),
+                    e.detail().unwrap_or_default()
+                ),
+            )
+        })
+}
+
 pub fn merge_option(a: Value, b: Option<Value>) -> Value {
     if let Some(b) = b { merge(a, b) } else { a }
 }
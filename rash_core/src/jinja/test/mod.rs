@@ -0,0 +1,20 @@
+mod path;
+mod regex;
+mod set;
+mod version;
+
+pub fn add_tests(env: &mut minijinja::Environment<'static>) {
+    env.add_test("file", path::is_file);
+    env.add_test("directory", path::is_directory);
+    env.add_test("link", path::is_link);
+    env.add_test("exists", path::exists);
+    env.add_test("abs", path::is_abs);
+
+    env.add_test("version", version::version);
+
+    env.add_test("match", regex::is_match);
+    env.add_test("search", regex::search);
+
+    env.add_test("subset", set::subset);
+    env.add_test("superset", set::superset);
+}
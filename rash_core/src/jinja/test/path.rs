@@ -0,0 +1,72 @@
+use std::path::Path;
+
+pub fn is_file(value: String) -> bool {
+    Path::new(&value).is_file()
+}
+
+pub fn is_directory(value: String) -> bool {
+    Path::new(&value).is_dir()
+}
+
+/// Check the path itself, without following it when it is a symlink.
+pub fn is_link(value: String) -> bool {
+    Path::new(&value).is_symlink()
+}
+
+/// Follow symlinks, so broken symlinks don't exist, as in Ansible.
+pub fn exists(value: String) -> bool {
+    Path::new(&value).exists()
+}
+
+pub fn is_abs(value: String) -> bool {
+    Path::new(&value).is_absolute()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::jinja::render_string;
+
+    use std::fs::{File, create_dir};
+    use std::os::unix::fs::symlink;
+
+    use minijinja::context;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_file_directory_link_exists() {
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("file");
+        let subdir = dir.path().join("subdir");
+        let link = dir.path().join("link");
+        let broken_link = dir.path().join("broken_link");
+        File::create(&file).unwrap();
+        create_dir(&subdir).unwrap();
+        symlink(&file, &link).unwrap();
+        symlink(dir.path().join("missing"), &broken_link).unwrap();
+
+        let template =
+            "{{ path is file }} {{ path is directory }} {{ path is link }} {{ path is exists }}";
+        let render = |path: &std::path::Path| {
+            render_string(template, &context! {path => path.to_str().unwrap()}).unwrap()
+        };
+
+        assert_eq!(render(&file), "True False False True");
+        assert_eq!(render(&subdir), "False True False True");
+        assert_eq!(render(&link), "True False True True");
+        assert_eq!(render(&broken_link), "False False True False");
+        assert_eq!(
+            render(&dir.path().join("missing")),
+            "False False False False"
+        );
+    }
+
+    #[test]
+    fn test_abs() {
+        let result = render_string(
+            "{{ '/etc/rash' is abs }} {{ 'rash/config.yml' is abs }} {{ '~/rash' is not abs }}",
+            &context! {},
+        )
+        .unwrap();
+        assert_eq!(result, "True False True");
+    }
+}
@@ -0,0 +1,72 @@
+use crate::jinja::filter::regex::build_regex;
+
+use std::result::Result as StdResult;
+
+use minijinja::Error as MinijinjaError;
+use minijinja::value::Kwargs;
+
+/// Match the pattern at the beginning of the string, as Python `re.match`.
+pub fn is_match(
+    value: String,
+    pattern: String,
+    options: Kwargs,
+) -> StdResult<bool, MinijinjaError> {
+    let re = build_regex(&format!(r"\A(?:{pattern})"), &options)?;
+    Ok(re.is_match(&value))
+}
+
+/// Match the pattern anywhere in the string, as Python `re.search`.
+pub fn search(value: String, pattern: String, options: Kwargs) -> StdResult<bool, MinijinjaError> {
+    let re = build_regex(&pattern, &options)?;
+    Ok(re.is_match(&value))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::jinja::render_string;
+
+    use minijinja::context;
+
+    #[test]
+    fn test_match() {
+        let result = render_string(
+            "{{ 'rash 2.0' is match('rash') }} {{ 'rash 2.0' is match('2\\.0') }}",
+            &context! {},
+        )
+        .unwrap();
+        assert_eq!(result, "True False");
+    }
+
+    #[test]
+    fn test_match_alternation() {
+        let result = render_string("{{ 'foo' is match('bar|o+') }}", &context! {}).unwrap();
+        assert_eq!(result, "False");
+    }
+
+    #[test]
+    fn test_search() {
+        let result = render_string(
+            "{{ 'rash 2.0' is search('\\d\\.\\d') }} {{ 'rash' is search('ansible') }}",
+            &context! {},
+        )
+        .unwrap();
+        assert_eq!(result, "True False");
+    }
+
+    #[test]
+    fn test_search_options() {
+        let result = render_string(
+            "{{ 'foo\\nBAR' is search('^bar$', ignorecase=true, multiline=true) }} \
+             {{ 'foo\\nBAR' is search('^bar$') }}",
+            &context! {},
+        )
+        .unwrap();
+        assert_eq!(result, "True False");
+    }
+
+    #[test]
+    fn test_search_invalid() {
+        assert!(render_string("{{ 'foo' is search('(') }}", &context! {}).is_err());
+        assert!(render_string("{{ 'foo' is search('f', foo=true) }}", &context! {}).is_err());
+    }
+}
@@ -0,0 +1,48 @@
+use std::result::Result as StdResult;
+
+use minijinja::{Error as MinijinjaError, Value};
+
+fn contains_all(container: &Value, items: &Value) -> StdResult<bool, MinijinjaError> {
+    let container: Vec<Value> = container.try_iter()?.collect();
+    Ok(items.try_iter()?.all(|item| container.contains(&item)))
+}
+
+pub fn subset(value: Value, other: Value) -> StdResult<bool, MinijinjaError> {
+    contains_all(&other, &value)
+}
+
+pub fn superset(value: Value, other: Value) -> StdResult<bool, MinijinjaError> {
+    contains_all(&value, &other)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::jinja::render_string;
+
+    use minijinja::context;
+
+    #[test]
+    fn test_subset() {
+        let result = render_string(
+            "{{ [1, 2] is subset([3, 2, 1]) }} {{ [1, 4] is subset([1, 2, 3]) }} {{ [] is subset([]) }}",
+            &context! {},
+        )
+        .unwrap();
+        assert_eq!(result, "True False True");
+    }
+
+    #[test]
+    fn test_superset() {
+        let result = render_string(
+            "{{ a is superset(['b']) }} {{ a is superset(['b', 'd']) }}",
+            &context! {a => vec!["a", "b", "c"]},
+        )
+        .unwrap();
+        assert_eq!(result, "True False");
+    }
+
+    #[test]
+    fn test_subset_not_iterable() {
+        assert!(render_string("{{ 1 is subset([1]) }}", &context! {}).is_err());
+    }
+}
@@ -0,0 +1,132 @@
+use crate::jinja::filter::invalid_operation;
+
+use std::cmp::Ordering;
+use std::result::Result as StdResult;
+use std::sync::LazyLock;
+
+use minijinja::value::Kwargs;
+use minijinja::{Error as MinijinjaError, Value};
+use regex::Regex;
+
+static LOOSE_COMPONENT_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\d+|[a-zA-Z]+").unwrap());
+
+/// Component of a loose version. Variant order makes strings lower than numbers.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum LooseComponent {
+    Str(String),
+    Num(u64),
+}
+
+fn parse_loose(version: &str) -> Vec<LooseComponent> {
+    LOOSE_COMPONENT_RE
+        .find_iter(version)
+        .map(|m| match m.as_str().parse::<u64>() {
+            Ok(number) => LooseComponent::Num(number),
+            Err(_) => LooseComponent::Str(m.as_str().to_owned()),
+        })
+        .collect()
+}
+
+fn parse_semver(version: &str) -> StdResult<semver::Version, MinijinjaError> {
+    semver::Version::parse(version)
+        .map_err(|e| invalid_operation(format!("Invalid semantic version '{version}': {e}")))
+}
+
+fn compare_ordering(ordering: Ordering, operator: &str) -> StdResult<bool, MinijinjaError> {
+    match operator {
+        "<" | "lt" => Ok(ordering.is_lt()),
+        "<=" | "le" => Ok(ordering.is_le()),
+        ">" | "gt" => Ok(ordering.is_gt()),
+        ">=" | "ge" => Ok(ordering.is_ge()),
+        "==" | "=" | "eq" => Ok(ordering.is_eq()),
+        "!=" | "<>" | "ne" => Ok(ordering.is_ne()),
+        _ => Err(invalid_operation(format!(
+            "Invalid version comparison operator '{operator}'"
+        ))),
+    }
+}
+
+/// Compare versions, as `version('2.0', '>=')`. `version_type` is `loose` (default), comparing
+/// numeric and alphabetic components in order, or `semver`/`semantic`.
+pub fn version(
+    value: Value,
+    version: Value,
+    operator: Option<String>,
+    options: Kwargs,
+) -> StdResult<bool, MinijinjaError> {
+    let version_type: Option<String> = options.get("version_type")?;
+    options.assert_all_used()?;
+
+    let (value, version) = (value.to_string(), version.to_string());
+    let ordering = match version_type.as_deref().unwrap_or("loose") {
+        "loose" => parse_loose(&value).cmp(&parse_loose(&version)),
+        "semver" | "semantic" => parse_semver(&value)?.cmp(&parse_semver(&version)?),
+        version_type => {
+            return Err(invalid_operation(format!(
+                "Invalid version_type '{version_type}', expected one of: loose, semver, semantic"
+            )));
+        }
+    };
+
+    compare_ordering(ordering, operator.as_deref().unwrap_or("=="))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::jinja::render_string;
+
+    use minijinja::context;
+
+    fn render(template: &str) -> String {
+        render_string(template, &context! {}).unwrap()
+    }
+
+    #[test]
+    fn test_version_loose() {
+        assert_eq!(render("{{ '2.10' is version('2.9', '>') }}"), "True");
+        assert_eq!(render("{{ '2.0' is version('2.0', '>=') }}"), "True");
+        assert_eq!(render("{{ '1.2.3' is version('1.10', 'lt') }}"), "True");
+        assert_eq!(render("{{ '1.0' is version('1.0.0', '<') }}"), "True");
+        assert_eq!(render("{{ '1.0a1' is version('1.0', 'gt') }}"), "True");
+        assert_eq!(render("{{ '1.0a1' is version('1.0.1', 'lt') }}"), "True");
+        assert_eq!(render("{{ 'v1.2' is version('v1.2') }}"), "True");
+        assert_eq!(render("{{ 2.1 is version('2.1', '!=') }}"), "False");
+    }
+
+    #[test]
+    fn test_version_semver() {
+        assert_eq!(
+            render("{{ '1.0.0-rc.1' is version('1.0.0', '<', version_type='semver') }}"),
+            "True"
+        );
+        assert_eq!(
+            render("{{ '1.0.0-rc.1' is version('1.0.0', '<', version_type='loose') }}"),
+            "False"
+        );
+        assert_eq!(
+            render("{{ '1.10.0' is version('1.9.0', 'ge', version_type='semantic') }}"),
+            "True"
+        );
+    }
+
+    #[test]
+    fn test_version_errors() {
+        let vars = context! {};
+        assert!(render_string("{{ '1.0' is version('1.0', '~=') }}", &vars).is_err());
+        assert!(
+            render_string(
+                "{{ '1.0' is version('1.0', version_type='semver') }}",
+                &vars
+            )
+            .is_err()
+        );
+        assert!(
+            render_string(
+                "{{ '1.0' is version('1.0', version_type='pep440') }}",
+                &vars
+            )
+            .is_err()
+        );
+    }
+}
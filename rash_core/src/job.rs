@@ -1,13 +1,48 @@
-use std::collections::HashMap;
-use std::io::Read;
-use std::process::Child;
-use std::sync::{Arc, LazyLock, Mutex};
-use std::time::{Duration, Instant};
+//! Job
+//!
+//! Background jobs started by `async` tasks. Jobs are persisted in a [`JobStore`], one directory
+//! per job with its metadata, stdout, stderr and exit code, so `async_status` and `rash jobs` can
+//! check them from other runs, even after the run which started them exited.
+
+use crate::error::{Error, ErrorKind, Result};
+use crate::utils::get_state_dir;
 
+use std::collections::HashMap;
+use std::fmt;
+use std::fs::{self, DirBuilder, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::os::unix::fs::{DirBuilderExt, OpenOptionsExt};
+use std::os::unix::process::CommandExt;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use nix::errno::Errno;
+use nix::sys::signal::{Signal, kill, killpg};
+use nix::unistd::Pid;
 use serde::{Deserialize, Serialize};
 
 pub type JobId = u64;
 
+/// Environment variable with the seconds finished jobs are kept before being removed.
+pub const TTL_ENV: &str = "RASH_JOBS_TTL";
+
+const DEFAULT_TTL: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+const META_FILE: &str = "job.json";
+const STDOUT_FILE: &str = "stdout";
+const STDERR_FILE: &str = "stderr";
+const RC_FILE: &str = "rc";
+const LAST_ID_FILE: &str = "last_id";
+
+/// Run the command (`$1`) saving its output and exit code in the job directory (`$2`). The exit
+/// code is written by the wrapper, so it is saved even if `rash` already exited.
+const WRAPPER: &str = r#"/bin/sh -c "$1" > "$2/stdout" 2> "$2/stderr"; echo $? > "$2/rc.tmp" && mv "$2/rc.tmp" "$2/rc""#;
+
+/// Jobs spawned by this process, kept to reap them when they exit.
+static CHILDREN: LazyLock<Mutex<HashMap<u32, Child>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum JobStatus {
     Pending,
@@ -16,318 +51,633 @@ pub enum JobStatus {
     Failed,
 }
 
-#[derive(Debug, Clone)]
-pub struct JobInfo {
-    pub status: JobStatus,
-    pub output: Option<String>,
-    pub error: Option<String>,
-    pub changed: bool,
-    pub elapsed: Duration,
+impl fmt::Display for JobStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JobStatus::Pending => write!(f, "pending"),
+            JobStatus::Running => write!(f, "running"),
+            JobStatus::Finished => write!(f, "finished"),
+            JobStatus::Failed => write!(f, "failed"),
+        }
+    }
+}
+
+/// Job metadata persisted in the job directory.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+struct JobMeta {
+    pid: u32,
+    /// Start time of the process in clock ticks since boot, to tell it apart from a later
+    /// process with the same PID.
+    #[serde(default)]
+    pid_start_time: Option<u64>,
+    command: String,
+    /// Seconds since Unix epoch.
+    started_at: f64,
+    /// Max runtime in seconds.
+    timeout: Option<f64>,
+    /// Set when the job is killed or times out.
+    error: Option<String>,
+    /// Seconds since Unix epoch, set when the job is killed or times out.
+    finished_at: Option<f64>,
 }
 
-#[derive(Debug)]
-pub struct Job {
+#[derive(Debug, Clone, PartialEq)]
+pub struct JobInfo {
     pub id: JobId,
+    pub pid: u32,
+    pub command: String,
     pub status: JobStatus,
-    pub started_at: Instant,
-    pub timeout: Option<Duration>,
-    pub child: Option<Child>,
+    /// Stdout and stderr of the command.
     pub output: Option<String>,
+    pub stdout: String,
+    pub stderr: String,
+    pub rc: Option<i32>,
     pub error: Option<String>,
     pub changed: bool,
+    pub elapsed: Duration,
 }
 
-impl Job {
-    pub fn new(id: JobId, timeout: Option<Duration>, child: Child) -> Self {
-        Job {
-            id,
-            status: JobStatus::Running,
-            started_at: Instant::now(),
-            timeout,
-            child: Some(child),
-            output: None,
-            error: None,
-            changed: false,
-        }
+impl JobInfo {
+    pub fn is_finished(&self) -> bool {
+        matches!(self.status, JobStatus::Finished | JobStatus::Failed)
     }
+}
 
-    pub fn is_timed_out(&self) -> bool {
-        if let Some(timeout) = self.timeout {
-            self.started_at.elapsed() > timeout
-        } else {
-            false
-        }
+fn now() -> f64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs_f64()
+}
+
+fn get_mtime(path: &Path) -> Option<f64> {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+        .map(|duration| duration.as_secs_f64())
+}
+
+fn get_output(stdout: &str, stderr: &str) -> Option<String> {
+    match (stdout.is_empty(), stderr.is_empty()) {
+        (true, true) => None,
+        (false, true) => Some(stdout.to_owned()),
+        _ => Some(format!("{stdout}\n{stderr}").trim().to_owned()),
     }
+}
+
+/// Return the start time of the process in clock ticks since boot, from `/proc/<pid>/stat`.
+fn get_process_start_time(pid: u32) -> Option<u64> {
+    let stat = fs::read_to_string(format!("/proc/{pid}/stat")).ok()?;
+    // fields are counted after the command name, which could contain spaces and parentheses
+    stat.rsplit_once(')')?
+        .1
+        .split_whitespace()
+        .nth(19)?
+        .parse()
+        .ok()
+}
 
-    pub fn elapsed(&self) -> Duration {
-        self.started_at.elapsed()
+/// Return true if the process exists, reaping it if it was spawned by this process. When the
+/// start time is known, the process must have it too, because PIDs are reused.
+fn is_process_running(pid: u32, start_time: Option<u64>) -> bool {
+    let mut children = CHILDREN.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(child) = children.get_mut(&pid) {
+        return match child.try_wait() {
+            Ok(None) => true,
+            _ => {
+                children.remove(&pid);
+                false
+            }
+        };
     }
+    matches!(
+        kill(Pid::from_raw(pid as i32), None),
+        Ok(()) | Err(Errno::EPERM)
+    ) && start_time.is_none_or(|start_time| get_process_start_time(pid) == Some(start_time))
 }
 
-#[derive(Debug, Default)]
-pub struct JobRegistry {
-    jobs: HashMap<JobId, Job>,
-    next_id: JobId,
+/// Return seconds finished jobs are kept, from [`TTL_ENV`] or 7 days by default.
+pub fn get_ttl() -> Duration {
+    std::env::var(TTL_ENV)
+        .ok()
+        .and_then(|ttl| ttl.parse().ok())
+        .map(Duration::from_secs)
+        .unwrap_or(DEFAULT_TTL)
 }
 
-impl JobRegistry {
-    pub fn new() -> Self {
-        JobRegistry {
-            jobs: HashMap::new(),
-            next_id: 1,
-        }
+/// Directory with a subdirectory per job, named by its ID.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JobStore {
+    dir: PathBuf,
+}
+
+impl JobStore {
+    pub fn new(dir: PathBuf) -> Self {
+        JobStore { dir }
     }
 
-    pub fn register(&mut self, timeout: Option<Duration>, child: Child) -> JobId {
-        let id = self.next_id;
-        self.next_id += 1;
-        let job = Job::new(id, timeout, child);
-        self.jobs.insert(id, job);
-        id
+    /// Open store in `$XDG_STATE_HOME/rash/jobs`, or `~/.local/state/rash/jobs` if
+    /// `XDG_STATE_HOME` is not set.
+    pub fn open() -> Result<Self> {
+        Ok(JobStore::new(get_state_dir()?.join("jobs")))
     }
 
-    pub fn get(&self, id: JobId) -> Option<&Job> {
-        self.jobs.get(&id)
+    fn get_job_dir(&self, id: JobId) -> PathBuf {
+        self.dir.join(id.to_string())
     }
 
-    pub fn get_mut(&mut self, id: JobId) -> Option<&mut Job> {
-        self.jobs.get_mut(&id)
+    /// Return job IDs sorted.
+    pub fn list(&self) -> Result<Vec<JobId>> {
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(e) => return Err(e.into()),
+        };
+        let mut ids = entries
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| entry.file_name().to_str()?.parse::<JobId>().ok())
+            .collect::<Vec<_>>();
+        ids.sort_unstable();
+        Ok(ids)
     }
 
-    pub fn remove(&mut self, id: JobId) -> Option<Job> {
-        self.jobs.remove(&id)
+    pub fn exists(&self, id: JobId) -> bool {
+        self.get_job_dir(id).is_dir()
     }
 
-    pub fn contains(&self, id: JobId) -> bool {
-        self.jobs.contains_key(&id)
+    /// Create the directory of a new job, with the ID following the last one created. The last
+    /// ID is kept in a file, so IDs of removed jobs are not reused.
+    fn create_job_dir(&self) -> Result<(JobId, PathBuf)> {
+        DirBuilder::new()
+            .recursive(true)
+            .mode(0o700)
+            .create(&self.dir)?;
+        let mut last_id_file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .mode(0o600)
+            .open(self.dir.join(LAST_ID_FILE))?;
+        // released when the file is closed
+        last_id_file.lock()?;
+        let mut last_id = String::new();
+        last_id_file.read_to_string(&mut last_id)?;
+        // stores created before the last ID file have just the job directories
+        let id = last_id
+            .trim()
+            .parse::<JobId>()
+            .unwrap_or_default()
+            .max(self.list()?.last().copied().unwrap_or_default())
+            + 1;
+
+        let job_dir = self.get_job_dir(id);
+        fs::create_dir(&job_dir)?;
+        last_id_file.set_len(0)?;
+        last_id_file.seek(SeekFrom::Start(0))?;
+        last_id_file.write_all(id.to_string().as_bytes())?;
+        Ok((id, job_dir))
     }
 
-    pub fn list(&self) -> Vec<JobId> {
-        self.jobs.keys().copied().collect()
+    fn read_meta(&self, id: JobId) -> Result<Option<JobMeta>> {
+        match fs::read_to_string(self.get_job_dir(id).join(META_FILE)) {
+            Ok(content) => Ok(Some(serde_json::from_str(&content).map_err(|e| {
+                Error::new(ErrorKind::InvalidData, format!("Invalid job {id}: {e}"))
+            })?)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
     }
-}
 
-pub static JOBS: LazyLock<Arc<Mutex<JobRegistry>>> =
-    LazyLock::new(|| Arc::new(Mutex::new(JobRegistry::new())));
+    fn write_meta(&self, id: JobId, meta: &JobMeta) -> Result<()> {
+        let job_dir = self.get_job_dir(id);
+        let tmp_path = job_dir.join(format!("{META_FILE}.tmp"));
+        fs::write(
+            &tmp_path,
+            serde_json::to_string(meta).map_err(|e| Error::new(ErrorKind::InvalidData, e))?,
+        )?;
+        fs::rename(tmp_path, job_dir.join(META_FILE))?;
+        Ok(())
+    }
 
-pub fn register_job(timeout: Option<Duration>, child: Child) -> JobId {
-    let mut registry = JOBS.lock().expect("Failed to lock job registry");
-    registry.register(timeout, child)
-}
+    /// Spawn command in background, returning its job ID. Finished jobs older than [`get_ttl`]
+    /// are removed before.
+    pub fn spawn(
+        &self,
+        command: &str,
+        chdir: Option<&str>,
+        env: &[(String, String)],
+        timeout: Option<Duration>,
+    ) -> Result<JobId> {
+        if let Err(e) = self.cleanup(get_ttl()) {
+            warn!("Failed to remove finished jobs: {e}");
+        }
+        let (id, job_dir) = self.create_job_dir()?;
+
+        let mut cmd = Command::new("/bin/sh");
+        cmd.arg("-c")
+            .arg(WRAPPER)
+            .arg("sh")
+            .arg(command)
+            .arg(&job_dir)
+            .envs(env.iter().map(|(key, value)| (key, value)))
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .process_group(0);
+        if let Some(dir) = chdir {
+            cmd.current_dir(dir);
+        }
 
-pub fn get_job(id: JobId) -> Option<JobStatus> {
-    check_and_update_job_status(id);
-    let registry = JOBS.lock().expect("Failed to lock job registry");
-    registry.get(id).map(|j| j.status.clone())
-}
+        let child = cmd.spawn().map_err(|e| {
+            let _ = fs::remove_dir_all(&job_dir);
+            Error::new(
+                ErrorKind::SubprocessFail,
+                format!("Failed to spawn async command: {e}"),
+            )
+        })?;
+        let pid = child.id();
+        CHILDREN
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(pid, child);
+
+        self.write_meta(
+            id,
+            &JobMeta {
+                pid,
+                pid_start_time: get_process_start_time(pid),
+                command: command.to_owned(),
+                started_at: now(),
+                timeout: timeout.map(|timeout| timeout.as_secs_f64()),
+                error: None,
+                finished_at: None,
+            },
+        )?;
+        Ok(id)
+    }
 
-fn check_and_update_job_status(id: JobId) {
-    let mut registry = JOBS.lock().expect("Failed to lock job registry");
-    if let Some(job) = registry.get_mut(id) {
-        if job.status != JobStatus::Running {
-            return;
+    /// Stop job process and its children, marking it as failed with the error.
+    fn stop(&self, id: JobId, meta: &mut JobMeta, error: String) -> Result<()> {
+        match killpg(Pid::from_raw(meta.pid as i32), Signal::SIGTERM) {
+            Ok(()) | Err(Errno::ESRCH) => (),
+            Err(e) => return Err(Error::new(ErrorKind::SubprocessFail, e)),
+        }
+        // wait for the process if it was spawned by this one, to reap it
+        let child = CHILDREN
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&meta.pid);
+        if let Some(mut child) = child {
+            let _ = child.wait();
         }
+        meta.error = Some(error);
+        meta.finished_at = Some(now());
+        self.write_meta(id, meta)
+    }
 
-        let timed_out = job.is_timed_out();
-        let timeout_val = job.timeout;
-
-        if let Some(ref mut child) = job.child {
-            match child.try_wait() {
-                Ok(Some(status)) => {
-                    let mut stdout = String::new();
-                    let mut stderr = String::new();
-
-                    if let Some(ref mut stdout_handle) = child.stdout {
-                        let _ = stdout_handle.read_to_string(&mut stdout);
-                    }
-                    if let Some(ref mut stderr_handle) = child.stderr {
-                        let _ = stderr_handle.read_to_string(&mut stderr);
-                    }
-
-                    let output = if stdout.is_empty() && stderr.is_empty() {
-                        None
-                    } else if stderr.is_empty() {
-                        Some(stdout)
-                    } else {
-                        Some(format!("{}\n{}", stdout, stderr).trim().to_string())
-                    };
-
-                    if status.success() {
-                        job.status = JobStatus::Finished;
-                        job.output = output;
-                        job.changed = true;
-                    } else {
-                        job.status = JobStatus::Failed;
-                        job.error = Some(format!(
-                            "Process exited with code {}: {}",
-                            status.code().unwrap_or(-1),
-                            stderr.trim()
-                        ));
-                        job.output = output;
-                    }
-                    job.child = None;
-                }
-                Ok(None) => {
-                    if timed_out {
-                        let _ = child.kill();
-                        job.status = JobStatus::Failed;
-                        job.error = Some(format!("Job timed out after {:?}", timeout_val));
-                        job.child = None;
-                    }
-                }
-                Err(e) => {
-                    job.status = JobStatus::Failed;
-                    job.error = Some(format!("Failed to check process status: {}", e));
-                    job.child = None;
+    /// Return job info, stopping it if it reached its timeout.
+    pub fn get_info(&self, id: JobId) -> Result<JobInfo> {
+        if !self.exists(id) {
+            return Err(Error::new(
+                ErrorKind::NotFound,
+                format!("Job with ID {id} not found"),
+            ));
+        }
+        let job_dir = self.get_job_dir(id);
+        let Some(mut meta) = self.read_meta(id)? else {
+            return Ok(JobInfo {
+                id,
+                pid: 0,
+                command: String::new(),
+                status: JobStatus::Pending,
+                output: None,
+                stdout: String::new(),
+                stderr: String::new(),
+                rc: None,
+                error: None,
+                changed: false,
+                elapsed: Duration::ZERO,
+            });
+        };
+
+        let rc_path = job_dir.join(RC_FILE);
+        let mut rc = fs::read_to_string(&rc_path)
+            .ok()
+            .and_then(|rc| rc.trim().parse::<i32>().ok());
+        if meta.error.is_none() && rc.is_none() {
+            let elapsed = now() - meta.started_at;
+            if !is_process_running(meta.pid, meta.pid_start_time) {
+                // the wrapper could write the exit code right before exiting
+                rc = fs::read_to_string(&rc_path)
+                    .ok()
+                    .and_then(|rc| rc.trim().parse::<i32>().ok());
+                if rc.is_none() {
+                    meta.error = Some("Job process exited without exit code".to_owned());
+                    meta.finished_at = Some(now());
+                    self.write_meta(id, &meta)?;
                 }
+            } else if meta.timeout.is_some_and(|timeout| elapsed > timeout) {
+                let timeout = Duration::from_secs_f64(meta.timeout.unwrap_or_default());
+                self.stop(id, &mut meta, format!("Job timed out after {timeout:?}"))?;
             }
         }
+
+        let stdout = fs::read_to_string(job_dir.join(STDOUT_FILE)).unwrap_or_default();
+        let stderr = fs::read_to_string(job_dir.join(STDERR_FILE)).unwrap_or_default();
+        let (status, error) = match (&meta.error, rc) {
+            (Some(error), _) => (JobStatus::Failed, Some(error.clone())),
+            (None, Some(0)) => (JobStatus::Finished, None),
+            (None, Some(code)) => (
+                JobStatus::Failed,
+                Some(format!(
+                    "Process exited with code {code}: {}",
+                    stderr.trim()
+                )),
+            ),
+            (None, None) => (JobStatus::Running, None),
+        };
+        let finished_at = meta
+            .finished_at
+            .or_else(|| rc.and_then(|_| get_mtime(&rc_path)))
+            .unwrap_or_else(now);
+
+        Ok(JobInfo {
+            id,
+            pid: meta.pid,
+            command: meta.command,
+            changed: status == JobStatus::Finished,
+            status,
+            output: get_output(&stdout, &stderr),
+            stdout,
+            stderr,
+            rc,
+            error,
+            elapsed: Duration::from_secs_f64((finished_at - meta.started_at).max(0.0)),
+        })
+    }
+
+    /// Kill a running job.
+    pub fn kill(&self, id: JobId) -> Result<JobInfo> {
+        let info = self.get_info(id)?;
+        if info.status != JobStatus::Running {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("Job {id} is not running"),
+            ));
+        }
+        if let Some(mut meta) = self.read_meta(id)? {
+            self.stop(id, &mut meta, "Job killed".to_owned())?;
+        }
+        self.get_info(id)
+    }
+
+    /// Remove jobs finished more than `ttl` ago, returning their IDs.
+    pub fn cleanup(&self, ttl: Duration) -> Result<Vec<JobId>> {
+        let expired = self
+            .list()?
+            .into_iter()
+            .filter_map(|id| self.get_info(id).ok())
+            .filter(|info| info.is_finished())
+            .filter(|info| {
+                let finished_at = self
+                    .read_meta(info.id)
+                    .ok()
+                    .flatten()
+                    .map(|meta| meta.started_at + info.elapsed.as_secs_f64())
+                    .unwrap_or_default();
+                now() - finished_at > ttl.as_secs_f64()
+            })
+            .map(|info| info.id)
+            .collect::<Vec<_>>();
+        for id in &expired {
+            trace!("removing job {id}");
+            fs::remove_dir_all(self.get_job_dir(*id))?;
+        }
+        Ok(expired)
     }
 }
 
-pub fn get_job_info(id: JobId) -> Option<JobInfo> {
-    check_and_update_job_status(id);
-    let registry = JOBS.lock().expect("Failed to lock job registry");
-    registry.get(id).map(|j| JobInfo {
-        status: j.status.clone(),
-        output: j.output.clone(),
-        error: j.error.clone(),
-        changed: j.changed,
-        elapsed: j.elapsed(),
-    })
+/// Spawn command in background in the default [`JobStore`], returning its job ID.
+pub fn spawn_job(
+    command: &str,
+    chdir: Option<&str>,
+    env: &[(String, String)],
+    timeout: Option<Duration>,
+) -> Result<JobId> {
+    JobStore::open()?.spawn(command, chdir, env, timeout)
 }
 
-pub fn update_job_status(
-    id: JobId,
-    status: JobStatus,
-    output: Option<String>,
-    error: Option<String>,
-    changed: bool,
-) -> bool {
-    let mut registry = JOBS.lock().expect("Failed to lock job registry");
-    if let Some(job) = registry.get_mut(id) {
-        job.status = status;
-        job.output = output;
-        job.error = error;
-        job.changed = changed;
-        job.child = None;
-        true
-    } else {
-        false
-    }
+pub fn get_job_info(id: JobId) -> Option<JobInfo> {
+    JobStore::open().and_then(|store| store.get_info(id)).ok()
 }
 
 pub fn job_exists(id: JobId) -> bool {
-    let registry = JOBS.lock().expect("Failed to lock job registry");
-    registry.contains(id)
+    JobStore::open().is_ok_and(|store| store.exists(id))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::process::{Command, Stdio};
+
     use std::thread;
-    use std::time::Duration;
 
-    #[test]
-    fn test_job_registry() {
-        let registry = JobRegistry::new();
-        assert!(registry.list().is_empty());
-    }
+    use tempfile::tempdir;
 
-    #[test]
-    fn test_register_job_and_get_status() {
-        let child = Command::new("sleep")
-            .arg("0.1")
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()
-            .expect("Failed to spawn sleep");
-
-        let job_id = register_job(None, child);
-        assert!(job_id > 0);
-
-        let mut status = get_job(job_id);
-        for _ in 0..20 {
-            if status == Some(JobStatus::Finished) {
-                break;
+    fn wait_finished(store: &JobStore, id: JobId) -> JobInfo {
+        for _ in 0..40 {
+            let info = store.get_info(id).unwrap();
+            if info.is_finished() {
+                return info;
             }
             thread::sleep(Duration::from_millis(50));
-            status = get_job(job_id);
         }
-        assert_eq!(status, Some(JobStatus::Finished));
+        panic!("job {id} not finished");
     }
 
     #[test]
-    fn test_get_job_info_updates_status() {
-        let child = Command::new("echo")
-            .arg("test_output")
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()
-            .expect("Failed to spawn echo");
-
-        let job_id = register_job(None, child);
-
-        let mut info = get_job_info(job_id);
-        for _ in 0..20 {
-            if info
-                .as_ref()
-                .map(|i| i.status == JobStatus::Finished)
-                .unwrap_or(false)
-            {
-                break;
-            }
-            thread::sleep(Duration::from_millis(50));
-            info = get_job_info(job_id);
-        }
-        assert!(info.is_some());
-        let info = info.unwrap();
+    fn test_spawn_and_get_info() {
+        let dir = tempdir().unwrap();
+        let store = JobStore::new(dir.path().to_path_buf());
+
+        let id = store
+            .spawn(
+                "echo $GREETING; echo oops >&2",
+                None,
+                &[("GREETING".to_owned(), "test_output".to_owned())],
+                None,
+            )
+            .unwrap();
+        assert_eq!(id, 1);
+        assert!(store.exists(id));
+
+        let info = wait_finished(&store, id);
         assert_eq!(info.status, JobStatus::Finished);
-        assert!(info.output.is_some());
-        assert!(info.output.unwrap().contains("test_output"));
+        assert_eq!(info.rc, Some(0));
+        assert_eq!(info.stdout, "test_output\n");
+        assert_eq!(info.stderr, "oops\n");
+        assert_eq!(info.output, Some("test_output\n\noops".to_owned()));
+        assert!(info.changed);
     }
 
     #[test]
-    fn test_job_timeout() {
-        let child = Command::new("sleep")
-            .arg("10")
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()
-            .expect("Failed to spawn sleep");
+    fn test_get_info_from_other_store() {
+        let dir = tempdir().unwrap();
+        let id = JobStore::new(dir.path().to_path_buf())
+            .spawn("sleep 0.1; pwd", Some("/"), &[], None)
+            .unwrap();
 
-        let timeout = Duration::from_millis(100);
-        let job_id = register_job(Some(timeout), child);
+        let info = wait_finished(&JobStore::new(dir.path().to_path_buf()), id);
+        assert_eq!(info.status, JobStatus::Finished);
+        assert_eq!(info.stdout, "/\n");
+        assert_eq!(info.command, "sleep 0.1; pwd");
+    }
 
+    #[test]
+    fn test_job_ids() {
+        let dir = tempdir().unwrap();
+        let store = JobStore::new(dir.path().to_path_buf());
+
+        assert_eq!(store.list().unwrap(), Vec::<JobId>::new());
+        let ids = (0..3)
+            .map(|_| store.spawn("true", None, &[], None).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(ids, vec![1, 2, 3]);
+        assert_eq!(store.list().unwrap(), vec![1, 2, 3]);
+        assert!(!store.exists(4));
+        assert_eq!(
+            store.get_info(4).unwrap_err().to_string(),
+            "Job with ID 4 not found"
+        );
+    }
+
+    #[test]
+    fn test_job_ids_not_reused() {
+        let dir = tempdir().unwrap();
+        let store = JobStore::new(dir.path().to_path_buf());
+        let first = store.spawn("true", None, &[], None).unwrap();
+        let last = store.spawn("true", None, &[], None).unwrap();
+        wait_finished(&store, first);
+        wait_finished(&store, last);
+
+        thread::sleep(Duration::from_millis(10));
+        assert_eq!(store.cleanup(Duration::ZERO).unwrap(), vec![first, last]);
+        assert_eq!(store.spawn("true", None, &[], None).unwrap(), last + 1);
+    }
+
+    #[test]
+    fn test_job_timeout() {
+        let dir = tempdir().unwrap();
+        let store = JobStore::new(dir.path().to_path_buf());
+        let id = store
+            .spawn("sleep 10", None, &[], Some(Duration::from_millis(100)))
+            .unwrap();
+
+        assert_eq!(store.get_info(id).unwrap().status, JobStatus::Running);
         thread::sleep(Duration::from_millis(200));
 
-        let info = get_job_info(job_id);
-        assert!(info.is_some());
-        let info = info.unwrap();
+        let info = store.get_info(id).unwrap();
         assert_eq!(info.status, JobStatus::Failed);
-        assert!(info.error.is_some());
         assert!(info.error.unwrap().contains("timed out"));
+        assert!(!is_process_running(info.pid, None));
     }
 
     #[test]
     fn test_job_failed_on_nonzero_exit() {
-        let child = Command::new("sh")
-            .arg("-c")
-            .arg("exit 1")
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()
-            .expect("Failed to spawn sh");
+        let dir = tempdir().unwrap();
+        let store = JobStore::new(dir.path().to_path_buf());
+        let id = store
+            .spawn("echo boom >&2; exit 3", None, &[], None)
+            .unwrap();
+
+        let info = wait_finished(&store, id);
+        assert_eq!(info.status, JobStatus::Failed);
+        assert_eq!(info.rc, Some(3));
+        assert_eq!(
+            info.error,
+            Some("Process exited with code 3: boom".to_owned())
+        );
+        assert!(!info.changed);
+    }
+
+    #[test]
+    fn test_kill() {
+        let dir = tempdir().unwrap();
+        let store = JobStore::new(dir.path().to_path_buf());
+        let id = store.spawn("sleep 10", None, &[], None).unwrap();
 
-        let job_id = register_job(None, child);
+        let info = store.kill(id).unwrap();
+        assert_eq!(info.status, JobStatus::Failed);
+        assert_eq!(info.error, Some("Job killed".to_owned()));
+        assert!(!is_process_running(info.pid, None));
+        assert_eq!(
+            store.kill(id).unwrap_err().to_string(),
+            format!("Job {id} is not running")
+        );
+    }
 
-        thread::sleep(Duration::from_millis(50));
+    #[test]
+    fn test_is_process_running_with_start_time() {
+        let pid = std::process::id();
+        let start_time = get_process_start_time(pid).unwrap();
+
+        assert!(is_process_running(pid, None));
+        assert!(is_process_running(pid, Some(start_time)));
+        // same PID reused by other process
+        assert!(!is_process_running(pid, Some(start_time + 1)));
+    }
 
-        let info = get_job_info(job_id);
-        assert!(info.is_some());
-        let info = info.unwrap();
+    #[test]
+    fn test_get_info_pid_reused() {
+        let dir = tempdir().unwrap();
+        let store = JobStore::new(dir.path().to_path_buf());
+        let id = store.spawn("true", None, &[], None).unwrap();
+        wait_finished(&store, id);
+        fs::remove_file(store.get_job_dir(id).join(RC_FILE)).unwrap();
+
+        // job metadata written by other run, with a PID now used by this process
+        let mut meta = store.read_meta(id).unwrap().unwrap();
+        meta.pid = std::process::id();
+        meta.pid_start_time = get_process_start_time(meta.pid).map(|t| t + 1);
+        meta.error = None;
+        meta.finished_at = None;
+        store.write_meta(id, &meta).unwrap();
+
+        let info = store.get_info(id).unwrap();
         assert_eq!(info.status, JobStatus::Failed);
-        assert!(info.error.is_some());
+        assert_eq!(
+            info.error,
+            Some("Job process exited without exit code".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_cleanup() {
+        let dir = tempdir().unwrap();
+        let store = JobStore::new(dir.path().to_path_buf());
+        let finished = store.spawn("true", None, &[], None).unwrap();
+        let running = store.spawn("sleep 10", None, &[], None).unwrap();
+        wait_finished(&store, finished);
+
+        assert_eq!(
+            store.cleanup(Duration::from_secs(60)).unwrap(),
+            Vec::<JobId>::new()
+        );
+        thread::sleep(Duration::from_millis(10));
+        assert_eq!(store.cleanup(Duration::ZERO).unwrap(), vec![finished]);
+        assert_eq!(store.list().unwrap(), vec![running]);
+
+        store.kill(running).unwrap();
+    }
+
+    #[test]
+    fn test_pending_job() {
+        let dir = tempdir().unwrap();
+        let store = JobStore::new(dir.path().to_path_buf());
+        fs::create_dir(dir.path().join("1")).unwrap();
+
+        assert_eq!(store.get_info(1).unwrap().status, JobStatus::Pending);
+        assert_eq!(store.create_job_dir().unwrap().0, 2);
     }
 }
@@ -0,0 +1,404 @@
+//! Journal
+//!
+//! Progress of a run, saved by [`Context::exec`] before executing each task. If the run fails,
+//! the journal is kept and `rash --resume` continues from the failed task, restoring the
+//! variables registered and the handlers notified by the previous tasks.
+//!
+//! Variables set by tasks with `no_log` and variables containing secrets, like vault decrypted
+//! values, are not saved: they are undefined when resuming.
+//!
+//! [`Context::exec`]: ../context/struct.Context.html#method.exec
+
+use crate::error::{Error, ErrorKind, Result};
+use crate::logger::mask_secrets;
+use crate::task::PendingHandlers;
+use crate::utils::get_state_dir;
+
+use std::collections::HashSet;
+use std::fs::{self, DirBuilder, File, OpenOptions, TryLockError};
+use std::io::{self, Write};
+use std::os::unix::fs::{DirBuilderExt, OpenOptionsExt};
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, RwLock};
+
+use minijinja::Value;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// State of the run before executing a task.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Entry {
+    /// Checksum of the script content when the journal was written.
+    pub script_hash: String,
+    /// Checksum of the script arguments and environment variables of the run.
+    pub args_hash: String,
+    /// Index of the next top level task to be executed, as printed by `--list-tasks`. Tasks in
+    /// a `block` or in included files are not tracked, so their top level task is executed again
+    /// from the beginning.
+    pub task_index: usize,
+    /// Variables registered by the executed tasks.
+    pub vars: serde_json::Value,
+    /// Handlers notified and not executed yet.
+    pub pending_handlers: Vec<String>,
+    /// Variables not saved because they could contain secrets.
+    #[serde(default)]
+    pub omitted_vars: Vec<String>,
+}
+
+impl Entry {
+    pub fn get_vars(&self) -> Option<Value> {
+        match &self.vars {
+            serde_json::Value::Null => None,
+            vars => Some(Value::from_serialize(vars)),
+        }
+    }
+
+    pub fn get_pending_handlers(&self) -> PendingHandlers {
+        let mut pending_handlers = PendingHandlers::new();
+        pending_handlers.notify(&self.pending_handlers);
+        pending_handlers
+    }
+}
+
+static NO_LOG_VARS: LazyLock<RwLock<HashSet<String>>> =
+    LazyLock::new(|| RwLock::new(HashSet::new()));
+
+/// Leave the variables set by a task with `no_log` out of the journal.
+pub fn add_no_log_vars(vars: &Value) {
+    let names = vars
+        .try_iter()
+        .into_iter()
+        .flatten()
+        .filter_map(|name| name.as_str().map(str::to_owned))
+        .collect::<Vec<_>>();
+    if !names.is_empty() {
+        let mut no_log_vars = NO_LOG_VARS.write().unwrap_or_else(|e| e.into_inner());
+        no_log_vars.extend(names);
+    }
+}
+
+/// Split variables in the ones that can be saved and the names of the ones set by tasks with
+/// `no_log` or containing registered secrets.
+fn split_secret_vars(vars: serde_json::Value) -> (serde_json::Value, Vec<String>) {
+    let serde_json::Value::Object(vars) = vars else {
+        return (vars, Vec::new());
+    };
+    let no_log_vars = NO_LOG_VARS.read().unwrap_or_else(|e| e.into_inner());
+    let (omitted, saved): (serde_json::Map<_, _>, serde_json::Map<_, _>) =
+        vars.into_iter().partition(|(name, value)| {
+            let value = value.to_string();
+            no_log_vars.contains(name) || mask_secrets(&value) != value
+        });
+    (
+        serde_json::Value::Object(saved),
+        omitted.into_iter().map(|(name, _)| name).collect(),
+    )
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Journal {
+    path: PathBuf,
+    script_hash: String,
+    args_hash: String,
+}
+
+fn sha256_hex(data: &[u8]) -> String {
+    Sha256::digest(data)
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+impl Journal {
+    /// Journal of the script saved in `dir`, identified by the script path. `args` are the
+    /// script arguments and environment variables of the run, which must be the same to resume.
+    pub fn new(dir: &Path, script_path: &Path, script: &str, args: &[String]) -> Self {
+        let script_path = std::path::absolute(script_path).unwrap_or(script_path.to_path_buf());
+        Journal {
+            path: dir.join(format!(
+                "{}.json",
+                sha256_hex(script_path.as_os_str().as_encoded_bytes())
+            )),
+            script_hash: sha256_hex(script.as_bytes()),
+            args_hash: sha256_hex(args.join("\0").as_bytes()),
+        }
+    }
+
+    /// Journal of the script in `$XDG_STATE_HOME/rash/journals`, or
+    /// `~/.local/state/rash/journals` if `XDG_STATE_HOME` is not set.
+    pub fn open(script_path: &Path, script: &str, args: &[String]) -> Result<Self> {
+        Ok(Journal::new(
+            &get_state_dir()?.join("journals"),
+            script_path,
+            script,
+            args,
+        ))
+    }
+
+    pub fn exists(&self) -> bool {
+        self.path.exists()
+    }
+
+    /// Lock the journal until the returned file is dropped, so concurrent runs of the same
+    /// script don't overwrite the progress of each other. Fail if another run holds it.
+    pub fn try_lock(&self) -> Result<File> {
+        self.create_dir()?;
+        let file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .mode(0o600)
+            .open(self.path.with_extension("lock"))?;
+        match file.try_lock() {
+            Ok(()) => Ok(file),
+            Err(TryLockError::WouldBlock) => Err(Error::new(
+                ErrorKind::Other,
+                "Journal is locked by another run of the script",
+            )),
+            Err(TryLockError::Error(e)) => Err(e.into()),
+        }
+    }
+
+    fn create_dir(&self) -> Result<()> {
+        if let Some(dir) = self.path.parent() {
+            DirBuilder::new().recursive(true).mode(0o700).create(dir)?;
+        }
+        Ok(())
+    }
+
+    /// Save the state before executing the task with `task_index`.
+    pub fn write(
+        &self,
+        task_index: usize,
+        vars: Option<&Value>,
+        pending_handlers: &PendingHandlers,
+    ) -> Result<()> {
+        let mut pending_handlers = pending_handlers
+            .get_pending()
+            .iter()
+            .cloned()
+            .collect::<Vec<_>>();
+        pending_handlers.sort();
+        let (vars, omitted_vars) = split_secret_vars(
+            serde_json::to_value(vars).map_err(|e| Error::new(ErrorKind::InvalidData, e))?,
+        );
+        let entry = Entry {
+            script_hash: self.script_hash.clone(),
+            args_hash: self.args_hash.clone(),
+            task_index,
+            vars,
+            pending_handlers,
+            omitted_vars,
+        };
+
+        self.create_dir()?;
+        // variables could still contain sensitive data
+        let tmp_path = self.path.with_extension("json.tmp");
+        let mut file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .mode(0o600)
+            .open(&tmp_path)?;
+        file.write_all(
+            serde_json::to_string(&entry)
+                .map_err(|e| Error::new(ErrorKind::InvalidData, e))?
+                .as_bytes(),
+        )?;
+        fs::rename(tmp_path, &self.path)?;
+        Ok(())
+    }
+
+    /// Return the entry to resume the run, failing if there is no journal or if the script or
+    /// its arguments changed since it was written.
+    pub fn read(&self) -> Result<Entry> {
+        let content = match fs::read_to_string(&self.path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                return Err(Error::new(
+                    ErrorKind::NotFound,
+                    "No journal found to resume, previous run finished or never started",
+                ));
+            }
+            Err(e) => return Err(e.into()),
+        };
+        let entry: Entry = serde_json::from_str(&content).map_err(|e| {
+            Error::new(
+                ErrorKind::InvalidData,
+                format!("Invalid journal '{}': {e}", self.path.display()),
+            )
+        })?;
+
+        if entry.script_hash != self.script_hash {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "Script changed since the journal was written, refusing to resume",
+            ));
+        }
+
+        if entry.args_hash != self.args_hash {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "Script arguments changed since the journal was written, refusing to resume",
+            ));
+        }
+        Ok(entry)
+    }
+
+    pub fn remove(&self) -> Result<()> {
+        match fs::remove_file(&self.path) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::os::unix::fs::PermissionsExt;
+
+    use minijinja::context;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_journal_write_read() {
+        let dir = tempdir().unwrap();
+        let journal = Journal::new(dir.path(), Path::new("/tmp/main.rh"), "- debug: {}", &[]);
+
+        let mut pending_handlers = PendingHandlers::new();
+        pending_handlers.notify(&["restart".to_owned(), "reload".to_owned()]);
+        journal
+            .write(3, Some(&context! {foo => "boo"}), &pending_handlers)
+            .unwrap();
+
+        let entry = journal.read().unwrap();
+        assert_eq!(entry.task_index, 3);
+        assert_eq!(entry.get_vars(), Some(context! {foo => "boo"}));
+        assert_eq!(entry.pending_handlers, vec!["reload", "restart"]);
+        assert_eq!(
+            entry.get_pending_handlers().get_pending(),
+            pending_handlers.get_pending()
+        );
+
+        let mode = fs::metadata(&journal.path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+
+    #[test]
+    fn test_journal_write_without_vars() {
+        let dir = tempdir().unwrap();
+        let journal = Journal::new(dir.path(), Path::new("/tmp/main.rh"), "- debug: {}", &[]);
+        journal.write(1, None, &PendingHandlers::new()).unwrap();
+
+        let entry = journal.read().unwrap();
+        assert_eq!(entry.get_vars(), None);
+        assert!(entry.get_pending_handlers().is_empty());
+    }
+
+    #[test]
+    fn test_journal_read_script_changed() {
+        let dir = tempdir().unwrap();
+        let script_path = Path::new("/tmp/main.rh");
+        Journal::new(dir.path(), script_path, "- debug: {}", &[])
+            .write(2, None, &PendingHandlers::new())
+            .unwrap();
+
+        let error = Journal::new(dir.path(), script_path, "- debug:\n    msg: foo", &[])
+            .read()
+            .unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+        assert_eq!(
+            error.to_string(),
+            "Script changed since the journal was written, refusing to resume"
+        );
+    }
+
+    #[test]
+    fn test_journal_read_args_changed() {
+        let dir = tempdir().unwrap();
+        let script_path = Path::new("/tmp/main.rh");
+        let args = ["--foo".to_owned(), "-e".to_owned(), "FOO=boo".to_owned()];
+        Journal::new(dir.path(), script_path, "", &args)
+            .write(2, None, &PendingHandlers::new())
+            .unwrap();
+
+        assert!(
+            Journal::new(dir.path(), script_path, "", &args)
+                .read()
+                .is_ok()
+        );
+        let error = Journal::new(dir.path(), script_path, "", &args[..1])
+            .read()
+            .unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+        assert_eq!(
+            error.to_string(),
+            "Script arguments changed since the journal was written, refusing to resume"
+        );
+    }
+
+    #[test]
+    fn test_journal_by_script_path() {
+        let dir = tempdir().unwrap();
+        Journal::new(dir.path(), Path::new("/tmp/main.rh"), "", &[])
+            .write(2, None, &PendingHandlers::new())
+            .unwrap();
+
+        let journal = Journal::new(dir.path(), Path::new("/tmp/other.rh"), "", &[]);
+        assert!(!journal.exists());
+        assert_eq!(journal.read().unwrap_err().kind(), ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn test_journal_write_omits_secret_vars() {
+        let dir = tempdir().unwrap();
+        let journal = Journal::new(dir.path(), Path::new("/tmp/main.rh"), "", &[]);
+
+        crate::logger::add_secret("journal-secret-value");
+        add_no_log_vars(&context! {journal_no_log_var => "boo"});
+        let vars = context! {
+            foo => "boo",
+            journal_no_log_var => "boo",
+            password => context! {content => "journal-secret-value"},
+        };
+        journal
+            .write(2, Some(&vars), &PendingHandlers::new())
+            .unwrap();
+
+        let content = fs::read_to_string(&journal.path).unwrap();
+        assert!(!content.contains("journal-secret-value"));
+        let entry = journal.read().unwrap();
+        assert_eq!(entry.get_vars(), Some(context! {foo => "boo"}));
+        assert_eq!(entry.omitted_vars, vec!["journal_no_log_var", "password"]);
+    }
+
+    #[test]
+    fn test_journal_try_lock() {
+        let dir = tempdir().unwrap();
+        let journal = Journal::new(dir.path(), Path::new("/tmp/main.rh"), "", &[]);
+
+        let lock = journal.try_lock().unwrap();
+        let error = journal.try_lock().unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Journal is locked by another run of the script"
+        );
+
+        drop(lock);
+        assert!(journal.try_lock().is_ok());
+    }
+
+    #[test]
+    fn test_journal_remove() {
+        let dir = tempdir().unwrap();
+        let journal = Journal::new(dir.path(), Path::new("/tmp/main.rh"), "", &[]);
+        journal.write(1, None, &PendingHandlers::new()).unwrap();
+        assert!(journal.exists());
+
+        journal.remove().unwrap();
+        assert!(!journal.exists());
+        journal.remove().unwrap();
+    }
+}
@@ -1,15 +1,21 @@
 #![allow(clippy::derive_partial_eq_without_eq)]
 
+pub mod connection;
 pub mod context;
 pub mod docopt;
 pub mod error;
+pub mod events;
 pub mod jinja;
 pub mod job;
+pub mod journal;
 pub mod logger;
 pub mod modules;
+pub mod push;
+pub mod stats;
 pub mod task;
 pub mod utils;
 pub mod vars;
+pub mod vault;
 
 #[macro_use]
 extern crate log;
@@ -24,6 +30,8 @@ mod tests {
     use task::parse_file;
     use vars::env;
 
+    use std::path::Path;
+
     #[test]
     fn test_command_ls() {
         let file = r#"
@@ -37,7 +45,7 @@ mod tests {
 
         let global_params = GlobalParams::default();
         let context = Context::new(
-            parse_file(file, &global_params).unwrap(),
+            parse_file(file, Path::new("main.rh"), &global_params).unwrap(),
             env::load(vec![]),
             None,
         );
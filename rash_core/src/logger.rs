@@ -1,9 +1,14 @@
 use crate::error::{Error, ErrorKind, Result};
+use crate::events;
 use crate::utils::get_terminal_width;
 
+use std::borrow::Cow;
+use std::cell::Cell;
+use std::collections::HashSet;
 use std::fmt;
 use std::io;
-use std::sync::atomic::{AtomicI32, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicI32, Ordering};
+use std::sync::{LazyLock, RwLock};
 
 use clap::ValueEnum;
 use console::{Style, style};
@@ -23,15 +28,21 @@ pub enum Output {
     Raw,
     /// output results as JSON for machine parsing
     Json,
+    /// output a versioned JSON event per line: task start and end, retries, loop items,
+    /// handlers and run recap
+    Jsonl,
 }
 
 static OUTPUT_FORMAT: AtomicI32 = AtomicI32::new(0);
+/// Add diffs to events, used just with `jsonl` output.
+static DIFF_EVENTS: AtomicBool = AtomicBool::new(false);
 
 fn output_to_int(output: &Output) -> i32 {
     match output {
         Output::Ansible => 0,
         Output::Raw => 1,
         Output::Json => 2,
+        Output::Jsonl => 3,
     }
 }
 
@@ -39,6 +50,7 @@ fn int_to_output(val: i32) -> Output {
     match val {
         1 => Output::Raw,
         2 => Output::Json,
+        3 => Output::Jsonl,
         _ => Output::Ansible,
     }
 }
@@ -55,6 +67,102 @@ pub fn is_json_output() -> bool {
     get_output_format() == Output::Json
 }
 
+/// Message logged instead of the result of tasks with `no_log`.
+pub const NO_LOG_MESSAGE: &str =
+    "the output has been hidden due to the fact that 'no_log: true' was specified for this result";
+
+const SECRET_MASK: &str = "********";
+/// Shorter values are not masked, they would redact unrelated output.
+const SECRET_MIN_LEN: usize = 4;
+
+static SECRETS: LazyLock<RwLock<HashSet<String>>> = LazyLock::new(|| RwLock::new(HashSet::new()));
+
+thread_local! {
+    /// Threads don't inherit it: threads executing tasks must set their own [`NoLogGuard`] from
+    /// the value of the thread spawning them, as `parallel` does.
+    static NO_LOG: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Register a secret value to be masked wherever it reaches the logger.
+pub fn add_secret(secret: &str) {
+    let variants = secret
+        .lines()
+        .map(str::to_owned)
+        .chain([secret.to_owned()])
+        // JSON output escapes special chars
+        .chain(
+            serde_json::to_string(secret)
+                .ok()
+                .map(|s| s.trim_matches('"').to_owned()),
+        )
+        .filter(|s| s.trim().len() >= SECRET_MIN_LEN)
+        .collect::<Vec<_>>();
+
+    if !variants.is_empty() {
+        let mut secrets = SECRETS.write().unwrap_or_else(|e| e.into_inner());
+        secrets.extend(variants);
+    }
+}
+
+/// Return registered secrets.
+pub fn get_secrets() -> Vec<String> {
+    SECRETS
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .iter()
+        .cloned()
+        .collect()
+}
+
+/// Replace registered secrets in a string with `********`.
+pub fn mask_secrets(s: &str) -> Cow<'_, str> {
+    let secrets = SECRETS.read().unwrap_or_else(|e| e.into_inner());
+    if secrets.is_empty() || !secrets.iter().any(|secret| s.contains(secret.as_str())) {
+        return Cow::Borrowed(s);
+    }
+
+    // longest first, so secrets containing other secrets are fully masked
+    let mut sorted_secrets = secrets.iter().collect::<Vec<_>>();
+    sorted_secrets.sort_by_key(|secret| std::cmp::Reverse(secret.len()));
+    Cow::Owned(
+        sorted_secrets
+            .into_iter()
+            .fold(s.to_owned(), |acc, secret| {
+                acc.replace(secret.as_str(), SECRET_MASK)
+            }),
+    )
+}
+
+/// Return true if the current task output must be hidden.
+pub fn is_no_log() -> bool {
+    NO_LOG.with(|no_log| no_log.get())
+}
+
+/// Hide task output until it is dropped. Nested guards keep output hidden.
+pub struct NoLogGuard {
+    previous: bool,
+}
+
+impl NoLogGuard {
+    pub fn new() -> Self {
+        NoLogGuard {
+            previous: NO_LOG.with(|no_log| no_log.replace(true)),
+        }
+    }
+}
+
+impl Default for NoLogGuard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for NoLogGuard {
+    fn drop(&mut self) {
+        NO_LOG.with(|no_log| no_log.set(self.previous));
+    }
+}
+
 impl fmt::Display for Line {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self.0 {
@@ -64,15 +172,32 @@ impl fmt::Display for Line {
     }
 }
 
+fn is_diff_enabled() -> bool {
+    // events are the only stdout logs, so `diff` target is never enabled with them
+    let is_enabled = match events::is_enabled() {
+        true => DIFF_EVENTS.load(Ordering::SeqCst),
+        false => log_enabled!(target: "diff", log::Level::Info),
+    };
+    is_enabled && !is_no_log()
+}
+
 /// Print iterator.
 fn print_diff<T>(iter: T, prefix: &str, style: &Style)
 where
     T: IntoIterator,
     T::Item: fmt::Display,
 {
-    if log_enabled!(target: "diff", log::Level::Info) {
+    if is_diff_enabled() && events::is_enabled() {
         iter.into_iter()
-            .for_each(|x| println!("{}{}", style.apply_to(prefix), style.apply_to(x)));
+            .for_each(|x| events::add_diff(&format!("{prefix}{x}\n")));
+    } else if is_diff_enabled() {
+        iter.into_iter().for_each(|x| {
+            println!(
+                "{}{}",
+                style.apply_to(prefix),
+                style.apply_to(mask_secrets(&x.to_string()))
+            )
+        });
     };
 }
 
@@ -100,10 +225,55 @@ where
     T: std::string::ToString,
     U: std::string::ToString,
 {
-    if log_enabled!(target: "diff", log::Level::Info) {
-        let o = original.to_string();
-        let m = modified.to_string();
+    diff_files_with_headers(original, modified, None, None);
+}
+
+/// Print formatted diff for files, preceded by their headers when any of them is passed.
+pub fn diff_files_with_headers<T, U>(
+    original: T,
+    modified: U,
+    original_header: Option<&str>,
+    modified_header: Option<&str>,
+) where
+    T: std::string::ToString,
+    U: std::string::ToString,
+{
+    if is_diff_enabled() {
+        let o = mask_secrets(&original.to_string()).into_owned();
+        let m = mask_secrets(&modified.to_string()).into_owned();
         let text_diff = TextDiff::from_lines(&o, &m);
+        let headers = match (original_header, modified_header) {
+            (None, None) => None,
+            _ => Some((
+                original_header.map_or("before".to_owned(), |h| format!("before: {h}")),
+                modified_header.map_or("after".to_owned(), |h| format!("after: {h}")),
+            )),
+        };
+
+        if events::is_enabled() {
+            let mut unified_diff = text_diff.unified_diff();
+            unified_diff.context_radius(3);
+            if let Some((original_header, modified_header)) = &headers {
+                unified_diff.header(original_header, modified_header);
+            }
+            events::add_diff(&unified_diff.to_string());
+            return;
+        }
+
+        if let Some((original_header, modified_header)) = &headers {
+            println!(
+                "{}",
+                Style::new()
+                    .red()
+                    .apply_to(format!("--- {original_header}"))
+            );
+            println!(
+                "{}",
+                Style::new()
+                    .green()
+                    .apply_to(format!("+++ {modified_header}"))
+            );
+        }
 
         for (idx, group) in text_diff.grouped_ops(3).iter().enumerate() {
             if idx > 0 {
@@ -158,10 +328,20 @@ where
     T: std::string::ToString,
     U: std::string::ToString,
 {
-    if log_enabled!(target: "diff", log::Level::Info) {
-        let o = original.to_string();
-        let m = modified.to_string();
+    if is_diff_enabled() {
+        let o = mask_secrets(&original.to_string()).into_owned();
+        let m = mask_secrets(&modified.to_string()).into_owned();
         let text_diff = TextDiff::from_lines(&o, &m);
+
+        if events::is_enabled() {
+            let diff_str = text_diff
+                .iter_all_changes()
+                .map(|change| format!("{}{change}", change.tag()))
+                .collect::<String>();
+            events::add_diff(&diff_str);
+            return;
+        }
+
         let diff_str = text_diff
             .iter_all_changes()
             .map(format_change)
@@ -172,6 +352,8 @@ where
 }
 
 fn ansible_log_format(out: FormatCallback, message: &fmt::Arguments, record: &log::Record) {
+    let message_string = message.to_string();
+    let message = mask_secrets(&message_string);
     let level = record.level();
     let target = record.target();
     let log_header = match (level, target) {
@@ -196,6 +378,7 @@ fn ansible_log_format(out: FormatCallback, message: &fmt::Arguments, record: &lo
         (log::Level::Info, "diff") => Style::new().color256(COLOR_BRIGHT_BLACK),
         (log::Level::Info, "ignoring") => Style::new().blue(),
         (log::Level::Info, "ok" | "ok_empty") => Style::new().green(),
+        (log::Level::Info, "skipped") => Style::new().cyan(),
         (log::Level::Info, _) => Style::new().white(),
         (log::Level::Debug, _) => Style::new().color256(COLOR_BRIGHT_BLUE), // bright blue
         (log::Level::Trace, _) => Style::new().color256(COLOR_BRIGHT_BLACK), // bright black
@@ -205,11 +388,11 @@ fn ansible_log_format(out: FormatCallback, message: &fmt::Arguments, record: &lo
         log_header = log_header,
         message = message,
         separator = match (level, target) {
-            (log::Level::Info, "task") => vec![
+            (log::Level::Info, "task" | "title") => vec![
                 "*";
                 {
                     let term_width = get_terminal_width();
-                    let message_total_len = log_header.len() + message.to_string().len();
+                    let message_total_len = log_header.len() + message.len();
                     if term_width > message_total_len {
                         term_width - message_total_len
                     } else {
@@ -225,12 +408,13 @@ fn ansible_log_format(out: FormatCallback, message: &fmt::Arguments, record: &lo
 }
 
 fn raw_log_format(out: FormatCallback, message: &fmt::Arguments, _record: &log::Record) {
-    out.finish(format_args!("{message}"))
+    out.finish(format_args!("{}", mask_secrets(&message.to_string())))
 }
 
 /// Setup logging according to the specified verbosity.
 pub fn setup_logging(verbosity: u8, diff: &bool, output: &Output) -> Result<()> {
     set_output_format(output);
+    DIFF_EVENTS.store(*diff, Ordering::SeqCst);
 
     let mut base_config = fern::Dispatch::new();
 
@@ -250,9 +434,18 @@ pub fn setup_logging(verbosity: u8, diff: &bool, output: &Output) -> Result<()>
     // Suppress task headers for internal task execution or raw/json output
     // For raw/json: suppress task headers but keep module output (ok/changed contain the output)
     base_config = match (output, is_internal) {
-        (Output::Raw | Output::Json, _) => {
-            // For raw/json: suppress only task headers, keep ok/changed for module output
-            base_config.level_for("task", log::LevelFilter::Error)
+        (Output::Raw, _) => {
+            // For raw: suppress only task headers and recap, keep ok/changed for module output
+            base_config
+                .level_for("task", log::LevelFilter::Error)
+                .level_for("title", log::LevelFilter::Error)
+                .level_for("recap", log::LevelFilter::Error)
+        }
+        (Output::Json, _) => {
+            // For json: suppress only task headers, keep ok/changed for module output and recap
+            base_config
+                .level_for("task", log::LevelFilter::Error)
+                .level_for("title", log::LevelFilter::Error)
         }
         (_, true) => {
             // For internal task with ansible output: suppress only task headers
@@ -263,14 +456,20 @@ pub fn setup_logging(verbosity: u8, diff: &bool, output: &Output) -> Result<()>
 
     let log_format = match output {
         Output::Ansible => ansible_log_format,
-        Output::Raw | Output::Json => raw_log_format,
+        Output::Raw | Output::Json | Output::Jsonl => raw_log_format,
     };
 
+    // For jsonl: stdout just contains events, errors and warnings are still logged to stderr
+    let is_event_output = *output == Output::Jsonl;
+
     base_config
         .format(log_format)
         .chain(
             fern::Dispatch::new()
-                .filter(|metadata| metadata.level() >= log::LevelFilter::Warn)
+                .filter(move |metadata| match is_event_output {
+                    true => metadata.target() == events::EVENT_TARGET,
+                    false => metadata.level() >= log::LevelFilter::Warn,
+                })
                 .chain(io::stdout()),
         )
         .chain(
@@ -281,3 +480,49 @@ pub fn setup_logging(verbosity: u8, diff: &bool, output: &Output) -> Result<()>
         .apply()
         .map_err(|e| Error::new(ErrorKind::InvalidData, e))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mask_secrets() {
+        add_secret("logger-test-secret");
+        assert_eq!(
+            mask_secrets("password: logger-test-secret"),
+            "password: ********"
+        );
+        assert_eq!(mask_secrets("nothing to hide"), "nothing to hide");
+    }
+
+    #[test]
+    fn test_mask_secrets_multiline() {
+        add_secret("logger-test-first\nlogger-test-second");
+        assert_eq!(mask_secrets("logger-test-second"), "********");
+        assert_eq!(
+            mask_secrets(r#"{"output":"logger-test-first\nlogger-test-second"}"#),
+            r#"{"output":"********"}"#
+        );
+    }
+
+    #[test]
+    fn test_add_secret_too_short() {
+        add_secret("abc");
+        assert!(!get_secrets().contains(&"abc".to_owned()));
+    }
+
+    #[test]
+    fn test_no_log_guard() {
+        assert!(!is_no_log());
+        {
+            let _guard = NoLogGuard::new();
+            assert!(is_no_log());
+            {
+                let _nested_guard = NoLogGuard::new();
+                assert!(is_no_log());
+            }
+            assert!(is_no_log());
+        }
+        assert!(!is_no_log());
+    }
+}
@@ -6,13 +6,6 @@
 /// ACLs provide fine-grained permission control beyond standard Unix permissions.
 /// They allow per-user and per-group permissions on files and directories.
 /// Useful for containers, IoT devices, and multi-user file sharing scenarios.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -59,7 +52,7 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -339,6 +332,7 @@ fn handle_present(params: &Params, check_mode: bool) -> Result<ModuleResult> {
                 changed: false,
                 output: Some(params.path.clone()),
                 extra: None,
+                ..Default::default()
             });
         }
         debug!(
@@ -360,6 +354,7 @@ fn handle_present(params: &Params, check_mode: bool) -> Result<ModuleResult> {
         changed: true,
         output: Some(params.path.clone()),
         extra: None,
+        ..Default::default()
     })
 }
 
@@ -383,12 +378,14 @@ fn handle_absent(params: &Params, check_mode: bool) -> Result<ModuleResult> {
                 changed: true,
                 output: Some(params.path.clone()),
                 extra: None,
+                ..Default::default()
             })
         }
         None => Ok(ModuleResult {
             changed: false,
             output: Some(params.path.clone()),
             extra: None,
+            ..Default::default()
         }),
     }
 }
@@ -415,6 +412,7 @@ fn handle_query(params: &Params) -> Result<ModuleResult> {
         changed: false,
         output: Some(params.path.clone()),
         extra: Some(extra),
+        ..Default::default()
     })
 }
 
@@ -452,6 +450,10 @@ impl Module for Acl {
     }
 }
 
+impl ModuleParams for Acl {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2,13 +2,6 @@
 /// # alternatives
 ///
 /// Manage symbolic links determining default commands.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -33,7 +26,7 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -304,6 +297,10 @@ impl Module for Alternatives {
     }
 }
 
+impl ModuleParams for Alternatives {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2,13 +2,6 @@
 /// # apk
 ///
 /// Manage packages with the apk package manager, which is used by Alpine Linux.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -44,7 +37,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Module, ModuleParams, ModuleResult, parse_params};
 use crate::utils::default_false;
 
 #[cfg(feature = "docs")]
@@ -157,6 +150,10 @@ impl Module for Apk {
     }
 }
 
+impl ModuleParams for Apk {
+    type Params = Params;
+}
+
 type IsChanged = bool;
 
 struct ApkClient {
@@ -379,6 +376,7 @@ fn apk(params: Params, check_mode: bool) -> Result<ModuleResult> {
         extra: Some(value::to_value(
             json!({"installed_packages": p_to_install, "removed_packages": p_to_remove, "upgraded": upgrade_changed, "cache_updated": cache_updated}),
         )?),
+        ..Default::default()
     })
 }
 
@@ -2,13 +2,6 @@
 /// # apt
 ///
 /// Manage packages with the apt package manager, which is used by Debian, Ubuntu and their variants.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -46,7 +39,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Module, ModuleParams, ModuleResult, parse_params};
 use crate::utils::default_false;
 
 #[cfg(feature = "docs")]
@@ -199,6 +192,10 @@ impl Module for Apt {
     }
 }
 
+impl ModuleParams for Apt {
+    type Params = Params;
+}
+
 type IsChanged = bool;
 
 struct AptClient {
@@ -441,6 +438,7 @@ fn apt(params: Params, check_mode: bool) -> Result<ModuleResult> {
             changed: true,
             output: None,
             extra: Some(value::to_value(json!({"deb": deb_path}))?),
+            ..Default::default()
         });
     }
 
@@ -542,6 +540,7 @@ fn apt(params: Params, check_mode: bool) -> Result<ModuleResult> {
         extra: Some(value::to_value(
             json!({"installed_packages": p_to_install, "removed_packages": p_to_remove, "upgraded_packages": p_to_upgrade, "upgraded": upgrade_changed}),
         )?),
+        ..Default::default()
     })
 }
 
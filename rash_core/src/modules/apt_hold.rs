@@ -8,13 +8,6 @@
 /// Holding packages prevents them from being automatically upgraded, which is
 /// critical for production systems and IoT devices where specific versions
 /// must be maintained. This module uses `apt-mark` to manage package holds.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -52,7 +45,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -290,6 +283,7 @@ fn apt_hold_impl(params: Params, check_mode: bool) -> Result<ModuleResult> {
         changed,
         output: None,
         extra,
+        ..Default::default()
     })
 }
 
@@ -322,6 +316,10 @@ impl Module for AptHold {
     }
 }
 
+impl ModuleParams for AptHold {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2,13 +2,6 @@
 /// # apt_repository
 ///
 /// Manage APT repositories on Debian/Ubuntu systems.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -40,7 +33,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger::{self, diff};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Capabilities, Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -320,6 +313,7 @@ pub fn apt_repository(params: Params, check_mode: bool) -> Result<ModuleResult>
         changed,
         output: Some(file_path.to_string_lossy().to_string()),
         extra: Some(serde_norway::Value::String(repo_line.trim().to_string())),
+        ..Default::default()
     })
 }
 
@@ -331,6 +325,13 @@ impl Module for AptRepository {
         "apt_repository"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            diff: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -354,6 +355,10 @@ impl Module for AptRepository {
     }
 }
 
+impl ModuleParams for AptRepository {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2,15 +2,6 @@
 /// # archive
 ///
 /// Creates a compressed archive of one or more files or directories.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: partial
-/// diff_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -39,7 +30,9 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger::diff;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{
+    Capabilities, CheckModeSupport, Module, ModuleParams, ModuleResult, parse_params,
+};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -543,6 +536,7 @@ fn run_archive(params: Params, check_mode: bool) -> Result<ModuleResult> {
                 dest.display()
             )),
             extra: None,
+            ..Default::default()
         });
     }
 
@@ -557,6 +551,7 @@ fn run_archive(params: Params, check_mode: bool) -> Result<ModuleResult> {
                 expanded_paths.len()
             )),
             extra: None,
+            ..Default::default()
         });
     }
 
@@ -612,6 +607,7 @@ fn run_archive(params: Params, check_mode: bool) -> Result<ModuleResult> {
             archive_size
         )),
         extra: None,
+        ..Default::default()
     })
 }
 
@@ -623,6 +619,14 @@ impl Module for Archive {
         "archive"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            check_mode: CheckModeSupport::Partial,
+            diff: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -639,6 +643,10 @@ impl Module for Archive {
     }
 }
 
+impl ModuleParams for Archive {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
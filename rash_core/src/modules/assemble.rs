@@ -2,13 +2,6 @@
 /// # assemble
 ///
 /// Assemble configuration files from fragments.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -35,7 +28,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger::diff;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Capabilities, Module, ModuleParams, ModuleResult, parse_params};
 use crate::utils::parse_octal;
 
 #[cfg(feature = "docs")]
@@ -227,6 +220,7 @@ fn assemble_fragments(params: Params, check_mode: bool) -> Result<ModuleResult>
             changed: false,
             output: Some(params.dest),
             extra: None,
+            ..Default::default()
         });
     }
 
@@ -237,6 +231,7 @@ fn assemble_fragments(params: Params, check_mode: bool) -> Result<ModuleResult>
             changed: true,
             output: Some(params.dest),
             extra: None,
+            ..Default::default()
         });
     }
 
@@ -271,6 +266,7 @@ fn assemble_fragments(params: Params, check_mode: bool) -> Result<ModuleResult>
         changed: true,
         output: Some(params.dest),
         extra: None,
+        ..Default::default()
     })
 }
 
@@ -282,6 +278,13 @@ impl Module for Assemble {
         "assemble"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            diff: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -301,6 +304,10 @@ impl Module for Assemble {
     }
 }
 
+impl ModuleParams for Assemble {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
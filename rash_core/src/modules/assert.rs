@@ -2,13 +2,6 @@
 /// # assert
 ///
 /// Assert given expressions are true.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: always
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -24,7 +17,9 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::jinja::is_render_string;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{
+    Capabilities, CheckModeSupport, Module, ModuleParams, ModuleResult, parse_params,
+};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -59,6 +54,7 @@ fn verify_conditions(params: Params, vars: &Value) -> Result<ModuleResult> {
         changed: false,
         output: None,
         extra: None,
+        ..Default::default()
     })
 }
 
@@ -70,6 +66,13 @@ impl Module for Assert {
         "assert"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            check_mode: CheckModeSupport::Always,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -89,6 +92,10 @@ impl Module for Assert {
     }
 }
 
+impl ModuleParams for Assert {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use minijinja::context;
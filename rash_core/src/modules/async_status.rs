@@ -3,12 +3,9 @@
 ///
 /// Check the status of an async task.
 ///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: none
-/// ```
+/// Jobs are persisted in `$XDG_STATE_HOME/rash/jobs`, so jobs started by previous runs can be
+/// checked too. The result contains `rc`, `stdout` and `stderr` of the command, as `command`
+/// results do, and the job `status`, `finished` and `failed` in `extra`.
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -24,7 +21,7 @@
 ///   async_status:
 ///     jid: "{{ job.rash_job_id }}"
 ///   register: result
-///   until: result.finished
+///   until: result.extra.finished
 ///   retries: 30
 ///   delay: 10
 /// ```
@@ -32,7 +29,9 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::job::{JobStatus, get_job_info, job_exists};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{
+    Capabilities, CheckModeSupport, Module, ModuleParams, ModuleResult, parse_params,
+};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -116,6 +115,14 @@ impl Module for AsyncStatus {
         "async_status"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            // it just reads the status of a job started in background
+            check_mode: CheckModeSupport::Always,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -171,7 +178,10 @@ impl Module for AsyncStatus {
         };
 
         Ok((
-            ModuleResult::new(info.changed, extra, Some(output_str)),
+            ModuleResult::new(info.changed, extra, Some(output_str))
+                .with_rc(info.rc)
+                .with_stdout(info.stdout)
+                .with_stderr(info.stderr),
             None,
         ))
     }
@@ -182,6 +192,10 @@ impl Module for AsyncStatus {
     }
 }
 
+impl ModuleParams for AsyncStatus {
+    type Params = Params;
+}
+
 #[derive(Debug)]
 pub struct AsyncPoll;
 
@@ -201,6 +215,13 @@ impl Module for AsyncPoll {
         "async_poll"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            check_mode: CheckModeSupport::Always,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -239,7 +260,13 @@ impl Module for AsyncPoll {
                         "changed": info.changed,
                         "elapsed": info.elapsed.as_secs(),
                     }))?);
-                    return Ok((ModuleResult::new(info.changed, extra, info.output), None));
+                    return Ok((
+                        ModuleResult::new(info.changed, extra, info.output)
+                            .with_rc(info.rc)
+                            .with_stdout(info.stdout)
+                            .with_stderr(info.stderr),
+                        None,
+                    ));
                 }
                 JobStatus::Failed => {
                     let extra = Some(value::to_value(json!({
@@ -252,7 +279,13 @@ impl Module for AsyncPoll {
                         "changed": info.changed,
                         "elapsed": info.elapsed.as_secs(),
                     }))?);
-                    return Ok((ModuleResult::new(info.changed, extra, info.output), None));
+                    return Ok((
+                        ModuleResult::new(info.changed, extra, info.output)
+                            .with_rc(info.rc)
+                            .with_stdout(info.stdout)
+                            .with_stderr(info.stderr),
+                        None,
+                    ));
                 }
                 JobStatus::Running | JobStatus::Pending => {
                     trace!(
@@ -274,6 +307,10 @@ impl Module for AsyncPoll {
     }
 }
 
+impl ModuleParams for AsyncPoll {
+    type Params = PollParams;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2,14 +2,6 @@
 /// # at
 ///
 /// Manage one-time scheduled jobs using the at daemon.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: partial
-///   details: In check mode, the module reports what would change but does not actually schedule jobs.
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -42,7 +34,9 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger::diff;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{
+    Capabilities, CheckModeSupport, Module, ModuleParams, ModuleResult, parse_params,
+};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -330,6 +324,7 @@ pub fn at(params: Params, check_mode: bool) -> Result<ModuleResult> {
                         changed: false,
                         output: Some(format!("Job already scheduled (id: {})", duplicates[0].id)),
                         extra: None,
+                        ..Default::default()
                     });
                 }
             }
@@ -346,6 +341,7 @@ pub fn at(params: Params, check_mode: bool) -> Result<ModuleResult> {
                             job_name
                         )),
                         extra: None,
+                        ..Default::default()
                     });
                 }
 
@@ -366,6 +362,7 @@ pub fn at(params: Params, check_mode: bool) -> Result<ModuleResult> {
                         changed: true,
                         output: Some(format!("Job '{}' updated (id: {})", job_name, job_id)),
                         extra: None,
+                        ..Default::default()
                     });
                 }
 
@@ -373,6 +370,7 @@ pub fn at(params: Params, check_mode: bool) -> Result<ModuleResult> {
                     changed: true,
                     output: Some(format!("Job '{}' would be updated", job_name)),
                     extra: None,
+                    ..Default::default()
                 });
             }
 
@@ -386,6 +384,7 @@ pub fn at(params: Params, check_mode: bool) -> Result<ModuleResult> {
                     changed: true,
                     output: Some(format!("Job '{}' would be scheduled", job_name)),
                     extra: None,
+                    ..Default::default()
                 });
             }
 
@@ -395,6 +394,7 @@ pub fn at(params: Params, check_mode: bool) -> Result<ModuleResult> {
                 changed: true,
                 output: Some(format!("Job '{}' scheduled (id: {})", job_name, job_id)),
                 extra: None,
+                ..Default::default()
             })
         }
         State::Absent => {
@@ -405,6 +405,7 @@ pub fn at(params: Params, check_mode: bool) -> Result<ModuleResult> {
                     changed: false,
                     output: Some(format!("Job '{}' not found", job_name)),
                     extra: None,
+                    ..Default::default()
                 });
             }
 
@@ -418,6 +419,7 @@ pub fn at(params: Params, check_mode: bool) -> Result<ModuleResult> {
                     changed: true,
                     output: Some(format!("Job '{}' would be removed", job_name)),
                     extra: None,
+                    ..Default::default()
                 });
             }
 
@@ -429,6 +431,7 @@ pub fn at(params: Params, check_mode: bool) -> Result<ModuleResult> {
                 changed: true,
                 output: Some(format!("Job '{}' removed", job_name)),
                 extra: None,
+                ..Default::default()
             })
         }
     }
@@ -442,6 +445,14 @@ impl Module for At {
         "at"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            check_mode: CheckModeSupport::Partial,
+            diff: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _global_params: &GlobalParams,
@@ -458,6 +469,10 @@ impl Module for At {
     }
 }
 
+impl ModuleParams for At {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2,13 +2,6 @@
 /// # auditd
 ///
 /// Manage Linux audit daemon rules.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -49,7 +42,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger::diff;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Capabilities, Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -238,6 +231,13 @@ impl Module for Auditd {
         "auditd"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            diff: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -254,6 +254,10 @@ impl Module for Auditd {
     }
 }
 
+impl ModuleParams for Auditd {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2,13 +2,6 @@
 /// # authorized_key
 ///
 /// Add or remove SSH authorized keys for a user.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -45,7 +38,7 @@
 /// ANCHOR_END: examples
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger::diff;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Capabilities, Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -374,6 +367,7 @@ pub fn authorized_key(params: Params, check_mode: bool) -> Result<ModuleResult>
         changed,
         output: Some(keys_path.to_string_lossy().to_string()),
         extra: None,
+        ..Default::default()
     })
 }
 
@@ -385,6 +379,13 @@ impl Module for AuthorizedKey {
         "authorized_key"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            diff: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &crate::context::GlobalParams,
@@ -404,6 +405,10 @@ impl Module for AuthorizedKey {
     }
 }
 
+impl ModuleParams for AuthorizedKey {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
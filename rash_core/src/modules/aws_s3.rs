@@ -2,13 +2,6 @@
 /// # aws_s3
 ///
 /// Manage AWS S3 objects for cloud storage operations.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -59,7 +52,7 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -607,6 +600,10 @@ impl Module for AwsS3 {
     }
 }
 
+impl ModuleParams for AwsS3 {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -6,13 +6,6 @@
 /// This module discards (TRIMs) blocks on a device, which is essential for
 /// SSDs and NVMe drives to maintain performance and longevity. Unlike
 /// traditional hard drives, SSDs need TRIM/DISCARD commands for proper reset.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -49,7 +42,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger::diff;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Capabilities, Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -99,6 +92,13 @@ impl Module for Blkdiscard {
         "blkdiscard"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            diff: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -118,6 +118,10 @@ impl Module for Blkdiscard {
     }
 }
 
+impl ModuleParams for Blkdiscard {
+    type Params = Params;
+}
+
 struct BlkdiscardClient;
 
 impl BlkdiscardClient {
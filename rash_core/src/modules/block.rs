@@ -5,13 +5,6 @@
 /// Similar to Ansible's block directive.
 ///
 /// Note: `vars` declared in a block are added to the parent context.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: parameters
 /// | Parameter | Required | Type | Values | Description                   |
@@ -38,7 +31,7 @@
 /// ANCHOR_END: examples
 use crate::context::{Context, GlobalParams};
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult};
+use crate::modules::{Capabilities, Module, ModuleResult, ValidateParams};
 use crate::task::{Task, Tasks};
 
 use minijinja::Value;
@@ -54,6 +47,13 @@ impl Module for Block {
         "block"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            r#become: false,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         global_params: &GlobalParams,
@@ -92,9 +92,11 @@ impl Module for Block {
     }
 }
 
+impl ValidateParams for Block {}
+
 impl Block {
     /// Parse YAML task definitions into validated Task objects.
-    fn parse_tasks_from_yaml<'a>(
+    pub(crate) fn parse_tasks_from_yaml<'a>(
         &self,
         task_yamls: &[YamlValue],
         global_params: &'a GlobalParams,
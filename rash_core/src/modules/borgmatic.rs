@@ -4,13 +4,6 @@
 /// Manage Borg/Borgmatic backups with support for create, extract, prune, and check operations.
 /// Borg is a deduplicating archiver with compression and authenticated encryption.
 /// Borgmatic is a wrapper that simplifies backup configuration and automation.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: partial
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -74,7 +67,9 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{
+    Capabilities, CheckModeSupport, Module, ModuleParams, ModuleResult, parse_params,
+};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -368,6 +363,7 @@ fn run_borgmatic(params: Params, check_mode: bool) -> Result<(ModuleResult, Opti
             changed,
             output: module_output,
             extra,
+            ..Default::default()
         },
         None,
     ))
@@ -381,6 +377,13 @@ impl Module for Borgmatic {
         "borgmatic"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            check_mode: CheckModeSupport::Partial,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -398,6 +401,10 @@ impl Module for Borgmatic {
     }
 }
 
+impl ModuleParams for Borgmatic {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
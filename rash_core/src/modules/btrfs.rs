@@ -2,13 +2,6 @@
 /// # btrfs
 ///
 /// Manage Btrfs subvolumes, snapshots, and properties.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -65,7 +58,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger::diff;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Capabilities, Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -125,6 +118,13 @@ impl Module for Btrfs {
         "btrfs"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            diff: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -148,6 +148,10 @@ impl Module for Btrfs {
     }
 }
 
+impl ModuleParams for Btrfs {
+    type Params = Params;
+}
+
 struct BtrfsClient {
     check_mode: bool,
 }
@@ -517,6 +521,7 @@ fn btrfs_module(params: Params, check_mode: bool) -> Result<ModuleResult> {
         changed: result.changed,
         output: result.output,
         extra: Some(serde_norway::value::to_value(extra)?),
+        ..Default::default()
     })
 }
 
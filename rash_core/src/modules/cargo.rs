@@ -2,13 +2,6 @@
 /// # cargo
 ///
 /// Manage Rust crates with cargo, Rust's package manager.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -58,7 +51,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Module, ModuleParams, ModuleResult, parse_params};
 use crate::utils::default_false;
 
 #[cfg(feature = "docs")]
@@ -202,6 +195,10 @@ impl Module for Cargo {
     }
 }
 
+impl ModuleParams for Cargo {
+    type Params = Params;
+}
+
 struct CargoClient {
     executable: PathBuf,
     extra_args: Option<String>,
@@ -402,6 +399,7 @@ fn cargo(params: Params, check_mode: bool) -> Result<ModuleResult> {
         extra: Some(value::to_value(
             json!({"installed_crates": p_to_install, "removed_crates": p_to_remove}),
         )?),
+        ..Default::default()
     })
 }
 
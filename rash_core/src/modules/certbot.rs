@@ -7,13 +7,6 @@
 /// using the certbot tool. It supports both HTTP-01 and DNS-01 challenges and is
 /// idempotent — it will only request a new certificate when one does not already exist
 /// or is within `expire_days` of expiration.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -44,7 +37,7 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -176,6 +169,7 @@ fn obtain_certificate(params: &Params, check_mode: bool) -> Result<ModuleResult>
             changed: false,
             output: Some(format!("Certificate for {} is valid", domain)),
             extra: None,
+            ..Default::default()
         });
     }
 
@@ -187,6 +181,7 @@ fn obtain_certificate(params: &Params, check_mode: bool) -> Result<ModuleResult>
                 params.domains.join(", ")
             )),
             extra: None,
+            ..Default::default()
         });
     }
 
@@ -238,6 +233,7 @@ fn obtain_certificate(params: &Params, check_mode: bool) -> Result<ModuleResult>
         changed: true,
         output: Some(format!("Certificate obtained for {}", domain)),
         extra,
+        ..Default::default()
     })
 }
 
@@ -251,6 +247,7 @@ fn remove_certificate(params: &Params, check_mode: bool) -> Result<ModuleResult>
             changed: false,
             output: Some(format!("Certificate for {} does not exist", domain)),
             extra: None,
+            ..Default::default()
         });
     }
 
@@ -259,6 +256,7 @@ fn remove_certificate(params: &Params, check_mode: bool) -> Result<ModuleResult>
             changed: true,
             output: Some(format!("Would remove certificate for {}", domain)),
             extra: None,
+            ..Default::default()
         });
     }
 
@@ -289,6 +287,7 @@ fn remove_certificate(params: &Params, check_mode: bool) -> Result<ModuleResult>
                     domain
                 )),
                 extra: None,
+                ..Default::default()
             });
         }
 
@@ -302,6 +301,7 @@ fn remove_certificate(params: &Params, check_mode: bool) -> Result<ModuleResult>
         changed: true,
         output: Some(format!("Certificate removed for {}", domain)),
         extra: None,
+        ..Default::default()
     })
 }
 
@@ -341,6 +341,10 @@ impl Module for Certbot {
     }
 }
 
+impl ModuleParams for Certbot {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
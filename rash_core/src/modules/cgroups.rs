@@ -4,13 +4,6 @@
 /// Manage Linux control groups (cgroups) for resource management.
 ///
 /// Supports both cgroup v1 and v2 filesystem-based management.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -55,7 +48,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger::diff;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Capabilities, Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -898,6 +891,13 @@ impl Module for Cgroups {
         "cgroups"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            diff: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -914,6 +914,10 @@ impl Module for Cgroups {
     }
 }
 
+impl ModuleParams for Cgroups {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2,13 +2,6 @@
 /// # chroot
 ///
 /// Execute commands within a chroot environment.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: none
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -42,7 +35,9 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{
+    Capabilities, CheckModeSupport, Module, ModuleParams, ModuleResult, parse_params,
+};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -478,6 +473,13 @@ impl Module for Chroot {
         "chroot"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            check_mode: CheckModeSupport::None,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -501,6 +503,10 @@ impl Module for Chroot {
     }
 }
 
+impl ModuleParams for Chroot {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -6,13 +6,6 @@
 /// Cloud-init is the industry-standard multi-distro method for cross-platform
 /// cloud instance initialization. This module manages cloud-init configuration
 /// files, user-data, meta-data, and network configuration.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -66,7 +59,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger::diff;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Capabilities, Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -146,6 +139,13 @@ impl Module for CloudInit {
         "cloud_init"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            diff: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -169,6 +169,10 @@ impl Module for CloudInit {
     }
 }
 
+impl ModuleParams for CloudInit {
+    type Params = Params;
+}
+
 fn get_config_path(params: &Params) -> PathBuf {
     let dir = params.directory.as_deref().unwrap_or(DEFAULT_CLOUD_CFG_DIR);
     PathBuf::from(dir)
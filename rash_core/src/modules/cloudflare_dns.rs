@@ -2,13 +2,6 @@
 /// # cloudflare_dns
 ///
 /// Manage DNS records on Cloudflare.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -86,7 +79,7 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -670,6 +663,10 @@ impl Module for CloudflareDns {
     }
 }
 
+impl ModuleParams for CloudflareDns {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
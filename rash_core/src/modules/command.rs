@@ -2,13 +2,6 @@
 /// # command
 ///
 /// Execute commands.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -32,7 +25,7 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Capabilities, Module, ModuleResult, ValidateParams, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -47,7 +40,6 @@ use minijinja::Value;
 use schemars::{JsonSchema, Schema};
 use serde::Deserialize;
 use serde_norway::Value as YamlValue;
-use serde_norway::value;
 
 #[derive(Debug, PartialEq, Deserialize)]
 #[cfg_attr(feature = "docs", derive(JsonSchema, DocJsonSchema))]
@@ -104,6 +96,13 @@ impl Module for Command {
         "command"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            r#async: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -131,6 +130,7 @@ impl Module for Command {
                     changed: true,
                     output: Some(format!("Would run: {}", cmd_str)),
                     extra: None,
+                    ..Default::default()
                 },
                 None,
             ));
@@ -173,32 +173,22 @@ impl Module for Command {
                         .map_err(|e| Error::new(ErrorKind::SubprocessFail, e))?;
 
                     trace!("exec - output: {output:?}");
-                    let stderr = String::from_utf8_lossy(&output.stderr);
-
-                    if !output.status.success() {
-                        return Err(Error::new(ErrorKind::InvalidData, stderr));
-                    }
-                    let output_string = String::from_utf8_lossy(&output.stdout);
-
-                    let module_output = if output_string.is_empty() {
-                        None
-                    } else {
-                        Some(output_string.into_owned())
+                    let stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+                    let stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+                    let module_output = (!stdout.is_empty()).then(|| stdout.clone());
+
+                    let result = ModuleResult::new(true, None, module_output)
+                        .with_rc(output.status.code())
+                        .with_stdout(stdout);
+                    let result = match output.status.success() {
+                        true => result.with_stderr(stderr),
+                        false => result
+                            .with_failed(true)
+                            .with_msg(stderr.clone())
+                            .with_stderr(stderr),
                     };
 
-                    let extra = Some(value::to_value(json!({
-                        "rc": output.status.code(),
-                        "stderr": stderr,
-                    }))?);
-
-                    Ok((
-                        ModuleResult {
-                            changed: true,
-                            output: module_output,
-                            extra,
-                        },
-                        None,
-                    ))
+                    Ok((result, None))
                 }
             },
         }
@@ -210,6 +200,15 @@ impl Module for Command {
     }
 }
 
+impl ValidateParams for Command {
+    fn validate_params(&self, params: YamlValue) -> Result<()> {
+        if params.as_str().is_none() {
+            parse_params::<Params>(params)?;
+        }
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
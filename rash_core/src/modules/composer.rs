@@ -5,13 +5,6 @@
 ///
 /// Composer is a tool for dependency management in PHP. It allows you to declare
 /// the dependent libraries your project needs and it installs them in your project for you.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -62,7 +55,7 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Module, ModuleParams, ModuleResult, parse_params};
 use crate::utils::default_false;
 
 #[cfg(feature = "docs")]
@@ -227,6 +220,10 @@ impl Module for Composer {
     }
 }
 
+impl ModuleParams for Composer {
+    type Params = Params;
+}
+
 struct ComposerClient {
     composer_executable: PathBuf,
     php_executable: Option<PathBuf>,
@@ -3,13 +3,6 @@
 ///
 /// Manage Linux connection tracking table entries. Essential for container
 /// networking, firewall troubleshooting, and IoT network management.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -51,7 +44,7 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -356,6 +349,10 @@ impl Module for Conntrack {
     }
 }
 
+impl ModuleParams for Conntrack {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2,13 +2,6 @@
 /// # consul_kv
 ///
 /// Manage Consul key-value store entries.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -62,7 +55,7 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -488,6 +481,10 @@ impl Module for ConsulKv {
     }
 }
 
+impl ModuleParams for ConsulKv {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2,13 +2,6 @@
 /// # copy
 ///
 /// Copy files to path.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -22,8 +15,9 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::logger::diff_files;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::logger::{diff_files, diff_files_with_headers};
+use crate::modules::dynamic::resolve_module_file;
+use crate::modules::{Capabilities, Diff, Module, ModuleParams, ModuleResult, parse_params};
 use crate::utils::parse_octal;
 
 #[cfg(feature = "docs")]
@@ -186,6 +180,7 @@ fn copy_symlink(src: &str, dest: &str, check_mode: bool) -> Result<Option<Module
                     changed: false,
                     output: Some(dest.to_owned()),
                     extra: None,
+                    ..Default::default()
                 }));
             }
             diff_files(
@@ -216,6 +211,7 @@ fn copy_symlink(src: &str, dest: &str, check_mode: bool) -> Result<Option<Module
         changed: true,
         output: Some(dest.to_owned()),
         extra: None,
+        ..Default::default()
     }))
 }
 
@@ -293,6 +289,14 @@ fn copy_directory(
                 true,
                 check_mode,
             )?;
+            if let Some(diff) = result.get_diff() {
+                diff_files_with_headers(
+                    &diff.before,
+                    &diff.after,
+                    diff.before_header.as_deref(),
+                    diff.after_header.as_deref(),
+                );
+            }
             if result.changed {
                 changed = true;
             }
@@ -330,6 +334,7 @@ fn copy_directory(
         changed,
         output: Some(dest.to_owned()),
         extra: None,
+        ..Default::default()
     })
 }
 
@@ -416,9 +421,14 @@ pub fn copy_file(params: Params, check_mode: bool) -> Result<ModuleResult> {
         }
     };
 
-    if content != desired_content {
-        diff_files(&content, &desired_content);
+    let diff = match (&content, &desired_content) {
+        (Content::Str(before), Content::Str(after)) if before != after => {
+            Some(Diff::new(before, after).with_headers(&params.dest, &params.dest))
+        }
+        _ => None,
+    };
 
+    if content != desired_content {
         if !check_mode {
             trace!("changing content: {:?}", desired_content);
             if dest_permissions.readonly() {
@@ -468,10 +478,10 @@ pub fn copy_file(params: Params, check_mode: bool) -> Result<ModuleResult> {
         None => (),
     };
 
-    Ok(ModuleResult {
-        changed,
-        output: Some(params.dest),
-        extra: None,
+    let result = ModuleResult::new(changed, None, Some(params.dest));
+    Ok(match diff {
+        Some(diff) => result.with_diff(diff),
+        None => result,
     })
 }
 
@@ -483,14 +493,29 @@ impl Module for Copy {
         "copy"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            diff: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
         optional_params: YamlValue,
-        _vars: &Value,
+        vars: &Value,
         check_mode: bool,
     ) -> Result<(ModuleResult, Option<Value>)> {
-        Ok((copy_file(parse_params(optional_params)?, check_mode)?, None))
+        let params: Params = parse_params(optional_params)?;
+        let params = match params.input {
+            Input::Src(src) => Params {
+                input: Input::Src(resolve_module_file(&src, "files", vars)),
+                ..params
+            },
+            Input::Content(_) => params,
+        };
+        Ok((copy_file(params, check_mode)?, None))
     }
 
     #[cfg(feature = "docs")]
@@ -499,6 +524,10 @@ impl Module for Copy {
     }
 }
 
+impl ModuleParams for Copy {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -666,6 +695,7 @@ mod tests {
                 changed: false,
                 output: Some(file_path.to_str().unwrap().to_owned()),
                 extra: None,
+                ..Default::default()
             }
         );
     }
@@ -713,6 +743,11 @@ mod tests {
                 changed: true,
                 output: Some(file_dest_path.to_str().unwrap().to_owned()),
                 extra: None,
+                diff: Some(Diff::new("", "test\n").with_headers(
+                    file_dest_path.to_str().unwrap().to_owned(),
+                    file_dest_path.to_str().unwrap().to_owned()
+                )),
+                ..Default::default()
             }
         );
     }
@@ -767,6 +802,7 @@ mod tests {
                 changed: false,
                 output: Some(file_dest_path.to_str().unwrap().to_owned()),
                 extra: None,
+                ..Default::default()
             }
         );
     }
@@ -807,6 +843,11 @@ mod tests {
                 changed: true,
                 output: Some(file_path.to_str().unwrap().to_owned()),
                 extra: None,
+                diff: Some(Diff::new("test\n", "fu").with_headers(
+                    file_path.to_str().unwrap().to_owned(),
+                    file_path.to_str().unwrap().to_owned()
+                )),
+                ..Default::default()
             }
         );
     }
@@ -848,6 +889,11 @@ mod tests {
                 changed: true,
                 output: Some(file_path.to_str().unwrap().to_owned()),
                 extra: None,
+                diff: Some(Diff::new("test\n", "fu").with_headers(
+                    file_path.to_str().unwrap().to_owned(),
+                    file_path.to_str().unwrap().to_owned()
+                )),
+                ..Default::default()
             }
         );
     }
@@ -886,6 +932,11 @@ mod tests {
                 changed: true,
                 output: Some(file_path.to_str().unwrap().to_owned()),
                 extra: None,
+                diff: Some(Diff::new("", "zoo").with_headers(
+                    file_path.to_str().unwrap().to_owned(),
+                    file_path.to_str().unwrap().to_owned()
+                )),
+                ..Default::default()
             }
         );
     }
@@ -926,6 +977,11 @@ mod tests {
                 changed: true,
                 output: Some(file_path.to_str().unwrap().to_owned()),
                 extra: None,
+                diff: Some(Diff::new("", "zoo\n").with_headers(
+                    file_path.to_str().unwrap().to_owned(),
+                    file_path.to_str().unwrap().to_owned()
+                )),
+                ..Default::default()
             }
         );
     }
@@ -954,6 +1010,11 @@ mod tests {
                 changed: true,
                 output: Some(file_path.to_str().unwrap().to_owned()),
                 extra: None,
+                diff: Some(Diff::new("", "zoo").with_headers(
+                    file_path.to_str().unwrap().to_owned(),
+                    file_path.to_str().unwrap().to_owned()
+                )),
+                ..Default::default()
             }
         );
     }
@@ -997,6 +1058,11 @@ mod tests {
                 changed: true,
                 output: Some(file_path.to_str().unwrap().to_owned()),
                 extra: None,
+                diff: Some(Diff::new("read_only\n", "zoo").with_headers(
+                    file_path.to_str().unwrap().to_owned(),
+                    file_path.to_str().unwrap().to_owned()
+                )),
+                ..Default::default()
             }
         );
     }
@@ -1045,6 +1111,11 @@ mod tests {
                 changed: true,
                 output: Some(file_path.to_str().unwrap().to_owned()),
                 extra: None,
+                diff: Some(Diff::new("read_only\n", "zoo").with_headers(
+                    file_path.to_str().unwrap().to_owned(),
+                    file_path.to_str().unwrap().to_owned()
+                )),
+                ..Default::default()
             }
         );
     }
@@ -1088,6 +1159,11 @@ mod tests {
                 changed: true,
                 output: Some(file_path.to_str().unwrap().to_owned()),
                 extra: None,
+                diff: Some(Diff::new("read_only\n", "zoo").with_headers(
+                    file_path.to_str().unwrap().to_owned(),
+                    file_path.to_str().unwrap().to_owned()
+                )),
+                ..Default::default()
             }
         );
     }
@@ -1133,6 +1209,7 @@ mod tests {
                 changed: false,
                 output: Some(file_path.to_str().unwrap().to_owned()),
                 extra: None,
+                ..Default::default()
             }
         );
     }
@@ -1176,6 +1253,11 @@ mod tests {
                 changed: true,
                 output: Some(file_path.to_str().unwrap().to_owned()),
                 extra: None,
+                diff: Some(Diff::new("read_only\n", "zoo").with_headers(
+                    file_path.to_str().unwrap().to_owned(),
+                    file_path.to_str().unwrap().to_owned()
+                )),
+                ..Default::default()
             }
         );
     }
@@ -1224,6 +1306,7 @@ mod tests {
                 changed: true,
                 output: Some(file_path.to_str().unwrap().to_owned()),
                 extra: None,
+                ..Default::default()
             }
         );
     }
@@ -2,13 +2,6 @@
 /// # cron
 ///
 /// Manage cron jobs and crontab entries.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -40,7 +33,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger::diff;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Capabilities, Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -369,6 +362,7 @@ pub fn cron(params: Params, check_mode: bool) -> Result<ModuleResult> {
         changed,
         output: Some(params.name),
         extra: None,
+        ..Default::default()
     })
 }
 
@@ -380,6 +374,13 @@ impl Module for Cron {
         "cron"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            diff: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _global_params: &GlobalParams,
@@ -396,6 +397,10 @@ impl Module for Cron {
     }
 }
 
+impl ModuleParams for Cron {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
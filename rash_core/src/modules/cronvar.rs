@@ -2,13 +2,6 @@
 /// # cronvar
 ///
 /// Manage variables in crontab files.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -35,7 +28,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger::diff;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Capabilities, Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -241,6 +234,7 @@ pub fn cronvar(params: Params, check_mode: bool) -> Result<ModuleResult> {
         changed,
         output: Some(params.name),
         extra: None,
+        ..Default::default()
     })
 }
 
@@ -252,6 +246,13 @@ impl Module for Cronvar {
         "cronvar"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            diff: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _global_params: &GlobalParams,
@@ -268,6 +269,10 @@ impl Module for Cronvar {
     }
 }
 
+impl ModuleParams for Cronvar {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
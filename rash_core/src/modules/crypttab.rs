@@ -2,13 +2,6 @@
 /// # crypttab
 ///
 /// Manage encrypted filesystem entries in /etc/crypttab.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -54,7 +47,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger::diff;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Capabilities, Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -279,6 +272,13 @@ impl Module for Crypttab {
         "crypttab"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            diff: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -295,6 +295,10 @@ impl Module for Crypttab {
     }
 }
 
+impl ModuleParams for Crypttab {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2,13 +2,6 @@
 /// # dconf
 ///
 /// Modify and read dconf database.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -39,7 +32,7 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -121,6 +114,7 @@ fn dconf_impl(params: Params, check_mode: bool) -> Result<ModuleResult> {
                     Some(format!("Key '{}' = {}", key, current_value))
                 },
                 extra,
+                ..Default::default()
             })
         }
         State::Present => {
@@ -137,6 +131,7 @@ fn dconf_impl(params: Params, check_mode: bool) -> Result<ModuleResult> {
                     changed: true,
                     output: Some(format!("Would set key '{}' to {}", key, value)),
                     extra: None,
+                    ..Default::default()
                 });
             }
 
@@ -166,6 +161,7 @@ fn dconf_impl(params: Params, check_mode: bool) -> Result<ModuleResult> {
                     changed: false,
                     output: Some(format!("Key '{}' already set to {}", key, value)),
                     extra: None,
+                    ..Default::default()
                 });
             }
 
@@ -194,6 +190,7 @@ fn dconf_impl(params: Params, check_mode: bool) -> Result<ModuleResult> {
                 changed: true,
                 output: Some(format!("Set key '{}' to {}", key, value)),
                 extra: None,
+                ..Default::default()
             })
         }
         State::Absent => {
@@ -203,6 +200,7 @@ fn dconf_impl(params: Params, check_mode: bool) -> Result<ModuleResult> {
                     changed: true,
                     output: Some(format!("Would reset key '{}'", key)),
                     extra: None,
+                    ..Default::default()
                 });
             }
 
@@ -232,6 +230,7 @@ fn dconf_impl(params: Params, check_mode: bool) -> Result<ModuleResult> {
                     changed: false,
                     output: Some(format!("Key '{}' is already not set", key)),
                     extra: None,
+                    ..Default::default()
                 });
             }
 
@@ -259,6 +258,7 @@ fn dconf_impl(params: Params, check_mode: bool) -> Result<ModuleResult> {
                 changed: true,
                 output: Some(format!("Reset key '{}'", key)),
                 extra: None,
+                ..Default::default()
             })
         }
     }
@@ -289,6 +289,10 @@ impl Module for Dconf {
     }
 }
 
+impl ModuleParams for Dconf {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2,13 +2,6 @@
 /// # debconf
 ///
 /// Configure a .deb package using debconf-set-selections.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -50,7 +43,7 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -235,6 +228,7 @@ fn debconf_impl(params: Params, check_mode: bool) -> Result<ModuleResult> {
                     package, question, value
                 )),
                 extra,
+                ..Default::default()
             });
         }
 
@@ -246,6 +240,7 @@ fn debconf_impl(params: Params, check_mode: bool) -> Result<ModuleResult> {
                     package, question, value
                 )),
                 extra: None,
+                ..Default::default()
             });
         }
 
@@ -348,6 +343,7 @@ fn debconf_impl(params: Params, check_mode: bool) -> Result<ModuleResult> {
                 package, question, value
             )),
             extra,
+            ..Default::default()
         })
     } else {
         let entries = get_all_selections(package)?;
@@ -359,6 +355,7 @@ fn debconf_impl(params: Params, check_mode: bool) -> Result<ModuleResult> {
                 extra: Some(value::to_value(json!({
                     "settings": []
                 }))?),
+                ..Default::default()
             });
         }
 
@@ -385,6 +382,7 @@ fn debconf_impl(params: Params, check_mode: bool) -> Result<ModuleResult> {
                 entries.len()
             )),
             extra,
+            ..Default::default()
         })
     }
 }
@@ -414,6 +412,10 @@ impl Module for Debconf {
     }
 }
 
+impl ModuleParams for Debconf {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
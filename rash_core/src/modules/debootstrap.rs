@@ -2,13 +2,6 @@
 /// # debootstrap
 ///
 /// Install a minimal Debian/Ubuntu base system into a directory using debootstrap.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -54,7 +47,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Module, ModuleParams, ModuleResult, parse_params};
 use crate::utils::default_false;
 
 #[cfg(feature = "docs")]
@@ -197,6 +190,10 @@ impl Module for Debootstrap {
     }
 }
 
+impl ModuleParams for Debootstrap {
+    type Params = Params;
+}
+
 struct DebootstrapClient {
     executable: String,
     check_mode: bool,
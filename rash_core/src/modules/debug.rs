@@ -3,13 +3,6 @@
 ///
 /// This module prints statements during execution and can be useful for debugging variables or
 /// expressions. Useful for debugging together with the `when` directive.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: none
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -29,7 +22,9 @@
 use crate::context::GlobalParams;
 use crate::error::Result;
 use crate::jinja::render_string;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{
+    Capabilities, CheckModeSupport, Module, ModuleParams, ModuleResult, parse_params,
+};
 use minijinja::Value;
 
 #[cfg(feature = "docs")]
@@ -69,6 +64,7 @@ fn debug(params: Params, vars: &Value) -> Result<ModuleResult> {
         changed: false,
         output: Some(output),
         extra: None,
+        ..Default::default()
     })
 }
 #[derive(Debug)]
@@ -79,6 +75,14 @@ impl Module for Debug {
         "debug"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            // it just prints messages, which are useful in check mode too
+            check_mode: CheckModeSupport::Always,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -95,6 +99,10 @@ impl Module for Debug {
     }
 }
 
+impl ModuleParams for Debug {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -150,6 +158,7 @@ mod tests {
                 changed: false,
                 output: Some("foo boo".to_owned()),
                 extra: None,
+                ..Default::default()
             }
         );
     }
@@ -171,6 +180,7 @@ mod tests {
                 changed: false,
                 output: Some("foo".to_owned()),
                 extra: None,
+                ..Default::default()
             }
         );
     }
@@ -7,13 +7,6 @@
 /// This module provides a unified, idempotent interface for package management
 /// across different Linux distributions. It automatically detects the appropriate
 /// package manager based on the system and performs the requested operation.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -53,7 +46,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Module, ModuleParams, ModuleResult, parse_params};
 use crate::utils::default_false;
 
 #[cfg(feature = "docs")]
@@ -553,6 +546,7 @@ fn distro_package(params: Params, check_mode: bool) -> Result<ModuleResult> {
                 "cache_updated": cache_updated,
                 "manager": format!("{:?}", manager).to_lowercase(),
             }))?),
+            ..Default::default()
         });
     }
 
@@ -604,6 +598,7 @@ fn distro_package(params: Params, check_mode: bool) -> Result<ModuleResult> {
             "cache_updated": cache_updated,
             "manager": format!("{:?}", manager).to_lowercase(),
         }))?),
+        ..Default::default()
     })
 }
 
@@ -638,6 +633,10 @@ impl Module for DistroPackage {
     }
 }
 
+impl ModuleParams for DistroPackage {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2,13 +2,6 @@
 /// # dmsetup
 ///
 /// Manage Linux device mapper devices.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -46,7 +39,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger::diff;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Capabilities, Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -106,6 +99,13 @@ impl Module for Dmsetup {
         "dmsetup"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            diff: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -129,6 +129,10 @@ impl Module for Dmsetup {
     }
 }
 
+impl ModuleParams for Dmsetup {
+    type Params = Params;
+}
+
 struct DmsetupClient {
     check_mode: bool,
 }
@@ -601,6 +605,7 @@ fn dmsetup_module(params: Params, check_mode: bool) -> Result<ModuleResult> {
         changed: result.changed,
         output: None,
         extra: Some(value::to_value(extra)?),
+        ..Default::default()
     })
 }
 
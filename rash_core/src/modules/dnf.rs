@@ -2,13 +2,6 @@
 /// # dnf
 ///
 /// Manage packages with the dnf package manager, which is used by Fedora and RHEL.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -45,7 +38,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Module, ModuleParams, ModuleResult, parse_params};
 use crate::utils::default_false;
 
 #[cfg(feature = "docs")]
@@ -171,6 +164,10 @@ impl Module for Dnf {
     }
 }
 
+impl ModuleParams for Dnf {
+    type Params = Params;
+}
+
 struct DnfClient {
     executable: PathBuf,
     extra_args: Option<String>,
@@ -404,6 +401,7 @@ fn dnf(params: Params, check_mode: bool) -> Result<ModuleResult> {
         extra: Some(value::to_value(
             json!({"installed_packages": p_to_install, "removed_packages": p_to_remove, "cache_updated": cache_updated}),
         )?),
+        ..Default::default()
     })
 }
 
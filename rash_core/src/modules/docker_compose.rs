@@ -2,13 +2,6 @@
 /// # docker_compose
 ///
 /// Manage Docker Compose projects for multi-container applications.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -80,7 +73,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger::diff;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Capabilities, Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -179,6 +172,13 @@ impl Module for DockerCompose {
         "docker_compose"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            diff: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -202,6 +202,10 @@ impl Module for DockerCompose {
     }
 }
 
+impl ModuleParams for DockerCompose {
+    type Params = Params;
+}
+
 impl DockerComposeClient {
     fn new(check_mode: bool) -> Self {
         DockerComposeClient { check_mode }
@@ -669,6 +673,7 @@ fn docker_compose(params: Params, check_mode: bool) -> Result<ModuleResult> {
         changed,
         output: final_output,
         extra: Some(value::to_value(extra)?),
+        ..Default::default()
     })
 }
 
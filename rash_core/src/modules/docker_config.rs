@@ -2,13 +2,6 @@
 /// # docker_config
 ///
 /// Manage Docker daemon configuration (daemon.json).
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -60,7 +53,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger::diff;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Capabilities, Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -636,6 +629,7 @@ pub fn docker_config(params: Params, check_mode: bool) -> Result<ModuleResult> {
         changed,
         output,
         extra: None,
+        ..Default::default()
     })
 }
 
@@ -647,6 +641,13 @@ impl Module for DockerConfig {
         "docker_config"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            diff: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -670,6 +671,10 @@ impl Module for DockerConfig {
     }
 }
 
+impl ModuleParams for DockerConfig {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
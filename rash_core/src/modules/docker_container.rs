@@ -2,13 +2,6 @@
 /// # docker_container
 ///
 /// Manage Docker containers.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -86,7 +79,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger::diff;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Capabilities, Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -240,6 +233,13 @@ impl Module for DockerContainer {
         "docker_container"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            diff: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -263,6 +263,10 @@ impl Module for DockerContainer {
     }
 }
 
+impl ModuleParams for DockerContainer {
+    type Params = Params;
+}
+
 struct DockerClient {
     check_mode: bool,
 }
@@ -764,6 +768,7 @@ fn docker_container(params: Params, check_mode: bool) -> Result<ModuleResult> {
         changed,
         output: final_output,
         extra: Some(value::to_value(extra)?),
+        ..Default::default()
     })
 }
 
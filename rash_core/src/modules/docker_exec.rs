@@ -2,13 +2,6 @@
 /// # docker_exec
 ///
 /// Execute commands inside running Docker containers.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: none
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -50,7 +43,9 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{
+    Capabilities, CheckModeSupport, Module, ModuleParams, ModuleResult, parse_params,
+};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -102,6 +97,14 @@ impl Module for DockerExec {
         "docker_exec"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            // the command is reported instead of executed in check mode
+            check_mode: CheckModeSupport::Full,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -125,6 +128,10 @@ impl Module for DockerExec {
     }
 }
 
+impl ModuleParams for DockerExec {
+    type Params = Params;
+}
+
 struct DockerClient;
 
 impl DockerClient {
@@ -190,6 +197,7 @@ fn docker_exec(params: Params, check_mode: bool) -> Result<ModuleResult> {
                 params.command, params.container
             )),
             extra: None,
+            ..Default::default()
         });
     }
 
@@ -269,6 +277,7 @@ fn docker_exec(params: Params, check_mode: bool) -> Result<ModuleResult> {
         changed: true,
         output: output_str,
         extra,
+        ..Default::default()
     })
 }
 
@@ -2,13 +2,6 @@
 /// # docker_image
 ///
 /// Manage Docker images.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -70,7 +63,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger::diff;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Capabilities, Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -199,6 +192,13 @@ impl Module for DockerImage {
         "docker_image"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            diff: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -222,6 +222,10 @@ impl Module for DockerImage {
     }
 }
 
+impl ModuleParams for DockerImage {
+    type Params = Params;
+}
+
 impl DockerClient {
     fn new(check_mode: bool) -> Self {
         DockerClient { check_mode }
@@ -666,6 +670,7 @@ fn docker_image(params: Params, check_mode: bool) -> Result<ModuleResult> {
         changed,
         output: final_output,
         extra: Some(value::to_value(extra)?),
+        ..Default::default()
     })
 }
 
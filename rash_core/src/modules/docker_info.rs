@@ -5,13 +5,6 @@
 ///
 /// Returns Docker version, system info, and availability status.
 /// This module never changes system state - it only collects information.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: always
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -41,7 +34,9 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{
+    Capabilities, CheckModeSupport, Module, ModuleParams, ModuleResult, parse_params,
+};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -107,6 +102,13 @@ impl Module for DockerInfo {
         "docker_info"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            check_mode: CheckModeSupport::Always,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -127,6 +129,10 @@ impl Module for DockerInfo {
     }
 }
 
+impl ModuleParams for DockerInfo {
+    type Params = Params;
+}
+
 fn check_docker_available() -> bool {
     Command::new("docker")
         .args(["info"])
@@ -216,6 +222,7 @@ fn docker_info(params: Params) -> Result<ModuleResult> {
             changed: false,
             output: Some("Docker is not available".to_string()),
             extra: Some(extra),
+            ..Default::default()
         });
     }
 
@@ -258,6 +265,7 @@ fn docker_info(params: Params) -> Result<ModuleResult> {
         changed: false,
         output: Some("Docker information collected".to_string()),
         extra: Some(extra),
+        ..Default::default()
     })
 }
 
@@ -2,13 +2,6 @@
 /// # docker_login
 ///
 /// Manage Docker registry authentication.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -52,7 +45,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger::diff;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Capabilities, Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -113,6 +106,13 @@ impl Module for DockerLogin {
         "docker_login"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            diff: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -136,6 +136,10 @@ impl Module for DockerLogin {
     }
 }
 
+impl ModuleParams for DockerLogin {
+    type Params = Params;
+}
+
 impl DockerClient {
     fn new(check_mode: bool) -> Self {
         DockerClient { check_mode }
@@ -2,13 +2,6 @@
 /// # docker_network
 ///
 /// Manage Docker networks for container orchestration.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -65,7 +58,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger::diff;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Capabilities, Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -190,6 +183,13 @@ impl Module for DockerNetwork {
         "docker_network"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            diff: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -213,6 +213,10 @@ impl Module for DockerNetwork {
     }
 }
 
+impl ModuleParams for DockerNetwork {
+    type Params = Params;
+}
+
 struct DockerClient {
     check_mode: bool,
 }
@@ -534,6 +538,7 @@ fn docker_network(params: Params, check_mode: bool) -> Result<ModuleResult> {
         changed,
         output: final_output,
         extra: Some(value::to_value(extra)?),
+        ..Default::default()
     })
 }
 
@@ -2,13 +2,6 @@
 /// # docker_prune
 ///
 /// Prune unused Docker resources (containers, images, volumes, networks, build cache).
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -37,7 +30,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger::diff;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Capabilities, Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -93,6 +86,13 @@ impl Module for DockerPrune {
         "docker_prune"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            diff: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -116,6 +116,10 @@ impl Module for DockerPrune {
     }
 }
 
+impl ModuleParams for DockerPrune {
+    type Params = Params;
+}
+
 struct DockerClient {
     check_mode: bool,
 }
@@ -486,6 +490,7 @@ fn docker_prune(params: Params, check_mode: bool) -> Result<ModuleResult> {
         changed,
         output: final_output,
         extra: Some(value::to_value(extra)?),
+        ..Default::default()
     })
 }
 
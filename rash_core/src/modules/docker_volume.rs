@@ -2,13 +2,6 @@
 /// # docker_volume
 ///
 /// Manage Docker volumes for persistent container storage.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -58,7 +51,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger::diff;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Capabilities, Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -122,6 +115,13 @@ impl Module for DockerVolume {
         "docker_volume"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            diff: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -145,6 +145,10 @@ impl Module for DockerVolume {
     }
 }
 
+impl ModuleParams for DockerVolume {
+    type Params = Params;
+}
+
 struct DockerClient {
     check_mode: bool,
 }
@@ -369,6 +373,7 @@ fn docker_volume(params: Params, check_mode: bool) -> Result<ModuleResult> {
         changed,
         output: final_output,
         extra: Some(value::to_value(extra)?),
+        ..Default::default()
     })
 }
 
@@ -9,13 +9,6 @@
 /// set packages to be held, unheld, installed, deinstalled, or purged.
 /// This is useful for preventing automatic package updates, locking package
 /// versions, or managing package states during system configuration.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -53,7 +46,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -289,6 +282,7 @@ fn dpkg_selections_impl(params: Params, check_mode: bool) -> Result<ModuleResult
         changed,
         output: None,
         extra,
+        ..Default::default()
     })
 }
 
@@ -321,6 +315,10 @@ impl Module for DpkgSelections {
     }
 }
 
+impl ModuleParams for DpkgSelections {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
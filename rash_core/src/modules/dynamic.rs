@@ -1,6 +1,6 @@
 use crate::context::{Context, GlobalParams};
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult};
+use crate::modules::{Module, ModuleResult, ValidateParams};
 use crate::task::parse_file;
 use crate::vars::builtin::Builtins;
 
@@ -8,6 +8,7 @@ use std::collections::HashMap;
 use std::fs::read_to_string;
 use std::path::{Path, PathBuf};
 
+use itertools::Itertools;
 use minijinja::{Value, context};
 #[cfg(feature = "docs")]
 use schemars::Schema;
@@ -26,6 +27,43 @@ pub enum ParamType {
     Boolean,
 }
 
+impl ParamType {
+    fn matches(&self, value: &YamlValue) -> bool {
+        matches!(
+            (self, value),
+            (ParamType::String, YamlValue::String(_))
+                | (ParamType::Number, YamlValue::Number(_))
+                | (ParamType::Object, YamlValue::Mapping(_))
+                | (ParamType::Array, YamlValue::Sequence(_))
+                | (ParamType::Boolean, YamlValue::Bool(_))
+        )
+    }
+
+    /// Return an error if `value` of `name` doesn't match the type.
+    fn check(&self, value: &YamlValue, name: &str, module_name: &str) -> Result<()> {
+        match self.matches(value) {
+            true => Ok(()),
+            false => Err(Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "Parameter '{name}' of module '{module_name}' must be {}, got: {value:?}",
+                    self.get_article_name()
+                ),
+            )),
+        }
+    }
+
+    fn get_article_name(&self) -> &'static str {
+        match self {
+            ParamType::String => "a string",
+            ParamType::Number => "a number",
+            ParamType::Object => "an object",
+            ParamType::Array => "an array",
+            ParamType::Boolean => "a boolean",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Deserialize)]
 #[allow(dead_code)]
 pub struct ParamDef {
@@ -37,6 +75,15 @@ pub struct ParamDef {
     pub default: Option<YamlValue>,
 }
 
+/// Value set by the module tasks which is returned in the `extra` field of the module result.
+#[derive(Debug, Clone, Deserialize)]
+#[allow(dead_code)]
+pub struct ReturnDef {
+    #[serde(rename = "type")]
+    pub return_type: Option<ParamType>,
+    pub description: Option<String>,
+}
+
 #[derive(Debug, Clone, Deserialize)]
 #[allow(dead_code)]
 pub struct ModuleMeta {
@@ -44,6 +91,8 @@ pub struct ModuleMeta {
     pub description: Option<String>,
     #[serde(default)]
     pub params: HashMap<String, ParamDef>,
+    #[serde(default)]
+    pub returns: HashMap<String, ReturnDef>,
 }
 
 #[derive(Debug, Clone)]
@@ -51,6 +100,7 @@ pub struct DynamicModule {
     name: String,
     meta: ModuleMeta,
     main_path: PathBuf,
+    dir: PathBuf,
 }
 
 impl DynamicModule {
@@ -101,6 +151,7 @@ impl DynamicModule {
             name,
             meta,
             main_path,
+            dir: module_dir.to_path_buf(),
         })
     }
 
@@ -108,7 +159,7 @@ impl DynamicModule {
         &self.name
     }
 
-    fn validate_params(&self, params: &YamlValue) -> Result<HashMap<String, YamlValue>> {
+    fn get_validated_params(&self, params: &YamlValue) -> Result<HashMap<String, YamlValue>> {
         let mut validated = HashMap::new();
 
         let params_map = match params {
@@ -127,6 +178,7 @@ impl DynamicModule {
 
             match value {
                 Some(v) => {
+                    param_def.param_type.check(v, param_name, &self.name)?;
                     validated.insert(param_name.clone(), v.clone());
                 }
                 None => {
@@ -145,20 +197,53 @@ impl DynamicModule {
             }
         }
 
-        for key in params_map.keys() {
-            if let YamlValue::String(key_str) = key
-                && !self.meta.params.contains_key(key_str)
-            {
-                trace!(
-                    "Unknown parameter '{}' passed to module '{}', ignoring",
-                    key_str, self.name
-                );
-            }
+        if let Some(key) = params_map.keys().find(|key| {
+            key.as_str()
+                .is_none_or(|key_str| !self.meta.params.contains_key(key_str))
+        }) {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "Unknown parameter '{}' for module '{}', expected one of: {:?}",
+                    key.as_str()
+                        .map(str::to_owned)
+                        .unwrap_or_else(|| format!("{key:?}")),
+                    self.name,
+                    self.meta.params.keys().sorted().collect::<Vec<_>>()
+                ),
+            ));
         }
 
         Ok(validated)
     }
 
+    /// Return values declared in `returns` which were set by the module tasks.
+    fn get_returns(&self, module_vars: Option<&Value>) -> Result<serde_norway::Mapping> {
+        let Some(module_vars) = module_vars else {
+            return Ok(serde_norway::Mapping::new());
+        };
+
+        self.meta
+            .returns
+            .iter()
+            .sorted_by_key(|(name, _)| *name)
+            .filter_map(|(name, return_def)| {
+                module_vars
+                    .get_attr(name)
+                    .ok()
+                    .filter(|value| !value.is_undefined())
+                    .map(|value| (name, return_def, value))
+            })
+            .map(|(name, return_def, value)| {
+                let yaml_value = serde_norway::to_value(&value)?;
+                if let Some(return_type) = &return_def.return_type {
+                    return_type.check(&yaml_value, name, &self.name)?;
+                }
+                Ok((YamlValue::String(name.clone()), yaml_value))
+            })
+            .collect()
+    }
+
     fn convert_to_value(params: HashMap<String, YamlValue>) -> Value {
         Value::from_serialize(
             params
@@ -169,6 +254,24 @@ impl DynamicModule {
     }
 }
 
+/// Resolve `path` of a file used by the tasks of a dynamic module.
+///
+/// Relative paths are searched in `subdir` (`templates` or `files`) of the module directory,
+/// and they are kept as they are if not found there.
+pub(crate) fn resolve_module_file(path: &str, subdir: &str, vars: &Value) -> String {
+    if Path::new(path).is_absolute() {
+        return path.to_owned();
+    }
+
+    vars.get_attr("module")
+        .and_then(|module| module.get_attr("dir"))
+        .ok()
+        .and_then(|dir| dir.as_str().map(|d| Path::new(d).join(subdir).join(path)))
+        .filter(|module_path| module_path.exists())
+        .map(|module_path| module_path.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.to_owned())
+}
+
 fn yaml_to_json(value: YamlValue) -> serde_json::Value {
     match value {
         YamlValue::Null => serde_json::Value::Null,
@@ -215,7 +318,7 @@ impl Module for DynamicModule {
         vars: &Value,
         check_mode: bool,
     ) -> Result<(ModuleResult, Option<Value>)> {
-        let validated_params = self.validate_params(&params)?;
+        let validated_params = self.get_validated_params(&params)?;
         let params_value = Self::convert_to_value(validated_params);
 
         let module_vars = context! {
@@ -223,6 +326,7 @@ impl Module for DynamicModule {
                 name => self.name.clone(),
                 params => params_value,
                 check_mode => check_mode,
+                dir => self.dir.to_string_lossy(),
             },
         };
 
@@ -237,7 +341,7 @@ impl Module for DynamicModule {
             )
         })?;
 
-        let tasks = parse_file(&main_content, global_params)?;
+        let tasks = parse_file(&main_content, &self.main_path, global_params)?;
 
         let builtins = Builtins::deserialize(vars.get_attr("rash")?)?;
         let module_builtins = builtins.update(&self.main_path)?;
@@ -263,11 +367,21 @@ impl Module for DynamicModule {
             .ok()
             .and_then(|v| v.as_str().map(String::from));
 
-        let extra = result_vars.get_attr("__module_extra").ok().map(|v| {
+        let module_extra = result_vars.get_attr("__module_extra").ok().map(|v| {
             let json_str = serde_json::to_string(&v).unwrap_or_default();
             serde_norway::from_str(&json_str).unwrap_or(YamlValue::Null)
         });
 
+        let returns = self.get_returns(result_context.get_scoped_vars())?;
+        let extra = match (module_extra, returns.is_empty()) {
+            (module_extra, true) => module_extra,
+            (Some(YamlValue::Mapping(mut extra_map)), false) => {
+                extra_map.extend(returns);
+                Some(YamlValue::Mapping(extra_map))
+            }
+            (_, false) => Some(YamlValue::Mapping(returns)),
+        };
+
         let new_vars = if changed || output.is_some() || extra.is_some() {
             let mut result_map = serde_json::Map::new();
             if let Some(o) = &output {
@@ -296,8 +410,19 @@ impl Module for DynamicModule {
     }
 }
 
+impl ValidateParams for DynamicModule {
+    fn validate_params(&self, params: YamlValue) -> Result<()> {
+        self.get_validated_params(&params)?;
+        Ok(())
+    }
+}
+
+/// Dynamic modules found in the search paths.
+///
+/// Modules are loaded once and live until the end of the execution, so tasks can reference
+/// them like builtin modules.
 pub struct DynamicModuleRegistry {
-    modules: HashMap<String, DynamicModule>,
+    modules: HashMap<String, &'static DynamicModule>,
     search_paths: Vec<PathBuf>,
 }
 
@@ -322,22 +447,22 @@ impl DynamicModuleRegistry {
         }
     }
 
-    pub fn get(&self, name: &str) -> Option<&DynamicModule> {
-        self.modules.get(name)
+    pub fn get(&self, name: &str) -> Option<&'static DynamicModule> {
+        self.modules.get(name).copied()
     }
 
-    pub fn load_module(&mut self, name: &str) -> Result<&DynamicModule> {
-        if self.modules.contains_key(name) {
-            return Ok(self.modules.get(name).unwrap());
+    pub fn load_module(&mut self, name: &str) -> Result<&'static DynamicModule> {
+        if let Some(module) = self.get(name) {
+            return Ok(module);
         }
 
         for search_path in &self.search_paths {
             let module_dir = search_path.join(name);
             if module_dir.exists() && module_dir.is_dir() {
-                let module = DynamicModule::load(&module_dir)?;
-                let module_name = module.name.clone();
-                self.modules.insert(module_name, module);
-                return Ok(self.modules.get(name).unwrap());
+                let module: &'static DynamicModule =
+                    Box::leak(Box::new(DynamicModule::load(&module_dir)?));
+                self.modules.insert(name.to_owned(), module);
+                return Ok(module);
             }
         }
 
@@ -347,7 +472,7 @@ impl DynamicModuleRegistry {
         ))
     }
 
-    pub fn is_dynamic_module(&mut self, name: &str) -> bool {
+    pub fn is_dynamic_module(&self, name: &str) -> bool {
         if self.modules.contains_key(name) {
             return true;
         }
@@ -387,10 +512,12 @@ impl DynamicModuleRegistry {
 
                         if meta_path.exists()
                             && main_path.exists()
+                            && let Some(name) = path.file_name().and_then(|n| n.to_str())
+                            && !self.modules.contains_key(name)
                             && let Ok(module) = DynamicModule::load(&path)
                         {
-                            let name = module.name.clone();
-                            self.modules.entry(name).or_insert(module);
+                            self.modules
+                                .insert(name.to_owned(), Box::leak(Box::new(module)));
                         }
                     }
                 }
@@ -471,7 +598,7 @@ params:
         let module = DynamicModule::load(&module_dir).unwrap();
 
         let params = YamlValue::Mapping(serde_norway::Mapping::new());
-        let result = module.validate_params(&params);
+        let result = module.get_validated_params(&params);
         assert!(result.is_err());
 
         let params = YamlValue::Mapping({
@@ -482,7 +609,7 @@ params:
             );
             m
         });
-        let result = module.validate_params(&params).unwrap();
+        let result = module.get_validated_params(&params).unwrap();
         assert!(result.contains_key("message"));
         assert!(result.contains_key("count"));
         assert_eq!(result["count"], YamlValue::Number(1.into()));
@@ -502,4 +629,98 @@ params:
         let module = registry.load_module("test_module").unwrap();
         assert_eq!(module.name, "test_module");
     }
+
+    fn params(yaml: &str) -> YamlValue {
+        serde_norway::from_str(yaml).unwrap()
+    }
+
+    #[test]
+    fn test_validate_params_type() {
+        let temp_dir = TempDir::new().unwrap();
+        let module = DynamicModule::load(&create_test_module(&temp_dir, "test_module")).unwrap();
+
+        let error = module
+            .get_validated_params(&params("{message: hello, count: two}"))
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            r#"Parameter 'count' of module 'test_module' must be a number, got: String("two")"#
+        );
+
+        let error = module
+            .get_validated_params(&params("{message: 1}"))
+            .unwrap_err();
+        assert!(error.to_string().contains("must be a string"));
+    }
+
+    #[test]
+    fn test_validate_params_unknown() {
+        let temp_dir = TempDir::new().unwrap();
+        let module = DynamicModule::load(&create_test_module(&temp_dir, "test_module")).unwrap();
+
+        let error = module
+            .validate_params(params("{message: hello, foo: bar}"))
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            r#"Unknown parameter 'foo' for module 'test_module', expected one of: ["count", "message"]"#
+        );
+    }
+
+    #[test]
+    fn test_get_returns() {
+        let temp_dir = TempDir::new().unwrap();
+        let module_dir = create_test_module(&temp_dir, "test_module");
+        std::fs::write(
+            module_dir.join("meta.yml"),
+            r#"
+name: test_module
+returns:
+  checksum:
+    type: string
+  size:
+    type: number
+  missing: {}
+"#,
+        )
+        .unwrap();
+        let module = DynamicModule::load(&module_dir).unwrap();
+
+        let returns = module
+            .get_returns(Some(
+                &context! {checksum => "abc", size => 3, other => true},
+            ))
+            .unwrap();
+        assert_eq!(
+            YamlValue::Mapping(returns),
+            params("{checksum: abc, size: 3}")
+        );
+
+        let error = module
+            .get_returns(Some(&context! {size => "big"}))
+            .unwrap_err();
+        assert!(error.to_string().contains("must be a number"));
+
+        assert!(module.get_returns(None).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_resolve_module_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let module_dir = create_test_module(&temp_dir, "test_module");
+        std::fs::create_dir_all(module_dir.join("templates")).unwrap();
+        std::fs::write(module_dir.join("templates/foo.j2"), "foo").unwrap();
+        let vars = context! {module => context! {dir => module_dir.to_str().unwrap()}};
+
+        assert_eq!(
+            resolve_module_file("foo.j2", "templates", &vars),
+            module_dir.join("templates/foo.j2").to_str().unwrap()
+        );
+        assert_eq!(resolve_module_file("bar.j2", "templates", &vars), "bar.j2");
+        assert_eq!(resolve_module_file("foo.j2", "files", &vars), "foo.j2");
+        assert_eq!(
+            resolve_module_file("foo.j2", "templates", &context! {}),
+            "foo.j2"
+        );
+    }
 }
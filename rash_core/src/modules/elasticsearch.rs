@@ -2,13 +2,6 @@
 /// # elasticsearch
 ///
 /// Manage Elasticsearch indices and documents.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -84,7 +77,7 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -493,6 +486,10 @@ impl Module for Elasticsearch {
     }
 }
 
+impl ModuleParams for Elasticsearch {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
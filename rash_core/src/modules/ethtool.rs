@@ -2,13 +2,6 @@
 /// # ethtool
 ///
 /// Manage Ethernet device settings using ethtool.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -55,7 +48,7 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -419,6 +412,10 @@ impl Module for Ethtool {
     }
 }
 
+impl ModuleParams for Ethtool {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -5,13 +5,6 @@
 ///
 /// This module automates interactive CLI programs by spawning a pseudo-terminal
 /// and responding to prompts based on pattern matching.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: none
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -36,7 +29,9 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{
+    Capabilities, CheckModeSupport, Module, ModuleParams, ModuleResult, parse_params,
+};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -309,6 +304,13 @@ impl Module for Expect {
         "expect"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            check_mode: CheckModeSupport::None,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -326,6 +328,10 @@ impl Module for Expect {
     }
 }
 
+impl ModuleParams for Expect {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
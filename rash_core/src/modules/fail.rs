@@ -5,13 +5,6 @@
 ///
 /// This module is useful for explicitly failing execution in conditional
 /// logic to provide meaningful error messages.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: none
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -34,7 +27,9 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{
+    Capabilities, CheckModeSupport, Module, ModuleParams, ModuleResult, parse_params,
+};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -66,6 +61,14 @@ impl Module for Fail {
         "fail"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            // the run would fail at the same point, so it must fail in check mode too
+            check_mode: CheckModeSupport::Always,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -83,6 +86,10 @@ impl Module for Fail {
     }
 }
 
+impl ModuleParams for Fail {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2,13 +2,6 @@
 /// # fail2ban
 ///
 /// Manage Fail2ban intrusion prevention system.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -49,7 +42,7 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -140,6 +133,10 @@ impl Module for Fail2ban {
     }
 }
 
+impl ModuleParams for Fail2ban {
+    type Params = Params;
+}
+
 fn get_jail_config_path(name: &str) -> String {
     format!("{}/{}{}", JAIL_CONF_DIR, name, JAIL_CONF_SUFFIX)
 }
@@ -3,13 +3,6 @@
 ///
 /// This module copies a file from a source path to a local destination path.
 /// Useful for retrieving files such as configurations, logs, and backups.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -37,7 +30,7 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -230,6 +223,10 @@ impl Module for Fetch {
     }
 }
 
+impl ModuleParams for Fetch {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
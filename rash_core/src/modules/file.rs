@@ -2,13 +2,6 @@
 /// # file
 ///
 /// Manage files and file properties.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -26,7 +19,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger::diff;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Capabilities, Module, ModuleParams, ModuleResult, parse_params};
 use crate::utils::parse_octal;
 
 #[cfg(feature = "docs")]
@@ -81,6 +74,7 @@ fn fail_if_not_exist(params: Params) -> Result<ModuleResult> {
             changed: false,
             output: Some(params.path),
             extra: None,
+            ..Default::default()
         }),
         Err(_) => Err(Error::new(
             ErrorKind::NotFound,
@@ -112,12 +106,14 @@ fn apply_permissions_if_necessary(
                 changed: true,
                 output: Some(params.path),
                 extra: None,
+                ..Default::default()
             })
         }
         false => Ok(ModuleResult {
             changed: false,
             output: Some(params.path),
             extra: None,
+            ..Default::default()
         }),
     }
 }
@@ -200,12 +196,14 @@ fn define_file(params: Params, check_mode: bool) -> Result<ModuleResult> {
                     changed: true,
                     output: Some(params.path),
                     extra: None,
+                    ..Default::default()
                 })
             }
             Err(_not_exists) => Ok(ModuleResult {
                 changed: false,
                 output: Some(params.path),
                 extra: None,
+                ..Default::default()
             }),
         },
         Some(State::Directory) => match &params.mode {
@@ -233,6 +231,7 @@ fn define_file(params: Params, check_mode: bool) -> Result<ModuleResult> {
                             changed: true,
                             output: Some(params.path),
                             extra: None,
+                            ..Default::default()
                         })
                     }
                 }
@@ -242,6 +241,7 @@ fn define_file(params: Params, check_mode: bool) -> Result<ModuleResult> {
                     changed: false,
                     output: Some(params.path),
                     extra: None,
+                    ..Default::default()
                 }),
                 Err(_not_exists) => {
                     diff("state: absent\n", "state: directory\n");
@@ -254,6 +254,7 @@ fn define_file(params: Params, check_mode: bool) -> Result<ModuleResult> {
                         changed: true,
                         output: Some(params.path),
                         extra: None,
+                        ..Default::default()
                     })
                 }
             },
@@ -279,6 +280,7 @@ fn define_file(params: Params, check_mode: bool) -> Result<ModuleResult> {
                             changed: true,
                             output: Some(params.path),
                             extra: None,
+                            ..Default::default()
                         })
                     }
                 }
@@ -288,6 +290,7 @@ fn define_file(params: Params, check_mode: bool) -> Result<ModuleResult> {
                     changed: false,
                     output: Some(params.path),
                     extra: None,
+                    ..Default::default()
                 }),
                 Err(_not_exists) => {
                     diff("state: absent\n", "state: file\n");
@@ -299,6 +302,7 @@ fn define_file(params: Params, check_mode: bool) -> Result<ModuleResult> {
                         changed: true,
                         output: Some(params.path),
                         extra: None,
+                        ..Default::default()
                     })
                 }
             },
@@ -314,6 +318,13 @@ impl Module for File {
         "file"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            diff: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -333,6 +344,10 @@ impl Module for File {
     }
 }
 
+impl ModuleParams for File {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -462,6 +477,7 @@ mod tests {
                 changed: false,
                 output: Some(file_path.to_str().unwrap().to_owned()),
                 extra: None,
+                ..Default::default()
             }
         );
     }
@@ -513,6 +529,7 @@ mod tests {
                 changed: true,
                 output: Some(dir_path.to_str().unwrap().to_owned()),
                 extra: None,
+                ..Default::default()
             }
         );
     }
@@ -540,6 +557,7 @@ mod tests {
                 changed: true,
                 output: Some(dir_path.to_str().unwrap().to_owned()),
                 extra: None,
+                ..Default::default()
             }
         );
     }
@@ -583,6 +601,7 @@ mod tests {
                 changed: true,
                 output: Some(dir_path.to_str().unwrap().to_owned()),
                 extra: None,
+                ..Default::default()
             }
         );
     }
@@ -616,6 +635,7 @@ mod tests {
                 changed: true,
                 output: Some(dir_path.to_str().unwrap().to_owned()),
                 extra: None,
+                ..Default::default()
             }
         );
     }
@@ -654,6 +674,7 @@ mod tests {
                 changed: true,
                 output: Some(file_path.to_str().unwrap().to_owned()),
                 extra: None,
+                ..Default::default()
             }
         );
     }
@@ -692,6 +713,7 @@ mod tests {
                 changed: true,
                 output: Some(file_path.to_str().unwrap().to_owned()),
                 extra: None,
+                ..Default::default()
             }
         );
     }
@@ -725,6 +747,7 @@ mod tests {
                 changed: true,
                 output: Some(file_path.to_str().unwrap().to_owned()),
                 extra: None,
+                ..Default::default()
             }
         );
     }
@@ -758,6 +781,7 @@ mod tests {
                 changed: true,
                 output: Some(file_path.to_str().unwrap().to_owned()),
                 extra: None,
+                ..Default::default()
             }
         );
     }
@@ -792,6 +816,7 @@ mod tests {
                 changed: true,
                 output: Some(dir_path.to_str().unwrap().to_owned()),
                 extra: None,
+                ..Default::default()
             }
         );
     }
@@ -826,6 +851,7 @@ mod tests {
                 changed: true,
                 output: Some(dir_path.to_str().unwrap().to_owned()),
                 extra: None,
+                ..Default::default()
             }
         );
     }
@@ -862,6 +888,7 @@ mod tests {
                 changed: true,
                 output: Some(dir_path.to_str().unwrap().to_owned()),
                 extra: None,
+                ..Default::default()
             }
         );
     }
@@ -898,6 +925,7 @@ mod tests {
                 changed: true,
                 output: Some(dir_path.to_str().unwrap().to_owned()),
                 extra: None,
+                ..Default::default()
             }
         );
     }
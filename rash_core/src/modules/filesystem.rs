@@ -2,13 +2,6 @@
 /// # filesystem
 ///
 /// Create filesystems on block devices.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -35,7 +28,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger::diff;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Capabilities, Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -113,6 +106,13 @@ impl Module for Filesystem {
         "filesystem"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            diff: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -132,6 +132,10 @@ impl Module for Filesystem {
     }
 }
 
+impl ModuleParams for Filesystem {
+    type Params = Params;
+}
+
 struct FilesystemClient;
 
 impl FilesystemClient {
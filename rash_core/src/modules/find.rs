@@ -2,13 +2,6 @@
 /// # find
 ///
 /// Return a list of files based on specific criteria.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: always
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -31,7 +24,9 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult, parse_if_json, parse_params};
+use crate::modules::{
+    Capabilities, CheckModeSupport, Module, ModuleParams, ModuleResult, parse_if_json, parse_params,
+};
 use crate::utils::default_false;
 
 #[cfg(feature = "docs")]
@@ -238,6 +233,7 @@ pub fn find(params: Params) -> Result<ModuleResult> {
         changed: false,
         output: None,
         extra: Some(value::to_value(json!(result))?),
+        ..Default::default()
     })
 }
 
@@ -249,6 +245,13 @@ impl Module for Find {
         "find"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            check_mode: CheckModeSupport::Always,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -265,6 +268,10 @@ impl Module for Find {
     }
 }
 
+impl ModuleParams for Find {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -370,6 +377,7 @@ mod tests {
                 extra: Some(
                     value::to_value(json!(vec![file_path.to_str().unwrap().to_owned()])).unwrap()
                 ),
+                ..Default::default()
             }
         );
     }
@@ -460,6 +468,7 @@ mod tests {
                     ]))
                     .unwrap()
                 ),
+                ..Default::default()
             }
         );
     }
@@ -489,6 +498,7 @@ mod tests {
                 extra: Some(
                     value::to_value(json!(vec![file_path.to_str().unwrap().to_owned()])).unwrap()
                 ),
+                ..Default::default()
             }
         );
     }
@@ -516,6 +526,7 @@ mod tests {
                 changed: false,
                 output: None,
                 extra: Some(value::to_value(json!(result)).unwrap()),
+                ..Default::default()
             }
         );
     }
@@ -581,6 +592,7 @@ mod tests {
                 extra: Some(
                     value::to_value(json!(vec![file_path.to_str().unwrap().to_owned(),])).unwrap()
                 ),
+                ..Default::default()
             }
         );
     }
@@ -612,6 +624,7 @@ mod tests {
                     value::to_value(json!(vec![ignore_path.to_str().unwrap().to_owned(),]))
                         .unwrap()
                 ),
+                ..Default::default()
             }
         );
     }
@@ -641,6 +654,7 @@ mod tests {
                 extra: Some(
                     value::to_value(json!(vec![dir_path.to_str().unwrap().to_owned(),])).unwrap()
                 ),
+                ..Default::default()
             }
         );
     }
@@ -669,6 +683,7 @@ mod tests {
                 changed: false,
                 output: None,
                 extra: Some(value::to_value(json!(result)).unwrap()),
+                ..Default::default()
             }
         );
     }
@@ -697,6 +712,7 @@ mod tests {
                 extra: Some(
                     value::to_value(json!(vec![file2_path.to_str().unwrap().to_owned(),])).unwrap()
                 ),
+                ..Default::default()
             }
         );
     }
@@ -764,6 +780,7 @@ mod tests {
                     value::to_value(json!(vec![parent_path.to_str().unwrap().to_owned(),]))
                         .unwrap()
                 ),
+                ..Default::default()
             }
         );
     }
@@ -791,6 +808,7 @@ mod tests {
                 extra: Some(
                     value::to_value(json!(vec![file1_path.to_str().unwrap().to_owned(),])).unwrap()
                 ),
+                ..Default::default()
             }
         );
     }
@@ -2,13 +2,6 @@
 /// # firewalld
 ///
 /// Manage firewall rules using firewalld.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -47,7 +40,7 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -113,6 +106,10 @@ impl Module for Firewalld {
     }
 }
 
+impl ModuleParams for Firewalld {
+    type Params = Params;
+}
+
 struct FirewalldClient {
     check_mode: bool,
 }
@@ -5,13 +5,6 @@
 ///
 /// Flatpak is a universal package format for Linux desktop applications.
 /// This module enables management of Flatpak packages in desktop and container environments.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -56,7 +49,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Module, ModuleParams, ModuleResult, parse_params};
 use crate::utils::default_false;
 
 #[cfg(feature = "docs")]
@@ -182,6 +175,10 @@ impl Module for Flatpak {
     }
 }
 
+impl ModuleParams for Flatpak {
+    type Params = Params;
+}
+
 struct FlatpakClient {
     executable: String,
     method: Method,
@@ -337,6 +334,7 @@ fn flatpak(params: Params, check_mode: bool) -> Result<ModuleResult> {
         extra: Some(value::to_value(
             json!({"installed_packages": p_to_install, "removed_packages": p_to_remove}),
         )?),
+        ..Default::default()
     })
 }
 
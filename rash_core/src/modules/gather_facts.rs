@@ -0,0 +1,957 @@
+/// ANCHOR: module
+/// # gather_facts
+///
+/// Gather facts about the host and expose them in the `facts` variable.
+///
+/// Facts are collected reading `/proc`, `/sys` and `/etc`, without executing any command.
+/// The `package` and `service` modules use `facts.pkg_mgr` and `facts.service_mgr` to
+/// choose their backend when they are available.
+///
+/// | Subset      | Facts                                                                                                                             |
+/// | ----------- | --------------------------------------------------------------------------------------------------------------------------------- |
+/// | os          | system, os_family, distribution, distribution_version, distribution_major_version, distribution_release, kernel, kernel_version, architecture, pkg_mgr |
+/// | hardware    | processor_count, processor_model, memtotal_mb, memfree_mb, memavailable_mb, swaptotal_mb, swapfree_mb                             |
+/// | mounts      | mounts                                                                                                                            |
+/// | network     | hostname, fqdn, domain, interfaces, default_ipv4, default_ipv6, all_ipv4_addresses, all_ipv6_addresses                            |
+/// | service_mgr | service_mgr                                                                                                                       |
+/// | virtual     | virtualization_type, virtualization_role                                                                                          |
+/// | user        | user_id, user_uid, user_gid, user_dir, user_shell                                                                                 |
+///
+/// `min` is a shortcut for `os`, `service_mgr` and `user`.
+/// ANCHOR_END: module
+/// ANCHOR: examples
+/// ## Example
+///
+/// ```yaml
+/// - gather_facts:
+///
+/// - debug:
+///     msg: "{{ facts.distribution }} {{ facts.distribution_version }} on {{ facts.architecture }}"
+///
+/// - name: Collect just network facts
+///   gather_facts:
+///     gather_subset:
+///       - network
+///
+/// - name: Collect everything but mounts
+///   gather_facts:
+///     gather_subset:
+///       - all
+///       - "!mounts"
+///
+/// - name: Install packages with the detected package manager
+///   package:
+///     name: curl
+///   when: facts.os_family == "Debian"
+/// ```
+/// ANCHOR_END: examples
+use crate::context::GlobalParams;
+use crate::error::{Error, ErrorKind, Result};
+use crate::modules::{
+    Capabilities, CheckModeSupport, Module, ModuleParams, ModuleResult, parse_params,
+};
+
+#[cfg(feature = "docs")]
+use rash_derive::DocJsonSchema;
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs::{self, read_to_string};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::path::Path;
+use std::str::FromStr;
+
+use minijinja::Value;
+use nix::ifaddrs::getifaddrs;
+use nix::sys::utsname::uname;
+use nix::unistd::{Uid, User};
+#[cfg(feature = "docs")]
+use schemars::{JsonSchema, Schema};
+use serde::Deserialize;
+use serde_json::{Map, json};
+use serde_norway::Value as YamlValue;
+use serde_with::{OneOrMany, serde_as};
+use strum_macros::EnumString;
+
+/// Variable name where facts are stored.
+pub const FACTS_VAR: &str = "facts";
+
+#[serde_as]
+#[derive(Debug, PartialEq, Deserialize)]
+#[cfg_attr(feature = "docs", derive(JsonSchema, DocJsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct Params {
+    /// Subsets of facts to collect: `all`, `min`, `os`, `hardware`, `mounts`, `network`,
+    /// `service_mgr`, `virtual` or `user`. Prefix a subset with `!` to exclude it.
+    /// **[default: `["all"]`]**
+    #[serde_as(deserialize_as = "OneOrMany<_>")]
+    #[serde(default = "default_gather_subset")]
+    gather_subset: Vec<String>,
+}
+
+fn default_gather_subset() -> Vec<String> {
+    vec!["all".to_owned()]
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, EnumString)]
+#[strum(serialize_all = "snake_case")]
+enum Subset {
+    Os,
+    Hardware,
+    Mounts,
+    Network,
+    ServiceMgr,
+    Virtual,
+    User,
+}
+
+const ALL_SUBSETS: [Subset; 7] = [
+    Subset::Os,
+    Subset::Hardware,
+    Subset::Mounts,
+    Subset::Network,
+    Subset::ServiceMgr,
+    Subset::Virtual,
+    Subset::User,
+];
+const MIN_SUBSETS: [Subset; 3] = [Subset::Os, Subset::ServiceMgr, Subset::User];
+
+fn expand_subset(name: &str) -> Result<Vec<Subset>> {
+    match name {
+        "all" => Ok(ALL_SUBSETS.to_vec()),
+        "min" => Ok(MIN_SUBSETS.to_vec()),
+        _ => Subset::from_str(name).map(|s| vec![s]).map_err(|_| {
+            Error::new(
+                ErrorKind::InvalidData,
+                format!("Invalid gather_subset `{name}`"),
+            )
+        }),
+    }
+}
+
+/// Resolve the subsets to collect. If just exclusions are defined, they apply over `all`.
+fn parse_subsets(gather_subset: &[String]) -> Result<BTreeSet<Subset>> {
+    let (excluded, included): (Vec<&String>, Vec<&String>) =
+        gather_subset.iter().partition(|s| s.starts_with('!'));
+
+    let mut subsets = BTreeSet::new();
+    if included.is_empty() {
+        subsets.extend(ALL_SUBSETS);
+    }
+    for name in included {
+        subsets.extend(expand_subset(name)?);
+    }
+    for name in excluded {
+        for subset in expand_subset(&name[1..])? {
+            subsets.remove(&subset);
+        }
+    }
+    Ok(subsets)
+}
+
+fn read_trimmed<P: AsRef<Path>>(path: P) -> Option<String> {
+    read_to_string(path).ok().map(|s| s.trim().to_owned())
+}
+
+fn insert_opt<T: Into<serde_json::Value>>(
+    facts: &mut Map<String, serde_json::Value>,
+    key: &str,
+    value: Option<T>,
+) {
+    facts.insert(
+        key.to_owned(),
+        value.map(Into::into).unwrap_or(serde_json::Value::Null),
+    );
+}
+
+fn parse_os_release(content: &str) -> BTreeMap<String, String> {
+    content
+        .lines()
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| {
+            (
+                key.trim().to_owned(),
+                value
+                    .trim()
+                    .trim_matches(|c| c == '"' || c == '\'')
+                    .to_owned(),
+            )
+        })
+        .collect()
+}
+
+fn get_os_family(id: &str, id_like: &str) -> Option<&'static str> {
+    let ids = std::iter::once(id).chain(id_like.split_whitespace());
+    for id in ids {
+        let family = match id {
+            "debian" | "ubuntu" | "linuxmint" | "raspbian" | "pop" | "kali" => "Debian",
+            "rhel" | "fedora" | "centos" | "rocky" | "almalinux" | "ol" | "amzn" => "RedHat",
+            "arch" | "archarm" | "manjaro" | "endeavouros" => "Archlinux",
+            "alpine" => "Alpine",
+            "suse" | "opensuse" | "sles" | "opensuse-leap" | "opensuse-tumbleweed" => "Suse",
+            "gentoo" => "Gentoo",
+            "openwrt" => "OpenWrt",
+            "void" => "Void",
+            "nixos" => "NixOS",
+            _ => continue,
+        };
+        return Some(family);
+    }
+    None
+}
+
+fn get_pkg_mgr(os_family: &str) -> Option<&'static str> {
+    match os_family {
+        "Debian" => Some("apt"),
+        "RedHat" => Some("dnf"),
+        "Archlinux" => Some("pacman"),
+        "Alpine" => Some("apk"),
+        "Suse" => Some("zypper"),
+        "OpenWrt" => Some("opkg"),
+        _ => None,
+    }
+}
+
+fn gather_os(facts: &mut Map<String, serde_json::Value>) {
+    let os_release = read_to_string("/etc/os-release")
+        .or_else(|_| read_to_string("/usr/lib/os-release"))
+        .map(|content| parse_os_release(&content))
+        .unwrap_or_default();
+    let get = |key: &str| os_release.get(key).cloned();
+
+    let id = get("ID").unwrap_or_default();
+    let os_family = get_os_family(&id, &get("ID_LIKE").unwrap_or_default());
+    let version = get("VERSION_ID");
+    let uts = uname().ok();
+
+    insert_opt(
+        facts,
+        "system",
+        uts.as_ref()
+            .map(|u| u.sysname().to_string_lossy().into_owned()),
+    );
+    insert_opt(facts, "os_family", os_family);
+    insert_opt(facts, "distribution", (!id.is_empty()).then_some(id));
+    insert_opt(
+        facts,
+        "distribution_major_version",
+        version
+            .as_deref()
+            .and_then(|v| v.split('.').next())
+            .map(String::from),
+    );
+    insert_opt(facts, "distribution_version", version);
+    insert_opt(facts, "distribution_release", get("VERSION_CODENAME"));
+    insert_opt(facts, "kernel", read_trimmed("/proc/sys/kernel/osrelease"));
+    insert_opt(
+        facts,
+        "kernel_version",
+        read_trimmed("/proc/sys/kernel/version"),
+    );
+    insert_opt(
+        facts,
+        "architecture",
+        uts.as_ref()
+            .map(|u| u.machine().to_string_lossy().into_owned()),
+    );
+    insert_opt(facts, "pkg_mgr", os_family.and_then(get_pkg_mgr));
+}
+
+fn parse_cpuinfo(content: &str) -> (usize, Option<String>) {
+    let mut count = 0;
+    let mut model = None;
+    for (key, value) in content.lines().filter_map(|line| line.split_once(':')) {
+        match key.trim() {
+            "processor" => count += 1,
+            "model name" | "Model" if model.is_none() => model = Some(value.trim().to_owned()),
+            _ => {}
+        }
+    }
+    (count, model)
+}
+
+fn parse_meminfo(content: &str) -> BTreeMap<String, u64> {
+    content
+        .lines()
+        .filter_map(|line| line.split_once(':'))
+        .filter_map(|(key, value)| {
+            let kb = value.split_whitespace().next()?.parse::<u64>().ok()?;
+            Some((key.trim().to_owned(), kb))
+        })
+        .collect()
+}
+
+fn gather_hardware(facts: &mut Map<String, serde_json::Value>) {
+    let (processor_count, processor_model) = read_to_string("/proc/cpuinfo")
+        .map(|content| parse_cpuinfo(&content))
+        .unwrap_or_default();
+    facts.insert("processor_count".to_owned(), json!(processor_count));
+    insert_opt(facts, "processor_model", processor_model);
+
+    let meminfo = read_to_string("/proc/meminfo")
+        .map(|content| parse_meminfo(&content))
+        .unwrap_or_default();
+    for (fact, key) in [
+        ("memtotal_mb", "MemTotal"),
+        ("memfree_mb", "MemFree"),
+        ("memavailable_mb", "MemAvailable"),
+        ("swaptotal_mb", "SwapTotal"),
+        ("swapfree_mb", "SwapFree"),
+    ] {
+        insert_opt(facts, fact, meminfo.get(key).map(|kb| kb / 1024));
+    }
+}
+
+/// Decode octal escapes used in `/proc/mounts`, e.g.: `\040` for spaces.
+fn decode_mount_field(field: &str) -> String {
+    let mut result = String::with_capacity(field.len());
+    let mut chars = field.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            let octal: String = chars.clone().take(3).collect();
+            if octal.len() == 3
+                && let Ok(byte) = u8::from_str_radix(&octal, 8)
+            {
+                result.push(byte as char);
+                chars.nth(2);
+                continue;
+            }
+        }
+        result.push(c);
+    }
+    result
+}
+
+fn parse_mounts(content: &str) -> Vec<serde_json::Value> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            match fields.as_slice() {
+                [device, mount, fstype, options, ..] => Some(json!({
+                    "device": decode_mount_field(device),
+                    "mount": decode_mount_field(mount),
+                    "fstype": fstype,
+                    "options": options,
+                })),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+fn gather_mounts(facts: &mut Map<String, serde_json::Value>) {
+    let mounts = read_to_string("/proc/mounts")
+        .map(|content| parse_mounts(&content))
+        .unwrap_or_default();
+    facts.insert("mounts".to_owned(), json!(mounts));
+}
+
+/// Return the first name with a domain of the `/etc/hosts` entry containing `hostname`.
+fn get_fqdn(hostname: &str, hosts: &str) -> String {
+    if hostname.contains('.') {
+        return hostname.to_owned();
+    }
+    hosts
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or_default())
+        .filter_map(|line| {
+            let names: Vec<&str> = line.split_whitespace().skip(1).collect();
+            names
+                .iter()
+                .any(|name| {
+                    *name == hostname
+                        || name
+                            .strip_prefix(hostname)
+                            .is_some_and(|s| s.starts_with('.'))
+                })
+                .then(|| names.into_iter().find(|name| name.contains('.')))
+                .flatten()
+        })
+        .next()
+        .unwrap_or(hostname)
+        .to_owned()
+}
+
+/// Return default IPv4 route interface and gateway from `/proc/net/route` content.
+fn parse_default_ipv4_route(content: &str) -> Option<(String, Ipv4Addr)> {
+    content.lines().skip(1).find_map(|line| {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        match fields.as_slice() {
+            [iface, "00000000", gateway, ..] => {
+                let gateway = u32::from_str_radix(gateway, 16).ok()?;
+                Some((iface.to_string(), Ipv4Addr::from(gateway.to_le_bytes())))
+            }
+            _ => None,
+        }
+    })
+}
+
+/// Return default IPv6 route interface and gateway from `/proc/net/ipv6_route` content.
+fn parse_default_ipv6_route(content: &str) -> Option<(String, Ipv6Addr)> {
+    content.lines().find_map(|line| {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        match fields.as_slice() {
+            [dest, "00", _, _, next_hop, .., iface]
+                if dest.chars().all(|c| c == '0') && *iface != "lo" =>
+            {
+                let gateway = u128::from_str_radix(next_hop, 16).ok()?;
+                Some((iface.to_string(), Ipv6Addr::from(gateway)))
+            }
+            _ => None,
+        }
+    })
+}
+
+fn get_interface_addresses() -> BTreeMap<String, (Vec<serde_json::Value>, Vec<serde_json::Value>)> {
+    let mut addresses: BTreeMap<String, (Vec<_>, Vec<_>)> = BTreeMap::new();
+    let Ok(ifaddrs) = getifaddrs() else {
+        return addresses;
+    };
+    for ifaddr in ifaddrs {
+        let entry = addresses.entry(ifaddr.interface_name.clone()).or_default();
+        let netmask = ifaddr.netmask.as_ref();
+        if let Some(address) = ifaddr.address.as_ref().and_then(|a| a.as_sockaddr_in()) {
+            let prefix = netmask
+                .and_then(|m| m.as_sockaddr_in())
+                .map(|m| m.ip().to_bits().count_ones());
+            entry
+                .0
+                .push(json!({"address": address.ip().to_string(), "prefix": prefix}));
+        } else if let Some(address) = ifaddr.address.as_ref().and_then(|a| a.as_sockaddr_in6()) {
+            let prefix = netmask
+                .and_then(|m| m.as_sockaddr_in6())
+                .map(|m| m.ip().to_bits().count_ones());
+            entry
+                .1
+                .push(json!({"address": address.ip().to_string(), "prefix": prefix}));
+        }
+    }
+    addresses
+}
+
+fn get_default_route_facts(
+    route: Option<(String, IpAddr)>,
+    interfaces: &Map<String, serde_json::Value>,
+    family: &str,
+) -> serde_json::Value {
+    match route {
+        Some((iface, gateway)) => json!({
+            "interface": iface,
+            "gateway": gateway.to_string(),
+            "address": interfaces
+                .get(&iface)
+                .and_then(|i| i[family].get(0))
+                .map(|a| a["address"].clone()),
+        }),
+        None => serde_json::Value::Null,
+    }
+}
+
+fn gather_network(facts: &mut Map<String, serde_json::Value>) {
+    let hostname = read_trimmed("/proc/sys/kernel/hostname").unwrap_or_default();
+    let fqdn = get_fqdn(&hostname, &read_to_string("/etc/hosts").unwrap_or_default());
+    let domain = fqdn
+        .split_once('.')
+        .map(|(_, d)| d.to_owned())
+        .unwrap_or_default();
+
+    let mut addresses = get_interface_addresses();
+    let mut interface_names: BTreeSet<String> = fs::read_dir("/sys/class/net")
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .map(|e| e.file_name().to_string_lossy().into_owned())
+                .collect()
+        })
+        .unwrap_or_default();
+    interface_names.extend(addresses.keys().cloned());
+
+    let mut interfaces = Map::new();
+    for name in interface_names {
+        let sys_path = Path::new("/sys/class/net").join(&name);
+        let (ipv4, ipv6) = addresses.remove(&name).unwrap_or_default();
+        interfaces.insert(
+            name,
+            json!({
+                "macaddress": read_trimmed(sys_path.join("address")),
+                "mtu": read_trimmed(sys_path.join("mtu")).and_then(|m| m.parse::<u64>().ok()),
+                "active": read_trimmed(sys_path.join("operstate"))
+                    .map(|state| state != "down"),
+                "ipv4": ipv4,
+                "ipv6": ipv6,
+            }),
+        );
+    }
+
+    let all_addresses = |family: &str| -> Vec<serde_json::Value> {
+        interfaces
+            .iter()
+            .filter(|(name, _)| name.as_str() != "lo")
+            .filter_map(|(_, i)| i[family].as_array())
+            .flatten()
+            .map(|a| a["address"].clone())
+            .collect()
+    };
+    facts.insert(
+        "all_ipv4_addresses".to_owned(),
+        json!(all_addresses("ipv4")),
+    );
+    facts.insert(
+        "all_ipv6_addresses".to_owned(),
+        json!(all_addresses("ipv6")),
+    );
+
+    let default_ipv4 = read_to_string("/proc/net/route")
+        .ok()
+        .and_then(|content| parse_default_ipv4_route(&content))
+        .map(|(iface, gateway)| (iface, IpAddr::V4(gateway)));
+    let default_ipv6 = read_to_string("/proc/net/ipv6_route")
+        .ok()
+        .and_then(|content| parse_default_ipv6_route(&content))
+        .map(|(iface, gateway)| (iface, IpAddr::V6(gateway)));
+    facts.insert(
+        "default_ipv4".to_owned(),
+        get_default_route_facts(default_ipv4, &interfaces, "ipv4"),
+    );
+    facts.insert(
+        "default_ipv6".to_owned(),
+        get_default_route_facts(default_ipv6, &interfaces, "ipv6"),
+    );
+
+    facts.insert("hostname".to_owned(), json!(hostname));
+    facts.insert("fqdn".to_owned(), json!(fqdn));
+    facts.insert("domain".to_owned(), json!(domain));
+    facts.insert(
+        "interfaces".to_owned(),
+        serde_json::Value::Object(interfaces),
+    );
+}
+
+/// Detect the init system from the PID 1 command name and well-known paths.
+fn get_service_mgr(init_comm: &str, path_exists: impl Fn(&str) -> bool) -> Option<&'static str> {
+    match init_comm {
+        "systemd" => Some("systemd"),
+        "runit" | "runit-init" => Some("runit"),
+        "s6-svscan" => Some("s6"),
+        "openrc-init" => Some("openrc"),
+        _ if path_exists("/run/systemd/system") => Some("systemd"),
+        _ if path_exists("/run/openrc") || path_exists("/sbin/openrc-run") => Some("openrc"),
+        "init" if path_exists("/etc/init.d") => Some("sysvinit"),
+        _ => None,
+    }
+}
+
+fn gather_service_mgr(facts: &mut Map<String, serde_json::Value>) {
+    let init_comm = read_trimmed("/proc/1/comm").unwrap_or_default();
+    insert_opt(
+        facts,
+        "service_mgr",
+        get_service_mgr(&init_comm, |path| Path::new(path).exists()),
+    );
+}
+
+/// Sources used to detect virtualization, read from the host.
+#[derive(Debug, Default)]
+struct VirtualizationSources {
+    systemd_container: Option<String>,
+    dockerenv: bool,
+    containerenv: bool,
+    init_cgroup: String,
+    product_name: String,
+    sys_vendor: String,
+    hypervisor_type: Option<String>,
+    cpu_flags_hypervisor: bool,
+}
+
+fn get_virtualization_type(sources: &VirtualizationSources) -> Option<String> {
+    if let Some(container) = &sources.systemd_container {
+        return Some(container.clone());
+    }
+    if sources.dockerenv {
+        return Some("docker".to_owned());
+    }
+    if sources.containerenv {
+        return Some("podman".to_owned());
+    }
+    for (pattern, container) in [
+        ("docker", "docker"),
+        ("kubepods", "containerd"),
+        ("lxc", "lxc"),
+        ("libpod", "podman"),
+    ] {
+        if sources.init_cgroup.contains(pattern) {
+            return Some(container.to_owned());
+        }
+    }
+
+    let dmi = format!("{} {}", sources.sys_vendor, sources.product_name);
+    for (pattern, vm) in [
+        ("KVM", "kvm"),
+        ("QEMU", "kvm"),
+        ("VMware", "VMware"),
+        ("VirtualBox", "virtualbox"),
+        ("Microsoft Corporation", "hyperv"),
+        ("Xen", "xen"),
+        ("Amazon EC2", "kvm"),
+        ("Google", "kvm"),
+    ] {
+        if dmi.contains(pattern) {
+            return Some(vm.to_owned());
+        }
+    }
+    if let Some(hypervisor) = &sources.hypervisor_type {
+        return Some(hypervisor.clone());
+    }
+    sources.cpu_flags_hypervisor.then(|| "unknown".to_owned())
+}
+
+fn gather_virtual(facts: &mut Map<String, serde_json::Value>) {
+    let sources = VirtualizationSources {
+        systemd_container: read_trimmed("/run/systemd/container"),
+        dockerenv: Path::new("/.dockerenv").exists(),
+        containerenv: Path::new("/run/.containerenv").exists(),
+        init_cgroup: read_to_string("/proc/1/cgroup").unwrap_or_default(),
+        product_name: read_trimmed("/sys/class/dmi/id/product_name").unwrap_or_default(),
+        sys_vendor: read_trimmed("/sys/class/dmi/id/sys_vendor").unwrap_or_default(),
+        hypervisor_type: read_trimmed("/sys/hypervisor/type"),
+        cpu_flags_hypervisor: read_to_string("/proc/cpuinfo")
+            .map(|content| {
+                content
+                    .lines()
+                    .filter(|line| line.starts_with("flags"))
+                    .any(|line| line.split_whitespace().any(|flag| flag == "hypervisor"))
+            })
+            .unwrap_or(false),
+    };
+    let virtualization_type = get_virtualization_type(&sources);
+    insert_opt(
+        facts,
+        "virtualization_role",
+        virtualization_type.as_ref().map(|_| "guest"),
+    );
+    insert_opt(facts, "virtualization_type", virtualization_type);
+}
+
+fn gather_user(facts: &mut Map<String, serde_json::Value>) {
+    let uid = Uid::current();
+    let user = User::from_uid(uid).ok().flatten();
+    facts.insert("user_uid".to_owned(), json!(uid.as_raw()));
+    insert_opt(facts, "user_id", user.as_ref().map(|u| u.name.clone()));
+    insert_opt(facts, "user_gid", user.as_ref().map(|u| u.gid.as_raw()));
+    insert_opt(
+        facts,
+        "user_dir",
+        user.as_ref().map(|u| u.dir.to_string_lossy().into_owned()),
+    );
+    insert_opt(
+        facts,
+        "user_shell",
+        user.as_ref()
+            .map(|u| u.shell.to_string_lossy().into_owned()),
+    );
+}
+
+fn gather_facts(params: Params) -> Result<serde_json::Value> {
+    let mut facts = Map::new();
+    for subset in parse_subsets(&params.gather_subset)? {
+        trace!("gathering {subset:?} facts");
+        match subset {
+            Subset::Os => gather_os(&mut facts),
+            Subset::Hardware => gather_hardware(&mut facts),
+            Subset::Mounts => gather_mounts(&mut facts),
+            Subset::Network => gather_network(&mut facts),
+            Subset::ServiceMgr => gather_service_mgr(&mut facts),
+            Subset::Virtual => gather_virtual(&mut facts),
+            Subset::User => gather_user(&mut facts),
+        }
+    }
+    Ok(serde_json::Value::Object(facts))
+}
+
+/// Return a fact as string if it was gathered previously.
+pub fn get_fact(vars: &Value, name: &str) -> Option<String> {
+    vars.get_attr(FACTS_VAR)
+        .ok()?
+        .get_attr(name)
+        .ok()?
+        .as_str()
+        .map(String::from)
+}
+
+#[derive(Debug)]
+pub struct GatherFacts;
+
+impl Module for GatherFacts {
+    fn get_name(&self) -> &str {
+        "gather_facts"
+    }
+
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            check_mode: CheckModeSupport::Always,
+            ..Default::default()
+        }
+    }
+
+    fn exec(
+        &self,
+        _: &GlobalParams,
+        optional_params: YamlValue,
+        vars: &Value,
+        _check_mode: bool,
+    ) -> Result<(ModuleResult, Option<Value>)> {
+        let params = match optional_params {
+            YamlValue::Null => YamlValue::Mapping(serde_norway::Mapping::new()),
+            other => other,
+        };
+        let mut facts = gather_facts(parse_params(params)?)?;
+
+        // Keep facts from previous executions with other subsets.
+        if let Ok(previous) = serde_json::to_value(vars.get_attr(FACTS_VAR)?)
+            && let (serde_json::Value::Object(previous), Some(current)) =
+                (previous, facts.as_object_mut())
+        {
+            for (key, value) in previous {
+                current.entry(key).or_insert(value);
+            }
+        }
+
+        Ok((
+            ModuleResult::new(false, Some(serde_norway::to_value(&facts)?), None),
+            Some(
+                [(FACTS_VAR, Value::from_serialize(&facts))]
+                    .into_iter()
+                    .collect(),
+            ),
+        ))
+    }
+
+    #[cfg(feature = "docs")]
+    fn get_json_schema(&self) -> Option<Schema> {
+        Some(Params::get_json_schema())
+    }
+}
+
+impl ModuleParams for GatherFacts {
+    type Params = Params;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use minijinja::context;
+
+    #[test]
+    fn test_parse_params_default() {
+        let params: Params = parse_params(YamlValue::Mapping(Default::default())).unwrap();
+        assert_eq!(params.gather_subset, vec!["all".to_owned()]);
+    }
+
+    #[test]
+    fn test_parse_params_single_subset() {
+        let yaml: YamlValue = serde_norway::from_str("gather_subset: network").unwrap();
+        let params: Params = parse_params(yaml).unwrap();
+        assert_eq!(params.gather_subset, vec!["network".to_owned()]);
+    }
+
+    #[test]
+    fn test_parse_params_random_field() {
+        let yaml: YamlValue = serde_norway::from_str("foo: boo").unwrap();
+        let error = parse_params::<Params>(yaml).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_parse_subsets() {
+        let to_vec = |s: &[&str]| s.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+
+        assert_eq!(
+            parse_subsets(&to_vec(&["all"])).unwrap(),
+            BTreeSet::from(ALL_SUBSETS)
+        );
+        assert_eq!(
+            parse_subsets(&to_vec(&["network", "user"])).unwrap(),
+            BTreeSet::from([Subset::Network, Subset::User])
+        );
+        assert_eq!(
+            parse_subsets(&to_vec(&["min", "!user"])).unwrap(),
+            BTreeSet::from([Subset::Os, Subset::ServiceMgr])
+        );
+        assert_eq!(
+            parse_subsets(&to_vec(&["!mounts", "!network"])).unwrap(),
+            BTreeSet::from([
+                Subset::Os,
+                Subset::Hardware,
+                Subset::ServiceMgr,
+                Subset::Virtual,
+                Subset::User
+            ])
+        );
+        assert_eq!(
+            parse_subsets(&to_vec(&["foo"])).unwrap_err().kind(),
+            ErrorKind::InvalidData
+        );
+    }
+
+    #[test]
+    fn test_parse_os_release() {
+        let os_release = parse_os_release(
+            r#"PRETTY_NAME="Ubuntu 24.04.1 LTS"
+NAME="Ubuntu"
+VERSION_ID="24.04"
+VERSION_CODENAME=noble
+ID=ubuntu
+ID_LIKE=debian
+"#,
+        );
+        assert_eq!(os_release["ID"], "ubuntu");
+        assert_eq!(os_release["VERSION_ID"], "24.04");
+        assert_eq!(os_release["VERSION_CODENAME"], "noble");
+    }
+
+    #[test]
+    fn test_get_os_family() {
+        assert_eq!(get_os_family("ubuntu", "debian"), Some("Debian"));
+        assert_eq!(get_os_family("rocky", "rhel centos fedora"), Some("RedHat"));
+        assert_eq!(get_os_family("foo", "arch"), Some("Archlinux"));
+        assert_eq!(get_os_family("alpine", ""), Some("Alpine"));
+        assert_eq!(get_os_family("foo", ""), None);
+        assert_eq!(get_pkg_mgr("Alpine"), Some("apk"));
+    }
+
+    #[test]
+    fn test_parse_cpuinfo() {
+        let (count, model) = parse_cpuinfo(
+            "processor\t: 0\nmodel name\t: AMD Ryzen 7\n\nprocessor\t: 1\nmodel name\t: AMD Ryzen 7\n",
+        );
+        assert_eq!(count, 2);
+        assert_eq!(model, Some("AMD Ryzen 7".to_owned()));
+    }
+
+    #[test]
+    fn test_parse_meminfo() {
+        let meminfo = parse_meminfo(
+            "MemTotal:       16318480 kB\nMemFree:         1024 kB\nHugePages_Total:       0\n",
+        );
+        assert_eq!(meminfo["MemTotal"], 16318480);
+        assert_eq!(meminfo["MemFree"], 1024);
+        assert_eq!(meminfo["HugePages_Total"], 0);
+    }
+
+    #[test]
+    fn test_parse_mounts() {
+        let mounts = parse_mounts(
+            "/dev/sda1 / ext4 rw,relatime 0 0\n/dev/sdb1 /mnt/my\\040disk vfat rw 0 0\n",
+        );
+        assert_eq!(
+            mounts,
+            vec![
+                json!({"device": "/dev/sda1", "mount": "/", "fstype": "ext4", "options": "rw,relatime"}),
+                json!({"device": "/dev/sdb1", "mount": "/mnt/my disk", "fstype": "vfat", "options": "rw"}),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_get_fqdn() {
+        let hosts = "127.0.0.1 localhost\n127.0.1.1 myhost.example.com myhost # comment\n";
+        assert_eq!(get_fqdn("myhost", hosts), "myhost.example.com");
+        assert_eq!(get_fqdn("other", hosts), "other");
+        assert_eq!(get_fqdn("other.example.org", hosts), "other.example.org");
+    }
+
+    #[test]
+    fn test_parse_default_ipv4_route() {
+        let route = "Iface\tDestination\tGateway \tFlags\tRefCnt\tUse\tMetric\tMask\n\
+            eth0\t0002A8C0\t00000000\t0001\t0\t0\t0\t00FFFFFF\n\
+            eth0\t00000000\t0102A8C0\t0003\t0\t0\t0\t00000000\n";
+        assert_eq!(
+            parse_default_ipv4_route(route),
+            Some(("eth0".to_owned(), Ipv4Addr::new(192, 168, 2, 1)))
+        );
+        assert_eq!(parse_default_ipv4_route(""), None);
+    }
+
+    #[test]
+    fn test_parse_default_ipv6_route() {
+        let route = "00000000000000000000000000000000 00 00000000000000000000000000000000 00 fe800000000000000000000000000001 00000400 00000001 00000000 00000003 eth0\n\
+            00000000000000000000000000000000 00 00000000000000000000000000000000 00 00000000000000000000000000000000 ffffffff 00000001 00000000 00200200 lo\n";
+        assert_eq!(
+            parse_default_ipv6_route(route),
+            Some(("eth0".to_owned(), "fe80::1".parse().unwrap()))
+        );
+    }
+
+    #[test]
+    fn test_get_service_mgr() {
+        assert_eq!(get_service_mgr("systemd", |_| false), Some("systemd"));
+        assert_eq!(
+            get_service_mgr("init", |p| p == "/run/openrc"),
+            Some("openrc")
+        );
+        assert_eq!(
+            get_service_mgr("init", |p| p == "/etc/init.d"),
+            Some("sysvinit")
+        );
+        assert_eq!(get_service_mgr("bash", |_| false), None);
+    }
+
+    #[test]
+    fn test_get_virtualization_type() {
+        assert_eq!(
+            get_virtualization_type(&VirtualizationSources::default()),
+            None
+        );
+        assert_eq!(
+            get_virtualization_type(&VirtualizationSources {
+                dockerenv: true,
+                sys_vendor: "QEMU".to_owned(),
+                ..Default::default()
+            }),
+            Some("docker".to_owned())
+        );
+        assert_eq!(
+            get_virtualization_type(&VirtualizationSources {
+                sys_vendor: "QEMU".to_owned(),
+                product_name: "Standard PC".to_owned(),
+                ..Default::default()
+            }),
+            Some("kvm".to_owned())
+        );
+        assert_eq!(
+            get_virtualization_type(&VirtualizationSources {
+                cpu_flags_hypervisor: true,
+                ..Default::default()
+            }),
+            Some("unknown".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_exec() {
+        let yaml: YamlValue = serde_norway::from_str("gather_subset: [os, user]").unwrap();
+        let vars = context! {facts => context! {foo => "boo", system => "old"}};
+        let (result, new_vars) = GatherFacts
+            .exec(&GlobalParams::default(), yaml, &vars, false)
+            .unwrap();
+
+        assert!(!result.get_changed());
+        let facts = new_vars.unwrap().get_attr("facts").unwrap();
+        assert_eq!(facts.get_attr("system").unwrap(), Value::from("Linux"));
+        assert_eq!(facts.get_attr("foo").unwrap(), Value::from("boo"));
+        assert_eq!(
+            facts.get_attr("user_uid").unwrap(),
+            Value::from(Uid::current().as_raw())
+        );
+        assert!(facts.get_attr("mounts").unwrap().is_undefined());
+    }
+
+    #[test]
+    fn test_get_fact() {
+        let vars = context! {facts => context! {pkg_mgr => "apt"}};
+        assert_eq!(get_fact(&vars, "pkg_mgr"), Some("apt".to_owned()));
+        assert_eq!(get_fact(&vars, "service_mgr"), None);
+        assert_eq!(get_fact(&context! {}, "pkg_mgr"), None);
+    }
+}
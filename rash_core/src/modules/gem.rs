@@ -2,13 +2,6 @@
 /// # gem
 ///
 /// Manage Ruby gems with the gem package manager.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -65,7 +58,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Module, ModuleParams, ModuleResult, parse_params};
 use crate::utils::default_false;
 
 #[cfg(feature = "docs")]
@@ -211,6 +204,10 @@ impl Module for Gem {
     }
 }
 
+impl ModuleParams for Gem {
+    type Params = Params;
+}
+
 type IsChanged = bool;
 
 struct GemClient {
@@ -529,6 +526,7 @@ fn gem(params: Params, check_mode: bool) -> Result<ModuleResult> {
             "updated_gems": p_to_update,
             "removed_gems": p_to_remove,
         }))?),
+        ..Default::default()
     })
 }
 
@@ -543,6 +541,7 @@ fn bundler_install(params: &Params, check_mode: bool) -> Result<ModuleResult> {
             extra: Some(value::to_value(
                 json!({"bundler": true, "chdir": params.chdir}),
             )?),
+            ..Default::default()
         });
     }
 
@@ -554,6 +553,7 @@ fn bundler_install(params: &Params, check_mode: bool) -> Result<ModuleResult> {
         extra: Some(value::to_value(
             json!({"bundler": true, "chdir": params.chdir}),
         )?),
+        ..Default::default()
     })
 }
 
@@ -2,15 +2,6 @@
 /// # get_url
 ///
 /// Downloads files from HTTP, HTTPS, or FTP to local destination.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: partial
-/// diff_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -55,7 +46,9 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger::diff_files;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{
+    Capabilities, CheckModeSupport, Module, ModuleParams, ModuleResult, parse_params,
+};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -267,6 +260,14 @@ impl Module for GetUrl {
         "get_url"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            check_mode: CheckModeSupport::Partial,
+            diff: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -332,6 +333,7 @@ impl Module for GetUrl {
                         file_path.display()
                     )),
                     extra: None,
+                    ..Default::default()
                 },
                 None,
             ));
@@ -346,6 +348,7 @@ impl Module for GetUrl {
                         file_path.display()
                     )),
                     extra: None,
+                    ..Default::default()
                 },
                 None,
             ));
@@ -460,6 +463,7 @@ impl Module for GetUrl {
                     file_path.display()
                 )),
                 extra,
+                ..Default::default()
             },
             None,
         ))
@@ -471,6 +475,10 @@ impl Module for GetUrl {
     }
 }
 
+impl ModuleParams for GetUrl {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2,15 +2,6 @@
 /// # git
 ///
 /// Manage git checkouts of repositories.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// diff_mode:
-///   support: none
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -46,7 +37,7 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -179,6 +170,7 @@ fn clone_repo(params: &Params, check_mode: bool) -> Result<ModuleResult> {
             changed: true,
             output: Some(format!("Would clone {} to {}", params.repo, params.dest)),
             extra: None,
+            ..Default::default()
         });
     }
 
@@ -223,6 +215,7 @@ fn clone_repo(params: &Params, check_mode: bool) -> Result<ModuleResult> {
         changed: true,
         output: Some(format!("Cloned {} to {}", params.repo, params.dest)),
         extra,
+        ..Default::default()
     })
 }
 
@@ -279,6 +272,7 @@ fn update_repo(params: &Params, check_mode: bool) -> Result<ModuleResult> {
             changed: true,
             output: Some(format!("Would update {} at {}", params.repo, params.dest)),
             extra: None,
+            ..Default::default()
         });
     }
 
@@ -323,6 +317,7 @@ fn update_repo(params: &Params, check_mode: bool) -> Result<ModuleResult> {
         changed,
         output: Some(output),
         extra,
+        ..Default::default()
     })
 }
 
@@ -352,6 +347,7 @@ fn manage_git(params: Params, check_mode: bool) -> Result<ModuleResult> {
                     params.dest, current_commit
                 )),
                 extra,
+                ..Default::default()
             })
         }
     } else if dest_path.exists() {
@@ -392,6 +388,10 @@ impl Module for Git {
     }
 }
 
+impl ModuleParams for Git {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
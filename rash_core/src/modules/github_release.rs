@@ -2,13 +2,6 @@
 /// # github_release
 ///
 /// Download release assets from GitHub releases.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: partial
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -35,7 +28,9 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{
+    Capabilities, CheckModeSupport, Module, ModuleParams, ModuleResult, parse_params,
+};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -297,6 +292,13 @@ impl Module for GithubRelease {
         "github_release"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            check_mode: CheckModeSupport::Partial,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -333,6 +335,7 @@ impl Module for GithubRelease {
                         dest_path.display()
                     )),
                     extra: None,
+                    ..Default::default()
                 },
                 None,
             ));
@@ -362,6 +365,7 @@ impl Module for GithubRelease {
                     dest_path.display()
                 )),
                 extra: Some(value::to_value(extra_data)?),
+                ..Default::default()
             },
             None,
         ))
@@ -373,6 +377,10 @@ impl Module for GithubRelease {
     }
 }
 
+impl ModuleParams for GithubRelease {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2,13 +2,6 @@
 /// # gpg_key
 ///
 /// Manage GPG keys for package verification and signing.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -53,7 +46,7 @@
 /// ```
 /// ANCHOR_END: examples
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -364,6 +357,7 @@ pub fn gpg_key(params: Params, check_mode: bool) -> Result<ModuleResult> {
                                 trust, key_id
                             )),
                             extra: None,
+                            ..Default::default()
                         });
                     }
                     set_trust_level(key_id, trust, gpg_home, use_gpg1)?;
@@ -380,6 +374,7 @@ pub fn gpg_key(params: Params, check_mode: bool) -> Result<ModuleResult> {
                     changed,
                     output: Some(format!("Key {} already exists", key_id)),
                     extra: Some(value::to_value(extra)?),
+                    ..Default::default()
                 });
             }
 
@@ -403,6 +398,7 @@ pub fn gpg_key(params: Params, check_mode: bool) -> Result<ModuleResult> {
                             .unwrap_or_default()
                     )),
                     extra: None,
+                    ..Default::default()
                 });
             }
 
@@ -439,6 +435,7 @@ pub fn gpg_key(params: Params, check_mode: bool) -> Result<ModuleResult> {
                 changed: true,
                 output: Some(format!("GPG key {} imported successfully", actual_key_id)),
                 extra: Some(value::to_value(extra)?),
+                ..Default::default()
             })
         }
         State::Absent => {
@@ -454,6 +451,7 @@ pub fn gpg_key(params: Params, check_mode: bool) -> Result<ModuleResult> {
                     changed: false,
                     output: Some(format!("Key {} does not exist", key_id)),
                     extra: None,
+                    ..Default::default()
                 });
             }
 
@@ -462,6 +460,7 @@ pub fn gpg_key(params: Params, check_mode: bool) -> Result<ModuleResult> {
                     changed: true,
                     output: Some(format!("Would delete GPG key {}", key_id)),
                     extra: None,
+                    ..Default::default()
                 });
             }
 
@@ -471,6 +470,7 @@ pub fn gpg_key(params: Params, check_mode: bool) -> Result<ModuleResult> {
                 changed: true,
                 output: Some(format!("GPG key {} deleted successfully", key_id)),
                 extra: None,
+                ..Default::default()
             })
         }
     }
@@ -500,6 +500,10 @@ impl Module for GpgKey {
     }
 }
 
+impl ModuleParams for GpgKey {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2,13 +2,6 @@
 /// # grafana
 ///
 /// Manage Grafana dashboards, datasources, folders, and organizations.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -120,7 +113,7 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -996,6 +989,10 @@ impl Module for Grafana {
     }
 }
 
+impl ModuleParams for Grafana {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -6,13 +6,6 @@
 /// Create, update, or delete Grafana dashboards via the Grafana HTTP API.
 /// Supports dashboard JSON definitions, folder assignment, and overwrite
 /// behavior. Useful for monitoring infrastructure automation.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -56,7 +49,7 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -495,6 +488,10 @@ impl Module for GrafanaDashboard {
     }
 }
 
+impl ModuleParams for GrafanaDashboard {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2,13 +2,6 @@
 /// # group
 ///
 /// Manage groups and group attributes.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -29,7 +22,7 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult};
+use crate::modules::{Module, ModuleParams, ModuleResult};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -160,6 +153,7 @@ fn exec_group_command(cmd: &[String], check_mode: bool) -> Result<(ModuleResult,
                 changed: true,
                 output: Some(format!("Would run: {}", cmd.join(" "))),
                 extra: None,
+                ..Default::default()
             },
             None,
         ));
@@ -188,6 +182,7 @@ fn exec_group_command(cmd: &[String], check_mode: bool) -> Result<(ModuleResult,
             changed: true,
             output: Some(stdout.into_owned()),
             extra: None,
+            ..Default::default()
         },
         None,
     ))
@@ -230,6 +225,7 @@ impl Module for Group {
                                 changed: false,
                                 output: None,
                                 extra: None,
+                                ..Default::default()
                             },
                             None,
                         ))
@@ -242,6 +238,7 @@ impl Module for Group {
                         changed: false,
                         output: Some("Group already absent".to_string()),
                         extra: None,
+                        ..Default::default()
                     },
                     None,
                 )),
@@ -259,6 +256,10 @@ impl Module for Group {
     }
 }
 
+impl ModuleParams for Group {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2,13 +2,6 @@
 /// # grub
 ///
 /// Manage GRUB bootloader installation, configuration, and updates.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -67,7 +60,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger::diff;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Capabilities, Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -162,6 +155,13 @@ impl Module for Grub {
         "grub"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            diff: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -178,6 +178,10 @@ impl Module for Grub {
     }
 }
 
+impl ModuleParams for Grub {
+    type Params = Params;
+}
+
 fn parse_grub_config(content: &str) -> HashMap<String, String> {
     let mut config = HashMap::new();
 
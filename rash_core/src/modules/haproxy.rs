@@ -2,13 +2,6 @@
 /// # haproxy
 ///
 /// Manage HAProxy load balancer backend and frontend configurations.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -58,7 +51,7 @@
 /// ANCHOR_END: examples
 use crate::error::Result;
 use crate::logger::diff;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Capabilities, Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -303,6 +296,7 @@ fn exec_present(params: &Params, config_path: &Path, check_mode: bool) -> Result
                     changed: false,
                     output: Some(params.config_file.clone()),
                     extra: None,
+                    ..Default::default()
                 });
             }
             let mut new_blocks = blocks.clone();
@@ -355,6 +349,7 @@ fn exec_present(params: &Params, config_path: &Path, check_mode: bool) -> Result
         changed: true,
         output: Some(params.config_file.clone()),
         extra: None,
+        ..Default::default()
     })
 }
 
@@ -364,6 +359,7 @@ fn exec_absent(params: &Params, config_path: &Path, check_mode: bool) -> Result<
             changed: false,
             output: Some(params.config_file.clone()),
             extra: None,
+            ..Default::default()
         });
     }
 
@@ -378,6 +374,7 @@ fn exec_absent(params: &Params, config_path: &Path, check_mode: bool) -> Result<
                 changed: false,
                 output: Some(params.config_file.clone()),
                 extra: None,
+                ..Default::default()
             });
         }
     };
@@ -402,6 +399,7 @@ fn exec_absent(params: &Params, config_path: &Path, check_mode: bool) -> Result<
         changed: true,
         output: Some(params.config_file.clone()),
         extra: None,
+        ..Default::default()
     })
 }
 
@@ -413,6 +411,13 @@ impl Module for Haproxy {
         "haproxy"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            diff: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &crate::context::GlobalParams,
@@ -432,6 +437,10 @@ impl Module for Haproxy {
     }
 }
 
+impl ModuleParams for Haproxy {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2,13 +2,6 @@
 /// # helm
 ///
 /// Manage Helm charts and repositories, the Kubernetes package manager.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -90,7 +83,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Module, ModuleParams, ModuleResult, parse_params};
 use crate::utils::default_false;
 
 #[cfg(feature = "docs")]
@@ -290,6 +283,10 @@ impl Module for Helm {
     }
 }
 
+impl ModuleParams for Helm {
+    type Params = Params;
+}
+
 struct HelmClient {
     executable: PathBuf,
     extra_args: Option<String>,
@@ -780,6 +777,7 @@ fn handle_repository(client: &HelmClient, params: &RepositoryParams) -> Result<M
             state.to_string().to_lowercase()
         )),
         extra: Some(extra),
+        ..Default::default()
     })
 }
 
@@ -833,6 +831,7 @@ fn handle_chart(client: &HelmClient, params: &ChartParams) -> Result<ModuleResul
             state.to_string().to_lowercase()
         )),
         extra: Some(extra),
+        ..Default::default()
     })
 }
 
@@ -863,6 +862,7 @@ fn handle_list(client: &HelmClient, params: &ListParams) -> Result<ModuleResult>
         changed: false,
         output: Some(format!("Found {} releases", releases_json.len())),
         extra: Some(extra),
+        ..Default::default()
     })
 }
 
@@ -2,13 +2,6 @@
 /// # helm_info
 ///
 /// Get information about Helm releases in Kubernetes clusters.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: none
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -40,7 +33,9 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{
+    Capabilities, CheckModeSupport, Module, ModuleParams, ModuleResult, parse_params,
+};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -84,6 +79,14 @@ impl Module for HelmInfo {
         "helm_info"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            // it just queries the releases
+            check_mode: CheckModeSupport::Always,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -104,6 +107,10 @@ impl Module for HelmInfo {
     }
 }
 
+impl ModuleParams for HelmInfo {
+    type Params = Params;
+}
+
 struct HelmClient;
 
 impl HelmClient {
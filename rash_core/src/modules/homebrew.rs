@@ -2,13 +2,6 @@
 /// # homebrew
 ///
 /// Manage packages with Homebrew, the macOS package manager.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -50,7 +43,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Module, ModuleParams, ModuleResult, parse_params};
 use crate::utils::default_false;
 
 #[cfg(feature = "docs")]
@@ -167,6 +160,10 @@ impl Module for Homebrew {
     }
 }
 
+impl ModuleParams for Homebrew {
+    type Params = Params;
+}
+
 type IsChanged = bool;
 
 struct HomebrewClient {
@@ -445,6 +442,7 @@ fn homebrew(params: Params, check_mode: bool) -> Result<ModuleResult> {
             "upgraded_all": upgrade_all_changed,
             "homebrew_updated": homebrew_updated
         }))?),
+        ..Default::default()
     })
 }
 
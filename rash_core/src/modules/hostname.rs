@@ -2,13 +2,6 @@
 /// # hostname
 ///
 /// Manage system hostname.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -31,7 +24,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger::diff;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Capabilities, Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -79,6 +72,13 @@ impl Module for Hostname {
         "hostname"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            diff: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -98,6 +98,10 @@ impl Module for Hostname {
     }
 }
 
+impl ModuleParams for Hostname {
+    type Params = Params;
+}
+
 fn validate_hostname(hostname: &str) -> Result<()> {
     if hostname.is_empty() {
         return Err(Error::new(
@@ -2,13 +2,6 @@
 /// # htpasswd
 ///
 /// Manage htpasswd files for HTTP Basic Authentication.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -38,7 +31,7 @@
 /// ANCHOR_END: examples
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger::diff;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Capabilities, Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -394,6 +387,13 @@ impl Module for Htpasswd {
         "htpasswd"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            diff: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &crate::context::GlobalParams,
@@ -410,6 +410,10 @@ impl Module for Htpasswd {
     }
 }
 
+impl ModuleParams for Htpasswd {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
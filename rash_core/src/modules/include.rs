@@ -3,17 +3,21 @@
 ///
 /// This module include tasks to be executed from another file.
 ///
-/// ## Attributes
+/// Relative paths are resolved against the directory of the including file. Tasks are parsed and
+/// executed when the `include` task is reached, so its path can be a template and it can be used
+/// with `loop`, executing the file once per item. Variables registered by the included tasks are
+/// not available after the `include` task.
 ///
-/// ```yaml
-/// check_mode:
-///   support: none
-/// ```
+/// Use the `import` keyword instead of `include` to add the tasks of the file when the script is
+/// parsed: they are listed by `--list-tasks` and checked by `--syntax-check`, as any other task.
+/// `import` accepts the same parameters, but its path cannot be a template.
 /// ANCHOR_END: module
 /// ANCHOR: parameters
-/// | Parameter | Required | Type   | Values | Description                                                 |
-/// | --------- | -------- | ------ | ------ | ----------------------------------------------------------- |
-/// | file      | true     | string |        | Parse target file and execute tasks in the current context. |
+/// | Parameter | Required | Type   | Values | Description                                                                                       |
+/// | --------- | -------- | ------ | ------ | ------------------------------------------------------------------------------------------------- |
+/// | file      | true     | string |        | Path of the file with the tasks. It can be passed directly as the value of `include`.             |
+/// | vars      |          | map    |        | Variables available just in the included tasks.                                                   |
+/// | apply     |          | map    |        | Task keywords added to all the included tasks: `become`, `environment`, `tags` and `when`.        |
 ///
 /// ANCHOR_END: parameters
 ///
@@ -26,22 +30,190 @@
 /// - include: "{{ rash.dir }}/bar.rh"
 ///
 /// - include: "{{ env.HOSTNAME }}.rh"
+///
+/// - name: Create users
+///   include:
+///     file: user.rh
+///     vars:
+///       shell: /bin/bash
+///     apply:
+///       become: true
+///       tags:
+///         - users
+///   loop:
+///     - alice
+///     - bob
+///
+/// - import: common.rh
+///
+/// - gather_facts:
+///
+/// - import:
+///     file: packages.rh
+///     apply:
+///       when: facts.os_family == "Debian"
 /// ```
 /// ANCHOR_END: examples
 use crate::context::{Context, GlobalParams};
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult};
-use crate::task::parse_file;
+use crate::jinja::merge_option;
+use crate::modules::{
+    Capabilities, CheckModeSupport, Module, ModuleResult, ValidateParams, parse_params,
+};
+use crate::task::parse_included_file;
 use crate::vars::builtin::Builtins;
 
-use std::fs::read_to_string;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use minijinja::{Value, context};
 #[cfg(feature = "docs")]
 use schemars::Schema;
 use serde::Deserialize;
-use serde_norway::Value as YamlValue;
+use serde_norway::{Mapping, Value as YamlValue};
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Params {
+    pub file: String,
+    pub vars: Option<Mapping>,
+    #[serde(default)]
+    pub apply: Apply,
+}
+
+/// Task keywords added to all the included tasks.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Apply {
+    #[serde(rename = "become")]
+    r#become: Option<bool>,
+    environment: Option<Mapping>,
+    tags: Option<YamlValue>,
+    when: Option<YamlValue>,
+}
+
+/// Return value as a list, if it is not a list already.
+fn to_sequence(value: YamlValue) -> Vec<YamlValue> {
+    match value {
+        YamlValue::Sequence(seq) => seq,
+        value => vec![value],
+    }
+}
+
+impl Apply {
+    /// Add keywords to the task, keeping the ones defined in the task: `become` and
+    /// `environment` variables are set if the task doesn't set them, and `tags` and `when`
+    /// conditions are added to the ones of the task.
+    fn apply_to(&self, task: &mut Mapping) {
+        if let Some(r#become) = self.r#become {
+            task.entry("become".into())
+                .or_insert(YamlValue::Bool(r#become));
+        }
+
+        if let Some(environment) = &self.environment {
+            match task.get_mut("environment") {
+                Some(YamlValue::Mapping(task_environment)) => {
+                    environment.iter().for_each(|(key, value)| {
+                        task_environment.entry(key.clone()).or_insert(value.clone());
+                    });
+                }
+                Some(_) => (),
+                None => {
+                    task.insert(
+                        "environment".into(),
+                        YamlValue::Mapping(environment.clone()),
+                    );
+                }
+            }
+        }
+
+        for (keyword, value) in [("tags", &self.tags), ("when", &self.when)] {
+            if let Some(value) = value {
+                let values = [
+                    to_sequence(value.clone()),
+                    task.remove(keyword).map(to_sequence).unwrap_or_default(),
+                ]
+                .concat();
+                task.insert(keyword.into(), YamlValue::Sequence(values));
+            }
+        }
+    }
+}
+
+impl Params {
+    /// Parse params from a file path or a mapping.
+    pub fn from_yaml(params: YamlValue) -> Result<Self> {
+        match params {
+            YamlValue::String(file) => Ok(Params {
+                file,
+                vars: None,
+                apply: Apply::default(),
+            }),
+            YamlValue::Mapping(_) => parse_params(params),
+            _ => Err(Error::new(
+                ErrorKind::InvalidData,
+                "file parameter must be a string",
+            )),
+        }
+    }
+
+    /// Return file path resolved against the directory of the including file.
+    pub fn get_path(&self, including_path: &Path) -> PathBuf {
+        including_path
+            .parent()
+            .unwrap_or(Path::new(""))
+            .join(&self.file)
+    }
+
+    /// Add `apply` keywords to the tasks. Keywords are added to the `apply` of `import` tasks,
+    /// so they are added to the imported tasks.
+    pub fn apply_to(&self, tasks: &mut [YamlValue]) {
+        for task in tasks.iter_mut().filter_map(YamlValue::as_mapping_mut) {
+            match get_import_mapping(task) {
+                Some(import) => {
+                    if let YamlValue::Mapping(apply) = import
+                        .entry("apply".into())
+                        .or_insert(YamlValue::Mapping(Mapping::new()))
+                    {
+                        self.apply.apply_to(apply)
+                    }
+                }
+                None => self.apply.apply_to(task),
+            }
+        }
+    }
+
+    /// Add `vars` to the `vars` of the tasks, keeping the ones defined in the task.
+    pub fn add_vars_to(&self, tasks: &mut [YamlValue]) {
+        let Some(vars) = &self.vars else {
+            return;
+        };
+        for task in tasks.iter_mut().filter_map(YamlValue::as_mapping_mut) {
+            let task = match get_import_mapping(task) {
+                Some(import) => import,
+                None => task,
+            };
+            if let YamlValue::Mapping(task_vars) = task
+                .entry("vars".into())
+                .or_insert(YamlValue::Mapping(Mapping::new()))
+            {
+                vars.iter().for_each(|(key, value)| {
+                    task_vars.entry(key.clone()).or_insert(value.clone());
+                })
+            }
+        }
+    }
+}
+
+/// Return params of the task if it is an `import` task, as a mapping.
+fn get_import_mapping(task: &mut Mapping) -> Option<&mut Mapping> {
+    let import = task.get_mut("import")?;
+    if let YamlValue::String(file) = import {
+        let mut params = Mapping::new();
+        params.insert("file".into(), YamlValue::String(file.clone()));
+        *import = YamlValue::Mapping(params);
+    }
+    import.as_mapping_mut()
+}
 
 #[derive(Debug)]
 pub struct Include;
@@ -51,6 +223,15 @@ impl Module for Include {
         "include"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            // included tasks are skipped or executed in check mode depending on their modules
+            check_mode: CheckModeSupport::Full,
+            r#become: false,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         global_params: &GlobalParams,
@@ -58,31 +239,25 @@ impl Module for Include {
         vars: &Value,
         _check_mode: bool,
     ) -> Result<(ModuleResult, Option<Value>)> {
-        match params {
-            YamlValue::String(script_file) => {
-                let script_path = Path::new(&script_file);
+        let params = Params::from_yaml(params)?;
+        let builtins = Builtins::deserialize(vars.get_attr("rash")?)?;
+        let script_path = params.get_path(builtins.get_path());
 
-                trace!("reading tasks from: {script_path:?}");
+        let tasks = parse_included_file(&script_path, &params, global_params)?;
+        let include_builtins = builtins.update(&script_path)?;
+        let include_vars = merge_option(
+            context! {rash => &include_builtins, ..vars.clone()},
+            params.vars.as_ref().map(Value::from_serialize),
+        );
 
-                let main_file = read_to_string(script_path).map_err(|e| {
-                    Error::new(ErrorKind::InvalidData, format!("Error reading file: {e:?}"))
-                })?;
+        trace!("Vars: {include_vars}");
+        Context::new(tasks, include_vars, None).exec()?;
 
-                let tasks = parse_file(&main_file, global_params)?;
-                let builtins = Builtins::deserialize(vars.get_attr("rash")?)?;
-                let include_builtins = builtins.update(script_path)?;
-                let include_vars = context! {rash => &include_builtins, ..vars.clone()};
-
-                trace!("Vars: {include_vars}");
-                Context::new(tasks, include_vars.clone(), None).exec()?;
+        Ok((ModuleResult::new(false, None, None), None))
+    }
 
-                Ok((ModuleResult::new(false, None, None), None))
-            }
-            _ => Err(Error::new(
-                ErrorKind::InvalidData,
-                "file parameter must be a string",
-            )),
-        }
+    fn force_string_on_params(&self) -> bool {
+        false
     }
 
     #[cfg(feature = "docs")]
@@ -90,3 +265,182 @@ impl Module for Include {
         None
     }
 }
+
+impl ValidateParams for Include {
+    fn validate_params(&self, params: YamlValue) -> Result<()> {
+        Params::from_yaml(params).map(|_| ())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_tasks(yaml: &str) -> Vec<YamlValue> {
+        serde_norway::from_str(yaml).unwrap()
+    }
+
+    #[test]
+    fn test_params_from_yaml() {
+        let params = Params::from_yaml(YamlValue::String("foo.rh".to_owned())).unwrap();
+        assert_eq!(params.file, "foo.rh");
+        assert_eq!(params.apply, Apply::default());
+
+        let params = Params::from_yaml(
+            serde_norway::from_str(
+                r#"
+            file: foo.rh
+            vars:
+              foo: boo
+            apply:
+              become: true
+            "#,
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(params.file, "foo.rh");
+        assert_eq!(params.apply.r#become, Some(true));
+
+        let error = Params::from_yaml(serde_norway::from_str("[foo.rh]").unwrap()).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+
+        let error = Params::from_yaml(
+            serde_norway::from_str(
+                r#"
+            file: foo.rh
+            apply:
+              register: foo
+            "#,
+            )
+            .unwrap(),
+        )
+        .unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_params_get_path() {
+        let params = Params::from_yaml(YamlValue::String("lib/foo.rh".to_owned())).unwrap();
+        assert_eq!(
+            params.get_path(Path::new("/scripts/main.rh")),
+            PathBuf::from("/scripts/lib/foo.rh")
+        );
+        assert_eq!(
+            params.get_path(Path::new("main.rh")),
+            PathBuf::from("lib/foo.rh")
+        );
+
+        let params = Params::from_yaml(YamlValue::String("/lib/foo.rh".to_owned())).unwrap();
+        assert_eq!(
+            params.get_path(Path::new("/scripts/main.rh")),
+            PathBuf::from("/lib/foo.rh")
+        );
+    }
+
+    #[test]
+    fn test_params_apply_to() {
+        let params = Params::from_yaml(
+            serde_norway::from_str(
+                r#"
+            file: foo.rh
+            apply:
+              become: true
+              environment:
+                FOO: boo
+                BAR: bar
+              tags: apply
+              when: foo is defined
+            "#,
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        let mut tasks = parse_tasks(
+            r#"
+            - command: ls
+              become: false
+              environment:
+                FOO: task
+              tags:
+                - task
+              when: bar
+            - import: bar.rh
+            "#,
+        );
+        params.apply_to(&mut tasks);
+
+        assert_eq!(
+            tasks,
+            parse_tasks(
+                r#"
+            - command: ls
+              become: false
+              environment:
+                FOO: task
+                BAR: bar
+              tags:
+                - apply
+                - task
+              when:
+                - foo is defined
+                - bar
+            - import:
+                file: bar.rh
+                apply:
+                  become: true
+                  environment:
+                    FOO: boo
+                    BAR: bar
+                  tags:
+                    - apply
+                  when:
+                    - foo is defined
+            "#,
+            )
+        );
+    }
+
+    #[test]
+    fn test_params_add_vars_to() {
+        let params = Params::from_yaml(
+            serde_norway::from_str(
+                r#"
+            file: foo.rh
+            vars:
+              foo: boo
+              bar: bar
+            "#,
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        let mut tasks = parse_tasks(
+            r#"
+            - command: ls
+              vars:
+                foo: task
+            - import:
+                file: bar.rh
+            "#,
+        );
+        params.add_vars_to(&mut tasks);
+
+        assert_eq!(
+            tasks,
+            parse_tasks(
+                r#"
+            - command: ls
+              vars:
+                foo: task
+                bar: bar
+            - import:
+                file: bar.rh
+                vars:
+                  foo: boo
+                  bar: bar
+            "#,
+            )
+        );
+    }
+}
@@ -2,13 +2,6 @@
 /// # incus
 ///
 /// Manage Incus/LXD containers and virtual machines.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -67,7 +60,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger::diff;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Capabilities, Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -160,6 +153,13 @@ impl Module for Incus {
         "incus"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            diff: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -183,6 +183,10 @@ impl Module for Incus {
     }
 }
 
+impl ModuleParams for Incus {
+    type Params = Params;
+}
+
 struct IncusClient {
     check_mode: bool,
 }
@@ -609,6 +613,7 @@ fn incus_instance(params: Params, check_mode: bool) -> Result<ModuleResult> {
         changed,
         output: final_output,
         extra: Some(value::to_value(extra)?),
+        ..Default::default()
     })
 }
 
@@ -2,13 +2,6 @@
 /// # ini_file
 ///
 /// Manage settings in INI-style configuration files.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -44,7 +37,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger::diff;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Capabilities, Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -311,6 +304,7 @@ pub fn ini_file(params: Params, check_mode: bool) -> Result<ModuleResult> {
         changed,
         output: Some(params.path),
         extra: None,
+        ..Default::default()
     })
 }
 
@@ -322,6 +316,13 @@ impl Module for IniFile {
         "ini_file"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            diff: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -338,6 +339,10 @@ impl Module for IniFile {
     }
 }
 
+impl ModuleParams for IniFile {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
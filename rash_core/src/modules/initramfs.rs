@@ -5,13 +5,6 @@
 ///
 /// This module works with initramfs-tools (Debian/Ubuntu) to configure
 /// and manage initramfs images, including modules, hooks, and configuration.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -63,7 +56,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger::diff;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Capabilities, Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -516,6 +509,13 @@ impl Module for Initramfs {
         "initramfs"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            diff: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -532,6 +532,10 @@ impl Module for Initramfs {
     }
 }
 
+impl ModuleParams for Initramfs {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2,13 +2,6 @@
 /// # interfaces_file
 ///
 /// Manage network interface configuration in /etc/network/interfaces.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -46,7 +39,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger::diff;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Capabilities, Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -510,6 +503,7 @@ pub fn interfaces_file(params: Params, check_mode: bool) -> Result<ModuleResult>
         changed,
         output: Some(path_str),
         extra: None,
+        ..Default::default()
     })
 }
 
@@ -521,6 +515,13 @@ impl Module for InterfacesFile {
         "interfaces_file"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            diff: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -540,6 +541,10 @@ impl Module for InterfacesFile {
     }
 }
 
+impl ModuleParams for InterfacesFile {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
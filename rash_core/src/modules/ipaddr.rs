@@ -2,13 +2,6 @@
 /// # ipaddr
 ///
 /// Manage IP addresses on network interfaces.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -40,7 +33,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger::diff;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Capabilities, Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -334,6 +327,13 @@ impl Module for Ipaddr {
         "ipaddr"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            diff: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -350,6 +350,10 @@ impl Module for Ipaddr {
     }
 }
 
+impl ModuleParams for Ipaddr {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
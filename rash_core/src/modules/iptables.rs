@@ -2,13 +2,6 @@
 /// # iptables
 ///
 /// Manage iptables firewall rules.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -72,7 +65,7 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -611,6 +604,10 @@ impl Module for Iptables {
     }
 }
 
+impl ModuleParams for Iptables {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
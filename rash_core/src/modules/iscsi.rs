@@ -6,13 +6,6 @@
 /// This module manages iSCSI (Internet Small Computer System Interface) storage
 /// connections. It supports target discovery, login/logout, CHAP authentication,
 /// and session management.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -53,7 +46,7 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -140,6 +133,10 @@ impl Module for Iscsi {
     }
 }
 
+impl ModuleParams for Iscsi {
+    type Params = Params;
+}
+
 fn parse_portal(portal: &str) -> (String, u16) {
     if let Some(idx) = portal.rfind(':') {
         let host = &portal[..idx];
@@ -2,15 +2,6 @@
 /// # iso_extract
 ///
 /// Extract contents from ISO files.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// diff_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -39,7 +30,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger::diff;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Capabilities, Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -225,6 +216,7 @@ fn run_iso_extract(params: Params, check_mode: bool) -> Result<ModuleResult> {
                 dest_path.display()
             )),
             extra: None,
+            ..Default::default()
         });
     }
 
@@ -326,6 +318,7 @@ fn run_iso_extract(params: Params, check_mode: bool) -> Result<ModuleResult> {
                 ),
             ]),
         )),
+        ..Default::default()
     })
 }
 
@@ -356,6 +349,13 @@ impl Module for IsoExtract {
         "iso_extract"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            diff: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -372,6 +372,10 @@ impl Module for IsoExtract {
     }
 }
 
+impl ModuleParams for IsoExtract {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
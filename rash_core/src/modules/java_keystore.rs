@@ -2,13 +2,6 @@
 /// # java_keystore
 ///
 /// Manage Java keystores for SSL/TLS certificate management.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -65,7 +58,7 @@
 /// ```
 /// ANCHOR_END: examples
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -372,6 +365,7 @@ pub fn java_keystore(params: Params, check_mode: bool) -> Result<ModuleResult> {
                         changed: true,
                         output: Some(format!("Would create keystore at {}", params.path)),
                         extra: None,
+                        ..Default::default()
                     });
                 }
 
@@ -398,6 +392,7 @@ pub fn java_keystore(params: Params, check_mode: bool) -> Result<ModuleResult> {
                             alias, params.path
                         )),
                         extra: Some(value::to_value(extra)?),
+                        ..Default::default()
                     });
                 }
 
@@ -409,6 +404,7 @@ pub fn java_keystore(params: Params, check_mode: bool) -> Result<ModuleResult> {
                             alias, params.path
                         )),
                         extra: None,
+                        ..Default::default()
                     });
                 }
 
@@ -427,6 +423,7 @@ pub fn java_keystore(params: Params, check_mode: bool) -> Result<ModuleResult> {
                     changed: true,
                     output: Some(format!("{} at {}", action, params.path)),
                     extra: None,
+                    ..Default::default()
                 });
             }
 
@@ -462,6 +459,7 @@ pub fn java_keystore(params: Params, check_mode: bool) -> Result<ModuleResult> {
                         pkcs12_path, alias, params.path
                     )),
                     extra: Some(value::to_value(extra)?),
+                    ..Default::default()
                 });
             }
 
@@ -508,6 +506,7 @@ pub fn java_keystore(params: Params, check_mode: bool) -> Result<ModuleResult> {
                         cert_path, alias, params.path
                     )),
                     extra: Some(value::to_value(extra)?),
+                    ..Default::default()
                 });
             }
 
@@ -519,6 +518,7 @@ pub fn java_keystore(params: Params, check_mode: bool) -> Result<ModuleResult> {
                 changed: true,
                 output: Some(format!("Keystore {} is present", params.path)),
                 extra: Some(value::to_value(extra)?),
+                ..Default::default()
             })
         }
         State::Absent => {
@@ -527,6 +527,7 @@ pub fn java_keystore(params: Params, check_mode: bool) -> Result<ModuleResult> {
                     changed: false,
                     output: Some(format!("Keystore {} does not exist", params.path)),
                     extra: None,
+                    ..Default::default()
                 });
             }
 
@@ -545,6 +546,7 @@ pub fn java_keystore(params: Params, check_mode: bool) -> Result<ModuleResult> {
                         alias, params.path
                     )),
                     extra: None,
+                    ..Default::default()
                 });
             }
 
@@ -556,6 +558,7 @@ pub fn java_keystore(params: Params, check_mode: bool) -> Result<ModuleResult> {
                         alias, params.path
                     )),
                     extra: None,
+                    ..Default::default()
                 });
             }
 
@@ -573,6 +576,7 @@ pub fn java_keystore(params: Params, check_mode: bool) -> Result<ModuleResult> {
                     alias, params.path
                 )),
                 extra: Some(value::to_value(extra)?),
+                ..Default::default()
             })
         }
     }
@@ -605,6 +609,10 @@ impl Module for JavaKeystore {
     }
 }
 
+impl ModuleParams for JavaKeystore {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
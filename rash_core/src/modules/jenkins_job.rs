@@ -2,13 +2,6 @@
 /// # jenkins_job
 ///
 /// Manage Jenkins jobs and builds.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -69,7 +62,7 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -339,6 +332,7 @@ pub fn jenkins_job(params: Params, check_mode: bool) -> Result<ModuleResult> {
                         changed: true,
                         output: Some(format!("Would create Jenkins job '{}'", params.name)),
                         extra: None,
+                        ..Default::default()
                     });
                 }
 
@@ -375,6 +369,7 @@ pub fn jenkins_job(params: Params, check_mode: bool) -> Result<ModuleResult> {
                     changed: true,
                     output: Some(output),
                     extra: Some(value::to_value(extra)?),
+                    ..Default::default()
                 });
             }
 
@@ -387,6 +382,7 @@ pub fn jenkins_job(params: Params, check_mode: bool) -> Result<ModuleResult> {
                             params.name
                         )),
                         extra: None,
+                        ..Default::default()
                     });
                 }
 
@@ -410,6 +406,7 @@ pub fn jenkins_job(params: Params, check_mode: bool) -> Result<ModuleResult> {
                     changed: true,
                     output: Some(format!("Triggered build for Jenkins job '{}'", params.name)),
                     extra: Some(value::to_value(extra)?),
+                    ..Default::default()
                 });
             }
 
@@ -424,6 +421,7 @@ pub fn jenkins_job(params: Params, check_mode: bool) -> Result<ModuleResult> {
                 changed: false,
                 output: Some(format!("Jenkins job '{}' already exists", params.name)),
                 extra: Some(value::to_value(extra)?),
+                ..Default::default()
             })
         }
         State::Absent => {
@@ -435,6 +433,7 @@ pub fn jenkins_job(params: Params, check_mode: bool) -> Result<ModuleResult> {
                     changed: false,
                     output: Some(format!("Jenkins job '{}' does not exist", params.name)),
                     extra: None,
+                    ..Default::default()
                 });
             }
 
@@ -443,6 +442,7 @@ pub fn jenkins_job(params: Params, check_mode: bool) -> Result<ModuleResult> {
                     changed: true,
                     output: Some(format!("Would delete Jenkins job '{}'", params.name)),
                     extra: None,
+                    ..Default::default()
                 });
             }
 
@@ -459,6 +459,7 @@ pub fn jenkins_job(params: Params, check_mode: bool) -> Result<ModuleResult> {
                 changed: true,
                 output: Some(format!("Deleted Jenkins job '{}'", params.name)),
                 extra: Some(value::to_value(extra)?),
+                ..Default::default()
             })
         }
     }
@@ -488,6 +489,10 @@ impl Module for JenkinsJob {
     }
 }
 
+impl ModuleParams for JenkinsJob {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
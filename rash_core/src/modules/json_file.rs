@@ -2,13 +2,6 @@
 /// # json_file
 ///
 /// Manage settings in JSON files.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -39,7 +32,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger::diff;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Capabilities, Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -281,6 +274,7 @@ pub fn json_file(params: Params, check_mode: bool) -> Result<ModuleResult> {
         changed,
         output: Some(path_str),
         extra: None,
+        ..Default::default()
     })
 }
 
@@ -292,6 +286,13 @@ impl Module for JsonFile {
         "json_file"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            diff: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -308,6 +309,10 @@ impl Module for JsonFile {
     }
 }
 
+impl ModuleParams for JsonFile {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
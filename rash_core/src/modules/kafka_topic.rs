@@ -6,13 +6,6 @@
 /// Create and delete Kafka topics with configurable partitions, replication
 /// factor, and topic-level configuration. Useful for streaming infrastructure
 /// management, event-driven architectures, and data pipeline automation.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -43,7 +36,7 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -634,6 +627,10 @@ impl Module for KafkaTopic {
     }
 }
 
+impl ModuleParams for KafkaTopic {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
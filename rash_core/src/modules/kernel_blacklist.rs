@@ -2,13 +2,6 @@
 /// # kernel_blacklist
 ///
 /// Manage kernel module blacklist entries.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -42,7 +35,7 @@
 use crate::context::GlobalParams;
 use crate::error::Result;
 use crate::logger::diff;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Capabilities, Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -183,6 +176,13 @@ impl Module for KernelBlacklist {
         "kernel_blacklist"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            diff: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -202,6 +202,10 @@ impl Module for KernelBlacklist {
     }
 }
 
+impl ModuleParams for KernelBlacklist {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
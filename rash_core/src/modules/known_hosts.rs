@@ -2,13 +2,6 @@
 /// # known_hosts
 ///
 /// Add or remove SSH known hosts entries.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -34,7 +27,7 @@
 /// ANCHOR_END: examples
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger::diff;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Capabilities, Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -370,6 +363,7 @@ pub fn known_hosts(params: Params, check_mode: bool) -> Result<ModuleResult> {
                 changed,
                 output: Some(known_hosts_path.to_string_lossy().to_string()),
                 extra: None,
+                ..Default::default()
             })
         }
         State::Absent => {
@@ -386,6 +380,7 @@ pub fn known_hosts(params: Params, check_mode: bool) -> Result<ModuleResult> {
                     changed: false,
                     output: Some(known_hosts_path.to_string_lossy().to_string()),
                     extra: None,
+                    ..Default::default()
                 });
             };
 
@@ -446,6 +441,7 @@ pub fn known_hosts(params: Params, check_mode: bool) -> Result<ModuleResult> {
                 changed,
                 output: Some(known_hosts_path.to_string_lossy().to_string()),
                 extra: None,
+                ..Default::default()
             })
         }
     }
@@ -459,6 +455,13 @@ impl Module for KnownHosts {
         "known_hosts"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            diff: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &crate::context::GlobalParams,
@@ -478,6 +481,10 @@ impl Module for KnownHosts {
     }
 }
 
+impl ModuleParams for KnownHosts {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
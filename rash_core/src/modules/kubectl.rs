@@ -2,13 +2,6 @@
 /// # kubectl
 ///
 /// Manage Kubernetes resources using kubectl.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -78,7 +71,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger::diff;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Capabilities, Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -708,6 +701,13 @@ impl Module for Kubectl {
         "kubectl"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            diff: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -728,6 +728,10 @@ impl Module for Kubectl {
     }
 }
 
+impl ModuleParams for Kubectl {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
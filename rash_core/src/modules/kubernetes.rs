@@ -4,13 +4,6 @@
 /// Manage Kubernetes resources declaratively using inline definitions or
 /// manifest files. This module uses `kubectl` under the hood and supports
 /// server-side apply, resource validation, and idempotent operations.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -116,7 +109,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger::diff;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Capabilities, Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -771,6 +764,13 @@ impl Module for Kubernetes {
         "kubernetes"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            diff: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -794,6 +794,10 @@ impl Module for Kubernetes {
     }
 }
 
+impl ModuleParams for Kubernetes {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2,13 +2,6 @@
 /// # lbu
 ///
 /// Manage Alpine Local Backup (lbu) for diskless Alpine systems.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -50,7 +43,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Module, ModuleParams, ModuleResult, parse_params};
 use crate::utils::default_false;
 
 #[cfg(feature = "docs")]
@@ -162,6 +155,10 @@ impl Module for Lbu {
     }
 }
 
+impl ModuleParams for Lbu {
+    type Params = Params;
+}
+
 struct LbuClient {
     executable: PathBuf,
     extra_args: Option<String>,
@@ -369,6 +366,7 @@ fn lbu(params: Params, check_mode: bool) -> Result<ModuleResult> {
         changed,
         output: None,
         extra: Some(value::to_value(json!({"actions": actions}))?),
+        ..Default::default()
     })
 }
 
@@ -2,13 +2,6 @@
 /// # libvirt
 ///
 /// Manage Libvirt virtual machines (domains).
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -86,7 +79,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger::diff;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Capabilities, Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -215,6 +208,13 @@ impl Module for Libvirt {
         "libvirt"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            diff: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -235,6 +235,10 @@ impl Module for Libvirt {
     }
 }
 
+impl ModuleParams for Libvirt {
+    type Params = Params;
+}
+
 struct LibvirtClient {
     uri: String,
     check_mode: bool,
@@ -711,6 +715,7 @@ fn libvirt(params: Params, check_mode: bool) -> Result<ModuleResult> {
         changed,
         output: final_output,
         extra: Some(value::to_value(extra)?),
+        ..Default::default()
     })
 }
 
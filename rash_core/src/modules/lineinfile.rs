@@ -2,13 +2,6 @@
 /// # lineinfile
 ///
 /// Ensure a particular line is in a file, or replace an existing line using a back-referenced regular expression.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -33,8 +26,7 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::logger::diff;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Capabilities, Diff, Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -116,6 +108,7 @@ pub fn lineinfile(params: Params, check_mode: bool) -> Result<ModuleResult> {
                 changed: false,
                 output: Some(params.path),
                 extra: None,
+                ..Default::default()
             });
         }
         String::new()
@@ -181,6 +174,8 @@ pub fn lineinfile(params: Params, check_mode: bool) -> Result<ModuleResult> {
         }
     }
 
+    let mut result = ModuleResult::new(changed, None, Some(params.path.clone()));
+
     if changed {
         let new_content = if lines.is_empty() {
             String::new()
@@ -188,8 +183,9 @@ pub fn lineinfile(params: Params, check_mode: bool) -> Result<ModuleResult> {
             format!("{}\n", lines.join("\n"))
         };
 
-        // Show diff
-        diff(&original_content, &new_content);
+        result = result.with_diff(
+            Diff::new(&original_content, &new_content).with_headers(&params.path, &params.path),
+        );
 
         if !check_mode {
             // Create parent directories if they don't exist
@@ -209,11 +205,7 @@ pub fn lineinfile(params: Params, check_mode: bool) -> Result<ModuleResult> {
         }
     }
 
-    Ok(ModuleResult {
-        changed,
-        output: Some(params.path),
-        extra: None,
-    })
+    Ok(result)
 }
 
 #[derive(Debug)]
@@ -224,6 +216,13 @@ impl Module for Lineinfile {
         "lineinfile"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            diff: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -243,6 +242,10 @@ impl Module for Lineinfile {
     }
 }
 
+impl ModuleParams for Lineinfile {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2,13 +2,6 @@
 /// # locale
 ///
 /// Manage system locale settings.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -38,7 +31,7 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -134,6 +127,10 @@ impl Module for Locale {
     }
 }
 
+impl ModuleParams for Locale {
+    type Params = Params;
+}
+
 fn locale_exists(name: &str) -> bool {
     let locale_path = Path::new(LOCALE_DEF_PATH).join(name);
     locale_path.exists() || locale_available_via_locale_a(name)
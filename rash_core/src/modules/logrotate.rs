@@ -2,13 +2,6 @@
 /// # logrotate
 ///
 /// Manage log rotation configurations.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -49,7 +42,7 @@
 use crate::context::GlobalParams;
 use crate::error::Result;
 use crate::logger::diff;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Capabilities, Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -373,6 +366,7 @@ pub fn logrotate(params: Params, check_mode: bool) -> Result<ModuleResult> {
         changed,
         output: Some(output),
         extra: None,
+        ..Default::default()
     })
 }
 
@@ -384,6 +378,13 @@ impl Module for Logrotate {
         "logrotate"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            diff: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _global_params: &GlobalParams,
@@ -400,6 +401,10 @@ impl Module for Logrotate {
     }
 }
 
+impl ModuleParams for Logrotate {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2,13 +2,6 @@
 /// # luks
 ///
 /// Manage LUKS (Linux Unified Key Setup) encrypted volumes.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -56,7 +49,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger::diff;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Capabilities, Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -121,6 +114,13 @@ impl Module for Luks {
         "luks"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            diff: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -144,6 +144,10 @@ impl Module for Luks {
     }
 }
 
+impl ModuleParams for Luks {
+    type Params = Params;
+}
+
 struct LuksClient {
     check_mode: bool,
 }
@@ -486,6 +490,7 @@ fn luks_module(params: Params, check_mode: bool) -> Result<ModuleResult> {
         changed: result.changed,
         output: None,
         extra: Some(value::to_value(extra)?),
+        ..Default::default()
     })
 }
 
@@ -2,13 +2,6 @@
 /// # lvg
 ///
 /// Manage LVM volume groups.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -38,7 +31,7 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -115,6 +108,10 @@ impl Module for Lvg {
     }
 }
 
+impl ModuleParams for Lvg {
+    type Params = Params;
+}
+
 struct LvgClient {
     check_mode: bool,
 }
@@ -2,13 +2,6 @@
 /// # lvm_snapshot
 ///
 /// Manage LVM snapshots for backup and rollback operations.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -32,7 +25,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger::diff;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Capabilities, Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -86,6 +79,13 @@ impl Module for LvmSnapshot {
         "lvm_snapshot"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            diff: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -109,6 +109,10 @@ impl Module for LvmSnapshot {
     }
 }
 
+impl ModuleParams for LvmSnapshot {
+    type Params = Params;
+}
+
 struct LvmSnapshotClient {
     check_mode: bool,
 }
@@ -321,6 +325,7 @@ fn lvm_snapshot_module(params: Params, check_mode: bool) -> Result<ModuleResult>
         changed: result.changed,
         output: result.output,
         extra: Some(value::to_value(extra)?),
+        ..Default::default()
     })
 }
 
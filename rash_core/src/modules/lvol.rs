@@ -2,13 +2,6 @@
 /// # lvol
 ///
 /// Manage LVM logical volumes.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -51,7 +44,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger::diff;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Capabilities, Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -116,6 +109,13 @@ impl Module for Lvol {
         "lvol"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            diff: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -139,6 +139,10 @@ impl Module for Lvol {
     }
 }
 
+impl ModuleParams for Lvol {
+    type Params = Params;
+}
+
 struct LvolClient {
     check_mode: bool,
 }
@@ -451,6 +455,7 @@ fn lvol_module(params: Params, check_mode: bool) -> Result<ModuleResult> {
         changed: result.changed,
         output: result.output,
         extra: Some(value::to_value(extra)?),
+        ..Default::default()
     })
 }
 
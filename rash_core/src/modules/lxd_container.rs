@@ -2,13 +2,6 @@
 /// # lxd_container
 ///
 /// Manage LXD containers.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -76,7 +69,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger::diff;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Capabilities, Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -181,6 +174,13 @@ impl Module for LxdContainer {
         "lxd_container"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            diff: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -204,6 +204,10 @@ impl Module for LxdContainer {
     }
 }
 
+impl ModuleParams for LxdContainer {
+    type Params = Params;
+}
+
 struct LxdClient {
     check_mode: bool,
     target: Option<String>,
@@ -684,6 +688,7 @@ fn lxd_container(params: Params, check_mode: bool) -> Result<ModuleResult> {
         changed,
         output: final_output,
         extra: Some(value::to_value(extra)?),
+        ..Default::default()
     })
 }
 
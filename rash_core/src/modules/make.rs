@@ -2,13 +2,6 @@
 /// # make
 ///
 /// Run make commands for build automation.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: none
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -42,7 +35,9 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{
+    Capabilities, CheckModeSupport, Module, ModuleParams, ModuleResult, parse_params,
+};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -82,6 +77,13 @@ impl Module for Make {
         "make"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            check_mode: CheckModeSupport::None,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -143,6 +145,7 @@ impl Module for Make {
                 changed: true,
                 output: module_output,
                 extra,
+                ..Default::default()
             },
             None,
         ))
@@ -154,6 +157,10 @@ impl Module for Make {
     }
 }
 
+impl ModuleParams for Make {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2,13 +2,6 @@
 /// # mdadm
 ///
 /// Manage Linux software RAID arrays using mdadm.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -56,7 +49,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger::diff;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Capabilities, Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -135,6 +128,13 @@ impl Module for Mdadm {
         "mdadm"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            diff: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -158,6 +158,10 @@ impl Module for Mdadm {
     }
 }
 
+impl ModuleParams for Mdadm {
+    type Params = Params;
+}
+
 struct MdadmClient {
     check_mode: bool,
 }
@@ -584,6 +588,7 @@ fn mdadm_module(params: Params, check_mode: bool) -> Result<ModuleResult> {
         changed,
         output: None,
         extra: Some(value::to_value(extra)?),
+        ..Default::default()
     })
 }
 
@@ -5,13 +5,6 @@
 ///
 /// This module provides special actions that control the execution flow,
 /// such as flushing handlers.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: parameters
 /// | Parameter | Required | Type   | Values          | Description                    |
@@ -31,7 +24,7 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult};
+use crate::modules::{Capabilities, Module, ModuleResult, ValidateParams};
 
 use minijinja::Value;
 #[cfg(feature = "docs")]
@@ -58,6 +51,13 @@ impl Module for Meta {
         "meta"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            r#become: false,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _global_params: &GlobalParams,
@@ -91,6 +91,8 @@ impl Module for Meta {
     }
 }
 
+impl ValidateParams for Meta {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
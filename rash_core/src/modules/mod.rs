@@ -60,6 +60,7 @@ mod filesystem;
 pub mod find;
 mod firewalld;
 mod flatpak;
+mod gather_facts;
 mod gem;
 mod get_url;
 mod git;
@@ -75,7 +76,7 @@ mod helm_info;
 mod homebrew;
 mod hostname;
 mod htpasswd;
-mod include;
+pub mod include;
 mod incus;
 mod ini_file;
 mod initramfs;
@@ -132,6 +133,7 @@ mod opkg;
 mod package;
 mod pacman;
 mod pam_limits;
+mod parallel;
 mod parted;
 mod passwordstore;
 mod patch;
@@ -265,6 +267,7 @@ use crate::modules::filesystem::Filesystem;
 use crate::modules::find::Find;
 use crate::modules::firewalld::Firewalld;
 use crate::modules::flatpak::Flatpak;
+use crate::modules::gather_facts::GatherFacts;
 use crate::modules::gem::Gem;
 use crate::modules::get_url::GetUrl;
 use crate::modules::git::Git;
@@ -337,6 +340,7 @@ use crate::modules::opkg::Opkg;
 use crate::modules::package::Package;
 use crate::modules::pacman::Pacman;
 use crate::modules::pam_limits::PamLimits;
+use crate::modules::parallel::Parallel;
 use crate::modules::parted::Parted;
 use crate::modules::passwordstore::Passwordstore;
 use crate::modules::patch::Patch;
@@ -413,25 +417,120 @@ use std::sync::{LazyLock, RwLock};
 use minijinja::Value;
 #[cfg(feature = "docs")]
 use schemars::Schema;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use serde_norway::Value as YamlValue;
 
-#[derive(Clone, Debug, PartialEq, Serialize)]
+// ANCHOR: module_result
+/// Result of a module execution, registered with `register`.
+///
+/// Besides `changed`, `failed`, `output` and `extra`, standard fields are just registered when
+/// modules set them.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
 pub struct ModuleResult {
     changed: bool,
     output: Option<String>,
     extra: Option<YamlValue>,
+    failed: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rc: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stdout: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stdout_lines: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stderr: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    msg: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    warnings: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    diff: Option<Diff>,
 }
 
+/// Changes made, or that would be made, by a module.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct Diff {
+    pub before: String,
+    pub after: String,
+    /// Usually, the path of the original file.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before_header: Option<String>,
+    /// Usually, the path of the modified file.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after_header: Option<String>,
+}
+// ANCHOR_END: module_result
+
 impl ModuleResult {
     pub fn new(changed: bool, extra: Option<YamlValue>, output: Option<String>) -> Self {
         Self {
             changed,
             extra,
             output,
+            ..Default::default()
+        }
+    }
+
+    /// Result of a task which failed with `ignore_errors`.
+    pub fn failed<T: Into<String>>(msg: T) -> Self {
+        Self {
+            failed: true,
+            msg: Some(msg.into()),
+            ..Default::default()
         }
     }
 
+    /// Override `changed`, e.g. with the result of `changed_when`.
+    pub fn with_changed(mut self, changed: bool) -> Self {
+        self.changed = changed;
+        self
+    }
+
+    /// Override `failed`, e.g. with the result of `failed_when`. Failed results are turned
+    /// into task errors, using `msg` as message.
+    pub fn with_failed(mut self, failed: bool) -> Self {
+        self.failed = failed;
+        self
+    }
+
+    /// Return code of the executed command.
+    pub fn with_rc(mut self, rc: Option<i32>) -> Self {
+        self.rc = rc;
+        self
+    }
+
+    /// Set `stdout` and `stdout_lines` from the output of the executed command.
+    pub fn with_stdout<T: Into<String>>(mut self, stdout: T) -> Self {
+        let stdout = stdout.into();
+        self.stdout_lines = Some(stdout.lines().map(String::from).collect());
+        self.stdout = Some(stdout);
+        self
+    }
+
+    pub fn with_stderr<T: Into<String>>(mut self, stderr: T) -> Self {
+        self.stderr = Some(stderr.into());
+        self
+    }
+
+    /// Human readable description of the result.
+    pub fn with_msg<T: Into<String>>(mut self, msg: T) -> Self {
+        self.msg = Some(msg.into());
+        self
+    }
+
+    /// Add a warning, printed after the result.
+    pub fn with_warning<T: Into<String>>(mut self, warning: T) -> Self {
+        self.warnings.push(warning.into());
+        self
+    }
+
+    /// Changes shown with `--diff`.
+    pub fn with_diff(mut self, diff: Diff) -> Self {
+        self.diff = Some(diff);
+        self
+    }
+
     pub fn get_changed(&self) -> bool {
         self.changed
     }
@@ -443,11 +542,136 @@ impl ModuleResult {
     pub fn get_output(&self) -> Option<String> {
         self.output.clone()
     }
+
+    pub fn is_failed(&self) -> bool {
+        self.failed
+    }
+
+    pub fn get_rc(&self) -> Option<i32> {
+        self.rc
+    }
+
+    pub fn get_stdout(&self) -> Option<&str> {
+        self.stdout.as_deref()
+    }
+
+    pub fn get_stdout_lines(&self) -> Option<&[String]> {
+        self.stdout_lines.as_deref()
+    }
+
+    pub fn get_stderr(&self) -> Option<&str> {
+        self.stderr.as_deref()
+    }
+
+    pub fn get_msg(&self) -> Option<&str> {
+        self.msg.as_deref()
+    }
+
+    pub fn get_warnings(&self) -> &[String] {
+        &self.warnings
+    }
+
+    pub fn get_diff(&self) -> Option<&Diff> {
+        self.diff.as_ref()
+    }
+}
+
+impl Diff {
+    pub fn new<T: Into<String>, U: Into<String>>(before: T, after: U) -> Self {
+        Diff {
+            before: before.into(),
+            after: after.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn with_headers<T: Into<String>, U: Into<String>>(
+        mut self,
+        before_header: T,
+        after_header: U,
+    ) -> Self {
+        self.before_header = Some(before_header.into());
+        self.after_header = Some(after_header.into());
+        self
+    }
+}
+
+/// How a module behaves when executed in check mode.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CheckModeSupport {
+    /// Reports what would change without changing anything.
+    #[default]
+    Full,
+    /// Reports what would change for some params, check the module docs.
+    Partial,
+    /// Runs as usual, because it never changes anything.
+    Always,
+    /// Cannot run without changing things, so it is skipped.
+    None,
+}
+
+impl std::fmt::Display for CheckModeSupport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CheckModeSupport::Full => write!(f, "full"),
+            CheckModeSupport::Partial => write!(f, "partial"),
+            CheckModeSupport::Always => write!(f, "always"),
+            CheckModeSupport::None => write!(f, "none"),
+        }
+    }
+}
+
+/// What a module supports, used by the task engine and to generate the module docs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Capabilities {
+    pub check_mode: CheckModeSupport,
+    /// Shows the changes made, or that would be made, with `--diff`.
+    pub diff: bool,
+    /// Can be executed as another user with `become`. Otherwise, it is executed by the current
+    /// user, passing `become` to the tasks it executes.
+    pub r#become: bool,
+    /// Can be executed in background with `async`.
+    pub r#async: bool,
+}
+
+impl Default for Capabilities {
+    fn default() -> Self {
+        Capabilities {
+            check_mode: CheckModeSupport::default(),
+            diff: false,
+            r#become: true,
+            r#async: false,
+        }
+    }
+}
+
+/// Type of the params of a module, used to validate them without executing the module.
+pub trait ModuleParams {
+    type Params: DeserializeOwned;
+}
+
+/// Check params against the module definition without executing it.
+///
+/// Modules with [`ModuleParams`] implement it parsing their params.
+pub trait ValidateParams {
+    fn validate_params(&self, _params: YamlValue) -> Result<()> {
+        Ok(())
+    }
 }
 
-pub trait Module: Send + Sync + std::fmt::Debug {
+impl<T: ModuleParams> ValidateParams for T {
+    fn validate_params(&self, params: YamlValue) -> Result<()> {
+        parse_params::<T::Params>(params).map(|_| ())
+    }
+}
+
+pub trait Module: ValidateParams + Send + Sync + std::fmt::Debug {
     fn get_name(&self) -> &str;
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities::default()
+    }
+
     fn exec(
         &self,
         global_params: &GlobalParams,
@@ -591,6 +815,10 @@ pub static MODULES: LazyLock<HashMap<&'static str, Box<dyn Module>>> = LazyLock:
         (Find.get_name(), Box::new(Find) as Box<dyn Module>),
         (Firewalld.get_name(), Box::new(Firewalld) as Box<dyn Module>),
         (Flatpak.get_name(), Box::new(Flatpak) as Box<dyn Module>),
+        (
+            GatherFacts.get_name(),
+            Box::new(GatherFacts) as Box<dyn Module>,
+        ),
         (Gem.get_name(), Box::new(Gem) as Box<dyn Module>),
         (GetUrl.get_name(), Box::new(GetUrl) as Box<dyn Module>),
         (Git.get_name(), Box::new(Git) as Box<dyn Module>),
@@ -727,6 +955,7 @@ pub static MODULES: LazyLock<HashMap<&'static str, Box<dyn Module>>> = LazyLock:
             Box::new(OpensslPrivatekey) as Box<dyn Module>,
         ),
         (Pacman.get_name(), Box::new(Pacman) as Box<dyn Module>),
+        (Parallel.get_name(), Box::new(Parallel) as Box<dyn Module>),
         (Parted.get_name(), Box::new(Parted) as Box<dyn Module>),
         (
             Passwordstore.get_name(),
@@ -876,9 +1105,24 @@ impl ModuleRef {
     }
 }
 
-#[inline(always)]
+/// Return true if `module` is a builtin module or a dynamic module found in the search paths.
 pub fn is_module(module: &str) -> bool {
-    MODULES.get(module).is_some()
+    MODULES.contains_key(module)
+        || DYNAMIC_REGISTRY
+            .read()
+            .is_ok_and(|registry| registry.is_dynamic_module(module))
+}
+
+/// Return module by name, loading dynamic modules from the search paths when required.
+pub fn get_module(name: &str) -> Result<&'static dyn Module> {
+    match MODULES.get(name) {
+        Some(module) => Ok(&**module),
+        None => DYNAMIC_REGISTRY
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .load_module(name)
+            .map(|module| module as &'static dyn Module),
+    }
 }
 
 #[inline(always)]
@@ -920,4 +1164,42 @@ mod tests {
             ]
         )
     }
+
+    #[test]
+    fn test_module_result_serialize() {
+        let result = ModuleResult::new(true, None, Some("foo".to_owned()))
+            .with_rc(Some(0))
+            .with_stdout("foo\nboo\n")
+            .with_warning("careful")
+            .with_diff(Diff::new("foo\n", "boo\n").with_headers("/tmp/a", "/tmp/b"));
+
+        assert_eq!(
+            serde_json::to_value(&result).unwrap(),
+            serde_json::json!({
+                "changed": true,
+                "output": "foo",
+                "extra": null,
+                "failed": false,
+                "rc": 0,
+                "stdout": "foo\nboo\n",
+                "stdout_lines": ["foo", "boo"],
+                "warnings": ["careful"],
+                "diff": {
+                    "before": "foo\n",
+                    "after": "boo\n",
+                    "before_header": "/tmp/a",
+                    "after_header": "/tmp/b",
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn test_module_result_failed() {
+        let result = ModuleResult::failed("boom");
+
+        assert!(result.is_failed());
+        assert!(!result.get_changed());
+        assert_eq!(result.get_msg(), Some("boom"));
+    }
 }
@@ -2,13 +2,6 @@
 /// # modprobe
 ///
 /// Load or unload kernel modules.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -40,7 +33,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger::diff;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Capabilities, Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -364,6 +357,13 @@ impl Module for Modprobe {
         "modprobe"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            diff: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -380,6 +380,10 @@ impl Module for Modprobe {
     }
 }
 
+impl ModuleParams for Modprobe {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
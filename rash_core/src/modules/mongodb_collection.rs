@@ -2,13 +2,6 @@
 /// # mongodb_collection
 ///
 /// Manage MongoDB collections.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -63,7 +56,7 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -661,6 +654,10 @@ impl Module for MongodbCollection {
     }
 }
 
+impl ModuleParams for MongodbCollection {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
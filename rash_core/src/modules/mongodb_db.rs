@@ -2,13 +2,6 @@
 /// # mongodb_db
 ///
 /// Manage MongoDB databases.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -58,7 +51,7 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -622,6 +615,10 @@ impl Module for MongodbDb {
     }
 }
 
+impl ModuleParams for MongodbDb {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
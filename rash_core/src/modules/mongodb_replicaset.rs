@@ -2,13 +2,6 @@
 /// # mongodb_replicaset
 ///
 /// Manage MongoDB replica sets.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -64,7 +57,7 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -463,6 +456,10 @@ impl Module for MongodbReplicaset {
     }
 }
 
+impl ModuleParams for MongodbReplicaset {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
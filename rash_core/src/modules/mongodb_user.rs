@@ -2,13 +2,6 @@
 /// # mongodb_user
 ///
 /// Manage MongoDB users and permissions.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -52,7 +45,7 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -270,6 +263,7 @@ fn create_user(params: &Params, check_mode: bool) -> Result<ModuleResult> {
                 params.name, params.database
             )),
             extra: None,
+            ..Default::default()
         });
     }
 
@@ -341,6 +335,7 @@ fn create_user(params: &Params, check_mode: bool) -> Result<ModuleResult> {
             params.name, params.database
         )),
         extra,
+        ..Default::default()
     })
 }
 
@@ -375,6 +370,7 @@ fn update_user(
                 "database": params.database,
                 "roles": existing_roles,
             }))?),
+            ..Default::default()
         });
     }
 
@@ -399,6 +395,7 @@ fn update_user(
                 changes.join(", ")
             )),
             extra: None,
+            ..Default::default()
         });
     }
 
@@ -461,6 +458,7 @@ fn update_user(
         changed: true,
         output: Some(format!("User '{}' updated", params.name)),
         extra,
+        ..Default::default()
     })
 }
 
@@ -473,6 +471,7 @@ fn drop_user(params: &Params, check_mode: bool) -> Result<ModuleResult> {
                 params.name, params.database
             )),
             extra: None,
+            ..Default::default()
         });
     }
 
@@ -503,6 +502,7 @@ fn drop_user(params: &Params, check_mode: bool) -> Result<ModuleResult> {
             params.name, params.database
         )),
         extra: None,
+        ..Default::default()
     })
 }
 
@@ -527,6 +527,7 @@ fn mongodb_user_impl(params: Params, check_mode: bool) -> Result<ModuleResult> {
                         params.name, params.database
                     )),
                     extra: None,
+                    ..Default::default()
                 }),
                 Some(_) => drop_user(&params, check_mode),
             }
@@ -559,6 +560,10 @@ impl Module for MongodbUser {
     }
 }
 
+impl ModuleParams for MongodbUser {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2,13 +2,6 @@
 /// # mount
 ///
 /// Control filesystem mounts.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -49,7 +42,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger::diff;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Capabilities, Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -106,6 +99,13 @@ impl Module for Mount {
         "mount"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            diff: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -129,6 +129,10 @@ impl Module for Mount {
     }
 }
 
+impl ModuleParams for Mount {
+    type Params = Params;
+}
+
 struct MountClient {
     check_mode: bool,
 }
@@ -419,6 +423,7 @@ fn mount_module(params: Params, check_mode: bool) -> Result<ModuleResult> {
         changed: result.changed,
         output: result.output,
         extra: Some(value::to_value(extra)?),
+        ..Default::default()
     })
 }
 
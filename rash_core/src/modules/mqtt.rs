@@ -6,13 +6,6 @@
 /// Publish and subscribe to MQTT topics with support for Quality of Service
 /// levels, message retention, and authentication. Essential for IoT device
 /// scripting, sensor data publishing, home automation, and edge computing.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -52,7 +45,7 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -407,6 +400,10 @@ impl Module for Mqtt {
     }
 }
 
+impl ModuleParams for Mqtt {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2,13 +2,6 @@
 /// # mysql_db
 ///
 /// Manage MySQL/MariaDB databases.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -50,7 +43,7 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -209,6 +202,7 @@ fn create_database(params: &Params, check_mode: bool) -> Result<ModuleResult> {
             changed: true,
             output: Some(format!("Would create database '{}'", params.name)),
             extra: None,
+            ..Default::default()
         });
     }
 
@@ -252,6 +246,7 @@ fn create_database(params: &Params, check_mode: bool) -> Result<ModuleResult> {
         changed: true,
         output: Some(format!("Database '{}' created", params.name)),
         extra,
+        ..Default::default()
     })
 }
 
@@ -261,6 +256,7 @@ fn drop_database(params: &Params, check_mode: bool) -> Result<ModuleResult> {
             changed: true,
             output: Some(format!("Would drop database '{}'", params.name)),
             extra: None,
+            ..Default::default()
         });
     }
 
@@ -288,6 +284,7 @@ fn drop_database(params: &Params, check_mode: bool) -> Result<ModuleResult> {
         changed: true,
         output: Some(format!("Database '{}' dropped", params.name)),
         extra: None,
+        ..Default::default()
     })
 }
 
@@ -305,6 +302,7 @@ fn dump_database(params: &Params, check_mode: bool) -> Result<ModuleResult> {
                 params.name, target
             )),
             extra: None,
+            ..Default::default()
         });
     }
 
@@ -351,6 +349,7 @@ fn dump_database(params: &Params, check_mode: bool) -> Result<ModuleResult> {
         changed: true,
         output: Some(format!("Database '{}' dumped to '{}'", params.name, target)),
         extra,
+        ..Default::default()
     })
 }
 
@@ -377,6 +376,7 @@ fn import_database(params: &Params, check_mode: bool) -> Result<ModuleResult> {
                 params.name, target
             )),
             extra: None,
+            ..Default::default()
         });
     }
 
@@ -440,6 +440,7 @@ fn import_database(params: &Params, check_mode: bool) -> Result<ModuleResult> {
             params.name, target
         )),
         extra,
+        ..Default::default()
     })
 }
 
@@ -474,6 +475,7 @@ fn mysql_db_impl(params: Params, check_mode: bool) -> Result<ModuleResult> {
                             changed: false,
                             output: Some(format!("Database '{}' already exists", params.name)),
                             extra,
+                            ..Default::default()
                         })
                     } else {
                         if check_mode {
@@ -484,6 +486,7 @@ fn mysql_db_impl(params: Params, check_mode: bool) -> Result<ModuleResult> {
                                     params.name
                                 )),
                                 extra: None,
+                                ..Default::default()
                             });
                         }
 
@@ -533,6 +536,7 @@ fn mysql_db_impl(params: Params, check_mode: bool) -> Result<ModuleResult> {
                             changed: true,
                             output: Some(format!("Database '{}' modified", params.name)),
                             extra,
+                            ..Default::default()
                         })
                     }
                 }
@@ -546,6 +550,7 @@ fn mysql_db_impl(params: Params, check_mode: bool) -> Result<ModuleResult> {
                     changed: false,
                     output: Some(format!("Database '{}' does not exist", params.name)),
                     extra: None,
+                    ..Default::default()
                 }),
                 Some(_) => drop_database(&params, check_mode),
             }
@@ -580,6 +585,10 @@ impl Module for MysqlDb {
     }
 }
 
+impl ModuleParams for MysqlDb {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2,13 +2,6 @@
 /// # mysql_query
 ///
 /// Execute SQL queries against MySQL/MariaDB databases.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -45,7 +38,7 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -136,6 +129,7 @@ fn execute_query(params: &Params, check_mode: bool) -> Result<ModuleResult> {
                 truncate_query(&params.query)
             )),
             extra: None,
+            ..Default::default()
         });
     }
 
@@ -194,6 +188,7 @@ fn execute_query(params: &Params, check_mode: bool) -> Result<ModuleResult> {
             stdout
         }),
         extra,
+        ..Default::default()
     })
 }
 
@@ -235,6 +230,10 @@ impl Module for MysqlQuery {
     }
 }
 
+impl ModuleParams for MysqlQuery {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2,13 +2,6 @@
 /// # mysql_replication
 ///
 /// Manage MySQL/MariaDB replication topology.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -48,7 +41,7 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -214,6 +207,7 @@ fn configure_primary(params: &Params, check_mode: bool) -> Result<ModuleResult>
             changed: true,
             output: Some("Would configure server as primary".to_string()),
             extra: None,
+            ..Default::default()
         });
     }
 
@@ -242,6 +236,7 @@ fn configure_primary(params: &Params, check_mode: bool) -> Result<ModuleResult>
         changed: true,
         output: Some("Server configured as primary".to_string()),
         extra,
+        ..Default::default()
     })
 }
 
@@ -273,6 +268,7 @@ fn configure_replica(params: &Params, check_mode: bool) -> Result<ModuleResult>
                 primary_host
             )),
             extra: None,
+            ..Default::default()
         });
     }
 
@@ -347,6 +343,7 @@ fn configure_replica(params: &Params, check_mode: bool) -> Result<ModuleResult>
             primary_host
         )),
         extra,
+        ..Default::default()
     })
 }
 
@@ -356,6 +353,7 @@ fn stop_replication(params: &Params, check_mode: bool) -> Result<ModuleResult> {
             changed: true,
             output: Some("Would stop replication and reset replica configuration".to_string()),
             extra: None,
+            ..Default::default()
         });
     }
 
@@ -373,6 +371,7 @@ fn stop_replication(params: &Params, check_mode: bool) -> Result<ModuleResult> {
         changed: true,
         output: Some("Replication stopped and replica configuration reset".to_string()),
         extra,
+        ..Default::default()
     })
 }
 
@@ -399,6 +398,7 @@ fn get_primary_status(params: &Params) -> Result<ModuleResult> {
             changed: false,
             output: Some("Server is not configured as primary (no binary log)".to_string()),
             extra,
+            ..Default::default()
         });
     }
 
@@ -423,6 +423,7 @@ fn get_primary_status(params: &Params) -> Result<ModuleResult> {
             file, position
         )),
         extra,
+        ..Default::default()
     })
 }
 
@@ -439,6 +440,7 @@ fn mysql_replication_impl(params: Params, check_mode: bool) -> Result<ModuleResu
                             "mode": "primary",
                             "state": "present",
                         }))?),
+                        ..Default::default()
                     })
                 } else {
                     configure_primary(&params, check_mode)
@@ -454,6 +456,7 @@ fn mysql_replication_impl(params: Params, check_mode: bool) -> Result<ModuleResu
                             "mode": "replica",
                             "state": "present",
                         }))?),
+                        ..Default::default()
                     })
                 } else {
                     configure_replica(&params, check_mode)
@@ -470,6 +473,7 @@ fn mysql_replication_impl(params: Params, check_mode: bool) -> Result<ModuleResu
                         "mode": "replica",
                         "state": "absent",
                     }))?),
+                    ..Default::default()
                 })
             } else {
                 stop_replication(&params, check_mode)
@@ -504,6 +508,10 @@ impl Module for MysqlReplication {
     }
 }
 
+impl ModuleParams for MysqlReplication {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2,13 +2,6 @@
 /// # mysql_user
 ///
 /// Manage MySQL/MariaDB database users.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -45,7 +38,7 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -209,6 +202,7 @@ fn create_user(params: &Params, check_mode: bool) -> Result<ModuleResult> {
                 params.name, params.host
             )),
             extra: None,
+            ..Default::default()
         });
     }
 
@@ -246,6 +240,7 @@ fn create_user(params: &Params, check_mode: bool) -> Result<ModuleResult> {
         changed: true,
         output: Some(format!("User '{}'@'{}' created", params.name, params.host)),
         extra: None,
+        ..Default::default()
     })
 }
 
@@ -258,6 +253,7 @@ fn drop_user(params: &Params, check_mode: bool) -> Result<ModuleResult> {
                 params.name, params.host
             )),
             extra: None,
+            ..Default::default()
         });
     }
 
@@ -289,6 +285,7 @@ fn drop_user(params: &Params, check_mode: bool) -> Result<ModuleResult> {
         changed: true,
         output: Some(format!("User '{}'@'{}' dropped", params.name, params.host)),
         extra: None,
+        ..Default::default()
     })
 }
 
@@ -391,6 +388,7 @@ fn update_password(params: &Params, check_mode: bool) -> Result<ModuleResult> {
                 params.name, params.host
             )),
             extra: None,
+            ..Default::default()
         });
     }
 
@@ -431,6 +429,7 @@ fn update_password(params: &Params, check_mode: bool) -> Result<ModuleResult> {
             params.name, params.host
         )),
         extra: None,
+        ..Default::default()
     })
 }
 
@@ -454,6 +453,7 @@ fn mysql_user_impl(params: Params, check_mode: bool) -> Result<ModuleResult> {
                         changed: true,
                         output: result.output,
                         extra,
+                        ..Default::default()
                     })
                 }
                 Some(_) => {
@@ -490,6 +490,7 @@ fn mysql_user_impl(params: Params, check_mode: bool) -> Result<ModuleResult> {
                             changed: true,
                             output: Some(messages.join("; ")),
                             extra,
+                            ..Default::default()
                         })
                     } else {
                         Ok(ModuleResult {
@@ -499,6 +500,7 @@ fn mysql_user_impl(params: Params, check_mode: bool) -> Result<ModuleResult> {
                                 params.name, params.host
                             )),
                             extra,
+                            ..Default::default()
                         })
                     }
                 }
@@ -515,6 +517,7 @@ fn mysql_user_impl(params: Params, check_mode: bool) -> Result<ModuleResult> {
                         params.name, params.host
                     )),
                     extra: None,
+                    ..Default::default()
                 }),
                 Some(_) => drop_user(&params, check_mode),
             }
@@ -547,6 +550,10 @@ impl Module for MysqlUser {
     }
 }
 
+impl ModuleParams for MysqlUser {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
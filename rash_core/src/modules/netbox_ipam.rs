@@ -2,13 +2,6 @@
 /// # netbox_ipam
 ///
 /// Manage IP addresses and prefixes in NetBox IPAM/DCIM system.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -78,7 +71,7 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -493,12 +486,14 @@ fn handle_ip_address(
                     changed: false,
                     output: Some(format!("Found IP address '{address}'")),
                     extra: Some(value::to_value(extra)?),
+                    ..Default::default()
                 })
             }
             None => Ok(ModuleResult {
                 changed: false,
                 output: Some(format!("IP address '{address}' not found")),
                 extra: None,
+                ..Default::default()
             }),
         },
         State::Present => {
@@ -512,6 +507,7 @@ fn handle_ip_address(
                     changed: false,
                     output: Some(format!("IP address '{address}' already exists")),
                     extra: Some(value::to_value(extra)?),
+                    ..Default::default()
                 });
             }
 
@@ -520,6 +516,7 @@ fn handle_ip_address(
                     changed: true,
                     output: Some(format!("Would create IP address '{address}'")),
                     extra: None,
+                    ..Default::default()
                 });
             }
 
@@ -578,6 +575,7 @@ fn handle_ip_address(
                 changed: true,
                 output: Some(format!("Created IP address '{address}'")),
                 extra: Some(value::to_value(extra)?),
+                ..Default::default()
             })
         }
         State::Absent => match existing {
@@ -587,6 +585,7 @@ fn handle_ip_address(
                         changed: true,
                         output: Some(format!("Would delete IP address '{address}'")),
                         extra: None,
+                        ..Default::default()
                     });
                 }
 
@@ -612,12 +611,14 @@ fn handle_ip_address(
                     changed: true,
                     output: Some(format!("Deleted IP address '{address}'")),
                     extra: Some(value::to_value(extra)?),
+                    ..Default::default()
                 })
             }
             None => Ok(ModuleResult {
                 changed: false,
                 output: Some(format!("IP address '{address}' does not exist")),
                 extra: None,
+                ..Default::default()
             }),
         },
     }
@@ -651,12 +652,14 @@ fn handle_prefix(
                     changed: false,
                     output: Some(format!("Found prefix '{prefix}'")),
                     extra: Some(value::to_value(extra)?),
+                    ..Default::default()
                 })
             }
             None => Ok(ModuleResult {
                 changed: false,
                 output: Some(format!("Prefix '{prefix}' not found")),
                 extra: None,
+                ..Default::default()
             }),
         },
         State::Present => {
@@ -670,6 +673,7 @@ fn handle_prefix(
                     changed: false,
                     output: Some(format!("Prefix '{prefix}' already exists")),
                     extra: Some(value::to_value(extra)?),
+                    ..Default::default()
                 });
             }
 
@@ -678,6 +682,7 @@ fn handle_prefix(
                     changed: true,
                     output: Some(format!("Would create prefix '{prefix}'")),
                     extra: None,
+                    ..Default::default()
                 });
             }
 
@@ -736,6 +741,7 @@ fn handle_prefix(
                 changed: true,
                 output: Some(format!("Created prefix '{prefix}'")),
                 extra: Some(value::to_value(extra)?),
+                ..Default::default()
             })
         }
         State::Absent => match existing {
@@ -745,6 +751,7 @@ fn handle_prefix(
                         changed: true,
                         output: Some(format!("Would delete prefix '{prefix}'")),
                         extra: None,
+                        ..Default::default()
                     });
                 }
 
@@ -770,12 +777,14 @@ fn handle_prefix(
                     changed: true,
                     output: Some(format!("Deleted prefix '{prefix}'")),
                     extra: Some(value::to_value(extra)?),
+                    ..Default::default()
                 })
             }
             None => Ok(ModuleResult {
                 changed: false,
                 output: Some(format!("Prefix '{prefix}' does not exist")),
                 extra: None,
+                ..Default::default()
             }),
         },
     }
@@ -806,12 +815,14 @@ fn handle_vlan(
                     changed: false,
                     output: Some(format!("Found VLAN {vlan_id}")),
                     extra: Some(value::to_value(extra)?),
+                    ..Default::default()
                 })
             }
             None => Ok(ModuleResult {
                 changed: false,
                 output: Some(format!("VLAN {vlan_id} not found")),
                 extra: None,
+                ..Default::default()
             }),
         },
         State::Present => {
@@ -825,6 +836,7 @@ fn handle_vlan(
                     changed: false,
                     output: Some(format!("VLAN {vlan_id} already exists")),
                     extra: Some(value::to_value(extra)?),
+                    ..Default::default()
                 });
             }
 
@@ -833,6 +845,7 @@ fn handle_vlan(
                     changed: true,
                     output: Some(format!("Would create VLAN {vlan_id}")),
                     extra: None,
+                    ..Default::default()
                 });
             }
 
@@ -897,6 +910,7 @@ fn handle_vlan(
                 changed: true,
                 output: Some(format!("Created VLAN {vlan_id}")),
                 extra: Some(value::to_value(extra)?),
+                ..Default::default()
             })
         }
         State::Absent => match existing {
@@ -906,6 +920,7 @@ fn handle_vlan(
                         changed: true,
                         output: Some(format!("Would delete VLAN {vlan_id}")),
                         extra: None,
+                        ..Default::default()
                     });
                 }
 
@@ -925,12 +940,14 @@ fn handle_vlan(
                     changed: true,
                     output: Some(format!("Deleted VLAN {vlan_id}")),
                     extra: Some(value::to_value(extra)?),
+                    ..Default::default()
                 })
             }
             None => Ok(ModuleResult {
                 changed: false,
                 output: Some(format!("VLAN {vlan_id} does not exist")),
                 extra: None,
+                ..Default::default()
             }),
         },
     }
@@ -962,12 +979,14 @@ fn handle_vrf(
                     changed: false,
                     output: Some(format!("Found VRF '{vrf_name}'")),
                     extra: Some(value::to_value(extra)?),
+                    ..Default::default()
                 })
             }
             None => Ok(ModuleResult {
                 changed: false,
                 output: Some(format!("VRF '{vrf_name}' not found")),
                 extra: None,
+                ..Default::default()
             }),
         },
         State::Present => {
@@ -981,6 +1000,7 @@ fn handle_vrf(
                     changed: false,
                     output: Some(format!("VRF '{vrf_name}' already exists")),
                     extra: Some(value::to_value(extra)?),
+                    ..Default::default()
                 });
             }
 
@@ -989,6 +1009,7 @@ fn handle_vrf(
                     changed: true,
                     output: Some(format!("Would create VRF '{vrf_name}'")),
                     extra: None,
+                    ..Default::default()
                 });
             }
 
@@ -1051,6 +1072,7 @@ fn handle_vrf(
                 changed: true,
                 output: Some(format!("Created VRF '{vrf_name}'")),
                 extra: Some(value::to_value(extra)?),
+                ..Default::default()
             })
         }
         State::Absent => match existing {
@@ -1060,6 +1082,7 @@ fn handle_vrf(
                         changed: true,
                         output: Some(format!("Would delete VRF '{vrf_name}'")),
                         extra: None,
+                        ..Default::default()
                     });
                 }
 
@@ -1079,12 +1102,14 @@ fn handle_vrf(
                     changed: true,
                     output: Some(format!("Deleted VRF '{vrf_name}'")),
                     extra: Some(value::to_value(extra)?),
+                    ..Default::default()
                 })
             }
             None => Ok(ModuleResult {
                 changed: false,
                 output: Some(format!("VRF '{vrf_name}' does not exist")),
                 extra: None,
+                ..Default::default()
             }),
         },
     }
@@ -1129,6 +1154,10 @@ impl Module for NetboxIpam {
     }
 }
 
+impl ModuleParams for NetboxIpam {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2,13 +2,6 @@
 /// # netplan
 ///
 /// Manage network configuration on Ubuntu systems using Netplan.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -101,7 +94,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger::diff;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Capabilities, Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -206,6 +199,13 @@ impl Module for Netplan {
         "netplan"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            diff: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -222,6 +222,10 @@ impl Module for Netplan {
     }
 }
 
+impl ModuleParams for Netplan {
+    type Params = Params;
+}
+
 fn get_renderer_string(renderer: &Renderer) -> &'static str {
     match renderer {
         Renderer::Networkd => "networkd",
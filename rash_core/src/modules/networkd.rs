@@ -2,13 +2,6 @@
 /// # networkd
 ///
 /// Manage systemd-networkd configuration files (.network, .link, .netdev).
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -78,7 +71,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger::diff;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Capabilities, Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -216,6 +209,13 @@ impl Module for Networkd {
         "networkd"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            diff: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -232,6 +232,10 @@ impl Module for Networkd {
     }
 }
 
+impl ModuleParams for Networkd {
+    type Params = Params;
+}
+
 fn get_config_path(params: &Params) -> PathBuf {
     let dir = params.directory.as_deref().unwrap_or(DEFAULT_NETWORKD_DIR);
     PathBuf::from(format!(
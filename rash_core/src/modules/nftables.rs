@@ -2,13 +2,6 @@
 /// # nftables
 ///
 /// Manage nftables firewall rules.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -93,7 +86,7 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -688,6 +681,10 @@ impl Module for Nftables {
     }
 }
 
+impl ModuleParams for Nftables {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2,13 +2,6 @@
 /// # nginx
 ///
 /// Manage Nginx web server site configurations.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -37,7 +30,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger::diff_files;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Capabilities, Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -242,6 +235,7 @@ fn exec_nginx(params: Params, check_mode: bool) -> Result<ModuleResult> {
         changed,
         output: Some(params.name.clone()),
         extra: None,
+        ..Default::default()
     })
 }
 
@@ -253,6 +247,13 @@ impl Module for Nginx {
         "nginx"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            diff: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -272,6 +273,10 @@ impl Module for Nginx {
     }
 }
 
+impl ModuleParams for Nginx {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2,13 +2,6 @@
 /// # nmcli
 ///
 /// Manage NetworkManager connections using nmcli.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -64,7 +57,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger::diff;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Capabilities, Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -150,6 +143,13 @@ impl Module for Nmcli {
         "nmcli"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            diff: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -166,6 +166,10 @@ impl Module for Nmcli {
     }
 }
 
+impl ModuleParams for Nmcli {
+    type Params = Params;
+}
+
 struct NmcliClient {
     check_mode: bool,
 }
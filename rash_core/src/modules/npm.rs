@@ -2,13 +2,6 @@
 /// # npm
 ///
 /// Manage Node.js packages with npm, Node.js package manager.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -54,7 +47,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Module, ModuleParams, ModuleResult, parse_params};
 use crate::utils::default_false;
 
 #[cfg(feature = "docs")]
@@ -182,6 +175,10 @@ impl Module for Npm {
     }
 }
 
+impl ModuleParams for Npm {
+    type Params = Params;
+}
+
 struct NpmClient {
     executable: PathBuf,
     extra_args: Option<String>,
@@ -429,6 +426,7 @@ fn npm(params: Params, check_mode: bool) -> Result<ModuleResult> {
             "updated_packages": p_to_update,
             "removed_packages": p_to_remove,
         }))?),
+        ..Default::default()
     })
 }
 
@@ -441,6 +439,7 @@ fn install_deps(params: &Params, client: &NpmClient) -> Result<ModuleResult> {
             extra: Some(value::to_value(
                 json!({"path": params.path, "production": params.production, "ci": params.ci}),
             )?),
+            ..Default::default()
         });
     }
 
@@ -452,6 +451,7 @@ fn install_deps(params: &Params, client: &NpmClient) -> Result<ModuleResult> {
         extra: Some(value::to_value(
             json!({"path": params.path, "production": params.production, "ci": params.ci}),
         )?),
+        ..Default::default()
     })
 }
 
@@ -2,13 +2,6 @@
 /// # nsupdate
 ///
 /// Manage DNS records using dynamic DNS updates (RFC 2136).
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -107,7 +100,7 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -579,6 +572,10 @@ impl Module for Nsupdate {
     }
 }
 
+impl ModuleParams for Nsupdate {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
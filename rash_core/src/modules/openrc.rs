@@ -3,13 +3,6 @@
 ///
 /// Control OpenRC services. This module is designed for Alpine Linux and
 /// other OpenRC-based systems.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -67,7 +60,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger::diff;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Capabilities, Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -142,6 +135,13 @@ impl Module for OpenRc {
         "openrc"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            diff: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -162,6 +162,10 @@ impl Module for OpenRc {
     }
 }
 
+impl ModuleParams for OpenRc {
+    type Params = Params;
+}
+
 struct OpenRcClient {
     check_mode: bool,
 }
@@ -461,6 +465,7 @@ fn openrc(params: Params, check_mode: bool) -> Result<ModuleResult> {
         changed,
         output: final_output,
         extra: Some(value::to_value(extra)?),
+        ..Default::default()
     })
 }
 
@@ -2,13 +2,6 @@
 /// # openssl_certificate
 ///
 /// Generate and manage SSL/TLS certificates.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -35,7 +28,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger::diff;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Capabilities, Module, ModuleParams, ModuleResult, parse_params};
 use crate::utils::parse_octal;
 
 #[cfg(feature = "docs")]
@@ -176,6 +169,7 @@ fn generate_certificate(params: &Params, check_mode: bool) -> Result<ModuleResul
                 changed: false,
                 output: Some(params.path.clone()),
                 extra: None,
+                ..Default::default()
             });
         }
     }
@@ -193,6 +187,7 @@ fn generate_certificate(params: &Params, check_mode: bool) -> Result<ModuleResul
                 changed: false,
                 output: Some(params.path.clone()),
                 extra: None,
+                ..Default::default()
             });
         }
         diff(&existing, &certificate);
@@ -221,6 +216,7 @@ fn generate_certificate(params: &Params, check_mode: bool) -> Result<ModuleResul
         changed: true,
         output: Some(params.path.clone()),
         extra: None,
+        ..Default::default()
     })
 }
 
@@ -232,6 +228,13 @@ impl Module for OpensslCertificate {
         "openssl_certificate"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            diff: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -257,6 +260,10 @@ impl Module for OpensslCertificate {
     }
 }
 
+impl ModuleParams for OpensslCertificate {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2,13 +2,6 @@
 /// # openssl_csr
 ///
 /// Generate Certificate Signing Requests (CSRs).
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -38,7 +31,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger::diff;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Capabilities, Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -299,6 +292,13 @@ impl Module for OpensslCsr {
         "openssl_csr"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            diff: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -316,6 +316,10 @@ impl Module for OpensslCsr {
     }
 }
 
+impl ModuleParams for OpensslCsr {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
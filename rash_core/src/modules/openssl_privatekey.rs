@@ -2,13 +2,6 @@
 /// # openssl_privatekey
 ///
 /// Generate SSL/TLS private keys.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -34,7 +27,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger::diff;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Capabilities, Module, ModuleParams, ModuleResult, parse_params};
 use crate::utils::parse_octal;
 
 #[cfg(feature = "docs")]
@@ -153,6 +146,7 @@ fn generate_private_key(params: &Params, check_mode: bool) -> Result<ModuleResul
             changed: false,
             output: Some(format!("Private key exists at {}", path)),
             extra: None,
+            ..Default::default()
         });
     }
 
@@ -169,6 +163,7 @@ fn generate_private_key(params: &Params, check_mode: bool) -> Result<ModuleResul
             changed: true,
             output: Some(format!("Would generate {} key at {}", key_type, path)),
             extra: None,
+            ..Default::default()
         });
     }
 
@@ -192,6 +187,7 @@ fn generate_private_key(params: &Params, check_mode: bool) -> Result<ModuleResul
         changed: true,
         output: Some(format!("Generated {} private key at {}", key_type, path)),
         extra: None,
+        ..Default::default()
     })
 }
 
@@ -201,6 +197,7 @@ fn remove_key(path: &str, check_mode: bool) -> Result<ModuleResult> {
             changed: false,
             output: Some(format!("Private key {} does not exist", path)),
             extra: None,
+            ..Default::default()
         });
     }
 
@@ -211,6 +208,7 @@ fn remove_key(path: &str, check_mode: bool) -> Result<ModuleResult> {
             changed: true,
             output: Some(format!("Would remove private key at {}", path)),
             extra: None,
+            ..Default::default()
         });
     }
 
@@ -220,6 +218,7 @@ fn remove_key(path: &str, check_mode: bool) -> Result<ModuleResult> {
         changed: true,
         output: Some(format!("Removed private key at {}", path)),
         extra: None,
+        ..Default::default()
     })
 }
 
@@ -231,6 +230,13 @@ impl Module for OpensslPrivatekey {
         "openssl_privatekey"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            diff: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -248,6 +254,10 @@ impl Module for OpensslPrivatekey {
     }
 }
 
+impl ModuleParams for OpensslPrivatekey {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2,13 +2,6 @@
 /// # opkg
 ///
 /// Manage packages with the opkg package manager, which is used by OpenWrt.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -38,7 +31,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Module, ModuleParams, ModuleResult, parse_params};
 use crate::utils::default_false;
 
 #[cfg(feature = "docs")]
@@ -156,6 +149,10 @@ impl Module for Opkg {
     }
 }
 
+impl ModuleParams for Opkg {
+    type Params = Params;
+}
+
 type IsChanged = bool;
 
 struct OpkgClient {
@@ -367,6 +364,7 @@ fn opkg(params: Params, check_mode: bool) -> Result<ModuleResult> {
         extra: Some(value::to_value(
             json!({"installed_packages": p_to_install, "removed_packages": p_to_remove, "upgraded": upgrade_changed, "cache_updated": cache_updated}),
         )?),
+        ..Default::default()
     })
 }
 
@@ -5,14 +5,8 @@
 ///
 /// This module provides a unified interface for package management across different
 /// Linux distributions. It automatically detects the appropriate package manager
-/// (apk, apt, dnf, pacman, or zypper) based on the system.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: partial
-/// ```
+/// (apk, apt, dnf, pacman, or zypper) based on the system, preferring `facts.pkg_mgr`
+/// if facts were collected with `gather_facts`.
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -43,7 +37,10 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::gather_facts::get_fact;
+use crate::modules::{
+    Capabilities, CheckModeSupport, Module, ModuleParams, ModuleResult, parse_params,
+};
 use crate::utils::default_false;
 
 #[cfg(feature = "docs")]
@@ -334,11 +331,18 @@ impl PackageClient {
     }
 }
 
-fn package(params: Params, check_mode: bool) -> Result<ModuleResult> {
+/// Return the package manager from `facts.pkg_mgr` if facts were gathered.
+fn get_facts_package_manager(vars: &Value) -> Option<PackageManager> {
+    get_fact(vars, "pkg_mgr").and_then(|m| serde_norway::from_value(YamlValue::String(m)).ok())
+}
+
+fn package(params: Params, vars: &Value, check_mode: bool) -> Result<ModuleResult> {
     let manager = params.use_manager.unwrap_or_else(|| {
-        detect_package_manager().unwrap_or_else(|| {
-            panic!("Could not detect package manager. Please specify 'use' parameter.");
-        })
+        get_facts_package_manager(vars)
+            .or_else(detect_package_manager)
+            .unwrap_or_else(|| {
+                panic!("Could not detect package manager. Please specify 'use' parameter.");
+            })
     });
 
     let client = PackageClient::new(manager.clone(), check_mode);
@@ -356,6 +360,7 @@ fn package(params: Params, check_mode: bool) -> Result<ModuleResult> {
             extra: Some(value::to_value(
                 json!({"upgraded": true, "manager": format!("{:?}", manager)}),
             )?),
+            ..Default::default()
         });
     }
 
@@ -366,6 +371,7 @@ fn package(params: Params, check_mode: bool) -> Result<ModuleResult> {
             extra: Some(value::to_value(
                 json!({"manager": format!("{:?}", manager)}),
             )?),
+            ..Default::default()
         });
     }
 
@@ -379,6 +385,7 @@ fn package(params: Params, check_mode: bool) -> Result<ModuleResult> {
                 extra: Some(value::to_value(
                     json!({"installed": params.name, "manager": format!("{:?}", manager)}),
                 )?),
+                ..Default::default()
             })
         }
         State::Absent => {
@@ -390,6 +397,7 @@ fn package(params: Params, check_mode: bool) -> Result<ModuleResult> {
                 extra: Some(value::to_value(
                     json!({"removed": params.name, "manager": format!("{:?}", manager)}),
                 )?),
+                ..Default::default()
             })
         }
     }
@@ -403,14 +411,24 @@ impl Module for Package {
         "package"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            check_mode: CheckModeSupport::Partial,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
         optional_params: YamlValue,
-        _vars: &Value,
+        vars: &Value,
         check_mode: bool,
     ) -> Result<(ModuleResult, Option<Value>)> {
-        Ok((package(parse_params(optional_params)?, check_mode)?, None))
+        Ok((
+            package(parse_params(optional_params)?, vars, check_mode)?,
+            None,
+        ))
     }
 
     fn force_string_on_params(&self) -> bool {
@@ -423,6 +441,10 @@ impl Module for Package {
     }
 }
 
+impl ModuleParams for Package {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -511,6 +533,16 @@ mod tests {
         assert_eq!(error.kind(), ErrorKind::InvalidData);
     }
 
+    #[test]
+    fn test_get_facts_package_manager() {
+        let vars = minijinja::context! {facts => minijinja::context! {pkg_mgr => "pacman"}};
+        assert_eq!(
+            get_facts_package_manager(&vars),
+            Some(PackageManager::Pacman)
+        );
+        assert_eq!(get_facts_package_manager(&minijinja::context! {}), None);
+    }
+
     #[test]
     fn test_package_client_install_cmd() {
         let client = PackageClient::new(PackageManager::Apt, false);
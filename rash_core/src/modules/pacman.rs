@@ -2,13 +2,6 @@
 /// # pacman
 ///
 /// Manage packages with the pacman package manager, which is used by Arch Linux and its variants.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -48,7 +41,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Module, ModuleParams, ModuleResult, parse_params};
 use crate::utils::default_false;
 
 #[cfg(feature = "docs")]
@@ -174,6 +167,10 @@ impl Module for Pacman {
     }
 }
 
+impl ModuleParams for Pacman {
+    type Params = Params;
+}
+
 type IsChanged = bool;
 
 struct PacmanClient {
@@ -406,6 +403,7 @@ fn pacman(params: Params, check_mode: bool) -> Result<ModuleResult> {
         extra: Some(value::to_value(
             json!({"installed_packages": p_to_install, "removed_packages": p_to_remove, "upgraded": upgrade_changed}),
         )?),
+        ..Default::default()
     })
 }
 
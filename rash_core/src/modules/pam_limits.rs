@@ -2,13 +2,6 @@
 /// # pam_limits
 ///
 /// Manage Linux PAM limits (ulimits).
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -55,7 +48,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger::diff;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Capabilities, Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -385,6 +378,13 @@ impl Module for PamLimits {
         "pam_limits"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            diff: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -404,6 +404,10 @@ impl Module for PamLimits {
     }
 }
 
+impl ModuleParams for PamLimits {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
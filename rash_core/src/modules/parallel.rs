@@ -0,0 +1,299 @@
+/// ANCHOR: module
+/// # parallel
+///
+/// This module executes a group of independent tasks at the same time.
+///
+/// Tasks are executed in a bounded pool of threads: up to the value of the `throttle` keyword of
+/// the `parallel` task, or up to 5 if it is not set. Each task gets the
+/// variables of the parent context, so a task cannot use the variables registered by the other
+/// tasks in the block. Once all tasks finish, the registered variables are added to the parent
+/// context in the order the tasks are declared, so the result doesn't depend on which task
+/// finished first.
+///
+/// If any task fails, the rest of the tasks are still executed to completion, and then the
+/// error of the first failed task in declaration order is returned.
+///
+/// Tasks are executed in threads of the same process, so they, and the tasks inside them, can't
+/// use what changes the whole process: `environment`, `become` with `become_method: syscall` and
+/// `debugger`. Use `become_method: sudo` instead.
+///
+/// Note: the output of the tasks is printed as they finish, so it can be interleaved.
+/// ANCHOR_END: module
+/// ANCHOR: parameters
+/// | Parameter | Required | Type | Values | Description                             |
+/// | --------- | -------- | ---- | ------ | --------------------------------------- |
+/// | parallel  | true     | list |        | List of tasks to execute concurrently   |
+///
+/// ANCHOR_END: parameters
+///
+/// ANCHOR: examples
+/// ## Example
+///
+/// ```yaml
+/// - name: Download artifacts
+///   throttle: 2
+///   parallel:
+///     - get_url:
+///         url: https://example.com/app.tar.gz
+///         dest: /tmp/app.tar.gz
+///
+///     - get_url:
+///         url: https://example.com/assets.tar.gz
+///         dest: /tmp/assets.tar.gz
+///
+///     - docker_image:
+///         name: nginx:latest
+///       register: nginx_image
+///
+/// - debug:
+///     msg: "{{ nginx_image.changed }}"
+/// ```
+/// ANCHOR_END: examples
+use crate::context::{Context, GlobalParams};
+use crate::error::{Error, ErrorKind, Result};
+use crate::jinja::merge_option;
+use crate::logger::{NoLogGuard, is_no_log};
+use crate::modules::block::Block;
+use crate::modules::{Capabilities, Module, ModuleResult, ValidateParams};
+use crate::task::{Debugger, Task};
+
+use std::panic;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+use minijinja::{Value, context};
+#[cfg(feature = "docs")]
+use schemars::Schema;
+use serde_norway::Value as YamlValue;
+
+/// Max number of tasks executed at the same time when `throttle` is not set.
+const DEFAULT_WORKERS: usize = 5;
+
+#[derive(Debug)]
+pub struct Parallel;
+
+impl Module for Parallel {
+    fn get_name(&self) -> &str {
+        "parallel"
+    }
+
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            r#become: false,
+            ..Default::default()
+        }
+    }
+
+    fn exec(
+        &self,
+        global_params: &GlobalParams,
+        params: YamlValue,
+        vars: &Value,
+        _check_mode: bool,
+    ) -> Result<(ModuleResult, Option<Value>)> {
+        match params {
+            YamlValue::Sequence(task_yamls) => {
+                // the debugger can't be inherited, it would read stdin from several threads
+                let children_global_params = GlobalParams {
+                    in_parallel: true,
+                    debugger: Debugger::Never,
+                    ..*global_params
+                };
+                let tasks = Block.parse_tasks_from_yaml(&task_yamls, &children_global_params)?;
+                let workers = global_params
+                    .throttle
+                    .unwrap_or(DEFAULT_WORKERS)
+                    .min(tasks.len());
+                trace!(
+                    "Parallel module executing {} tasks in {workers} threads",
+                    tasks.len()
+                );
+
+                let new_vars = exec_tasks(&tasks, vars, workers)?;
+
+                // Parallel is a control structure, so it doesn't display its own output
+                Ok((ModuleResult::new(false, None, None), new_vars))
+            }
+            _ => Err(Error::new(
+                ErrorKind::InvalidData,
+                "parallel parameter must be a sequence of tasks",
+            )),
+        }
+    }
+
+    fn force_string_on_params(&self) -> bool {
+        false
+    }
+
+    #[cfg(feature = "docs")]
+    fn get_json_schema(&self) -> Option<Schema> {
+        None
+    }
+}
+
+impl ValidateParams for Parallel {}
+
+/// Execute tasks in `workers` threads, returning the variables registered by them merged in
+/// declaration order, or the error of the first failed task.
+///
+/// `no_log` is set per thread, so workers hide their output if the parent task does.
+fn exec_tasks(tasks: &[Task], vars: &Value, workers: usize) -> Result<Option<Value>> {
+    let next_index = AtomicUsize::new(0);
+    let no_log = is_no_log();
+
+    let mut results = thread::scope(|s| {
+        let handles = (0..workers)
+            .map(|_| {
+                s.spawn(|| {
+                    let _no_log_guard = no_log.then(NoLogGuard::new);
+                    let mut results = Vec::new();
+                    loop {
+                        let index = next_index.fetch_add(1, Ordering::Relaxed);
+                        let Some(task) = tasks.get(index) else {
+                            return results;
+                        };
+                        let result = Context::new(vec![task.clone()], vars.clone(), None)
+                            .exec()
+                            .map(|context| context.get_scoped_vars().cloned());
+                        results.push((index, result));
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().unwrap_or_else(|e| panic::resume_unwind(e)))
+            .collect::<Vec<_>>()
+    });
+    results.sort_by_key(|(index, _)| *index);
+
+    let new_vars = results
+        .into_iter()
+        .try_fold(context! {}, |new_vars, (_, result)| {
+            result.map(|vars| merge_option(new_vars, vars))
+        })?;
+
+    Ok((new_vars != context! {}).then_some(new_vars))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::time::{Duration, Instant};
+
+    fn exec_parallel(yaml: &str, global_params: &GlobalParams) -> Result<Option<Value>> {
+        let params: YamlValue = serde_norway::from_str(yaml).unwrap();
+        Parallel
+            .exec(global_params, params, &context! {}, false)
+            .map(|(_, vars)| vars)
+    }
+
+    #[test]
+    fn test_parallel_merge_vars_in_order() {
+        let vars = exec_parallel(
+            r#"
+            - command: sh -c "sleep 0.3; echo first"
+              register: first
+            - set_vars:
+                foo: boo
+            - set_vars:
+                foo: second
+            "#,
+            &GlobalParams::default(),
+        )
+        .unwrap()
+        .unwrap();
+
+        assert_eq!(
+            vars.get_attr("first")
+                .unwrap()
+                .get_attr("output")
+                .unwrap()
+                .as_str(),
+            Some("first\n")
+        );
+        assert_eq!(vars.get_attr("foo").unwrap(), Value::from("second"));
+    }
+
+    #[test]
+    fn test_parallel_concurrent() {
+        let start = Instant::now();
+        exec_parallel(
+            r#"
+            - command: sleep 0.5
+            - command: sleep 0.5
+            - command: sleep 0.5
+            "#,
+            &GlobalParams {
+                throttle: Some(3),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert!(start.elapsed() < Duration::from_millis(1400));
+    }
+
+    #[test]
+    fn test_parallel_throttle() {
+        let start = Instant::now();
+        exec_parallel(
+            r#"
+            - command: sleep 0.3
+            - command: sleep 0.3
+            "#,
+            &GlobalParams {
+                throttle: Some(1),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert!(start.elapsed() >= Duration::from_millis(600));
+    }
+
+    #[test]
+    fn test_parallel_first_error_in_order() {
+        let error = exec_parallel(
+            r#"
+            - command: sh -c "sleep 0.3; echo first failure >&2; exit 1"
+            - command: "true"
+            - fail:
+                msg: second failure
+            "#,
+            &GlobalParams::default(),
+        )
+        .unwrap_err();
+
+        assert_eq!(error.to_string(), "first failure\n");
+    }
+
+    #[test]
+    fn test_parallel_no_log() {
+        let _no_log_guard = NoLogGuard::new();
+        let error = exec_parallel(
+            r#"
+            - fail:
+                msg: topsecret-value
+            "#,
+            &GlobalParams::default(),
+        )
+        .unwrap_err();
+
+        assert_eq!(error.to_string(), crate::logger::NO_LOG_MESSAGE);
+    }
+
+    #[test]
+    fn test_parallel_empty() {
+        let vars = exec_parallel("[]", &GlobalParams::default()).unwrap();
+        assert_eq!(vars, None);
+    }
+
+    #[test]
+    fn test_parallel_invalid_params() {
+        let error = exec_parallel("foo: boo", &GlobalParams::default()).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+    }
+}
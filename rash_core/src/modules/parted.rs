@@ -2,13 +2,6 @@
 /// # parted
 ///
 /// Manage disk partitions using parted.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -47,7 +40,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger::diff;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Capabilities, Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -107,6 +100,13 @@ impl Module for Parted {
         "parted"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            diff: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -130,6 +130,10 @@ impl Module for Parted {
     }
 }
 
+impl ModuleParams for Parted {
+    type Params = Params;
+}
+
 struct PartedClient {
     check_mode: bool,
 }
@@ -445,6 +449,7 @@ fn parted_module(params: Params, check_mode: bool) -> Result<ModuleResult> {
         changed: result.changed,
         output: None,
         extra: Some(value::to_value(extra)?),
+        ..Default::default()
     })
 }
 
@@ -5,13 +5,6 @@
 ///
 /// Pass uses GPG for encryption and Git for version control. This module enables
 /// secure credential management in scripts, container entrypoints, and IoT devices.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -66,7 +59,7 @@
 /// ```
 /// ANCHOR_END: examples
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -276,6 +269,7 @@ fn exec_present(
             changed: false,
             output: Some(password),
             extra: Some(value::to_value(extra_data)?),
+            ..Default::default()
         });
     }
 
@@ -303,6 +297,7 @@ fn exec_present(
             changed: true,
             output: Some(action),
             extra: None,
+            ..Default::default()
         });
     }
 
@@ -319,6 +314,7 @@ fn exec_present(
             changed: true,
             output: Some(generated),
             extra: Some(value::to_value(extra_data)?),
+            ..Default::default()
         });
     }
 
@@ -341,6 +337,7 @@ fn exec_present(
             changed: true,
             output: Some(output),
             extra: Some(value::to_value(extra_data)?),
+            ..Default::default()
         });
     }
 
@@ -356,6 +353,7 @@ fn exec_present(
             changed: true,
             output: Some(password.clone()),
             extra: Some(value::to_value(extra_data)?),
+            ..Default::default()
         });
     }
 
@@ -374,6 +372,7 @@ fn exec_absent(params: &Params, store_dir: Option<&str>, check_mode: bool) -> Re
             changed: false,
             output: Some(format!("Password {} does not exist", params.path)),
             extra: None,
+            ..Default::default()
         });
     }
 
@@ -382,6 +381,7 @@ fn exec_absent(params: &Params, store_dir: Option<&str>, check_mode: bool) -> Re
             changed: true,
             output: Some(format!("Would remove password {}", params.path)),
             extra: None,
+            ..Default::default()
         });
     }
 
@@ -391,6 +391,7 @@ fn exec_absent(params: &Params, store_dir: Option<&str>, check_mode: bool) -> Re
         changed: true,
         output: Some(format!("Password {} removed successfully", params.path)),
         extra: None,
+        ..Default::default()
     })
 }
 
@@ -421,6 +422,10 @@ impl Module for Passwordstore {
     }
 }
 
+impl ModuleParams for Passwordstore {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2,13 +2,6 @@
 /// # patch
 ///
 /// Apply patch files to source files using the system `patch` command.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -41,7 +34,7 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -210,6 +203,7 @@ pub fn patch(params: Params, check_mode: bool) -> Result<ModuleResult> {
         changed,
         output: Some(output.trim().to_string()),
         extra: None,
+        ..Default::default()
     })
 }
 
@@ -237,6 +231,10 @@ impl Module for Patch {
     }
 }
 
+impl ModuleParams for Patch {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
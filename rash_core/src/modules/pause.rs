@@ -5,13 +5,6 @@
 ///
 /// This module is useful for debugging, rate limiting, or waiting for
 /// external processes that don't have a clear signal.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -30,7 +23,7 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::Result;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -114,6 +107,10 @@ impl Module for Pause {
     }
 }
 
+impl ModuleParams for Pause {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2,13 +2,6 @@
 /// # pids
 ///
 /// Find process IDs matching criteria.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: always
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -37,7 +30,9 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{
+    Capabilities, CheckModeSupport, Module, ModuleParams, ModuleResult, parse_params,
+};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -243,6 +238,13 @@ impl Module for Pids {
         "pids"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            check_mode: CheckModeSupport::Always,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -259,6 +261,10 @@ impl Module for Pids {
     }
 }
 
+impl ModuleParams for Pids {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -5,13 +5,6 @@
 ///
 /// This is a simple connectivity test module. It returns `pong` on success.
 /// If the module is called but the connection fails, the task will fail.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: always
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -31,7 +24,9 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::Result;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{
+    Capabilities, CheckModeSupport, Module, ModuleParams, ModuleResult, parse_params,
+};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -61,6 +56,7 @@ pub fn ping(params: Params) -> Result<ModuleResult> {
         changed: false,
         output: Some(params.data),
         extra: None,
+        ..Default::default()
     })
 }
 
@@ -72,6 +68,13 @@ impl Module for Ping {
         "ping"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            check_mode: CheckModeSupport::Always,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -88,6 +91,10 @@ impl Module for Ping {
     }
 }
 
+impl ModuleParams for Ping {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2,13 +2,6 @@
 /// # pip
 ///
 /// Manage Python packages with pip.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -56,7 +49,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -170,6 +163,10 @@ impl Module for Pip {
     }
 }
 
+impl ModuleParams for Pip {
+    type Params = Params;
+}
+
 struct PipClient {
     executable: PathBuf,
     extra_args: Option<String>,
@@ -424,6 +421,7 @@ fn pip(params: Params, check_mode: bool) -> Result<ModuleResult> {
         extra: Some(value::to_value(
             json!({"installed_packages": p_to_install, "removed_packages": p_to_remove, "requirements_installed": requirements_installed}),
         )?),
+        ..Default::default()
     })
 }
 
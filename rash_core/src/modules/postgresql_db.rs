@@ -2,13 +2,6 @@
 /// # postgresql_db
 ///
 /// Add or remove PostgreSQL databases.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -62,7 +55,7 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -484,6 +477,10 @@ impl Module for PostgresqlDb {
     }
 }
 
+impl ModuleParams for PostgresqlDb {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
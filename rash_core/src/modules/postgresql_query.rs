@@ -2,13 +2,6 @@
 /// # postgresql_query
 ///
 /// Execute SQL queries against PostgreSQL databases.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -53,7 +46,7 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -171,6 +164,7 @@ fn execute_query(params: &Params, check_mode: bool) -> Result<ModuleResult> {
                 truncate_query(&params.query)
             )),
             extra: None,
+            ..Default::default()
         });
     }
 
@@ -232,6 +226,7 @@ fn execute_query(params: &Params, check_mode: bool) -> Result<ModuleResult> {
             stdout
         }),
         extra,
+        ..Default::default()
     })
 }
 
@@ -269,6 +264,10 @@ impl Module for PostgresqlQuery {
     }
 }
 
+impl ModuleParams for PostgresqlQuery {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2,13 +2,6 @@
 /// # postgresql_user
 ///
 /// Add or remove PostgreSQL users (roles).
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -52,7 +45,7 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -368,6 +361,10 @@ impl Module for PostgresqlUser {
     }
 }
 
+impl ModuleParams for PostgresqlUser {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
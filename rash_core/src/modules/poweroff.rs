@@ -6,13 +6,6 @@
 /// This module provides functionality to power off, shut down, or halt systems.
 /// Supports scheduling delayed actions, custom messages, and forced shutdowns.
 /// Useful for IoT devices, container hosts, and automated maintenance scenarios.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -50,7 +43,7 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -126,6 +119,10 @@ impl Module for Poweroff {
     }
 }
 
+impl ModuleParams for Poweroff {
+    type Params = Params;
+}
+
 fn has_systemctl() -> bool {
     Command::new("systemctl")
         .arg("--version")
@@ -2,13 +2,6 @@
 /// # prometheus
 ///
 /// Manage Prometheus monitoring configuration, including targets, alert rules, and scrape configs.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -79,7 +72,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger::diff;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Capabilities, Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -287,6 +280,7 @@ fn exec_get(params: Params) -> Result<ModuleResult> {
         changed: false,
         output: Some(params.config_file.clone()),
         extra: Some(YamlValue::Mapping(extra)),
+        ..Default::default()
     })
 }
 
@@ -367,6 +361,7 @@ fn exec_add(params: Params, check_mode: bool) -> Result<ModuleResult> {
         changed: any_changed,
         output: Some(params.config_file.clone()),
         extra: None,
+        ..Default::default()
     })
 }
 
@@ -482,6 +477,7 @@ fn exec_remove(params: Params, check_mode: bool) -> Result<ModuleResult> {
         changed,
         output: Some(params.config_file.clone()),
         extra: None,
+        ..Default::default()
     })
 }
 
@@ -618,6 +614,7 @@ fn exec_update(params: Params, check_mode: bool) -> Result<ModuleResult> {
         changed,
         output: Some(params.config_file.clone()),
         extra: None,
+        ..Default::default()
     })
 }
 
@@ -640,6 +637,13 @@ impl Module for Prometheus {
         "prometheus"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            diff: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -659,6 +663,10 @@ impl Module for Prometheus {
     }
 }
 
+impl ModuleParams for Prometheus {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
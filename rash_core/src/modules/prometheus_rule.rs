@@ -2,13 +2,6 @@
 /// # prometheus_rule
 ///
 /// Manage Prometheus alerting rule groups in rule files.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -42,7 +35,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger::diff;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Capabilities, Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -241,6 +234,7 @@ pub fn prometheus_rule(params: Params, check_mode: bool) -> Result<ModuleResult>
         changed,
         output: Some(file_str),
         extra: None,
+        ..Default::default()
     })
 }
 
@@ -252,6 +246,13 @@ impl Module for PrometheusRule {
         "prometheus_rule"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            diff: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -271,6 +272,10 @@ impl Module for PrometheusRule {
     }
 }
 
+impl ModuleParams for PrometheusRule {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
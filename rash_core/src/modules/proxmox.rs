@@ -2,13 +2,6 @@
 /// # proxmox
 ///
 /// Manage Proxmox VE virtual machines and containers.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -86,7 +79,7 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -1130,6 +1123,10 @@ impl Module for Proxmox {
     }
 }
 
+impl ModuleParams for Proxmox {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
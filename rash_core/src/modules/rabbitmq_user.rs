@@ -2,13 +2,6 @@
 /// # rabbitmq_user
 ///
 /// Manage RabbitMQ users and permissions.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -64,7 +57,7 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -414,6 +407,10 @@ impl Module for RabbitmqUser {
     }
 }
 
+impl ModuleParams for RabbitmqUser {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2,13 +2,6 @@
 /// # rclone
 ///
 /// Sync files and directories to/from cloud storage providers using rclone.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: partial
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -52,7 +45,9 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{
+    Capabilities, CheckModeSupport, Module, ModuleParams, ModuleResult, parse_params,
+};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -272,6 +267,7 @@ fn run_rclone(params: Params, check_mode: bool) -> Result<(ModuleResult, Option<
             changed,
             output: module_output,
             extra,
+            ..Default::default()
         },
         None,
     ))
@@ -285,6 +281,13 @@ impl Module for Rclone {
         "rclone"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            check_mode: CheckModeSupport::Partial,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -302,6 +305,10 @@ impl Module for Rclone {
     }
 }
 
+impl ModuleParams for Rclone {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -6,13 +6,6 @@
 /// This module provides functionality to reboot systems, schedule delayed reboots,
 /// and check if a reboot is required. Useful for IoT devices, container hosts,
 /// and configuration management scenarios.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -50,7 +43,7 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -136,6 +129,10 @@ impl Module for Reboot {
     }
 }
 
+impl ModuleParams for Reboot {
+    type Params = Params;
+}
+
 fn is_reboot_required() -> bool {
     let paths = [
         "/var/run/reboot-required",
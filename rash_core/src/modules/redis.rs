@@ -2,13 +2,6 @@
 /// # redis
 ///
 /// Unified utility to interact with Redis instances.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -70,7 +63,7 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -462,6 +455,10 @@ impl Module for Redis {
     }
 }
 
+impl ModuleParams for Redis {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2,13 +2,6 @@
 /// # replace
 ///
 /// Replace all instances of a particular string in a file using a back-referenced regular expression.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -41,7 +34,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger::diff;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Capabilities, Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -211,6 +204,7 @@ pub fn replace(params: Params, check_mode: bool) -> Result<ModuleResult> {
             changed: false,
             output: Some(params.path.clone()),
             extra: None,
+            ..Default::default()
         });
     }
 
@@ -235,6 +229,7 @@ pub fn replace(params: Params, check_mode: bool) -> Result<ModuleResult> {
             changed: true,
             output: Some(params.path.clone()),
             extra: None,
+            ..Default::default()
         });
     }
 
@@ -271,6 +266,7 @@ pub fn replace(params: Params, check_mode: bool) -> Result<ModuleResult> {
         changed: true,
         output: Some(params.path.clone()),
         extra: None,
+        ..Default::default()
     })
 }
 
@@ -282,6 +278,13 @@ impl Module for Replace {
         "replace"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            diff: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -298,6 +301,10 @@ impl Module for Replace {
     }
 }
 
+impl ModuleParams for Replace {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
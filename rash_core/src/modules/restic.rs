@@ -4,13 +4,6 @@
 /// Manage Restic backups with support for multiple backends (local, S3, B2, REST, etc.).
 /// Restic is a modern, fast, secure backup program with encryption, deduplication,
 /// and cloud storage support.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: partial
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -79,7 +72,9 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{
+    Capabilities, CheckModeSupport, Module, ModuleParams, ModuleResult, parse_params,
+};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -425,6 +420,7 @@ fn run_restic(params: Params, check_mode: bool) -> Result<(ModuleResult, Option<
             changed,
             output: module_output,
             extra,
+            ..Default::default()
         },
         None,
     ))
@@ -438,6 +434,13 @@ impl Module for Restic {
         "restic"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            check_mode: CheckModeSupport::Partial,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -455,6 +458,10 @@ impl Module for Restic {
     }
 }
 
+impl ModuleParams for Restic {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
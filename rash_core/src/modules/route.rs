@@ -2,13 +2,6 @@
 /// # route
 ///
 /// Manage network routing tables using ip route commands.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -46,7 +39,7 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -336,6 +329,10 @@ impl Module for Route {
     }
 }
 
+impl ModuleParams for Route {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
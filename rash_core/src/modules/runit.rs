@@ -2,13 +2,6 @@
 /// # runit
 ///
 /// Manage Runit services.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -55,7 +48,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger::diff;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Capabilities, Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -130,6 +123,13 @@ impl Module for Runit {
         "runit"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            diff: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -150,6 +150,10 @@ impl Module for Runit {
     }
 }
 
+impl ModuleParams for Runit {
+    type Params = Params;
+}
+
 struct RunitClient {
     check_mode: bool,
     service_dir: PathBuf,
@@ -478,6 +482,7 @@ fn runit(params: Params, check_mode: bool) -> Result<ModuleResult> {
         changed,
         output: final_output,
         extra: Some(value::to_value(extra)?),
+        ..Default::default()
     })
 }
 
@@ -2,13 +2,6 @@
 /// # seboolean
 ///
 /// Manage SELinux boolean settings.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -39,7 +32,7 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -186,6 +179,10 @@ impl Module for Seboolean {
     }
 }
 
+impl ModuleParams for Seboolean {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -4,13 +4,6 @@
 /// Change SELinux policy and modes.
 ///
 /// This module manages SELinux configuration and state.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: always
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -32,7 +25,9 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{
+    Capabilities, CheckModeSupport, Module, ModuleParams, ModuleResult, parse_params,
+};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -236,6 +231,7 @@ pub fn selinux(params: Params, check_mode: bool) -> Result<ModuleResult> {
                 .unwrap_or("unchanged")
         )),
         extra: None,
+        ..Default::default()
     })
 }
 
@@ -247,6 +243,13 @@ impl Module for Selinux {
         "selinux"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            check_mode: CheckModeSupport::Always,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -263,6 +266,10 @@ impl Module for Selinux {
     }
 }
 
+impl ModuleParams for Selinux {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
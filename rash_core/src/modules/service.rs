@@ -3,13 +3,7 @@
 ///
 /// Manage services on target hosts. This module is a wrapper for service
 /// management on different init systems (systemd, sysvinit, openrc).
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
+/// If `use` is not defined, `facts.service_mgr` from `gather_facts` is used when available.
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -50,7 +44,8 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger::diff;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::gather_facts::get_fact;
+use crate::modules::{Capabilities, Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -112,14 +107,24 @@ impl Module for Service {
         "service"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            diff: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
         optional_params: YamlValue,
-        _vars: &Value,
+        vars: &Value,
         check_mode: bool,
     ) -> Result<(ModuleResult, Option<Value>)> {
-        Ok((service(parse_params(optional_params)?, check_mode)?, None))
+        Ok((
+            service(parse_params(optional_params)?, vars, check_mode)?,
+            None,
+        ))
     }
 
     fn force_string_on_params(&self) -> bool {
@@ -132,6 +137,10 @@ impl Module for Service {
     }
 }
 
+impl ModuleParams for Service {
+    type Params = Params;
+}
+
 struct ServiceResult {
     changed: bool,
     output: Option<String>,
@@ -621,12 +630,17 @@ fn validate_service_name(name: &str) -> Result<()> {
     Ok(())
 }
 
-fn service(params: Params, check_mode: bool) -> Result<ModuleResult> {
+/// Return the service manager from `facts.service_mgr` if facts were gathered.
+fn get_facts_service_manager(vars: &Value) -> Option<ServiceManager> {
+    get_fact(vars, "service_mgr").and_then(|m| serde_norway::from_value(YamlValue::String(m)).ok())
+}
+
+fn service(params: Params, vars: &Value, check_mode: bool) -> Result<ModuleResult> {
     validate_service_name(&params.name)?;
 
-    let manager = match params.service_manager {
-        Some(ref m) => m.clone(),
-        None => detect_service_manager()?,
+    let manager = match (params.service_manager, get_facts_service_manager(vars)) {
+        (Some(m), _) | (None, Some(m)) => m,
+        (None, None) => detect_service_manager()?,
     };
 
     let client = get_client(&manager, check_mode);
@@ -723,6 +737,7 @@ fn service(params: Params, check_mode: bool) -> Result<ModuleResult> {
         changed,
         output: final_output,
         extra: Some(value::to_value(extra)?),
+        ..Default::default()
     })
 }
 
@@ -788,6 +803,19 @@ mod tests {
         assert_eq!(error.kind(), ErrorKind::InvalidData);
     }
 
+    #[test]
+    fn test_get_facts_service_manager() {
+        let vars = minijinja::context! {facts => minijinja::context! {service_mgr => "openrc"}};
+        assert_eq!(
+            get_facts_service_manager(&vars),
+            Some(ServiceManager::Openrc)
+        );
+
+        let vars = minijinja::context! {facts => minijinja::context! {service_mgr => "runit"}};
+        assert_eq!(get_facts_service_manager(&vars), None);
+        assert_eq!(get_facts_service_manager(&minijinja::context! {}), None);
+    }
+
     #[test]
     fn test_validate_service_name() {
         assert!(validate_service_name("httpd").is_ok());
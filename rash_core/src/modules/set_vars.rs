@@ -2,13 +2,6 @@
 /// # set_vars
 ///
 /// This module allows setting new variables.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: always
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: parameters
 /// | Parameter | Required | Type  | Values | Description                                                         |
@@ -40,7 +33,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::jinja::render;
-use crate::modules::{Module, ModuleResult};
+use crate::modules::{Capabilities, CheckModeSupport, Module, ModuleResult, ValidateParams};
 
 use minijinja::{Value, context};
 #[cfg(feature = "docs")]
@@ -55,6 +48,13 @@ impl Module for SetVars {
         "set_vars"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            check_mode: CheckModeSupport::Always,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -98,6 +98,7 @@ impl Module for SetVars {
                 changed: false,
                 output: None,
                 extra: None,
+                ..Default::default()
             },
             Some(new_vars),
         ))
@@ -108,3 +109,5 @@ impl Module for SetVars {
         None
     }
 }
+
+impl ValidateParams for SetVars {}
@@ -6,12 +6,7 @@
 /// Environment variables from .env files are loaded into the `env` namespace, while
 /// YAML and JSON variables are loaded as top-level context variables.
 ///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: always
-/// ```
+/// Files encrypted with `rash vault` are decrypted transparently.
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -31,11 +26,18 @@
 /// - name: Load from single file
 ///   setup:
 ///     from: vars/production.yml
+///
+/// - name: Load secrets encrypted with `rash vault encrypt`
+///   setup:
+///     from: vars/secrets.yml
 /// ```
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{
+    Capabilities, CheckModeSupport, Module, ModuleParams, ModuleResult, parse_params,
+};
+use crate::vault;
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -71,6 +73,12 @@ fn load_file_vars_with_type(file_path: &str) -> Result<(serde_json::Value, bool)
             format!("Failed to read file '{file_path}': {e}"),
         )
     })?;
+    let content = vault::decrypt_if_encrypted(content).map_err(|e| {
+        Error::new(
+            e.kind(),
+            format!("Failed to decrypt file '{file_path}': {e}"),
+        )
+    })?;
 
     detect_and_load_file_format(&content, path)
 }
@@ -270,6 +278,13 @@ impl Module for Setup {
         "setup"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            check_mode: CheckModeSupport::Always,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -286,6 +301,10 @@ impl Module for Setup {
     }
 }
 
+impl ModuleParams for Setup {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2,13 +2,6 @@
 /// # sgdisk
 ///
 /// Manage GPT disk partitions using sgdisk (part of gdisk/gptfdisk).
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -65,7 +58,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger::diff;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Capabilities, Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -130,6 +123,13 @@ impl Module for Sgdisk {
         "sgdisk"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            diff: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -153,6 +153,10 @@ impl Module for Sgdisk {
     }
 }
 
+impl ModuleParams for Sgdisk {
+    type Params = Params;
+}
+
 struct SgdiskClient {
     check_mode: bool,
 }
@@ -515,6 +519,7 @@ fn sgdisk_module(params: Params, check_mode: bool) -> Result<ModuleResult> {
         changed: result.changed,
         output: None,
         extra: Some(value::to_value(extra)?),
+        ..Default::default()
     })
 }
 
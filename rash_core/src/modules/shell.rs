@@ -5,13 +5,6 @@
 /// variables. This module extends the command module by providing full shell
 /// features including pipes, redirections, environment variable expansion,
 /// shell glob expansion, and subshell execution.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -43,7 +36,7 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Capabilities, Module, ModuleResult, ValidateParams, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -57,7 +50,6 @@ use minijinja::Value;
 use schemars::{JsonSchema, Schema};
 use serde::Deserialize;
 use serde_norway::Value as YamlValue;
-use serde_norway::value;
 
 #[derive(Debug, PartialEq, Deserialize)]
 #[cfg_attr(feature = "docs", derive(JsonSchema, DocJsonSchema))]
@@ -94,6 +86,13 @@ impl Module for Shell {
         "shell"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            r#async: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -165,25 +164,22 @@ impl Module for Shell {
             .map_err(|e| Error::new(ErrorKind::SubprocessFail, e))?;
 
         trace!("exec - output: {output:?}");
-        let stderr = String::from_utf8_lossy(&output.stderr);
-
-        if !output.status.success() {
-            return Err(Error::new(ErrorKind::InvalidData, stderr));
-        }
-        let output_string = String::from_utf8_lossy(&output.stdout);
-
-        let module_output = if output_string.is_empty() {
-            None
-        } else {
-            Some(output_string.into_owned())
+        let stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+        let stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+        let module_output = (!stdout.is_empty()).then(|| stdout.clone());
+
+        let result = ModuleResult::new(true, None, module_output)
+            .with_rc(output.status.code())
+            .with_stdout(stdout);
+        let result = match output.status.success() {
+            true => result.with_stderr(stderr),
+            false => result
+                .with_failed(true)
+                .with_msg(stderr.clone())
+                .with_stderr(stderr),
         };
 
-        let extra = Some(value::to_value(json!({
-            "rc": output.status.code(),
-            "stderr": stderr,
-        }))?);
-
-        Ok((ModuleResult::new(true, extra, module_output), None))
+        Ok((result, None))
     }
 
     #[cfg(feature = "docs")]
@@ -192,6 +188,15 @@ impl Module for Shell {
     }
 }
 
+impl ValidateParams for Shell {
+    fn validate_params(&self, params: YamlValue) -> Result<()> {
+        if params.as_str().is_none() {
+            parse_params::<Params>(params)?;
+        }
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -451,7 +456,7 @@ mod tests {
     }
 
     #[test]
-    fn test_shell_extra_contains_rc() {
+    fn test_shell_result_contains_rc() {
         let shell = Shell;
         let yaml: YamlValue = serde_norway::from_str(
             r#"
@@ -464,11 +469,10 @@ mod tests {
             .exec(&GlobalParams::default(), yaml, &Value::UNDEFINED, false)
             .unwrap();
 
-        let extra = result.get_extra().unwrap();
-        let extra_map = extra.as_mapping().unwrap();
-        assert_eq!(
-            extra_map.get(serde_norway::Value::String("rc".to_string())),
-            Some(&serde_norway::Value::Number(0.into()))
-        );
+        assert_eq!(result.get_rc(), Some(0));
+        assert_eq!(result.get_stdout(), Some("ok\n"));
+        assert_eq!(result.get_stdout_lines(), Some(&["ok".to_owned()][..]));
+        assert_eq!(result.get_stderr(), Some(""));
+        assert_eq!(result.get_extra(), None);
     }
 }
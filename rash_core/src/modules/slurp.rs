@@ -3,13 +3,6 @@
 ///
 /// This module reads a file and returns its content base64 encoded.
 /// Useful for reading files (including binary) for use in templates or registering variables.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: none
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -32,7 +25,9 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{
+    Capabilities, CheckModeSupport, Module, ModuleParams, ModuleResult, parse_params,
+};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -75,6 +70,7 @@ fn slurp(params: Params) -> Result<ModuleResult> {
         changed: false,
         output: None,
         extra: Some(extra),
+        ..Default::default()
     })
 }
 
@@ -93,6 +89,14 @@ impl Module for Slurp {
         "slurp"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            // it just reads the file
+            check_mode: CheckModeSupport::Always,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -109,6 +113,10 @@ impl Module for Slurp {
     }
 }
 
+impl ModuleParams for Slurp {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
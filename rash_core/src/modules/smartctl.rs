@@ -3,13 +3,6 @@
 ///
 /// Monitor disk health using SMART (Self-Monitoring, Analysis and Reporting Technology).
 /// Requires smartmontools to be installed.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -51,7 +44,7 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -210,6 +203,10 @@ impl Module for Smartctl {
     }
 }
 
+impl ModuleParams for Smartctl {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
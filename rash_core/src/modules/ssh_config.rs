@@ -2,13 +2,6 @@
 /// # ssh_config
 ///
 /// Manage SSH client configuration in ~/.ssh/config or /etc/ssh/ssh_config.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -41,7 +34,7 @@
 /// ANCHOR_END: examples
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger::diff;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Capabilities, Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -468,6 +461,7 @@ pub fn ssh_config(params: Params, check_mode: bool) -> Result<ModuleResult> {
                 changed,
                 output: Some(ssh_config_path.to_string_lossy().to_string()),
                 extra: None,
+                ..Default::default()
             })
         }
         State::Absent => {
@@ -478,6 +472,7 @@ pub fn ssh_config(params: Params, check_mode: bool) -> Result<ModuleResult> {
                     changed: false,
                     output: Some(ssh_config_path.to_string_lossy().to_string()),
                     extra: None,
+                    ..Default::default()
                 });
             };
 
@@ -493,6 +488,7 @@ pub fn ssh_config(params: Params, check_mode: bool) -> Result<ModuleResult> {
                     changed: false,
                     output: Some(ssh_config_path.to_string_lossy().to_string()),
                     extra: None,
+                    ..Default::default()
                 });
             }
 
@@ -514,6 +510,7 @@ pub fn ssh_config(params: Params, check_mode: bool) -> Result<ModuleResult> {
                 changed: true,
                 output: Some(ssh_config_path.to_string_lossy().to_string()),
                 extra: None,
+                ..Default::default()
             })
         }
     }
@@ -527,6 +524,13 @@ impl Module for SshConfig {
         "ssh_config"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            diff: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &crate::context::GlobalParams,
@@ -546,6 +550,10 @@ impl Module for SshConfig {
     }
 }
 
+impl ModuleParams for SshConfig {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
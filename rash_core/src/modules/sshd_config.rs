@@ -2,13 +2,6 @@
 /// # sshd_config
 ///
 /// Manage SSH server configuration in /etc/ssh/sshd_config.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -56,7 +49,7 @@
 /// ANCHOR_END: examples
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger::diff;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Capabilities, Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -457,6 +450,7 @@ pub fn sshd_config(params: Params, check_mode: bool) -> Result<ModuleResult> {
                     changed: false,
                     output: Some(config_path.to_string_lossy().to_string()),
                     extra: None,
+                    ..Default::default()
                 });
             }
 
@@ -499,6 +493,7 @@ pub fn sshd_config(params: Params, check_mode: bool) -> Result<ModuleResult> {
                 changed: true,
                 output: Some(config_path.to_string_lossy().to_string()),
                 extra: None,
+                ..Default::default()
             })
         }
         State::Absent => {
@@ -507,6 +502,7 @@ pub fn sshd_config(params: Params, check_mode: bool) -> Result<ModuleResult> {
                     changed: false,
                     output: Some(config_path.to_string_lossy().to_string()),
                     extra: None,
+                    ..Default::default()
                 });
             }
 
@@ -520,6 +516,7 @@ pub fn sshd_config(params: Params, check_mode: bool) -> Result<ModuleResult> {
                     changed: false,
                     output: Some(config_path.to_string_lossy().to_string()),
                     extra: None,
+                    ..Default::default()
                 });
             }
 
@@ -556,6 +553,7 @@ pub fn sshd_config(params: Params, check_mode: bool) -> Result<ModuleResult> {
                 changed: true,
                 output: Some(config_path.to_string_lossy().to_string()),
                 extra: None,
+                ..Default::default()
             })
         }
     }
@@ -569,6 +567,13 @@ impl Module for SshdConfig {
         "sshd_config"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            diff: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &crate::context::GlobalParams,
@@ -588,6 +593,10 @@ impl Module for SshdConfig {
     }
 }
 
+impl ModuleParams for SshdConfig {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
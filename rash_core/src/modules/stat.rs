@@ -2,13 +2,6 @@
 /// # stat
 ///
 /// Retrieve file or file system status.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: always
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -34,7 +27,9 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{
+    Capabilities, CheckModeSupport, Module, ModuleParams, ModuleResult, parse_params,
+};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -393,6 +388,7 @@ pub fn stat(params: Params) -> Result<ModuleResult> {
         changed: false,
         output: None,
         extra: Some(extra),
+        ..Default::default()
     })
 }
 
@@ -404,6 +400,13 @@ impl Module for Stat {
         "stat"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            check_mode: CheckModeSupport::Always,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -420,6 +423,10 @@ impl Module for Stat {
     }
 }
 
+impl ModuleParams for Stat {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
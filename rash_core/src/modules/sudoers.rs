@@ -2,13 +2,6 @@
 /// # sudoers
 ///
 /// Manage sudoers configuration entries in /etc/sudoers.d.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -55,7 +48,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger::diff;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Capabilities, Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -276,6 +269,13 @@ impl Module for Sudoers {
         "sudoers"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            diff: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -292,6 +292,10 @@ impl Module for Sudoers {
     }
 }
 
+impl ModuleParams for Sudoers {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2,13 +2,6 @@
 /// # supervisor
 ///
 /// Manage Supervisor process control daemon programs.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -59,7 +52,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger::diff;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Capabilities, Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -173,6 +166,13 @@ impl Module for Supervisor {
         "supervisor"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            diff: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -196,6 +196,10 @@ impl Module for Supervisor {
     }
 }
 
+impl ModuleParams for Supervisor {
+    type Params = Params;
+}
+
 struct SupervisorctlClient {
     check_mode: bool,
 }
@@ -630,6 +634,7 @@ fn supervisor(params: Params, check_mode: bool) -> Result<ModuleResult> {
         changed,
         output: final_output,
         extra: Some(value::to_value(extra)?),
+        ..Default::default()
     })
 }
 
@@ -2,13 +2,6 @@
 /// # swapfile
 ///
 /// Manage swap files on Linux systems.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -47,7 +40,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger::diff;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Capabilities, Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -473,6 +466,13 @@ impl Module for Swapfile {
         "swapfile"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            diff: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -489,6 +489,10 @@ impl Module for Swapfile {
     }
 }
 
+impl ModuleParams for Swapfile {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2,13 +2,6 @@
 /// # synchronize
 ///
 /// Wrap rsync to synchronize files.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: none
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -29,7 +22,9 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{
+    Capabilities, CheckModeSupport, Module, ModuleParams, ModuleResult, parse_params,
+};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -151,6 +146,7 @@ pub fn run_rsync(params: Params) -> Result<(ModuleResult, Option<Value>)> {
             changed: true,
             output: module_output,
             extra,
+            ..Default::default()
         },
         None,
     ))
@@ -164,6 +160,13 @@ impl Module for Synchronize {
         "synchronize"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            check_mode: CheckModeSupport::None,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -181,6 +184,10 @@ impl Module for Synchronize {
     }
 }
 
+impl ModuleParams for Synchronize {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
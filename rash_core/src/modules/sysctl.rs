@@ -2,13 +2,6 @@
 /// # sysctl
 ///
 /// Manage kernel parameters via sysctl.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -42,7 +35,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger::diff;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Capabilities, Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -347,6 +340,13 @@ impl Module for Sysctl {
         "sysctl"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            diff: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -363,6 +363,10 @@ impl Module for Sysctl {
     }
 }
 
+impl ModuleParams for Sysctl {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
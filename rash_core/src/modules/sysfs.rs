@@ -4,13 +4,6 @@
 /// Manage sysfs attributes for kernel and device configuration.
 /// Essential for IoT devices and embedded systems where hardware parameters
 /// need to be tuned at runtime.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -40,7 +33,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger::diff;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Capabilities, Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -161,6 +154,13 @@ impl Module for Sysfs {
         "sysfs"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            diff: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -177,6 +177,10 @@ impl Module for Sysfs {
     }
 }
 
+impl ModuleParams for Sysfs {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
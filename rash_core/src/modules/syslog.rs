@@ -6,13 +6,6 @@
 /// This module enables scripts to log messages to the system log daemon,
 /// useful for operational logging, debugging, and audit trails in
 /// container/IoT environments.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: none
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -44,7 +37,9 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{
+    Capabilities, CheckModeSupport, Module, ModuleParams, ModuleResult, parse_params,
+};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -200,6 +195,13 @@ impl Module for Syslog {
         "syslog"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            check_mode: CheckModeSupport::None,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -216,6 +218,10 @@ impl Module for Syslog {
     }
 }
 
+impl ModuleParams for Syslog {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2,13 +2,6 @@
 /// # systemd
 ///
 /// Control systemd services.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -53,7 +46,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger::diff;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Capabilities, Module, ModuleParams, ModuleResult, parse_params};
 use crate::utils::default_false;
 
 #[cfg(feature = "docs")]
@@ -149,6 +142,13 @@ impl Module for Systemd {
         "systemd"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            diff: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -169,6 +169,10 @@ impl Module for Systemd {
     }
 }
 
+impl ModuleParams for Systemd {
+    type Params = Params;
+}
+
 struct SystemdClient {
     check_mode: bool,
     scope: Option<Scope>,
@@ -390,6 +394,7 @@ fn systemd(params: Params, check_mode: bool) -> Result<ModuleResult> {
                 changed,
                 output: None,
                 extra: None,
+                ..Default::default()
             });
         }
     };
@@ -484,6 +489,7 @@ fn systemd(params: Params, check_mode: bool) -> Result<ModuleResult> {
         changed,
         output: final_output,
         extra: Some(value::to_value(extra)?),
+        ..Default::default()
     })
 }
 
@@ -2,13 +2,6 @@
 /// # tailscale
 ///
 /// Manage Tailscale mesh VPN networking.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -44,7 +37,7 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -115,6 +108,10 @@ impl Module for Tailscale {
     }
 }
 
+impl ModuleParams for Tailscale {
+    type Params = Params;
+}
+
 fn run_tailscale(args: &[&str]) -> Result<std::process::Output> {
     Command::new(TAILSCALE_BIN)
         .args(args)
@@ -6,13 +6,6 @@
 /// This module creates temporary files or directories with optional prefix,
 /// suffix, and permission settings. The created path is returned in the
 /// output and can be registered for use in subsequent tasks.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -40,7 +33,7 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Module, ModuleParams, ModuleResult, parse_params};
 use crate::utils::parse_octal;
 
 #[cfg(feature = "docs")]
@@ -95,6 +88,7 @@ fn create_temp_directory(params: Params, check_mode: bool) -> Result<ModuleResul
             changed: true,
             output: Some(format!("{}/{}tmp", base_path, prefix)),
             extra: None,
+            ..Default::default()
         });
     }
 
@@ -132,6 +126,7 @@ fn create_temp_directory(params: Params, check_mode: bool) -> Result<ModuleResul
         changed: true,
         output: Some(path_str),
         extra: None,
+        ..Default::default()
     })
 }
 
@@ -148,6 +143,7 @@ fn create_temp_file(params: Params, check_mode: bool) -> Result<ModuleResult> {
             changed: true,
             output: Some(format!("{}/{}{}tmp", base_path, prefix, suffix)),
             extra: None,
+            ..Default::default()
         });
     }
 
@@ -189,6 +185,7 @@ fn create_temp_file(params: Params, check_mode: bool) -> Result<ModuleResult> {
         changed: true,
         output: Some(path_str),
         extra: None,
+        ..Default::default()
     })
 }
 
@@ -226,6 +223,10 @@ impl Module for Tempfile {
     }
 }
 
+impl ModuleParams for Tempfile {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2,13 +2,6 @@
 /// # template
 ///
 /// Render [MiniJinja template](https://docs.rs/minijinja/latest/minijinja/syntax/index.html).
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -25,7 +18,8 @@ use crate::error::Result;
 use crate::jinja::render_string;
 use crate::modules::copy::copy_file;
 use crate::modules::copy::{Input, Params as CopyParams};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::dynamic::resolve_module_file;
+use crate::modules::{Capabilities, Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -55,6 +49,10 @@ pub struct Params {
 }
 
 fn render_content(params: Params, vars: &Value) -> Result<CopyParams> {
+    let params = Params {
+        src: resolve_module_file(&params.src, "templates", vars),
+        ..params
+    };
     let mode = match params.mode.as_deref() {
         Some("preserve") => {
             let src_metadata = metadata(&params.src)?;
@@ -81,6 +79,13 @@ impl Module for Template {
         "template"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            diff: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -103,6 +108,10 @@ impl Module for Template {
     }
 }
 
+impl ModuleParams for Template {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
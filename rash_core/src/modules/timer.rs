@@ -6,13 +6,6 @@
 /// This module provides named timers that can be started, stopped, and read
 /// to measure elapsed time between tasks. Useful for debugging, performance
 /// optimization, and IoT devices with limited resources.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: parameters
 /// | Parameter | Required | Type   | Values          | Description                                  |
@@ -93,7 +86,7 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -274,6 +267,10 @@ impl Module for Timer {
     }
 }
 
+impl ModuleParams for Timer {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -21,12 +21,6 @@
 /// - `extra.stats.by_comm`: Event count grouped by command name
 /// - `extra.duration_ms`: Actual trace duration in milliseconds
 ///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: none
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -75,7 +69,9 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{
+    Capabilities, CheckModeSupport, Module, ModuleParams, ModuleResult, parse_params,
+};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -358,6 +354,13 @@ impl Module for Trace {
         "trace"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            check_mode: CheckModeSupport::None,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -388,6 +391,10 @@ impl Module for Trace {
     }
 }
 
+impl ModuleParams for Trace {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2,13 +2,6 @@
 /// # ufw
 ///
 /// Manage Ubuntu Uncomplicated Firewall (UFW).
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -100,7 +93,7 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -738,6 +731,10 @@ impl Module for Ufw {
     }
 }
 
+impl ModuleParams for Ufw {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
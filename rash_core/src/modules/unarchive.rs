@@ -2,15 +2,6 @@
 /// # unarchive
 ///
 /// Unpacks an archive (tar, tar.gz, tar.bz2, tar.xz, zip) to a destination.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// diff_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -37,7 +28,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger::diff;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Capabilities, Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -693,6 +684,7 @@ fn run_unarchive(params: Params, check_mode: bool) -> Result<ModuleResult> {
                 dest.display()
             )),
             extra: None,
+            ..Default::default()
         });
     }
 
@@ -823,6 +815,7 @@ fn run_unarchive(params: Params, check_mode: bool) -> Result<ModuleResult> {
             dest.display()
         )),
         extra: None,
+        ..Default::default()
     })
 }
 
@@ -834,6 +827,13 @@ impl Module for Unarchive {
         "unarchive"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            diff: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -850,6 +850,10 @@ impl Module for Unarchive {
     }
 }
 
+impl ModuleParams for Unarchive {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
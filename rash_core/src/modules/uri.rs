@@ -2,13 +2,6 @@
 /// # uri
 ///
 /// Interacts with HTTP and HTTPS web services.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: none
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -42,7 +35,9 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{
+    Capabilities, CheckModeSupport, Module, ModuleParams, ModuleResult, parse_params,
+};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -170,6 +165,13 @@ impl Module for Uri {
         "uri"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            check_mode: CheckModeSupport::None,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -244,6 +246,7 @@ impl Module for Uri {
                 changed: false, // URI requests don't typically change the system state
                 output,
                 extra,
+                ..Default::default()
             },
             None,
         ))
@@ -255,6 +258,10 @@ impl Module for Uri {
     }
 }
 
+impl ModuleParams for Uri {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
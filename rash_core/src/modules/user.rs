@@ -2,13 +2,6 @@
 /// # user
 ///
 /// Manage user accounts and user attributes.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -42,7 +35,7 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult};
+use crate::modules::{Module, ModuleParams, ModuleResult};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -323,6 +316,7 @@ fn exec_user_command(cmd: &[String], check_mode: bool) -> Result<(ModuleResult,
                 changed: true,
                 output: Some(format!("Would run: {}", cmd.join(" "))),
                 extra: None,
+                ..Default::default()
             },
             None,
         ));
@@ -346,6 +340,7 @@ fn exec_user_command(cmd: &[String], check_mode: bool) -> Result<(ModuleResult,
             changed: true,
             output: Some(stdout.into_owned()),
             extra: None,
+            ..Default::default()
         },
         None,
     ))
@@ -388,6 +383,7 @@ impl Module for User {
                                 changed: false,
                                 output: None,
                                 extra: None,
+                                ..Default::default()
                             },
                             None,
                         ))
@@ -400,6 +396,7 @@ impl Module for User {
                         changed: false,
                         output: Some("User already absent".to_string()),
                         extra: None,
+                        ..Default::default()
                     },
                     None,
                 )),
@@ -417,6 +414,10 @@ impl Module for User {
     }
 }
 
+impl ModuleParams for User {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
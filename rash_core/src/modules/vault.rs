@@ -2,13 +2,6 @@
 /// # vault
 ///
 /// Interact with HashiCorp Vault for secrets management.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -67,7 +60,7 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -503,6 +496,10 @@ impl Module for Vault {
     }
 }
 
+impl ModuleParams for Vault {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -3,13 +3,6 @@
 ///
 /// Read, write, and delete secrets from HashiCorp Vault with granular
 /// secret operations supporting both KV v1 and v2 engines.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -59,7 +52,7 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -512,6 +505,10 @@ impl Module for VaultSecret {
     }
 }
 
+impl ModuleParams for VaultSecret {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -3,13 +3,6 @@
 ///
 /// Manage HashiCorp Vault tokens - create, renew, revoke, and lookup tokens.
 /// Complements the existing vault module for complete Vault integration.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -73,7 +66,7 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -560,6 +553,10 @@ impl Module for VaultToken {
     }
 }
 
+impl ModuleParams for VaultToken {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
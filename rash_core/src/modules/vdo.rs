@@ -2,13 +2,6 @@
 /// # vdo
 ///
 /// Manage VDO (Virtual Data Optimizer) volumes.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -43,7 +36,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger::diff;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Capabilities, Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -109,6 +102,13 @@ impl Module for Vdo {
         "vdo"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            diff: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -132,6 +132,10 @@ impl Module for Vdo {
     }
 }
 
+impl ModuleParams for Vdo {
+    type Params = Params;
+}
+
 struct VdoClient {
     check_mode: bool,
 }
@@ -567,6 +571,7 @@ fn vdo_module(params: Params, check_mode: bool) -> Result<ModuleResult> {
         changed: result.changed,
         output: result.output,
         extra: Some(value::to_value(extra)?),
+        ..Default::default()
     })
 }
 
@@ -3,13 +3,6 @@
 ///
 /// Wait until a TCP port accepts connections or `timeout` is reached.
 /// This module fails unless `ignore_errors` is set to `true`.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: none
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -28,7 +21,9 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{
+    Capabilities, CheckModeSupport, Module, ModuleParams, ModuleResult, parse_params,
+};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -118,6 +113,14 @@ impl Module for WaitFor {
         "wait_for"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            // it just waits, so the following tasks find the same state as in a normal run
+            check_mode: CheckModeSupport::Always,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -134,6 +137,10 @@ impl Module for WaitFor {
     }
 }
 
+impl ModuleParams for WaitFor {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -6,13 +6,6 @@
 /// This module sends Wake-on-LAN magic packets to wake up sleeping devices.
 /// Useful for IoT device management, remote server wake-up, scheduled wake-up
 /// automation, and energy-saving workflows.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -31,7 +24,7 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -195,6 +188,10 @@ impl Module for WakeOnLan {
     }
 }
 
+impl ModuleParams for WakeOnLan {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2,13 +2,6 @@
 /// # wipefs
 ///
 /// Wipe filesystem, RAID, or partition table signatures from block devices.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -47,7 +40,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger::diff;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Capabilities, Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -101,6 +94,13 @@ impl Module for Wipefs {
         "wipefs"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            diff: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -120,6 +120,10 @@ impl Module for Wipefs {
     }
 }
 
+impl ModuleParams for Wipefs {
+    type Params = Params;
+}
+
 struct WipefsClient {
     check_mode: bool,
 }
@@ -2,13 +2,6 @@
 /// # wireguard
 ///
 /// Manage WireGuard VPN interfaces and peers.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -57,7 +50,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger::diff_files;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Capabilities, Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -121,6 +114,13 @@ impl Module for Wireguard {
         "wireguard"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            diff: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -137,6 +137,10 @@ impl Module for Wireguard {
     }
 }
 
+impl ModuleParams for Wireguard {
+    type Params = Params;
+}
+
 fn config_path(interface: &str) -> String {
     format!("{WG_CONFIG_DIR}/{interface}.conf")
 }
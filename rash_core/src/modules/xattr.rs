@@ -6,13 +6,6 @@
 /// Extended attributes are key-value metadata stored on filesystems that support them.
 /// They are useful for security labeling, custom metadata, and container/overlay filesystem
 /// configurations.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -46,7 +39,7 @@
 /// ANCHOR_END: examples
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -235,6 +228,7 @@ fn handle_present(params: &Params, check_mode: bool) -> Result<ModuleResult> {
             changed: false,
             output: Some(params.path.clone()),
             extra: None,
+            ..Default::default()
         }),
         Some(current) => {
             debug!(
@@ -248,6 +242,7 @@ fn handle_present(params: &Params, check_mode: bool) -> Result<ModuleResult> {
                 changed: true,
                 output: Some(params.path.clone()),
                 extra: None,
+                ..Default::default()
             })
         }
         None => {
@@ -259,6 +254,7 @@ fn handle_present(params: &Params, check_mode: bool) -> Result<ModuleResult> {
                 changed: true,
                 output: Some(params.path.clone()),
                 extra: None,
+                ..Default::default()
             })
         }
     }
@@ -287,12 +283,14 @@ fn handle_absent(params: &Params, check_mode: bool) -> Result<ModuleResult> {
                 changed: true,
                 output: Some(params.path.clone()),
                 extra: None,
+                ..Default::default()
             })
         }
         None => Ok(ModuleResult {
             changed: false,
             output: Some(params.path.clone()),
             extra: None,
+            ..Default::default()
         }),
     }
 }
@@ -324,6 +322,7 @@ fn handle_all(params: &Params) -> Result<ModuleResult> {
         changed: false,
         output: Some(params.path.clone()),
         extra: Some(extra),
+        ..Default::default()
     })
 }
 
@@ -361,6 +360,10 @@ impl Module for Xattr {
     }
 }
 
+impl ModuleParams for Xattr {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
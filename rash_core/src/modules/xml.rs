@@ -2,13 +2,6 @@
 /// # xml
 ///
 /// Manage settings in XML configuration files.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -40,7 +33,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger::diff;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Capabilities, Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -438,6 +431,7 @@ pub fn xml(params: Params, check_mode: bool) -> Result<ModuleResult> {
         changed,
         output: Some(params.path),
         extra: None,
+        ..Default::default()
     })
 }
 
@@ -449,6 +443,13 @@ impl Module for Xml {
         "xml"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            diff: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -465,6 +466,10 @@ impl Module for Xml {
     }
 }
 
+impl ModuleParams for Xml {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2,13 +2,6 @@
 /// # yum_repository
 ///
 /// Manage YUM/DNF repositories on RHEL/Fedora systems.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Examples
@@ -46,7 +39,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger::diff;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Capabilities, Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -417,6 +410,7 @@ pub fn yum_repository(params: Params, check_mode: bool) -> Result<ModuleResult>
         changed,
         output: Some(repo_path.to_string_lossy().to_string()),
         extra: None,
+        ..Default::default()
     })
 }
 
@@ -428,6 +422,13 @@ impl Module for YumRepository {
         "yum_repository"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            diff: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -447,6 +448,10 @@ impl Module for YumRepository {
     }
 }
 
+impl ModuleParams for YumRepository {
+    type Params = Params;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2,13 +2,6 @@
 /// # zfs
 ///
 /// Manage ZFS datasets.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -71,7 +64,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger::diff;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Capabilities, Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -141,6 +134,13 @@ impl Module for Zfs {
         "zfs"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            diff: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -164,6 +164,10 @@ impl Module for Zfs {
     }
 }
 
+impl ModuleParams for Zfs {
+    type Params = Params;
+}
+
 struct ZfsClient {
     check_mode: bool,
 }
@@ -663,6 +667,7 @@ fn zfs_module(params: Params, check_mode: bool) -> Result<ModuleResult> {
         changed: result.changed,
         output: result.output,
         extra: Some(value::to_value(extra)?),
+        ..Default::default()
     })
 }
 
@@ -2,13 +2,6 @@
 /// # zpool
 ///
 /// Manage ZFS storage pools.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -78,7 +71,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger::diff;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Capabilities, Module, ModuleParams, ModuleResult, parse_params};
 
 #[cfg(feature = "docs")]
 use rash_derive::DocJsonSchema;
@@ -163,6 +156,13 @@ impl Module for Zpool {
         "zpool"
     }
 
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities {
+            diff: true,
+            ..Default::default()
+        }
+    }
+
     fn exec(
         &self,
         _: &GlobalParams,
@@ -186,6 +186,10 @@ impl Module for Zpool {
     }
 }
 
+impl ModuleParams for Zpool {
+    type Params = Params;
+}
+
 struct ZpoolClient {
     check_mode: bool,
 }
@@ -653,6 +657,7 @@ fn zpool_module(params: Params, check_mode: bool) -> Result<ModuleResult> {
         changed: result.changed,
         output: result.output,
         extra: Some(value::to_value(extra)?),
+        ..Default::default()
     })
 }
 
@@ -2,13 +2,6 @@
 /// # zypper
 ///
 /// Manage packages on openSUSE and SUSE Linux Enterprise Server.
-///
-/// ## Attributes
-///
-/// ```yaml
-/// check_mode:
-///   support: full
-/// ```
 /// ANCHOR_END: module
 /// ANCHOR: examples
 /// ## Example
@@ -51,7 +44,7 @@
 use crate::context::GlobalParams;
 use crate::error::{Error, ErrorKind, Result};
 use crate::logger;
-use crate::modules::{Module, ModuleResult, parse_params};
+use crate::modules::{Module, ModuleParams, ModuleResult, parse_params};
 use crate::utils::default_false;
 
 #[cfg(feature = "docs")]
@@ -191,6 +184,10 @@ impl Module for Zypper {
     }
 }
 
+impl ModuleParams for Zypper {
+    type Params = Params;
+}
+
 type IsChanged = bool;
 
 struct ZypperClient {
@@ -489,6 +486,7 @@ fn zypper(params: Params, check_mode: bool) -> Result<ModuleResult> {
         extra: Some(value::to_value(
             json!({"installed_packages": p_to_install, "removed_packages": p_to_remove, "cache_updated": cache_updated}),
         )?),
+        ..Default::default()
     })
 }
 
@@ -0,0 +1,251 @@
+/// Inventory
+///
+/// Hosts where scripts are pushed with `--inventory`, with their SSH settings and vars.
+use crate::error::{Error, ErrorKind, Result};
+use crate::vault;
+
+use std::collections::BTreeMap;
+use std::fs::read_to_string;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use glob::Pattern;
+use serde::Deserialize;
+use serde_norway::{Mapping, Value as YamlValue};
+
+// ANCHOR: inventory
+/// Inventory file content.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Inventory {
+    /// Vars for all hosts.
+    #[serde(default)]
+    pub vars: Mapping,
+    /// Hosts by name. Entries without value use the name as address.
+    #[serde(default)]
+    hosts: BTreeMap<String, Option<Host>>,
+}
+
+/// SSH settings and vars of a host.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Host {
+    /// Address to connect to. Defaults to the host name.
+    pub host: Option<String>,
+    pub port: Option<u16>,
+    pub user: Option<String>,
+    pub identity_file: Option<PathBuf>,
+    /// Extra arguments passed to `ssh`. E.g.: `["-o", "StrictHostKeyChecking=no"]`.
+    #[serde(default)]
+    pub ssh_args: Vec<String>,
+    /// Vars for this host, overriding inventory vars.
+    #[serde(default)]
+    pub vars: Mapping,
+}
+// ANCHOR_END: inventory
+
+impl Inventory {
+    /// Read inventory from a YAML file, decrypting it if it is vault encrypted.
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let content = read_to_string(path)
+            .map_err(Error::from)
+            .and_then(vault::decrypt_if_encrypted)
+            .map_err(|e| {
+                Error::new(
+                    e.kind(),
+                    format!("Failed to read inventory '{}': {e}", path.display()),
+                )
+            })?;
+        Self::from_str(&content).map_err(|e| {
+            Error::new(
+                ErrorKind::InvalidData,
+                format!("Invalid inventory '{}': {e}", path.display()),
+            )
+        })
+    }
+
+    fn from_str(content: &str) -> Result<Self> {
+        serde_norway::from_str(content).map_err(|e| Error::new(ErrorKind::InvalidData, e))
+    }
+
+    /// Return hosts, sorted by name, matching any of the glob patterns. All hosts are returned
+    /// if there are no patterns.
+    pub fn select(&self, patterns: &[Pattern]) -> Result<Vec<(String, Host)>> {
+        let hosts = self
+            .hosts
+            .iter()
+            .filter(|(name, _)| patterns.is_empty() || patterns.iter().any(|p| p.matches(name)))
+            .map(|(name, host)| (name.clone(), host.clone().unwrap_or_default()))
+            .collect::<Vec<_>>();
+
+        match hosts.is_empty() {
+            true => Err(Error::new(
+                ErrorKind::NotFound,
+                format!(
+                    "No hosts in inventory matching: {}",
+                    patterns
+                        .iter()
+                        .map(Pattern::as_str)
+                        .collect::<Vec<_>>()
+                        .join(",")
+                ),
+            )),
+            false => Ok(hosts),
+        }
+    }
+
+    /// Return inventory vars merged with host vars and `inventory_hostname`.
+    pub fn get_host_vars(&self, name: &str, host: &Host) -> Mapping {
+        let mut vars = self.vars.clone();
+        host.vars.iter().for_each(|(key, value)| {
+            vars.insert(key.clone(), value.clone());
+        });
+        vars.insert(
+            YamlValue::String("inventory_hostname".to_owned()),
+            YamlValue::String(name.to_owned()),
+        );
+        vars
+    }
+}
+
+impl Host {
+    /// Return `ssh` command to execute `remote_command` in the host.
+    pub fn ssh_command(&self, name: &str, remote_command: &str) -> Command {
+        let mut cmd = Command::new("ssh");
+        cmd.args(["-o", "BatchMode=yes"]);
+        if let Some(port) = self.port {
+            cmd.arg("-p").arg(port.to_string());
+        }
+        if let Some(user) = &self.user {
+            cmd.arg("-l").arg(user);
+        }
+        if let Some(identity_file) = &self.identity_file {
+            cmd.arg("-i").arg(identity_file);
+        }
+        cmd.args(&self.ssh_args)
+            .arg("--")
+            .arg(self.host.as_deref().unwrap_or(name))
+            .arg(remote_command);
+        cmd
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INVENTORY: &str = r#"
+vars:
+  env: prod
+  greeting: hello
+hosts:
+  web1:
+    host: 10.0.0.1
+    user: deploy
+    port: 2222
+    vars:
+      greeting: hi
+  web2:
+  db1:
+    identity_file: /tmp/id_ed25519
+    ssh_args: ["-o", "StrictHostKeyChecking=no"]
+"#;
+
+    fn get_names(hosts: &[(String, Host)]) -> Vec<&str> {
+        hosts.iter().map(|(name, _)| name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_select() {
+        let inventory = Inventory::from_str(INVENTORY).unwrap();
+
+        assert_eq!(
+            get_names(&inventory.select(&[]).unwrap()),
+            vec!["db1", "web1", "web2"]
+        );
+        assert_eq!(
+            get_names(&inventory.select(&[Pattern::new("web*").unwrap()]).unwrap()),
+            vec!["web1", "web2"]
+        );
+        assert_eq!(
+            get_names(
+                &inventory
+                    .select(&[Pattern::new("db1").unwrap(), Pattern::new("web2").unwrap()])
+                    .unwrap()
+            ),
+            vec!["db1", "web2"]
+        );
+    }
+
+    #[test]
+    fn test_select_not_found() {
+        let inventory = Inventory::from_str(INVENTORY).unwrap();
+        let error = inventory
+            .select(&[Pattern::new("cache*").unwrap()])
+            .unwrap_err();
+
+        assert_eq!(error.kind(), ErrorKind::NotFound);
+        assert_eq!(error.to_string(), "No hosts in inventory matching: cache*");
+    }
+
+    #[test]
+    fn test_get_host_vars() {
+        let inventory = Inventory::from_str(INVENTORY).unwrap();
+        let hosts = inventory.select(&[Pattern::new("web1").unwrap()]).unwrap();
+        let (name, host) = &hosts[0];
+
+        let vars = inventory.get_host_vars(name, host);
+        assert_eq!(
+            serde_norway::to_string(&vars).unwrap(),
+            "env: prod\ngreeting: hi\ninventory_hostname: web1\n"
+        );
+    }
+
+    #[test]
+    fn test_invalid_inventory() {
+        assert!(Inventory::from_str("hosts:\n  web1:\n    address: foo\n").is_err());
+        assert!(Inventory::from_str("foo: bar\n").is_err());
+    }
+
+    #[test]
+    fn test_ssh_command() {
+        let inventory = Inventory::from_str(INVENTORY).unwrap();
+        let hosts = inventory.select(&[]).unwrap();
+        let get_args = |index: usize| {
+            let (name, host) = &hosts[index];
+            host.ssh_command(name, "uname -m")
+                .get_args()
+                .map(|arg| arg.to_string_lossy().into_owned())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            get_args(0),
+            vec![
+                "-o",
+                "BatchMode=yes",
+                "-i",
+                "/tmp/id_ed25519",
+                "-o",
+                "StrictHostKeyChecking=no",
+                "--",
+                "db1",
+                "uname -m"
+            ]
+        );
+        assert_eq!(
+            get_args(1),
+            vec![
+                "-o",
+                "BatchMode=yes",
+                "-p",
+                "2222",
+                "-l",
+                "deploy",
+                "--",
+                "10.0.0.1",
+                "uname -m"
+            ]
+        );
+    }
+}
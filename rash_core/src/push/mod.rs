@@ -0,0 +1,727 @@
+//! Push
+//!
+//! Run scripts in remote hosts over SSH. The `rash` binary, cached by its checksum, and the
+//! script directory are uploaded to each host and executed there with `--output jsonl`. Its
+//! events are streamed back and reported by host.
+
+pub mod inventory;
+
+pub use inventory::{Host, Inventory};
+
+use crate::error::{Error, ErrorKind, Result};
+use crate::events::{self, EVENT_TARGET, Event};
+use crate::logger::{Output, get_output_format};
+use crate::stats::{self, Recap, TaskStatus, TaskTiming};
+
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Output as ProcessOutput, Stdio};
+use std::sync::Mutex;
+use std::thread;
+
+use flate2::Compression;
+use flate2::write::GzEncoder;
+use ignore::WalkBuilder;
+use serde_json::{Map, Value as JsonValue};
+use sha2::{Digest, Sha256};
+
+/// Remote directory where binaries are cached.
+const REMOTE_CACHE_DIR: &str = "${XDG_CACHE_HOME:-$HOME/.cache}/rash";
+/// Vars file added to the uploaded bundle.
+const VARS_FILE: &str = ".rash_push_vars.yml";
+/// Vault password file added to the uploaded bundle when a vault password is set.
+const VAULT_PASSWORD_FILE: &str = ".rash_push_vault_password";
+/// `ssh` exit code on connection errors.
+const SSH_ERROR_EXIT_CODE: i32 = 255;
+
+/// Options of the remote executions.
+#[derive(Debug, Clone)]
+pub struct PushOptions {
+    /// Path to the script. Its directory is uploaded with it, unless `script` is set.
+    pub script_path: PathBuf,
+    /// Inline script, uploaded alone with the file name of `script_path`.
+    pub script: Option<String>,
+    /// `rash` arguments for the remote execution. E.g.: `["--check"]`.
+    pub args: Vec<String>,
+    pub script_args: Vec<String>,
+    /// Vault password to decrypt files in the remote hosts.
+    pub vault_password: Option<String>,
+    /// Max number of hosts running at the same time.
+    pub forks: usize,
+}
+
+/// Result of the execution in a host.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HostResult {
+    pub name: String,
+    pub recap: Recap,
+    /// Connection to the host failed.
+    pub unreachable: bool,
+    /// Execution failed, even if no task failed. E.g.: invalid script.
+    pub failed: bool,
+    /// Durations of the tasks executed in the host.
+    pub timings: Vec<TaskTiming>,
+}
+
+/// Local `rash` binary uploaded to the hosts.
+#[derive(Debug)]
+struct Binary {
+    path: PathBuf,
+    checksum: String,
+}
+
+impl Binary {
+    fn current() -> Result<Self> {
+        let path = std::env::current_exe()?;
+        let checksum = Sha256::digest(fs::read(&path)?)
+            .iter()
+            .map(|b| format!("{b:02x}"))
+            .collect();
+        Ok(Binary { path, checksum })
+    }
+
+    /// Return remote path, as shell expression.
+    fn get_remote_path(&self) -> String {
+        format!("{REMOTE_CACHE_DIR}/rash-{}", self.checksum)
+    }
+}
+
+/// Files uploaded with the script.
+#[derive(Debug)]
+struct Bundle {
+    root: PathBuf,
+    /// Paths relative to `root`.
+    files: Vec<PathBuf>,
+    script_name: String,
+    script: Option<String>,
+}
+
+impl Bundle {
+    /// Collect files in the script directory, respecting `.gitignore` and skipping hidden files.
+    fn new(options: &PushOptions) -> Result<Self> {
+        let script_name = options
+            .script_path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .ok_or_else(|| {
+                Error::new(
+                    ErrorKind::InvalidData,
+                    format!("Invalid script path: {}", options.script_path.display()),
+                )
+            })?;
+        let root = match options.script_path.parent() {
+            Some(parent) if parent != Path::new("") => parent.to_path_buf(),
+            _ => PathBuf::from("."),
+        };
+
+        if options.script.is_some() {
+            return Ok(Bundle {
+                root,
+                files: vec![],
+                script_name,
+                script: options.script.clone(),
+            });
+        }
+
+        let mut files = WalkBuilder::new(&root)
+            .build()
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_type().is_some_and(|t| t.is_file()))
+            .filter_map(|entry| entry.path().strip_prefix(&root).ok().map(Path::to_path_buf))
+            .collect::<Vec<_>>();
+        if !files.iter().any(|file| file == Path::new(&script_name)) {
+            files.push(PathBuf::from(&script_name));
+        }
+        trace!("push bundle files: {files:?}");
+
+        Ok(Bundle {
+            root,
+            files,
+            script_name,
+            script: None,
+        })
+    }
+
+    /// Return `tar.gz` archive with the files, the inline script and the extra files.
+    fn archive(&self, extra_files: &[(&str, String)]) -> Result<Vec<u8>> {
+        let mut tar = tar::Builder::new(GzEncoder::new(Vec::new(), Compression::default()));
+        for file in &self.files {
+            tar.append_path_with_name(self.root.join(file), file)?;
+        }
+
+        let inline_script = self.script.iter().map(|s| (self.script_name.as_str(), s));
+        for (name, content) in inline_script.chain(extra_files.iter().map(|(n, c)| (*n, c))) {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(content.len() as u64);
+            header.set_mode(0o600);
+            header.set_cksum();
+            tar.append_data(&mut header, name, content.as_bytes())?;
+        }
+
+        Ok(tar.into_inner()?.finish()?)
+    }
+}
+
+fn quote(s: &str) -> Result<String> {
+    shlex::try_quote(s)
+        .map(|quoted| quoted.into_owned())
+        .map_err(|e| Error::new(ErrorKind::InvalidData, e))
+}
+
+fn get_stderr(output: &ProcessOutput) -> String {
+    String::from_utf8_lossy(&output.stderr).trim().to_owned()
+}
+
+/// Execution of the script in a host.
+struct HostRun<'a> {
+    name: &'a str,
+    host: &'a Host,
+    result: HostResult,
+    /// A failed task was already reported.
+    reported_failure: bool,
+}
+
+impl<'a> HostRun<'a> {
+    fn new(name: &'a str, host: &'a Host) -> Self {
+        HostRun {
+            name,
+            host,
+            result: HostResult {
+                name: name.to_owned(),
+                ..Default::default()
+            },
+            reported_failure: false,
+        }
+    }
+
+    fn exec(
+        mut self,
+        inventory: &Inventory,
+        binary: &Binary,
+        bundle: &Bundle,
+        options: &PushOptions,
+    ) -> HostResult {
+        let result = self
+            .upload_binary(binary)
+            .and_then(|_| self.run(inventory, binary, bundle, options));
+        if let Err(e) = result {
+            self.result.failed = true;
+            self.fail(&e.to_string());
+        }
+        self.result
+    }
+
+    fn fail(&self, message: &str) {
+        if self.result.unreachable {
+            events::emit(Event::HostUnreachable {
+                host: self.name,
+                error: message,
+            });
+        }
+        error!("[{}] {message}", self.name);
+    }
+
+    /// Upload binary if it is not in the remote cache yet.
+    fn upload_binary(&mut self, binary: &Binary) -> Result<()> {
+        let remote_path = binary.get_remote_path();
+        let output = self
+            .host
+            .ssh_command(
+                self.name,
+                &format!(r#"uname -m; test -x "{remote_path}" && echo cached || echo missing"#),
+            )
+            .output()?;
+        if output.status.code() == Some(SSH_ERROR_EXIT_CODE) {
+            self.result.unreachable = true;
+            return Err(Error::new(ErrorKind::SubprocessFail, get_stderr(&output)));
+        }
+        if !output.status.success() {
+            return Err(Error::new(ErrorKind::SubprocessFail, get_stderr(&output)));
+        }
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let mut lines = stdout.lines();
+        let arch = lines.next().unwrap_or_default().trim();
+        if arch != std::env::consts::ARCH {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "Remote architecture '{arch}' does not match local '{}'",
+                    std::env::consts::ARCH
+                ),
+            ));
+        }
+        if lines.next() == Some("cached") {
+            debug!("[{}] binary already cached: {remote_path}", self.name);
+            return Ok(());
+        }
+
+        debug!("[{}] uploading binary to {remote_path}", self.name);
+        let output = self
+            .host
+            .ssh_command(
+                self.name,
+                &format!(
+                    r#"mkdir -p "{REMOTE_CACHE_DIR}" && cat > "{remote_path}.$$" && chmod 755 "{remote_path}.$$" && mv "{remote_path}.$$" "{remote_path}""#
+                ),
+            )
+            .stdin(File::open(&binary.path)?)
+            .output()?;
+        match output.status.success() {
+            true => Ok(()),
+            false => Err(Error::new(
+                ErrorKind::SubprocessFail,
+                format!("Failed to upload binary: {}", get_stderr(&output)),
+            )),
+        }
+    }
+
+    fn get_remote_command(
+        &self,
+        binary: &Binary,
+        bundle: &Bundle,
+        options: &PushOptions,
+    ) -> Result<String> {
+        let mut args = vec![
+            "--output".to_owned(),
+            "jsonl".to_owned(),
+            "--internal-vars".to_owned(),
+            VARS_FILE.to_owned(),
+        ];
+        if options.vault_password.is_some() {
+            args.extend([
+                "--vault-password-file".to_owned(),
+                VAULT_PASSWORD_FILE.to_owned(),
+            ]);
+        }
+        args.extend(options.args.iter().cloned());
+        args.extend(["--".to_owned(), format!("./{}", bundle.script_name)]);
+        args.extend(options.script_args.iter().cloned());
+
+        let args = args
+            .iter()
+            .map(|arg| quote(arg))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(format!(
+            r#"dir=$(mktemp -d) && trap 'rm -rf "$dir"' EXIT && tar -xzf - -C "$dir" && cd "$dir" && "{}" {}"#,
+            binary.get_remote_path(),
+            args.join(" ")
+        ))
+    }
+
+    /// Upload the bundle and run the script, reporting its events.
+    fn run(
+        &mut self,
+        inventory: &Inventory,
+        binary: &Binary,
+        bundle: &Bundle,
+        options: &PushOptions,
+    ) -> Result<()> {
+        let vars = serde_norway::to_string(&inventory.get_host_vars(self.name, self.host))?;
+        let mut extra_files = vec![(VARS_FILE, vars)];
+        if let Some(password) = &options.vault_password {
+            extra_files.push((VAULT_PASSWORD_FILE, password.clone()));
+        }
+        let archive = bundle.archive(&extra_files)?;
+
+        let mut child = self
+            .host
+            .ssh_command(
+                self.name,
+                &self.get_remote_command(binary, bundle, options)?,
+            )
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+        let mut stdin = child.stdin.take().expect("stdin is piped");
+        let stdout = child.stdout.take().expect("stdout is piped");
+        let mut stderr = child.stderr.take().expect("stderr is piped");
+
+        let stderr_content = thread::scope(|scope| -> Result<String> {
+            let writer = scope.spawn(move || stdin.write_all(&archive));
+            let reader = scope.spawn(move || {
+                let mut content = String::new();
+                stderr.read_to_string(&mut content).map(|_| content)
+            });
+            for line in BufReader::new(stdout).lines() {
+                self.handle_line(&line?);
+            }
+            // remote command can exit before reading stdin, e.g.: if `mktemp` fails
+            let _ = writer.join().expect("writer thread panicked");
+            Ok(reader.join().expect("reader thread panicked")?)
+        })?;
+        let status = child.wait()?;
+
+        stderr_content
+            .lines()
+            .for_each(|line| debug!("[{}] {line}", self.name));
+        if status.code() == Some(SSH_ERROR_EXIT_CODE) {
+            self.result.unreachable = true;
+        }
+        if !status.success() && !self.reported_failure {
+            return Err(Error::new(
+                ErrorKind::SubprocessFail,
+                match stderr_content.trim() {
+                    "" => format!("Remote execution exited with {status}"),
+                    stderr => stderr.to_owned(),
+                },
+            ));
+        }
+        self.result.failed |= !status.success();
+        Ok(())
+    }
+
+    fn handle_line(&mut self, line: &str) {
+        let mut event = match serde_json::from_str::<Map<String, JsonValue>>(line) {
+            Ok(event) => event,
+            Err(_) => {
+                debug!("[{}] {line}", self.name);
+                return;
+            }
+        };
+
+        let get_str = |key: &str| {
+            event
+                .get(key)
+                .and_then(JsonValue::as_str)
+                .unwrap_or_default()
+        };
+        match get_str("event") {
+            "run_end" => {
+                self.result.recap = event
+                    .get("recap")
+                    .cloned()
+                    .and_then(|recap| serde_json::from_value(recap).ok())
+                    .unwrap_or_default();
+            }
+            "task_end" | "loop_item" if get_str("status") == "failed" => {
+                self.reported_failure = true;
+            }
+            _ => (),
+        }
+        if let Some(timing) = get_task_timing(&event) {
+            self.result.timings.push(timing);
+        }
+
+        match get_output_format() {
+            Output::Jsonl => {
+                event.insert("host".to_owned(), JsonValue::from(self.name));
+                info!(target: EVENT_TARGET, "{}", JsonValue::Object(event));
+            }
+            _ => self.log_event(&event),
+        }
+    }
+
+    /// Log event as the tasks executed locally, with the host name.
+    fn log_event(&self, event: &Map<String, JsonValue>) {
+        let name = self.name;
+        let get_str = |key: &str| event.get(key).and_then(JsonValue::as_str);
+        let task = get_str("task").unwrap_or_default();
+
+        match get_str("event").unwrap_or_default() {
+            "task_start" | "handler_start" => info!(target: "task", "[{task}] - {name} - "),
+            "task_retry" => info!(
+                "[{name}] {task}: retrying ({}/{})",
+                event.get("attempt").unwrap_or(&JsonValue::Null),
+                event.get("retries").unwrap_or(&JsonValue::Null),
+            ),
+            "task_end"
+                if matches!(get_str("module"), Some("block" | "parallel" | "include"))
+                    && !matches!(get_str("status"), Some("skipped" | "rescued")) => {}
+            "task_end" | "loop_item" => {
+                let output = get_str("output")
+                    .map(|o| format!(" {o}"))
+                    .unwrap_or_default();
+                let error = get_str("error").unwrap_or_default();
+                match get_str("status").unwrap_or_default() {
+                    "changed" => info!(target: "changed", "[{name}]{output}"),
+                    "failed" => error!(target: "task", "[{name}] {error}"),
+                    "ignored" => info!(target: "ignoring", "[{name}] {error}"),
+                    "skipped" => debug!("[{name}] skipping: {task}"),
+                    _ => info!(target: "ok", "[{name}]{output}"),
+                }
+                if let Some(diff) = get_str("diff") {
+                    info!(target: "diff", "{}", diff.trim_end());
+                }
+            }
+            event_name => trace!("[{name}] {event_name}: {event:?}"),
+        }
+    }
+}
+
+/// Return the timing of a `task_end` event. As in local runs, `block`, `parallel` and `include`
+/// tasks are omitted unless skipped or rescued, because their tasks are timed one by one.
+fn get_task_timing(event: &Map<String, JsonValue>) -> Option<TaskTiming> {
+    let get_str = |key: &str| event.get(key).and_then(JsonValue::as_str);
+    let status = serde_json::from_value(event.get("status")?.clone()).ok()?;
+    let is_control_flow = matches!(get_str("module"), Some("block" | "parallel" | "include"));
+    match get_str("event")? {
+        "task_end"
+            if !is_control_flow || matches!(status, TaskStatus::Skipped | TaskStatus::Rescued) =>
+        {
+            Some(TaskTiming {
+                name: get_str("task")?.to_owned(),
+                status,
+                duration: event.get("duration")?.as_f64()?,
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Run the script in the hosts, in parallel up to `options.forks`, returning the results in
+/// the same order.
+pub fn push(
+    inventory: &Inventory,
+    hosts: &[(String, Host)],
+    options: &PushOptions,
+) -> Result<Vec<HostResult>> {
+    let binary = Binary::current()?;
+    let bundle = Bundle::new(options)?;
+    let queue = Mutex::new(hosts.iter().enumerate());
+    let results = Mutex::new(Vec::with_capacity(hosts.len()));
+
+    thread::scope(|scope| {
+        for _ in 0..options.forks.clamp(1, hosts.len().max(1)) {
+            scope.spawn(|| {
+                loop {
+                    let next = queue.lock().unwrap_or_else(|e| e.into_inner()).next();
+                    let Some((index, (name, host))) = next else {
+                        break;
+                    };
+                    let result =
+                        HostRun::new(name, host).exec(inventory, &binary, &bundle, options);
+                    results
+                        .lock()
+                        .unwrap_or_else(|e| e.into_inner())
+                        .push((index, result));
+                }
+            });
+        }
+    });
+
+    let mut results = results.into_inner().unwrap_or_else(|e| e.into_inner());
+    results.sort_by_key(|(index, _)| *index);
+    Ok(results.into_iter().map(|(_, result)| result).collect())
+}
+
+/// Log recap by host and, if `profile` is enabled, the slowest tasks of all hosts.
+///
+/// JSON output logs a single object with the results and the task timings by host.
+pub fn log_recap(results: &[HostResult], profile: bool) {
+    match get_output_format() {
+        Output::Json => {
+            let hosts = results
+                .iter()
+                .map(|result| {
+                    (
+                        result.name.clone(),
+                        json!({
+                            "recap": result.recap,
+                            "unreachable": result.unreachable,
+                            "failed": result.failed,
+                            "tasks": result.timings,
+                        }),
+                    )
+                })
+                .collect::<Map<_, _>>();
+            info!(target: "recap", "{}", json!({ "hosts": hosts }));
+        }
+        _ => {
+            let width = results
+                .iter()
+                .map(|r| r.name.len())
+                .max()
+                .unwrap_or_default();
+            info!(target: "title", "PLAY RECAP ");
+            results.iter().for_each(|result| {
+                info!(target: "recap",
+                    "{:<width$} : unreachable={:<4} {}",
+                    result.name,
+                    result.unreachable as u8,
+                    result.recap,
+                )
+            });
+            if profile {
+                stats::log_profile(
+                    results
+                        .iter()
+                        .flat_map(|result| {
+                            result.timings.iter().map(|timing| TaskTiming {
+                                name: format!("[{}] {}", result.name, timing.name),
+                                ..timing.clone()
+                            })
+                        })
+                        .collect(),
+                );
+            }
+        }
+    }
+}
+
+/// Return true if any host is unreachable, failed or has failed tasks.
+pub fn is_failed(results: &[HostResult]) -> bool {
+    results
+        .iter()
+        .any(|result| result.unreachable || result.failed || result.recap.failed > 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::io::Cursor;
+
+    use flate2::read::GzDecoder;
+    use tempfile::tempdir;
+
+    fn options(script_path: &Path) -> PushOptions {
+        PushOptions {
+            script_path: script_path.to_path_buf(),
+            script: None,
+            args: vec!["--check".to_owned()],
+            script_args: vec!["foo bar".to_owned()],
+            vault_password: None,
+            forks: 5,
+        }
+    }
+
+    fn get_archive_files(archive: &[u8]) -> Vec<(String, String)> {
+        let mut tar = tar::Archive::new(GzDecoder::new(Cursor::new(archive)));
+        let mut files = tar
+            .entries()
+            .unwrap()
+            .map(|entry| {
+                let mut entry = entry.unwrap();
+                let mut content = String::new();
+                entry.read_to_string(&mut content).unwrap();
+                (entry.path().unwrap().display().to_string(), content)
+            })
+            .collect::<Vec<_>>();
+        files.sort();
+        files
+    }
+
+    #[test]
+    fn test_bundle_archive() {
+        let dir = tempdir().unwrap();
+        let script_path = dir.path().join("main.rh");
+        fs::write(&script_path, "- debug: msg=hi\n").unwrap();
+        fs::create_dir(dir.path().join("templates")).unwrap();
+        fs::write(dir.path().join("templates/app.j2"), "{{ foo }}").unwrap();
+        fs::write(dir.path().join(".hidden"), "secret").unwrap();
+
+        let bundle = Bundle::new(&options(&script_path)).unwrap();
+        let archive = bundle
+            .archive(&[(VARS_FILE, "foo: 1\n".to_owned())])
+            .unwrap();
+
+        assert_eq!(
+            get_archive_files(&archive),
+            vec![
+                (VARS_FILE.to_owned(), "foo: 1\n".to_owned()),
+                ("main.rh".to_owned(), "- debug: msg=hi\n".to_owned()),
+                ("templates/app.j2".to_owned(), "{{ foo }}".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_bundle_archive_inline_script() {
+        let bundle = Bundle::new(&PushOptions {
+            script: Some("- debug: msg=hi\n".to_owned()),
+            ..options(Path::new("rash"))
+        })
+        .unwrap();
+
+        assert_eq!(bundle.root, PathBuf::from("."));
+        assert_eq!(
+            get_archive_files(&bundle.archive(&[]).unwrap()),
+            vec![("rash".to_owned(), "- debug: msg=hi\n".to_owned())]
+        );
+    }
+
+    #[test]
+    fn test_get_remote_command() {
+        let binary = Binary {
+            path: PathBuf::from("/usr/bin/rash"),
+            checksum: "abc".to_owned(),
+        };
+        let bundle = Bundle::new(&PushOptions {
+            script: Some(String::new()),
+            ..options(Path::new("rash"))
+        })
+        .unwrap();
+        let host = Host::default();
+
+        assert_eq!(
+            HostRun::new("web1", &host)
+                .get_remote_command(&binary, &bundle, &options(Path::new("rash")))
+                .unwrap(),
+            format!(
+                r#"dir=$(mktemp -d) && trap 'rm -rf "$dir"' EXIT && tar -xzf - -C "$dir" && cd "$dir" && "{REMOTE_CACHE_DIR}/rash-abc" --output jsonl --internal-vars {VARS_FILE} --check -- ./rash 'foo bar'"#
+            )
+        );
+    }
+
+    #[test]
+    fn test_handle_line() {
+        let host = Host::default();
+        let mut run = HostRun::new("web1", &host);
+
+        run.handle_line("not an event");
+        run.handle_line(
+            r#"{"version":1,"timestamp":1.0,"event":"task_end","task":"rash:foo","module":"command","status":"failed","error":"boom","duration":0.25}"#,
+        );
+        run.handle_line(
+            r#"{"version":1,"timestamp":1.0,"event":"task_end","task":"rash:bar","module":"block","status":"ok","duration":0.5}"#,
+        );
+        run.handle_line(
+            r#"{"version":1,"timestamp":1.0,"event":"run_end","recap":{"ok":2,"changed":1,"failed":1,"skipped":0,"rescued":0,"ignored":0},"duration":0.5}"#,
+        );
+
+        assert!(run.reported_failure);
+        assert_eq!(
+            run.result.recap,
+            Recap {
+                ok: 2,
+                changed: 1,
+                failed: 1,
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            run.result.timings,
+            vec![TaskTiming {
+                name: "rash:foo".to_owned(),
+                status: TaskStatus::Failed,
+                duration: 0.25,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_is_failed() {
+        let ok = HostResult {
+            name: "web1".to_owned(),
+            ..Default::default()
+        };
+        assert!(!is_failed(std::slice::from_ref(&ok)));
+        assert!(is_failed(&[
+            ok.clone(),
+            HostResult {
+                unreachable: true,
+                ..ok.clone()
+            }
+        ]));
+        assert!(is_failed(&[HostResult {
+            recap: Recap {
+                failed: 1,
+                ..Default::default()
+            },
+            ..ok
+        }]));
+    }
+}
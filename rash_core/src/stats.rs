@@ -0,0 +1,257 @@
+//! Stats
+//!
+//! Task results and durations of the current run, reported at the end of it with `--recap`
+//! and `--profile`.
+
+use crate::error::Result;
+use crate::events::{self, Event, Outcome, TaskInfo};
+use crate::logger::{Output, get_output_format};
+use crate::task::TaskExecResult;
+use crate::utils::get_terminal_width;
+
+use std::fmt;
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+/// Max number of tasks printed in the profile report.
+const PROFILE_TASKS_LIMIT: usize = 20;
+
+/// Final status of a task execution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskStatus {
+    Ok,
+    Changed,
+    Failed,
+    Skipped,
+    Rescued,
+    Ignored,
+}
+
+/// Counters of task results. Changed tasks are counted as `ok` too.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Recap {
+    pub ok: usize,
+    pub changed: usize,
+    pub failed: usize,
+    pub skipped: usize,
+    pub rescued: usize,
+    pub ignored: usize,
+}
+
+impl fmt::Display for Recap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "ok={:<4} changed={:<4} failed={:<4} skipped={:<4} rescued={:<4} ignored={}",
+            self.ok, self.changed, self.failed, self.skipped, self.rescued, self.ignored
+        )
+    }
+}
+
+/// Wall-clock duration of a task.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TaskTiming {
+    pub name: String,
+    pub status: TaskStatus,
+    /// Duration in seconds.
+    pub duration: f64,
+}
+
+#[derive(Debug, Default)]
+struct Stats {
+    recap: Recap,
+    timings: Vec<TaskTiming>,
+}
+
+static STATS: LazyLock<Mutex<Stats>> = LazyLock::new(|| Mutex::new(Stats::default()));
+
+fn with_stats<T>(f: impl FnOnce(&mut Stats) -> T) -> T {
+    f(&mut STATS.lock().unwrap_or_else(|e| e.into_inner()))
+}
+
+/// Add task result to the recap and its duration to the timings.
+pub fn record_task(name: &str, status: TaskStatus, duration: Duration) {
+    debug!("{name} finished in {duration:.2?}");
+    with_stats(|stats| {
+        let recap = &mut stats.recap;
+        match status {
+            TaskStatus::Ok => recap.ok += 1,
+            TaskStatus::Changed => {
+                recap.ok += 1;
+                recap.changed += 1;
+            }
+            TaskStatus::Failed => recap.failed += 1,
+            TaskStatus::Skipped => recap.skipped += 1,
+            TaskStatus::Rescued => recap.rescued += 1,
+            TaskStatus::Ignored => recap.ignored += 1,
+        };
+        stats.timings.push(TaskTiming {
+            name: name.to_owned(),
+            status,
+            duration: duration.as_secs_f64(),
+        });
+    });
+}
+
+/// Execute a task, record its result and emit its start and end events.
+///
+/// `block`, `parallel` and `include` tasks are just recorded when skipped, rescued or failed without any
+/// of their tasks failing, because their tasks are recorded one by one.
+pub fn track_task<F>(task: &TaskInfo, exec: F) -> Result<TaskExecResult>
+where
+    F: FnOnce() -> Result<TaskExecResult>,
+{
+    events::emit(match task.handler {
+        true => Event::HandlerStart(task),
+        false => Event::TaskStart(task),
+    });
+
+    let failed_before = get_recap().failed;
+    let start = Instant::now();
+    let result = exec();
+    let duration = start.elapsed();
+
+    if events::is_enabled() {
+        events::emit(Event::TaskEnd {
+            task,
+            outcome: Outcome::new(&result, duration),
+        });
+    }
+
+    let status = match &result {
+        Ok(exec_result) => exec_result.get_status(),
+        Err(_) if get_recap().failed > failed_before => return result,
+        Err(_) => TaskStatus::Failed,
+    };
+
+    let is_control_flow = matches!(task.module.as_str(), "block" | "parallel" | "include");
+    if !is_control_flow
+        || matches!(
+            status,
+            TaskStatus::Skipped | TaskStatus::Rescued | TaskStatus::Failed
+        )
+    {
+        record_task(&task.task, status, duration);
+    }
+    result
+}
+
+pub fn get_recap() -> Recap {
+    with_stats(|stats| stats.recap.clone())
+}
+
+/// Overwrite failed counter, used to forget failures recovered by `rescue` tasks.
+pub fn set_failed(failed: usize) {
+    with_stats(|stats| stats.recap.failed = failed);
+}
+
+/// Return task timings in execution order.
+pub fn get_timings() -> Vec<TaskTiming> {
+    with_stats(|stats| stats.timings.clone())
+}
+
+/// Return the slowest tasks, sorted by duration.
+pub fn get_slowest_tasks(limit: usize) -> Vec<TaskTiming> {
+    sort_slowest(get_timings(), limit)
+}
+
+fn sort_slowest(mut timings: Vec<TaskTiming>, limit: usize) -> Vec<TaskTiming> {
+    timings.sort_by(|a, b| b.duration.total_cmp(&a.duration));
+    timings.truncate(limit);
+    timings
+}
+
+fn format_profile_line(timing: &TaskTiming) -> String {
+    let duration = format!("{:.2}s", timing.duration);
+    let fill_len = get_terminal_width()
+        .saturating_sub(timing.name.len() + duration.len() + 2)
+        .max(3);
+    format!("{} {} {duration}", timing.name, "-".repeat(fill_len))
+}
+
+/// Log run recap and, if `profile` is enabled, the slowest tasks.
+///
+/// JSON output logs a single object with the recap and the timings of all tasks.
+pub fn log_recap(profile: bool) {
+    match get_output_format() {
+        Output::Json => {
+            let recap = json!({
+                "recap": get_recap(),
+                "tasks": get_timings(),
+            });
+            info!(target: "recap", "{recap}");
+        }
+        _ => {
+            info!(target: "title", "PLAY RECAP ");
+            info!(target: "recap", "{}", get_recap());
+            if profile {
+                log_profile(get_timings());
+            }
+        }
+    }
+}
+
+/// Log the slowest tasks of `timings` with their durations.
+pub fn log_profile(timings: Vec<TaskTiming>) {
+    info!(target: "title", "TASKS PROFILE ");
+    sort_slowest(timings, PROFILE_TASKS_LIMIT)
+        .iter()
+        .for_each(|timing| info!(target: "recap", "{}", format_profile_line(timing)));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timing(name: &str, duration: f64) -> TaskTiming {
+        TaskTiming {
+            name: name.to_owned(),
+            status: TaskStatus::Ok,
+            duration,
+        }
+    }
+
+    #[test]
+    fn test_recap_display() {
+        let recap = Recap {
+            ok: 3,
+            changed: 1,
+            ..Default::default()
+        };
+        assert_eq!(
+            recap.to_string(),
+            "ok=3    changed=1    failed=0    skipped=0    rescued=0    ignored=0"
+        );
+    }
+
+    #[test]
+    fn test_format_profile_line() {
+        let line = format_profile_line(&timing("rash:install packages", 12.345));
+        assert!(line.starts_with("rash:install packages ---"));
+        assert!(line.ends_with("--- 12.35s"));
+    }
+
+    #[test]
+    fn test_sort_slowest() {
+        let timings = vec![
+            timing("rash:a", 1.0),
+            timing("rash:b", 3.0),
+            timing("rash:c", 2.0),
+        ];
+        assert_eq!(
+            sort_slowest(timings, 2),
+            vec![timing("rash:b", 3.0), timing("rash:c", 2.0)]
+        );
+    }
+
+    #[test]
+    fn test_task_status_serialize() {
+        assert_eq!(
+            serde_json::to_string(&timing("rash:debug", 0.5)).unwrap(),
+            r#"{"name":"rash:debug","status":"ok","duration":0.5}"#
+        );
+    }
+}
@@ -0,0 +1,70 @@
+use crate::context::GlobalParams;
+use crate::error::Result;
+use crate::jinja::render_string;
+use crate::modules::include::Params as IncludeParams;
+use crate::task::{Task, parse_included_file, set_script_path};
+
+use std::path::Path;
+
+use minijinja::Value;
+use serde_norway::Value as YamlValue;
+
+/// Function called with each child task and the vars to render it.
+pub type Visit<'f> = dyn FnMut(&Task, &Value) -> Result<()> + 'f;
+
+fn for_each_in_sequence(
+    tasks_yaml: Option<&YamlValue>,
+    global_params: &GlobalParams,
+    vars: &Value,
+    f: &mut Visit,
+) -> Result<()> {
+    for child_yaml in tasks_yaml
+        .and_then(|yaml| yaml.as_sequence())
+        .into_iter()
+        .flatten()
+    {
+        f(&Task::new(child_yaml, global_params)?, vars)?;
+    }
+    Ok(())
+}
+
+/// Call `f` with each task executed by the module of `task`: tasks of `block` and `parallel`
+/// and tasks of the file included by `include`.
+pub fn for_each_main_child(task: &Task, vars: &Value, f: &mut Visit) -> Result<()> {
+    let children_global_params = task.get_children_global_params();
+    match task.module.get_name() {
+        "block" | "parallel" => {
+            for_each_in_sequence(Some(&task.params), &children_global_params, vars, f)
+        }
+        "include" => {
+            // Included files are resolved in runtime, so they are walked when they can be
+            // rendered with the initial vars.
+            let params = match IncludeParams::from_yaml(task.params.clone()).and_then(|params| {
+                render_string(&params.file, vars).map(|file| IncludeParams { file, ..params })
+            }) {
+                Ok(params) => params,
+                _ => {
+                    debug!("skipping tasks from include: {:?}", task.params);
+                    return Ok(());
+                }
+            };
+            let including_path = task.get_script_path(vars).unwrap_or_default();
+            let include_path = params.get_path(Path::new(&including_path));
+            let include_vars = set_script_path(vars.clone(), &include_path)?;
+            for child in parse_included_file(&include_path, &params, &children_global_params)? {
+                f(&child, &include_vars)?;
+            }
+            Ok(())
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Call `f` with each task created from `task`, in order: tasks executed by its module and
+/// tasks in its `rescue` and `always` sections.
+pub fn for_each_child(task: &Task, vars: &Value, f: &mut Visit) -> Result<()> {
+    for_each_main_child(task, vars, f)?;
+    let children_global_params = task.get_children_global_params();
+    for_each_in_sequence(task.rescue.as_ref(), &children_global_params, vars, f)?;
+    for_each_in_sequence(task.always.as_ref(), &children_global_params, vars, f)
+}
@@ -0,0 +1,303 @@
+//! Debugger
+//!
+//! Interactive prompt opened when a task fails, to inspect its variables, fix its params and
+//! retry it, skip it or quit.
+
+use crate::error::{Error, ErrorKind, Result};
+use crate::jinja::eval_expression;
+use crate::logger::{NO_LOG_MESSAGE, is_no_log, mask_secrets};
+
+use std::io::{BufRead, Write};
+
+use minijinja::Value;
+use serde::Deserialize;
+use serde_norway::Value as YamlValue;
+
+const HELP: &str = "\
+Commands:
+  p, print EXPR      print the result of a template expression, e.g.: p item.name | upper
+  vars               print all variables of the task
+  params [VALUE]     print task params, or replace them with a YAML value
+  set KEY VALUE      set a task param to a YAML value
+  r, retry           execute the task again, with the new params
+  s, skip            skip the task and continue with the next one
+  q, quit            fail with the task error
+  h, help            print this help";
+
+/// When the debugger is opened for a task.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Debugger {
+    #[default]
+    Never,
+    OnFailed,
+}
+
+impl Debugger {
+    pub fn from_yaml(value: &YamlValue) -> Result<Self> {
+        Debugger::deserialize(value).map_err(|e| {
+            Error::new(
+                ErrorKind::InvalidData,
+                format!("Invalid debugger value {value:?}: {e}"),
+            )
+        })
+    }
+}
+
+/// What to do with the failed task, as chosen in the debugger.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Action {
+    /// Execute the task again with these params.
+    Retry(YamlValue),
+    Skip,
+    Quit,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Command {
+    Print(String),
+    Vars,
+    Params(Option<YamlValue>),
+    Set(String, YamlValue),
+    Retry,
+    Skip,
+    Quit,
+    Help,
+}
+
+impl Command {
+    fn parse(line: &str) -> Result<Self> {
+        let line = line.trim();
+        let (command, args) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+        let args = args.trim();
+        let parse_value = |s: &str| -> Result<YamlValue> { Ok(serde_norway::from_str(s)?) };
+
+        match (command, args) {
+            ("p" | "print", "") => Err(Error::new(
+                ErrorKind::InvalidData,
+                "missing expression to print",
+            )),
+            ("p" | "print", expression) => Ok(Command::Print(expression.to_owned())),
+            ("vars", "") => Ok(Command::Vars),
+            ("params", "") => Ok(Command::Params(None)),
+            ("params", value) => Ok(Command::Params(Some(parse_value(value)?))),
+            ("set", args) => match args.split_once(char::is_whitespace) {
+                Some((key, value)) => Ok(Command::Set(key.to_owned(), parse_value(value.trim())?)),
+                None => Err(Error::new(ErrorKind::InvalidData, "usage: set KEY VALUE")),
+            },
+            ("r" | "retry", "") => Ok(Command::Retry),
+            ("s" | "skip", "") => Ok(Command::Skip),
+            ("q" | "quit", "") => Ok(Command::Quit),
+            ("h" | "help" | "", "") => Ok(Command::Help),
+            _ => Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("unknown command: {line}"),
+            )),
+        }
+    }
+}
+
+/// Values are masked as in the task output, and hidden with `no_log`.
+fn to_pretty_string<T: serde::Serialize>(value: &T) -> String {
+    if is_no_log() {
+        return NO_LOG_MESSAGE.to_owned();
+    }
+    let s = serde_json::to_string_pretty(value).unwrap_or_else(|e| e.to_string());
+    mask_secrets(&s).into_owned()
+}
+
+/// Read commands from `input` until the user chooses what to do with the task, writing the
+/// output to `output`. On end of input, the task fails.
+pub fn run<R: BufRead, W: Write>(
+    mut input: R,
+    mut output: W,
+    task_name: &str,
+    error: &Error,
+    vars: &Value,
+    params: &YamlValue,
+) -> Result<Action> {
+    let mut params = params.clone();
+    writeln!(output, "[{task_name}] failed: {error}")?;
+    writeln!(
+        output,
+        "Entering debugger, type `help` to list the commands."
+    )?;
+
+    loop {
+        write!(output, "(debug) ")?;
+        output.flush()?;
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 {
+            writeln!(output)?;
+            return Ok(Action::Quit);
+        }
+
+        let result = Command::parse(&line).and_then(|command| match command {
+            Command::Print(expression) => {
+                let value = eval_expression(&expression, vars)?;
+                writeln!(output, "{}", to_pretty_string(&value))?;
+                Ok(None)
+            }
+            Command::Vars => {
+                writeln!(output, "{}", to_pretty_string(vars))?;
+                Ok(None)
+            }
+            Command::Params(None) => {
+                writeln!(output, "{}", to_pretty_string(&params))?;
+                Ok(None)
+            }
+            Command::Params(Some(value)) => {
+                params = value;
+                Ok(None)
+            }
+            Command::Set(key, value) => match params.as_mapping_mut() {
+                Some(mapping) => {
+                    mapping.insert(YamlValue::String(key), value);
+                    Ok(None)
+                }
+                None => Err(Error::new(
+                    ErrorKind::InvalidData,
+                    "params are not a mapping, replace them with `params VALUE`",
+                )),
+            },
+            Command::Retry => Ok(Some(Action::Retry(params.clone()))),
+            Command::Skip => Ok(Some(Action::Skip)),
+            Command::Quit => Ok(Some(Action::Quit)),
+            Command::Help => {
+                writeln!(output, "{HELP}")?;
+                Ok(None)
+            }
+        });
+
+        match result {
+            Ok(Some(action)) => return Ok(action),
+            Ok(None) => (),
+            Err(e) => writeln!(output, "error: {e}")?,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use minijinja::context;
+
+    fn run_commands(commands: &str, params: &str) -> (Action, String) {
+        let mut output = Vec::new();
+        let action = run(
+            commands.as_bytes(),
+            &mut output,
+            "test",
+            &Error::new(ErrorKind::InvalidData, "boom"),
+            &context! {foo => "boo", items => vec![1, 2]},
+            &serde_norway::from_str(params).unwrap(),
+        )
+        .unwrap();
+        (action, String::from_utf8(output).unwrap())
+    }
+
+    #[test]
+    fn test_debugger_from_yaml() {
+        assert_eq!(
+            Debugger::from_yaml(&YamlValue::String("on_failed".to_owned())).unwrap(),
+            Debugger::OnFailed
+        );
+        assert_eq!(
+            Debugger::from_yaml(&YamlValue::String("never".to_owned())).unwrap(),
+            Debugger::Never
+        );
+        let error = Debugger::from_yaml(&YamlValue::Bool(true)).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_command_parse() {
+        assert_eq!(
+            Command::parse("p foo | upper\n").unwrap(),
+            Command::Print("foo | upper".to_owned())
+        );
+        assert_eq!(
+            Command::parse("set cmd ls -l").unwrap(),
+            Command::Set("cmd".to_owned(), YamlValue::String("ls -l".to_owned()))
+        );
+        assert_eq!(
+            Command::parse("params {cmd: ls}").unwrap(),
+            Command::Params(Some(serde_norway::from_str("cmd: ls").unwrap()))
+        );
+        assert_eq!(Command::parse("\n").unwrap(), Command::Help);
+        assert!(Command::parse("p").is_err());
+        assert!(Command::parse("set cmd").is_err());
+        assert!(Command::parse("retry now").is_err());
+        assert!(Command::parse("foo").is_err());
+    }
+
+    #[test]
+    fn test_run_print() {
+        let (action, output) = run_commands("p foo | upper\np items | length\nvars\nq\n", "{}");
+        assert_eq!(action, Action::Quit);
+        assert!(output.starts_with("[test] failed: boom\n"));
+        assert!(output.contains("(debug) \"BOO\"\n"));
+        assert!(output.contains("(debug) 2\n"));
+        assert!(output.contains("\"foo\": \"boo\""));
+    }
+
+    #[test]
+    fn test_run_print_error() {
+        let (action, output) = run_commands("p undefined_var.foo\ns\n", "{}");
+        assert_eq!(action, Action::Skip);
+        assert!(output.contains("(debug) error: "));
+    }
+
+    #[test]
+    fn test_run_retry_with_params() {
+        let (action, output) =
+            run_commands("params\nset cmd ls -l\nset chdir /tmp\nr\n", "cmd: ls");
+        assert_eq!(
+            action,
+            Action::Retry(serde_norway::from_str("{cmd: ls -l, chdir: /tmp}").unwrap())
+        );
+        assert!(output.contains("\"cmd\": \"ls\""));
+    }
+
+    #[test]
+    fn test_run_set_not_mapping() {
+        let (action, output) = run_commands("set cmd ls\nparams ls -l\nretry\n", "ls");
+        assert_eq!(action, Action::Retry(YamlValue::String("ls -l".to_owned())));
+        assert!(output.contains("error: params are not a mapping"));
+    }
+
+    #[test]
+    fn test_run_masks_secrets() {
+        crate::logger::add_secret("debugger-secret");
+        let mut output = Vec::new();
+        run(
+            "p token\nvars\nparams\nq\n".as_bytes(),
+            &mut output,
+            "test",
+            &Error::new(ErrorKind::InvalidData, "boom"),
+            &context! {token => "debugger-secret"},
+            &serde_norway::from_str("password: debugger-secret").unwrap(),
+        )
+        .unwrap();
+        let output = String::from_utf8(output).unwrap();
+
+        assert!(!output.contains("debugger-secret"));
+        assert_eq!(output.matches("********").count(), 3);
+    }
+
+    #[test]
+    fn test_run_no_log() {
+        let _no_log_guard = crate::logger::NoLogGuard::new();
+        let (_, output) = run_commands("p foo\nvars\nq\n", "{}");
+
+        assert!(!output.contains("\"boo\""));
+        assert_eq!(output.matches(NO_LOG_MESSAGE).count(), 2);
+    }
+
+    #[test]
+    fn test_run_eof() {
+        let (action, _) = run_commands("vars\n", "{}");
+        assert_eq!(action, Action::Quit);
+    }
+}
@@ -0,0 +1,222 @@
+use crate::error::{Error, ErrorKind, Result};
+use crate::jinja::{render_map, render_string};
+use crate::vault;
+
+use std::fs::read_to_string;
+use std::path::Path;
+
+use minijinja::{Value, context};
+use serde::Deserialize;
+use serde_norway::{Mapping, Value as YamlValue};
+
+/// Script-wide settings, declared in the document mapping next to `tasks` and `handlers`.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Header {
+    /// Variables available in all the tasks, rendered before the first task.
+    pub vars: Option<Mapping>,
+    /// YAML or JSON files with variables, relative to the script directory.
+    #[serde(default)]
+    pub vars_files: Vec<String>,
+    /// Environment variables set before the first task.
+    pub environment: Option<Mapping>,
+    /// Run all the tasks with become, as `--become` does.
+    #[serde(rename = "become")]
+    pub r#become: Option<bool>,
+    /// Run all the tasks in check mode, as `--check` does.
+    pub check_mode: Option<bool>,
+}
+
+impl Header {
+    /// Parse header from the keys of the script mapping besides `tasks` and `handlers`.
+    /// Scripts with just a list of tasks have an empty header.
+    pub fn from_yaml(yaml: &YamlValue) -> Result<Self> {
+        match yaml {
+            YamlValue::Mapping(mapping) => {
+                let mut mapping = mapping.clone();
+                mapping.remove("tasks");
+                mapping.remove("handlers");
+                Header::deserialize(YamlValue::Mapping(mapping)).map_err(|e| {
+                    Error::new(
+                        ErrorKind::InvalidData,
+                        format!("Invalid script header: {e}"),
+                    )
+                })
+            }
+            _ => Ok(Header::default()),
+        }
+    }
+
+    /// Return environment variables in the same form as `--environment` values.
+    pub fn get_environment(&self) -> Result<Vec<(String, String)>> {
+        self.environment
+            .iter()
+            .flatten()
+            .map(|(key, value)| match (key.as_str(), value) {
+                (Some(key), YamlValue::String(value)) => Ok((key.to_owned(), value.to_owned())),
+                (Some(key), YamlValue::Number(value)) => Ok((key.to_owned(), value.to_string())),
+                (Some(key), YamlValue::Bool(value)) => Ok((key.to_owned(), value.to_string())),
+                _ => Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!("Invalid environment variable in script header: {key:?}: {value:?}"),
+                )),
+            })
+            .collect()
+    }
+
+    /// Return `vars` rendered with `vars`, overridden by the variables of `vars_files` in order.
+    pub fn get_vars(&self, script_path: &Path, vars: &Value) -> Result<Value> {
+        let header_vars = match &self.vars {
+            Some(header_vars) => {
+                Value::from_serialize(render_map(header_vars.clone(), vars, false)?)
+            }
+            None => context! {},
+        };
+
+        self.vars_files
+            .iter()
+            .try_fold(header_vars, |header_vars, file| {
+                let path = script_path
+                    .parent()
+                    .unwrap_or(Path::new(""))
+                    .join(render_string(file, vars)?);
+                let file_vars = read_vars_file(&path)?;
+                Ok(context! {..file_vars, ..header_vars})
+            })
+    }
+}
+
+fn read_vars_file(path: &Path) -> Result<Value> {
+    let content = read_to_string(path)
+        .map_err(|e| {
+            Error::new(
+                ErrorKind::InvalidData,
+                format!("Error reading vars file {}: {e}", path.display()),
+            )
+        })
+        .and_then(vault::decrypt_if_encrypted)?;
+
+    match serde_norway::from_str(&content)? {
+        YamlValue::Mapping(vars) => Ok(Value::from_serialize(vars)),
+        _ => Err(Error::new(
+            ErrorKind::InvalidData,
+            format!("Expected a YAML mapping of vars in {}", path.display()),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::fs;
+
+    use tempfile::tempdir;
+
+    fn header(yaml: &str) -> Result<Header> {
+        Header::from_yaml(&serde_norway::from_str(yaml).unwrap())
+    }
+
+    #[test]
+    fn test_header_from_yaml() {
+        let header = header(
+            r#"
+            vars:
+              foo: boo
+            vars_files:
+              - vars.yml
+            environment:
+              FOO: boo
+            become: true
+            check_mode: false
+            tasks:
+              - debug:
+                  msg: foo
+            handlers: []
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(header.vars_files, vec!["vars.yml"]);
+        assert_eq!(header.r#become, Some(true));
+        assert_eq!(header.check_mode, Some(false));
+    }
+
+    #[test]
+    fn test_header_from_yaml_tasks_list() {
+        assert_eq!(header("- debug: {}").unwrap(), Header::default());
+    }
+
+    #[test]
+    fn test_header_from_yaml_unknown_key() {
+        let error = header("tasks: []\nbecome_user: foo").unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+        assert!(
+            error
+                .to_string()
+                .starts_with("Invalid script header: unknown field `become_user`")
+        );
+    }
+
+    #[test]
+    fn test_header_get_environment() {
+        let environment = header("environment:\n  FOO: boo\n  PORT: 8080\n  DEBUG: true")
+            .unwrap()
+            .get_environment()
+            .unwrap();
+        assert_eq!(
+            environment,
+            vec![
+                ("FOO".to_owned(), "boo".to_owned()),
+                ("PORT".to_owned(), "8080".to_owned()),
+                ("DEBUG".to_owned(), "true".to_owned()),
+            ]
+        );
+
+        let error = header("environment:\n  FOO: [boo]")
+            .unwrap()
+            .get_environment()
+            .unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_header_get_vars() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("first.yml"), "foo: first\nbar: first\n").unwrap();
+        fs::write(dir.path().join("second.json"), r#"{"bar": "second"}"#).unwrap();
+
+        let vars = header(
+            r#"
+            vars:
+              foo: "{{ name }}"
+              boo: "{{ name }}-boo"
+            vars_files:
+              - first.yml
+              - "{{ name }}.json"
+            "#,
+        )
+        .unwrap()
+        .get_vars(&dir.path().join("main.rh"), &context! {name => "second"})
+        .unwrap();
+
+        assert_eq!(
+            vars,
+            context! {foo => "first", bar => "second", boo => "second-boo"}
+        );
+    }
+
+    #[test]
+    fn test_header_get_vars_file_not_found() {
+        let error = header("vars_files: [missing.yml]")
+            .unwrap()
+            .get_vars(Path::new("/tmp/main.rh"), &context! {})
+            .unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+        assert!(
+            error
+                .to_string()
+                .starts_with("Error reading vars file /tmp/missing.yml: ")
+        );
+    }
+}
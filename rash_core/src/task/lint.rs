@@ -0,0 +1,1023 @@
+use crate::context::GlobalParams;
+use crate::error::Result;
+use crate::jinja::{compile_expression, compile_string, render_map, render_string};
+use crate::modules::include::Params as IncludeParams;
+use crate::task::{Header, Task, get_import_params};
+use crate::vars::builtin::Builtins;
+
+use std::collections::HashSet;
+use std::fmt;
+use std::fs::read_to_string;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+use minijinja::{Value, context};
+use regex::Regex;
+use serde::Deserialize;
+use serde_norway::{Mapping, Value as YamlValue};
+
+/// Problem found in a script without executing it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LintProblem {
+    pub path: PathBuf,
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl fmt::Display for LintProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "{}:{}: {}", self.path.display(), line, self.message),
+            None => write!(f, "{}: {}", self.path.display(), self.message),
+        }
+    }
+}
+
+/// Path to a node in a YAML document.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum Segment {
+    Key(String),
+    Index(usize),
+}
+
+fn key(k: &str) -> Segment {
+    Segment::Key(k.to_owned())
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum TokenKind {
+    Item,
+    Key(String),
+}
+
+/// Block sequence item or mapping key found in a YAML document.
+#[derive(Debug, Clone, PartialEq)]
+struct Token {
+    line: usize,
+    column: usize,
+    kind: TokenKind,
+}
+
+static KEY_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"^("[^"]*"|'[^']*'|[^\s#'"\{\[&*!|>%@`][^#]*?)\s*:(\s|$)"#).unwrap()
+});
+static BLOCK_SCALAR_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"[|>][-+0-9]*\s*(#.*)?$").unwrap());
+
+/// Line locator for block style YAML documents.
+///
+/// It doesn't parse YAML, it just tracks sequence items and mapping keys by their
+/// indentation, which is enough to point problems to the line of the task or attribute.
+#[derive(Debug)]
+pub(super) struct Positions {
+    tokens: Vec<Token>,
+}
+
+impl Positions {
+    pub(super) fn new(content: &str) -> Self {
+        let mut tokens = Vec::new();
+        let mut block_scalar_column: Option<usize> = None;
+
+        for (index, raw_line) in content.lines().enumerate() {
+            let indent = raw_line.len() - raw_line.trim_start_matches(' ').len();
+            let mut rest = raw_line.trim_start_matches(' ');
+
+            if let Some(column) = block_scalar_column {
+                if rest.is_empty() || indent > column {
+                    continue;
+                }
+                block_scalar_column = None;
+            }
+
+            if rest.is_empty() || rest.starts_with('#') || rest.starts_with("---") {
+                continue;
+            }
+
+            let mut column = indent;
+            let line_column = indent;
+            while rest == "-" || rest.starts_with("- ") {
+                tokens.push(Token {
+                    line: index + 1,
+                    column,
+                    kind: TokenKind::Item,
+                });
+                let trimmed = rest[1..].trim_start_matches(' ');
+                column += rest.len() - trimmed.len();
+                rest = trimmed;
+            }
+
+            if let Some(captures) = KEY_REGEX.captures(rest) {
+                let key = captures[1].trim_matches(|c| c == '"' || c == '\'');
+                tokens.push(Token {
+                    line: index + 1,
+                    column,
+                    kind: TokenKind::Key(key.to_owned()),
+                });
+                rest = &rest[captures[0].len()..];
+            }
+
+            if BLOCK_SCALAR_REGEX.is_match(rest) && !rest.trim_start().starts_with('#') {
+                block_scalar_column = Some(line_column);
+            }
+        }
+
+        Positions { tokens }
+    }
+
+    /// Return the line where the node of the path starts.
+    pub(super) fn find(&self, path: &[Segment]) -> Option<usize> {
+        let mut region: &[Token] = &self.tokens;
+        let mut line = None;
+
+        for segment in path {
+            let column = region.first()?.column;
+            let (position, token) = region
+                .iter()
+                .enumerate()
+                .filter(|(_, token)| token.column == column)
+                .filter(|(_, token)| match (segment, &token.kind) {
+                    (Segment::Index(_), TokenKind::Item) => true,
+                    (Segment::Key(k), TokenKind::Key(token_key)) => k == token_key,
+                    _ => false,
+                })
+                .nth(match segment {
+                    Segment::Index(i) => *i,
+                    Segment::Key(_) => 0,
+                })?;
+
+            let children = &region[position + 1..];
+            let end = children
+                .iter()
+                .position(|child| match token.kind {
+                    TokenKind::Item => child.column <= column,
+                    TokenKind::Key(_) => {
+                        child.column < column
+                            || (child.column == column && child.kind != TokenKind::Item)
+                    }
+                })
+                .unwrap_or(children.len());
+            region = &children[..end];
+            line = Some(token.line);
+        }
+
+        line
+    }
+}
+
+fn is_template(s: &str) -> bool {
+    s.contains("{{") || s.contains("{%") || s.contains("{#")
+}
+
+/// Placeholder of the n-th template of the params, delimited to be found in error messages.
+fn template_placeholder(n: usize) -> String {
+    format!("RASH_LINT_TEMPLATE_{n}_")
+}
+
+/// Replace each template with its own placeholder, counting them in `count`.
+fn replace_templates(map: Mapping, count: &mut usize) -> Mapping {
+    map.into_iter()
+        .map(|(k, v)| (k, replace_value_templates(v, count)))
+        .collect()
+}
+
+fn replace_value_templates(value: YamlValue, count: &mut usize) -> YamlValue {
+    match value {
+        YamlValue::String(s) if is_template(&s) => {
+            *count += 1;
+            YamlValue::String(template_placeholder(*count - 1))
+        }
+        YamlValue::Sequence(seq) => YamlValue::Sequence(
+            seq.into_iter()
+                .map(|v| replace_value_templates(v, count))
+                .collect(),
+        ),
+        YamlValue::Mapping(map) => YamlValue::Mapping(replace_templates(map, count)),
+        value => value,
+    }
+}
+
+fn replace_placeholder(value: YamlValue, placeholder: &str, new: &YamlValue) -> YamlValue {
+    match value {
+        YamlValue::String(s) if s == placeholder => new.clone(),
+        YamlValue::Sequence(seq) => YamlValue::Sequence(
+            seq.into_iter()
+                .map(|v| replace_placeholder(v, placeholder, new))
+                .collect(),
+        ),
+        YamlValue::Mapping(map) => YamlValue::Mapping(
+            map.into_iter()
+                .map(|(k, v)| (k, replace_placeholder(v, placeholder, new)))
+                .collect(),
+        ),
+        value => value,
+    }
+}
+
+static EXPECTED_VARIANT_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"unknown variant `[^`]*`, expected (?:one of )?`([^`]*)`").unwrap()
+});
+static EXPECTED_NUMBER_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"expected .*\b([ui](8|16|32|64|128|size)|f32|f64|integer|number)\b").unwrap()
+});
+
+/// Value of the type expected in a deserialization error, to replace a template placeholder.
+fn expected_value(message: &str) -> Option<YamlValue> {
+    if let Some(captures) = EXPECTED_VARIANT_REGEX.captures(message) {
+        return Some(YamlValue::String(captures[1].to_owned()));
+    }
+    let expected = &message[message.rfind("expected")?..];
+    if EXPECTED_NUMBER_REGEX.is_match(expected) {
+        Some(YamlValue::Number(0.into()))
+    } else if expected.contains("bool") {
+        Some(YamlValue::Bool(true))
+    } else if expected.contains("sequence") {
+        Some(YamlValue::Sequence(Vec::new()))
+    } else if expected.contains("map") || expected.contains("struct") {
+        Some(YamlValue::Mapping(Mapping::new()))
+    } else {
+        None
+    }
+}
+
+/// Script file being checked.
+struct Source {
+    path: PathBuf,
+    positions: Positions,
+}
+
+impl Source {
+    fn new(path: &Path, content: &str) -> Self {
+        Source {
+            path: path.to_path_buf(),
+            positions: Positions::new(content),
+        }
+    }
+}
+
+struct Linter<'a> {
+    vars: &'a Value,
+    problems: Vec<LintProblem>,
+    notified: Vec<(String, LintProblem)>,
+    include_stack: Vec<PathBuf>,
+}
+
+impl<'a> Linter<'a> {
+    fn new(vars: &'a Value) -> Self {
+        Linter {
+            vars,
+            problems: Vec::new(),
+            notified: Vec::new(),
+            include_stack: Vec::new(),
+        }
+    }
+
+    fn problem(&self, source: &Source, path: &[Segment], message: String) -> LintProblem {
+        LintProblem {
+            path: source.path.clone(),
+            line: source.positions.find(path),
+            message,
+        }
+    }
+
+    fn add_problem(&mut self, source: &Source, path: &[Segment], message: String) {
+        let problem = self.problem(source, path, message);
+        self.problems.push(problem);
+    }
+
+    fn parse_yaml(&mut self, source: &Source, content: &str) -> Option<YamlValue> {
+        match serde_norway::from_str(content) {
+            Ok(yaml) => Some(yaml),
+            Err(e) => {
+                self.problems.push(LintProblem {
+                    path: source.path.clone(),
+                    line: e.location().map(|location| location.line()),
+                    message: e.to_string(),
+                });
+                None
+            }
+        }
+    }
+
+    /// Check main script, with `tasks` and `handlers` sections or just a list of tasks.
+    fn lint_main(&mut self, source: &Source, content: &str) {
+        let yaml = match self.parse_yaml(source, content) {
+            Some(yaml) => yaml,
+            None => return,
+        };
+        let global_params = GlobalParams::default();
+
+        let handler_names = match &yaml {
+            YamlValue::Sequence(tasks) => {
+                self.lint_tasks(source, tasks, &[], &global_params);
+                HashSet::new()
+            }
+            YamlValue::Mapping(mapping) => {
+                if let Err(e) = Header::from_yaml(&yaml) {
+                    self.add_problem(source, &[], e.to_string());
+                }
+                if let Some(vars) = mapping.get("vars") {
+                    self.lint_templates(source, &[key("vars")], vars);
+                }
+
+                match mapping.get("tasks") {
+                    Some(YamlValue::Sequence(tasks)) => {
+                        self.lint_tasks(source, tasks, &[key("tasks")], &global_params)
+                    }
+                    Some(_) => self.add_problem(
+                        source,
+                        &[key("tasks")],
+                        "tasks must be a YAML sequence".to_owned(),
+                    ),
+                    None => {
+                        self.add_problem(source, &[], "No tasks section found in file".to_owned())
+                    }
+                };
+
+                match mapping.get("handlers") {
+                    Some(YamlValue::Sequence(handlers)) => {
+                        self.lint_handlers(source, handlers, &global_params)
+                    }
+                    Some(_) => {
+                        self.add_problem(
+                            source,
+                            &[key("handlers")],
+                            "handlers must be a YAML sequence".to_owned(),
+                        );
+                        HashSet::new()
+                    }
+                    None => HashSet::new(),
+                }
+            }
+            _ => {
+                self.add_problem(
+                    source,
+                    &[],
+                    "Expected a YAML sequence of tasks or a mapping with tasks (and optional handlers)"
+                        .to_owned(),
+                );
+                HashSet::new()
+            }
+        };
+
+        let notified = std::mem::take(&mut self.notified);
+        self.problems.extend(
+            notified
+                .into_iter()
+                .filter(|(name, _)| !handler_names.contains(name))
+                .map(|(_, problem)| problem),
+        );
+    }
+
+    fn lint_handlers(
+        &mut self,
+        source: &Source,
+        handlers: &[YamlValue],
+        global_params: &GlobalParams,
+    ) -> HashSet<String> {
+        let mut names = HashSet::new();
+        for (index, handler) in handlers.iter().enumerate() {
+            let path = [key("handlers"), Segment::Index(index)];
+            match handler.get("name").and_then(|n| n.as_str()) {
+                Some(name) => {
+                    names.insert(name.to_owned());
+                }
+                None => self.add_problem(
+                    source,
+                    &path,
+                    "Handler must have a 'name' attribute".to_owned(),
+                ),
+            };
+            self.lint_task(source, handler, &path, global_params);
+        }
+        names
+    }
+
+    fn lint_tasks(
+        &mut self,
+        source: &Source,
+        tasks: &[YamlValue],
+        parent: &[Segment],
+        global_params: &GlobalParams,
+    ) {
+        let is_file_tasks = parent.is_empty() || parent == [key("tasks")];
+        for (index, task_yaml) in tasks.iter().enumerate() {
+            let path = [parent, &[Segment::Index(index)]].concat();
+            if is_file_tasks && task_yaml.get("import").is_some() {
+                self.lint_import(source, task_yaml, &path, global_params);
+            } else {
+                self.lint_task(source, task_yaml, &path, global_params);
+            }
+        }
+    }
+
+    fn lint_task(
+        &mut self,
+        source: &Source,
+        task_yaml: &YamlValue,
+        path: &[Segment],
+        global_params: &GlobalParams,
+    ) {
+        let task = match Task::new(task_yaml, global_params) {
+            Ok(task) => task,
+            Err(e) => return self.add_problem(source, path, e.to_string()),
+        };
+        let attr_path = |attr: &str| [path, &[key(attr)]].concat();
+        let module_name = task.module.get_name();
+
+        for (attr, expression) in [
+            ("when", &task.when),
+            ("changed_when", &task.changed_when),
+            ("failed_when", &task.failed_when),
+            ("until", &task.until),
+        ] {
+            if let Some(Err(e)) = expression.as_deref().map(compile_expression) {
+                self.add_problem(source, &attr_path(attr), e.to_string());
+            }
+        }
+
+        for (attr, value) in [
+            ("name", task.name.clone().map(YamlValue::String)),
+            ("loop", task.r#loop.clone()),
+            ("vars", task.vars.clone()),
+            ("environment", task.environment.clone()),
+            (
+                "loop_control",
+                task.loop_control
+                    .get_label()
+                    .map(|label| YamlValue::String(label.to_owned())),
+            ),
+        ] {
+            if let Some(value) = value {
+                self.lint_templates(source, &attr_path(attr), &value);
+            }
+        }
+
+        if let Some(notify) = task.get_notify() {
+            for name in notify {
+                let problem = self.problem(
+                    source,
+                    &attr_path("notify"),
+                    format!("Handler '{name}' not found"),
+                );
+                self.notified.push((name.clone(), problem));
+            }
+        }
+
+        let children_global_params = task.get_children_global_params();
+        for (attr, children) in [
+            ("block", (module_name == "block").then_some(&task.params)),
+            (
+                "parallel",
+                (module_name == "parallel").then_some(&task.params),
+            ),
+            ("rescue", task.rescue.as_ref()),
+            ("always", task.always.as_ref()),
+        ] {
+            match children {
+                Some(YamlValue::Sequence(tasks)) => {
+                    self.lint_tasks(source, tasks, &attr_path(attr), &children_global_params)
+                }
+                Some(_) => self.add_problem(
+                    source,
+                    &attr_path(attr),
+                    format!("{attr} must be a YAML sequence of tasks"),
+                ),
+                None => (),
+            }
+        }
+
+        if matches!(module_name, "block" | "parallel") {
+            return;
+        }
+
+        self.lint_templates(source, &attr_path(module_name), &task.params);
+        if let Err(e) = Self::validate_params(&task) {
+            self.add_problem(
+                source,
+                &attr_path(module_name),
+                format!("Invalid params for module '{module_name}': {e}"),
+            );
+        }
+
+        if module_name == "include" {
+            self.lint_include(
+                source,
+                &task,
+                &attr_path(module_name),
+                &children_global_params,
+            );
+        }
+    }
+
+    fn lint_templates(&mut self, source: &Source, path: &[Segment], value: &YamlValue) {
+        match value {
+            YamlValue::String(s) if is_template(s) => {
+                if let Err(e) = compile_string(s) {
+                    self.add_problem(source, path, e.to_string());
+                }
+            }
+            YamlValue::Sequence(seq) => seq
+                .iter()
+                .for_each(|v| self.lint_templates(source, path, v)),
+            YamlValue::Mapping(map) => map
+                .values()
+                .for_each(|v| self.lint_templates(source, path, v)),
+            _ => (),
+        }
+    }
+
+    /// Validate params as they would be passed to the module, with templates replaced by
+    /// placeholders.
+    fn validate_params(task: &Task) -> Result<()> {
+        let params = match &task.params {
+            YamlValue::Null => YamlValue::Mapping(Mapping::new()),
+            YamlValue::String(s) if is_template(s) => return Ok(()),
+            YamlValue::Mapping(map) => {
+                let mut count = 0;
+                let rendered = render_map(
+                    replace_templates(map.clone(), &mut count),
+                    &context! {},
+                    task.module.force_string_on_params(),
+                )?;
+                return Self::validate_templated_params(task, rendered, count);
+            }
+            params => params.clone(),
+        };
+        task.module.validate_params(params)
+    }
+
+    /// Templated values could be of any type once rendered, so errors caused by a placeholder
+    /// are fixed replacing it with a value of the expected type, and params are validated
+    /// again. Errors in values without templates are returned.
+    fn validate_templated_params(task: &Task, mut params: YamlValue, count: usize) -> Result<()> {
+        let mut placeholders = (0..count).map(template_placeholder).collect::<Vec<_>>();
+        loop {
+            let error = match task.module.validate_params(params.clone()) {
+                Ok(()) => return Ok(()),
+                Err(e) => e,
+            };
+            let message = error.to_string();
+            let i = match placeholders.iter().position(|p| message.contains(p)) {
+                Some(i) => i,
+                None => return Err(error),
+            };
+            match expected_value(&message) {
+                Some(value) => {
+                    let placeholder = placeholders.remove(i);
+                    params = replace_placeholder(params, &placeholder, &value);
+                }
+                // value can't be checked without rendering it
+                None => return Ok(()),
+            }
+        }
+    }
+
+    /// Check included file when its path can be rendered with the initial vars.
+    fn lint_include(
+        &mut self,
+        source: &Source,
+        task: &Task,
+        path: &[Segment],
+        global_params: &GlobalParams,
+    ) {
+        let params = match IncludeParams::from_yaml(task.params.clone()).and_then(|params| {
+            render_string(&params.file, self.vars).map(|file| IncludeParams { file, ..params })
+        }) {
+            Ok(params) => params,
+            _ => {
+                debug!("skipping dynamic include: {:?}", task.params);
+                return;
+            }
+        };
+
+        let include_path = canonicalize(&params.get_path(&source.path));
+        if self.include_stack.contains(&include_path) {
+            return;
+        }
+        self.lint_file(source, path, &include_path, &params, global_params);
+    }
+
+    /// Check imported file, which must exist and can't be imported recursively.
+    fn lint_import(
+        &mut self,
+        source: &Source,
+        task_yaml: &YamlValue,
+        path: &[Segment],
+        global_params: &GlobalParams,
+    ) {
+        let params = match get_import_params(task_yaml) {
+            Ok(params) => params,
+            Err(e) => return self.add_problem(source, path, e.to_string()),
+        };
+
+        let import_path = canonicalize(&params.get_path(&source.path));
+        if self.include_stack.contains(&import_path) {
+            return self.add_problem(
+                source,
+                path,
+                format!("Circular import of {}", import_path.display()),
+            );
+        }
+        self.lint_file(source, path, &import_path, &params, global_params);
+    }
+
+    /// Check tasks of an included or imported file.
+    fn lint_file(
+        &mut self,
+        source: &Source,
+        path: &[Segment],
+        file_path: &Path,
+        params: &IncludeParams,
+        global_params: &GlobalParams,
+    ) {
+        let content = match read_to_string(file_path) {
+            Ok(content) => content,
+            Err(e) => {
+                return self.add_problem(
+                    source,
+                    path,
+                    format!("Error reading file {}: {e}", file_path.display()),
+                );
+            }
+        };
+
+        let file_vars = Builtins::deserialize(self.vars.get_attr("rash").unwrap_or_default())
+            .ok()
+            .and_then(|builtins| builtins.update(file_path).ok())
+            .map(|builtins| context! {rash => &builtins, ..self.vars.clone()})
+            .unwrap_or_else(|| self.vars.clone());
+
+        let file_source = Source::new(file_path, &content);
+        let yaml = match self.parse_yaml(&file_source, &content) {
+            Some(yaml) => yaml,
+            None => return,
+        };
+
+        match yaml {
+            YamlValue::Sequence(mut tasks) => {
+                params.add_vars_to(&mut tasks);
+                params.apply_to(&mut tasks);
+                let mut file_linter = Linter {
+                    vars: &file_vars,
+                    problems: Vec::new(),
+                    notified: Vec::new(),
+                    include_stack: [self.include_stack.clone(), vec![file_path.to_path_buf()]]
+                        .concat(),
+                };
+                file_linter.lint_tasks(&file_source, &tasks, &[], global_params);
+                self.problems.extend(file_linter.problems);
+                self.notified.extend(file_linter.notified);
+            }
+            _ => self.add_problem(
+                &file_source,
+                &[],
+                "Expected a YAML sequence of tasks".to_owned(),
+            ),
+        }
+    }
+}
+
+fn canonicalize(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or(path.to_path_buf())
+}
+
+/// Check a script without executing it: YAML syntax, task attributes, module params, Jinja
+/// syntax, notified handlers, imports and static includes.
+///
+/// `vars` are used to render `include` paths, which are skipped if they can't be rendered.
+/// Problems are sorted by line, grouped by file.
+pub fn lint(path: &Path, content: &str, vars: &Value) -> Vec<LintProblem> {
+    let source = Source::new(path, content);
+    let mut linter = Linter::new(vars);
+    linter.include_stack.push(canonicalize(path));
+    linter.lint_main(&source, content);
+
+    let mut paths: Vec<PathBuf> = Vec::new();
+    linter.problems.iter().for_each(|problem| {
+        if !paths.contains(&problem.path) {
+            paths.push(problem.path.clone())
+        }
+    });
+    linter
+        .problems
+        .sort_by_key(|problem| (paths.iter().position(|p| p == &problem.path), problem.line));
+    linter.problems
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::fs;
+
+    use tempfile::tempdir;
+
+    fn lint_str(content: &str) -> Vec<String> {
+        lint(Path::new("test.rh"), content, &context! {})
+            .iter()
+            .map(|problem| problem.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_positions_find() {
+        let content = r#"
+# comment
+tasks:
+  - name: first
+    command: ls
+
+  - block:
+      - debug:
+          msg: |
+            - foo: bar
+      - name: nested
+        debug:
+          msg: bar
+    rescue:
+    - debug:
+        msg: rescue
+handlers:
+  - name: restart
+    command: "true"
+"#;
+        let positions = Positions::new(content);
+        let find = |path: &[Segment]| positions.find(path);
+        let task = |i| [key("tasks"), Segment::Index(i)];
+
+        assert_eq!(find(&[key("tasks")]), Some(3));
+        assert_eq!(find(&task(0)), Some(4));
+        assert_eq!(find(&[&task(0)[..], &[key("command")]].concat()), Some(5));
+        assert_eq!(find(&task(1)), Some(7));
+        assert_eq!(
+            find(&[&task(1)[..], &[key("block"), Segment::Index(1)]].concat()),
+            Some(11)
+        );
+        assert_eq!(
+            find(
+                &[
+                    &task(1)[..],
+                    &[key("block"), Segment::Index(1), key("debug")]
+                ]
+                .concat()
+            ),
+            Some(12)
+        );
+        assert_eq!(
+            find(&[&task(1)[..], &[key("rescue"), Segment::Index(0)]].concat()),
+            Some(15)
+        );
+        assert_eq!(find(&task(2)), None);
+        assert_eq!(
+            find(&[key("handlers"), Segment::Index(0), key("command")]),
+            Some(19)
+        );
+    }
+
+    #[test]
+    fn test_positions_find_sequence() {
+        let content = r#"- name: first
+  command: ls
+- debug:
+    msg: "{{ foo }}"
+  when: foo is defined
+"#;
+        let positions = Positions::new(content);
+        assert_eq!(positions.find(&[Segment::Index(1)]), Some(3));
+        assert_eq!(positions.find(&[Segment::Index(1), key("when")]), Some(5));
+    }
+
+    #[test]
+    fn test_lint_ok() {
+        let problems = lint_str(
+            r#"
+tasks:
+  - name: "{{ foo | default('bar') }}"
+    command:
+      cmd: "echo {{ foo }}"
+      chdir: /tmp
+    when: foo is defined
+    notify: restart
+  - shell: echo hi
+  - copy:
+      content: "{{ foo }}"
+      dest: /tmp/foo
+handlers:
+  - name: restart
+    command: "true"
+"#,
+        );
+        assert_eq!(problems, Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_lint_problems() {
+        let problems = lint_str(
+            r#"- name: unknown param
+  command:
+    cmd: ls
+    foo: bar
+- name: bad expression
+  debug:
+    msg: hi
+  when: foo ==
+- block:
+    - name: bad template
+      debug:
+        msg: "{{ foo"
+  rescue:
+    - notify: missing
+      command: ls
+- name: missing module
+  foo: bar
+"#,
+        );
+        assert_eq!(problems.len(), 5);
+        assert!(problems[0].starts_with("test.rh:2: Invalid params for module 'command'"));
+        assert!(problems[0].contains("unknown field `foo`"));
+        assert!(problems[1].starts_with("test.rh:8: "));
+        assert!(problems[2].starts_with("test.rh:11: "));
+        assert_eq!(problems[3], "test.rh:14: Handler 'missing' not found");
+        assert!(problems[4].starts_with("test.rh:16: "));
+    }
+
+    #[test]
+    fn test_lint_header() {
+        let problems = lint_str(
+            r#"
+vars:
+  foo: "{{ bar"
+become_user: foo
+tasks:
+  - debug:
+      msg: "{{ foo }}"
+"#,
+        );
+        assert_eq!(problems.len(), 2);
+        assert!(
+            problems[0].starts_with("test.rh: Invalid script header: unknown field `become_user`")
+        );
+        assert!(problems[1].starts_with("test.rh:2: syntax error"));
+    }
+
+    #[test]
+    fn test_lint_yaml_error() {
+        let problems = lint_str("- command: ls\n  foo: [bar\n");
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].starts_with("test.rh:"));
+    }
+
+    #[test]
+    fn test_lint_templated_params() {
+        let problems = lint_str(
+            r#"
+- copy:
+    content: "{{ foo }}"
+    dest: /tmp/foo
+- file:
+    path: "{{ foo }}"
+    state: "{{ state }}"
+- file:
+    path: "{{ foo }}"
+    state: unknown
+- copy:
+    content: "{{ foo }}"
+"#,
+        );
+        assert_eq!(problems.len(), 2);
+        assert!(problems[0].starts_with("test.rh:8: Invalid params for module 'file'"));
+        assert!(problems[0].contains("unknown variant `unknown`"));
+        assert!(problems[1].starts_with("test.rh:11: Invalid params for module 'copy'"));
+        assert!(problems[1].contains("missing field `dest`"));
+    }
+
+    #[test]
+    fn test_lint_templated_params_other_fields() {
+        let problems = lint_str(
+            r#"
+- file:
+    state: "{{ state }}"
+    path: [foo]
+- authorized_key:
+    user: "{{ user }}"
+    key: {foo: bar}
+- authorized_key:
+    user: root
+    key: "{{ keys }}"
+- async_status:
+    jid: "{{ job.rash_job_id }}"
+"#,
+        );
+        assert_eq!(problems.len(), 2);
+        assert!(problems[0].starts_with("test.rh:2: Invalid params for module 'file'"));
+        assert!(problems[0].contains("invalid type: sequence, expected a string"));
+        assert!(problems[1].starts_with("test.rh:5: Invalid params for module 'authorized_key'"));
+        assert!(
+            problems[1].contains("did not match any variant"),
+            "{}",
+            problems[1]
+        );
+    }
+
+    #[test]
+    fn test_lint_include() {
+        let dir = tempdir().unwrap();
+        let included_path = dir.path().join("included.rh");
+        fs::write(
+            &included_path,
+            r#"
+- debug:
+    msg: hi
+  notify: restart
+- command:
+    foo: bar
+"#,
+        )
+        .unwrap();
+        let content = format!(
+            r#"
+tasks:
+  - include: {}
+  - include: "{{{{ undefined_var }}}}.rh"
+handlers:
+  - name: restart
+    command: "true"
+"#,
+            included_path.display()
+        );
+
+        let problems = lint(Path::new("main.rh"), &content, &context! {});
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].path, included_path);
+        assert_eq!(problems[0].line, Some(5));
+    }
+
+    #[test]
+    fn test_lint_import() {
+        let dir = tempdir().unwrap();
+        fs::write(
+            dir.path().join("imported.rh"),
+            r#"
+- debug:
+    msg: "{{ foo"
+- import: main.rh
+"#,
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("included.rh"),
+            r#"
+- command:
+    foo: bar
+"#,
+        )
+        .unwrap();
+        let main_path = dir.path().join("main.rh");
+        let content = r#"
+- import:
+    file: imported.rh
+    apply:
+      tags: imported
+- include:
+    file: included.rh
+    vars:
+      foo: bar
+- block:
+    - import: imported.rh
+- import: imported.rh
+  when: true
+"#;
+        fs::write(&main_path, content).unwrap();
+
+        let problems = lint(&main_path, content, &context! {})
+            .iter()
+            .map(|problem| problem.to_string())
+            .collect::<Vec<_>>();
+        let dir_path = dir.path().canonicalize().unwrap();
+        let imported_path = dir_path.join("imported.rh");
+        assert_eq!(problems.len(), 5);
+        assert!(problems[0].starts_with(&format!("{}:2: ", imported_path.display())));
+        assert_eq!(
+            problems[1],
+            format!(
+                "{}:4: Circular import of {}",
+                imported_path.display(),
+                dir_path.join("main.rh").display()
+            )
+        );
+        assert!(problems[2].starts_with(&format!(
+            "{}:2: Invalid params for module 'command'",
+            dir_path.join("included.rh").display()
+        )));
+        assert_eq!(
+            problems[3],
+            format!(
+                "{}:11: import is only supported in task lists of files, use include instead",
+                main_path.display()
+            )
+        );
+        assert!(problems[4].ends_with(
+            ":12: Invalid keyword String(\"when\") in import task, task keywords can be added with apply"
+        ));
+    }
+}
@@ -0,0 +1,173 @@
+use crate::error::{Error, ErrorKind, Result};
+
+use std::time::Duration;
+
+use minijinja::Value;
+use serde::Deserialize;
+use serde_norway::Value as YamlValue;
+
+/// Default variable name for the current loop item.
+pub const DEFAULT_LOOP_VAR: &str = "item";
+/// Variable name for the extended loop information.
+pub const EXTENDED_LOOP_VAR: &str = "rash_loop";
+
+/// Options to customize how `loop` iterations are executed and logged.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LoopControl {
+    /// Variable name for the current item. **[default: `item`]**
+    loop_var: Option<String>,
+    /// Variable name for the current iteration index, starting at 0.
+    index_var: Option<String>,
+    /// Template rendered in each iteration to identify it in logs.
+    label: Option<String>,
+    /// Seconds to wait between iterations.
+    #[serde(default)]
+    pause: u64,
+    /// Expose `index`, `index0`, `first`, `last`, `length`, `previtem` and `nextitem`
+    /// in the `rash_loop` variable.
+    #[serde(default)]
+    extended: bool,
+}
+
+impl LoopControl {
+    /// Parse `loop_control` attribute, returning default values if it is not defined.
+    pub fn from_yaml(value: Option<&YamlValue>) -> Result<Self> {
+        match value {
+            Some(v) => serde_norway::from_value(v.clone()).map_err(|e| {
+                Error::new(ErrorKind::InvalidData, format!("Invalid loop_control: {e}"))
+            }),
+            None => Ok(LoopControl::default()),
+        }
+    }
+
+    pub fn get_loop_var(&self) -> &str {
+        self.loop_var.as_deref().unwrap_or(DEFAULT_LOOP_VAR)
+    }
+
+    pub fn get_label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+
+    /// Time to wait before executing each iteration but the first one.
+    pub fn get_pause(&self) -> Option<Duration> {
+        (self.pause > 0).then(|| Duration::from_secs(self.pause))
+    }
+
+    /// Variables of the iteration `index` over `items`.
+    pub fn get_iteration_vars(&self, items: &[YamlValue], index: usize) -> Value {
+        let mut vars = vec![(
+            self.get_loop_var().to_owned(),
+            Value::from_serialize(&items[index]),
+        )];
+
+        if let Some(index_var) = &self.index_var {
+            vars.push((index_var.clone(), Value::from(index)));
+        }
+
+        if self.extended {
+            let mut loop_info = vec![
+                ("index", Value::from(index + 1)),
+                ("index0", Value::from(index)),
+                ("first", Value::from(index == 0)),
+                ("last", Value::from(index + 1 == items.len())),
+                ("length", Value::from(items.len())),
+            ];
+            if index > 0 {
+                loop_info.push(("previtem", Value::from_serialize(&items[index - 1])));
+            }
+            if let Some(next_item) = items.get(index + 1) {
+                loop_info.push(("nextitem", Value::from_serialize(next_item)));
+            }
+            vars.push((
+                EXTENDED_LOOP_VAR.to_owned(),
+                loop_info.into_iter().collect::<Value>(),
+            ));
+        }
+
+        vars.into_iter().collect::<Value>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn items() -> Vec<YamlValue> {
+        serde_norway::from_str("[a, b, c]").unwrap()
+    }
+
+    #[test]
+    fn test_from_yaml_default() {
+        let loop_control = LoopControl::from_yaml(None).unwrap();
+        assert_eq!(loop_control, LoopControl::default());
+        assert_eq!(loop_control.get_loop_var(), "item");
+        assert_eq!(loop_control.get_pause(), None);
+    }
+
+    #[test]
+    fn test_from_yaml() {
+        let yaml: YamlValue = serde_norway::from_str(
+            r#"
+            loop_var: package
+            index_var: idx
+            label: "{{ package }}"
+            pause: 2
+            extended: true
+            "#,
+        )
+        .unwrap();
+        let loop_control = LoopControl::from_yaml(Some(&yaml)).unwrap();
+        assert_eq!(loop_control.get_loop_var(), "package");
+        assert_eq!(loop_control.get_label(), Some("{{ package }}"));
+        assert_eq!(loop_control.get_pause(), Some(Duration::from_secs(2)));
+    }
+
+    #[test]
+    fn test_from_yaml_unknown_field() {
+        let yaml: YamlValue = serde_norway::from_str("foo: bar").unwrap();
+        let error = LoopControl::from_yaml(Some(&yaml)).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_get_iteration_vars() {
+        let loop_control = LoopControl {
+            loop_var: Some("package".to_owned()),
+            index_var: Some("idx".to_owned()),
+            ..Default::default()
+        };
+        let vars = loop_control.get_iteration_vars(&items(), 1);
+        assert_eq!(vars.get_attr("package").unwrap(), Value::from("b"));
+        assert_eq!(vars.get_attr("idx").unwrap(), Value::from(1));
+        assert!(vars.get_attr("item").unwrap().is_undefined());
+        assert!(vars.get_attr("rash_loop").unwrap().is_undefined());
+    }
+
+    #[test]
+    fn test_get_iteration_vars_extended() {
+        let loop_control = LoopControl {
+            extended: true,
+            ..Default::default()
+        };
+        let first = loop_control
+            .get_iteration_vars(&items(), 0)
+            .get_attr("rash_loop")
+            .unwrap();
+        assert_eq!(first.get_attr("index").unwrap(), Value::from(1));
+        assert_eq!(first.get_attr("first").unwrap(), Value::from(true));
+        assert_eq!(first.get_attr("last").unwrap(), Value::from(false));
+        assert_eq!(first.get_attr("length").unwrap(), Value::from(3));
+        assert!(first.get_attr("previtem").unwrap().is_undefined());
+        assert_eq!(first.get_attr("nextitem").unwrap(), Value::from("b"));
+
+        let last = loop_control
+            .get_iteration_vars(&items(), 2)
+            .get_attr("rash_loop")
+            .unwrap();
+        assert_eq!(last.get_attr("index0").unwrap(), Value::from(2));
+        assert_eq!(last.get_attr("last").unwrap(), Value::from(true));
+        assert_eq!(last.get_attr("previtem").unwrap(), Value::from("b"));
+        assert!(last.get_attr("nextitem").unwrap().is_undefined());
+    }
+}
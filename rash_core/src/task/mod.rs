@@ -1,30 +1,50 @@
+mod children;
+mod debugger;
 mod handler;
+mod header;
+mod lint;
+mod loop_control;
 mod new;
+mod start_at;
+mod tags;
 mod valid;
 
+pub use debugger::Debugger;
 pub use handler::{Handlers, PendingHandlers, parse_notify_value};
+pub use header::Header;
+pub use lint::{LintProblem, lint};
+pub use start_at::StartAtTask;
+pub use tags::list_tags;
 
+use crate::connection::Connection;
 use crate::context::{BecomeMethod, GlobalParams};
 use crate::error::{Error, ErrorKind, Result};
+use crate::events::{self, Event, Outcome, TaskInfo};
 use crate::jinja::{
     is_render_string, merge_option, render, render_force_string, render_map, render_string,
 };
-use crate::job::{JobStatus, get_job_info, register_job};
-use crate::logger::is_json_output;
-use crate::modules::{Module, ModuleResult};
+use crate::job::{JobStatus, get_job_info, spawn_job};
+use crate::journal;
+use crate::logger::{self, NO_LOG_MESSAGE, NoLogGuard, get_secrets, is_json_output, is_no_log};
+use crate::modules::include::Params as IncludeParams;
+use crate::modules::{CheckModeSupport, Module, ModuleResult};
+use crate::stats::{self, TaskStatus};
+use crate::task::lint::{Positions, Segment};
+use crate::task::loop_control::LoopControl;
 use crate::task::new::TaskNew;
+use crate::vars::builtin::Builtins;
 
 use rash_derive::FieldNames;
 
 use std::collections::HashMap;
 use std::env;
-use std::fs::{self, File};
-use std::io::Write;
-use std::path::PathBuf;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
 use std::process::{Command as StdCommand, Output, Stdio, exit};
 use std::result::Result as StdResult;
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use ipc_channel::ipc::{self, IpcReceiver, IpcSender};
 use minijinja::{Value, context};
@@ -33,29 +53,19 @@ use nix::unistd::{ForkResult, Uid, User, fork, setgid, setuid};
 use serde::{Deserialize, Serialize};
 use serde_error::Error as SerdeError;
 use serde_norway::Value as YamlValue;
+use tempfile::NamedTempFile;
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct TaskExecResult {
     changed: bool,
     vars: Option<Value>,
     flush_handlers: bool,
-}
-
-#[derive(Debug, Clone, Serialize)]
-struct JsonResult {
-    changed: bool,
-    output: Option<String>,
-    extra: Option<serde_json::Value>,
-}
-
-impl JsonResult {
-    fn new(changed: bool, output: Option<String>, extra: Option<YamlValue>) -> Self {
-        Self {
-            changed,
-            output,
-            extra: extra.and_then(|v| serde_json::to_value(v).ok()),
-        }
-    }
+    #[serde(default)]
+    skipped: bool,
+    #[serde(default)]
+    ignored: bool,
+    #[serde(default)]
+    rescued: bool,
 }
 
 impl TaskExecResult {
@@ -63,7 +73,7 @@ impl TaskExecResult {
         TaskExecResult {
             changed,
             vars,
-            flush_handlers: false,
+            ..Default::default()
         }
     }
 
@@ -72,6 +82,24 @@ impl TaskExecResult {
         self
     }
 
+    /// Mark result as not executed because of `when` condition.
+    pub fn with_skipped(mut self) -> Self {
+        self.skipped = true;
+        self
+    }
+
+    /// Mark result as failed with `ignore_errors`.
+    pub fn with_ignored(mut self) -> Self {
+        self.ignored = true;
+        self
+    }
+
+    /// Mark result as recovered by `rescue` tasks.
+    pub fn with_rescued(mut self) -> Self {
+        self.rescued = true;
+        self
+    }
+
     pub fn get_changed(&self) -> bool {
         self.changed
     }
@@ -87,6 +115,43 @@ impl TaskExecResult {
     pub fn is_flush_handlers(&self) -> bool {
         self.flush_handlers
     }
+
+    /// Return status to be reported in the run recap.
+    pub fn get_status(&self) -> TaskStatus {
+        if self.rescued {
+            TaskStatus::Rescued
+        } else if self.ignored {
+            TaskStatus::Ignored
+        } else if self.skipped {
+            TaskStatus::Skipped
+        } else if self.changed {
+            TaskStatus::Changed
+        } else {
+            TaskStatus::Ok
+        }
+    }
+}
+
+/// Error of a failed task with the result it registered, so its `rescue` tasks can read it.
+#[derive(Debug)]
+struct RegisteredError {
+    error: Error,
+    register_vars: Value,
+}
+
+impl std::fmt::Display for RegisteredError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.error.fmt(f)
+    }
+}
+
+impl std::error::Error for RegisteredError {}
+
+/// Return the variables registered by the task which failed with `e`, if any.
+fn get_registered_vars(e: &Error) -> Option<Value> {
+    e.get_ref()
+        .and_then(|inner| inner.downcast_ref::<RegisteredError>())
+        .map(|registered_error| registered_error.register_vars.clone())
 }
 
 /// Internal task serialization for sudo become method.
@@ -101,6 +166,9 @@ pub struct InternalTaskData {
     pub vars: Value,
     /// The task to execute
     pub task: YamlValue,
+    /// Secrets to be masked in logs
+    #[serde(default)]
+    pub secrets: Vec<String>,
 }
 
 /// Environment variable name for internal task file path
@@ -115,6 +183,9 @@ pub const RASH_INTERNAL_OUTPUT_ENV: &str = "RASH_INTERNAL_OUTPUT";
 /// Environment variable name to indicate internal task (suppresses task header)
 pub const RASH_INTERNAL_TASK_FLAG: &str = "RASH_INTERNAL";
 
+/// Interval to check if a job of a throttled `async` loop finished.
+const THROTTLE_CHECK_INTERVAL: Duration = Duration::from_millis(100);
+
 /// Check if we're running as an internal task execution (for sudo become)
 pub fn is_internal_task_execution() -> Option<PathBuf> {
     env::var(RASH_INTERNAL_TASK_ENV).ok().map(PathBuf::from)
@@ -135,10 +206,52 @@ pub fn is_internal_execution() -> bool {
     env::var(RASH_INTERNAL_TASK_FLAG).is_ok()
 }
 
+/// Write the task for the `--internal-task` process in a temporary file, removed on drop.
+/// It contains the secrets, so it is only readable by its owner, the become user when running
+/// as root.
+fn write_task_file(internal_data: &InternalTaskData, become_user: &str) -> Result<NamedTempFile> {
+    let task_content =
+        serde_yaml::to_string(internal_data).map_err(|e| Error::new(ErrorKind::Other, e))?;
+
+    let mut task_file = tempfile::Builder::new()
+        .prefix("rash_task_")
+        .suffix(".yaml")
+        .tempfile()
+        .map_err(|e| {
+            Error::new(
+                ErrorKind::Other,
+                format!("Failed to create task file: {}", e),
+            )
+        })?;
+    task_file
+        .write_all(task_content.as_bytes())
+        .map_err(|e| Error::new(ErrorKind::Other, e))?;
+
+    if Uid::effective().is_root()
+        && let Ok(Some(user)) = User::from_name(become_user)
+    {
+        nix::unistd::chown(task_file.path(), Some(user.uid), Some(user.gid)).map_err(|e| {
+            Error::new(
+                ErrorKind::Other,
+                format!("Failed to change owner of task file: {}", e),
+            )
+        })?;
+    }
+    Ok(task_file)
+}
+
 fn log_module_result(changed: bool, result: &ModuleResult) {
+    let censored_result;
+    let result = match is_no_log() {
+        true => {
+            censored_result = ModuleResult::new(changed, None, Some(NO_LOG_MESSAGE.to_owned()));
+            &censored_result
+        }
+        false => result,
+    };
+
     if is_json_output() {
-        let json_result = JsonResult::new(changed, result.get_output(), result.get_extra());
-        match serde_json::to_string(&json_result) {
+        match serde_json::to_string(&result.clone().with_changed(changed)) {
             Ok(json_str) => {
                 let target = if changed { "changed" } else { "ok" };
                 info!(target: target, "{}", json_str);
@@ -147,8 +260,24 @@ fn log_module_result(changed: bool, result: &ModuleResult) {
                 error!("Failed to serialize JSON result: {}", e);
             }
         }
+        return;
+    }
+
+    if let Some(diff) = result.get_diff() {
+        logger::diff_files_with_headers(
+            &diff.before,
+            &diff.after,
+            diff.before_header.as_deref(),
+            diff.after_header.as_deref(),
+        );
+    }
+
+    let output = result
+        .get_output()
+        .or_else(|| result.get_msg().map(String::from));
+    if events::is_enabled() {
+        events::set_output(output);
     } else {
-        let output = result.get_output();
         let target = match changed {
             true => "changed",
             false => "ok",
@@ -159,6 +288,11 @@ fn log_module_result(changed: bool, result: &ModuleResult) {
             output.unwrap_or_else(|| "".to_owned())
         );
     }
+
+    result.get_warnings().iter().for_each(|warning| {
+        warn!("{warning}");
+        events::add_warning(warning);
+    });
 }
 
 /// Main structure at definition level which prepares [`Module`] executions.
@@ -195,12 +329,17 @@ pub struct Task<'a> {
     ///
     /// [`ModuleResult`]: ../modules/struct.ModuleResult.html
     changed_when: Option<String>,
+    /// Template expression passed directly without {{ }}; if true the task fails.
+    /// The module result is accessible by its `register` name.
+    failed_when: Option<String>,
     /// Template expression passed directly without {{ }}; if true errors are ignored.
     ignore_errors: Option<bool>,
     /// Task name.
     name: Option<String>,
     /// `loop` receives a Template (with {{ }}) or a list to iterate over it.
     r#loop: Option<YamlValue>,
+    /// Customize `loop` iterations: `loop_var`, `index_var`, `label`, `pause` and `extended`.
+    loop_control: LoopControl,
     /// Variable name to store [`ModuleResult`].
     ///
     /// [`ModuleResult`]: ../modules/struct.ModuleResult.html
@@ -227,6 +366,24 @@ pub struct Task<'a> {
     r#async: Option<u64>,
     /// Poll interval in seconds for async task status. 0 = fire and forget.
     poll: Option<u64>,
+    /// Max number of tasks of a `parallel` block, or items of an `async` loop, executed at the
+    /// same time.
+    throttle: Option<usize>,
+    /// Hide params, output, extra and diffs of the task from logs.
+    no_log: bool,
+    /// Target where the module is executed: `local`, `chroot`, `docker`, `podman`, `nsenter`
+    /// or `systemd-nspawn`. Tasks in `block`, `rescue`, `always` and included files inherit it.
+    connection: Option<YamlValue>,
+    /// Tags to select or skip the task with `--tags` and `--skip-tags`.
+    /// Tasks in `block`, `rescue`, `always` and included files inherit them.
+    tags: Vec<String>,
+    /// Open the interactive debugger when the task fails: `on_failed` or `never`.
+    /// Tasks in `block`, `rescue`, `always` and included files inherit it.
+    debugger: Debugger,
+    /// Line of the task in its file, when it is known.
+    line: Option<usize>,
+    /// Path of the file of the task, when it is imported from another file.
+    file_path: Option<String>,
     /// Global parameters.
     global_params: &'a GlobalParams<'a>,
 }
@@ -255,7 +412,8 @@ impl<'a> Task<'a> {
 
     #[inline(always)]
     fn is_attr(attr: &str) -> bool {
-        Self::get_field_names().contains(attr)
+        !matches!(attr, "line" | "file_path" | "global_params")
+            && Self::get_field_names().contains(attr)
     }
 
     #[inline(always)]
@@ -288,7 +446,7 @@ impl<'a> Task<'a> {
             YamlValue::Null => Ok(YamlValue::Mapping(serde_norway::Mapping::new())),
             YamlValue::Sequence(_) => {
                 // For sequence parameters (like block tasks), pass through without string rendering
-                if self.module.get_name() == "block" {
+                if matches!(self.module.get_name(), "block" | "parallel") {
                     Ok(original_params)
                 } else {
                     Err(Error::new(
@@ -370,7 +528,10 @@ impl<'a> Task<'a> {
         // safe unwrap, previous verification self.r#loop.is_some()
         let loop_some = self.r#loop.clone().unwrap();
 
-        let extended_vars = self.extend_vars(context! {item => "",..vars})?;
+        let loop_var_vars = [(self.loop_control.get_loop_var(), Value::from(""))]
+            .into_iter()
+            .collect::<Value>();
+        let extended_vars = self.extend_vars(context! {..loop_var_vars, ..vars})?;
         match loop_some.as_str() {
             Some(s) => {
                 let value: YamlValue = serde_norway::from_str(&render_string(s, &extended_vars)?)?;
@@ -383,6 +544,38 @@ impl<'a> Task<'a> {
         }
     }
 
+    /// Return the vars of each `loop` iteration, binding its item as defined in `loop_control`.
+    fn render_loop_vars(&self, vars: Value) -> Result<Vec<Value>> {
+        let items = self.render_iterator(vars.clone())?;
+        Ok((0..items.len())
+            .map(|index| {
+                let iteration_vars = self.loop_control.get_iteration_vars(&items, index);
+                context! {..iteration_vars, ..vars.clone()}
+            })
+            .collect())
+    }
+
+    /// Wait `pause` seconds between iterations and log the iteration `label`.
+    fn start_loop_iteration(&self, index: usize, vars: &Value) -> Result<()> {
+        if index > 0
+            && let Some(pause) = self.loop_control.get_pause()
+        {
+            trace!("pause loop: {pause:?}");
+            thread::sleep(pause);
+        }
+
+        if let Some(label) = self.loop_control.get_label() {
+            let extended_vars = self.extend_vars(vars.clone())?;
+            info!(target: "task",
+                "[{}] - ({}) - ",
+                self.get_rendered_name(extended_vars.clone())
+                    .unwrap_or_else(|_| self.module.get_name().to_owned()),
+                render_string(label, &extended_vars)?,
+            );
+        }
+        Ok(())
+    }
+
     fn is_changed(&self, result: &ModuleResult, vars: &Value) -> Result<bool> {
         trace!("changed_when: {:?}", self.changed_when);
         match &self.changed_when {
@@ -391,6 +584,84 @@ impl<'a> Task<'a> {
         }
     }
 
+    fn is_failed(&self, result: &ModuleResult, vars: &Value) -> Result<bool> {
+        trace!("failed_when: {:?}", self.failed_when);
+        match &self.failed_when {
+            Some(s) => {
+                let result_vars = match &self.register {
+                    Some(register) => {
+                        let register_vars = [(register.clone(), Value::from_serialize(result))]
+                            .into_iter()
+                            .collect::<Value>();
+                        context! {..register_vars, ..vars.clone()}
+                    }
+                    None => vars.clone(),
+                };
+                is_render_string(s, &result_vars)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// Replace error message if task output must be hidden.
+    fn censor_error(&self, error: Error) -> Error {
+        match is_no_log() {
+            true => Error::new(error.kind(), NO_LOG_MESSAGE),
+            false => error,
+        }
+    }
+
+    /// Override `failed` in module result with `failed_when`, if defined, and return the error
+    /// of failed results.
+    fn check_failed_when(
+        &self,
+        result: ModuleResult,
+        vars: &Value,
+    ) -> Result<(ModuleResult, Option<Error>)> {
+        let result = match self.failed_when {
+            Some(_) => {
+                let failed = self.is_failed(&result, vars)?;
+                result.with_failed(failed)
+            }
+            None => result,
+        };
+        let error = match (result.is_failed(), &self.failed_when) {
+            (false, _) => None,
+            (true, Some(failed_when)) => Some(Error::new(
+                ErrorKind::Other,
+                format!(
+                    "failed_when condition `{failed_when}` is true{}",
+                    result
+                        .get_output()
+                        .map(|output| format!(": {output}"))
+                        .unwrap_or_default()
+                ),
+            )),
+            (true, None) => Some(Error::new(
+                ErrorKind::InvalidData,
+                result.get_msg().unwrap_or("module failed"),
+            )),
+        };
+        Ok((result, error.map(|e| self.censor_error(e))))
+    }
+
+    /// Register the failed result and return the error, or ignore it if errors are ignored.
+    fn fail(&self, e: Error, result: Option<ModuleResult>) -> Result<TaskExecResult> {
+        match self.ignore_errors {
+            Some(true) => Ok(self.ignore_error(e, result)),
+            _ => match self.get_failed_register_vars(&e, result) {
+                Some(register_vars) => Err(Error::new(
+                    e.kind(),
+                    RegisteredError {
+                        error: e,
+                        register_vars,
+                    },
+                )),
+                None => Err(e),
+            },
+        }
+    }
+
     fn is_until_satisfied(&self, vars: &Value) -> Result<bool> {
         trace!("until: {:?}", self.until);
         match &self.until {
@@ -404,7 +675,7 @@ impl<'a> Task<'a> {
         let delay_secs = self.delay.unwrap_or(0);
 
         for attempt in 0..=max_retries {
-            let result = self.exec_module(vars.clone())?;
+            let result = self.exec_module_with_debugger(vars.clone())?;
 
             if self.until.is_some() {
                 let result_vars = result.clone().take_vars().unwrap_or(context! {});
@@ -421,6 +692,12 @@ impl<'a> Task<'a> {
                         "until condition not satisfied on attempt {}, retrying in {} seconds",
                         attempt, delay_secs
                     );
+                    events::emit(Event::TaskRetry {
+                        task: &self.get_display_name(&vars),
+                        attempt: attempt + 1,
+                        retries: max_retries,
+                        delay: delay_secs,
+                    });
                     if delay_secs > 0 {
                         std::thread::sleep(std::time::Duration::from_secs(delay_secs));
                     }
@@ -462,12 +739,10 @@ impl<'a> Task<'a> {
         let env_vars = self.render_environment(&extended_vars)?;
 
         let module_name = self.module.get_name();
-        if module_name != "command" && module_name != "shell" {
+        if !self.module.get_capabilities().r#async {
             return Err(Error::new(
                 ErrorKind::InvalidData,
-                format!(
-                    "Async execution only supported for command/shell modules, got: {module_name}"
-                ),
+                format!("Async execution not supported by {module_name} module"),
             ));
         }
 
@@ -483,26 +758,7 @@ impl<'a> Task<'a> {
         };
 
         let chdir = rendered_params.get("chdir").and_then(|d| d.as_str());
-
-        let mut cmd = StdCommand::new("/bin/sh");
-        cmd.arg("-c").arg(&cmd_str);
-        if let Some(dir) = chdir {
-            cmd.current_dir(dir);
-        }
-        cmd.stdout(Stdio::piped()).stderr(Stdio::piped());
-
-        for (key, value) in &env_vars {
-            cmd.env(key, value);
-        }
-
-        let child = cmd.spawn().map_err(|e| {
-            Error::new(
-                ErrorKind::SubprocessFail,
-                format!("Failed to spawn async command: {e}"),
-            )
-        })?;
-
-        let job_id = register_job(self.get_async_timeout(), child);
+        let job_id = spawn_job(&cmd_str, chdir, &env_vars, self.get_async_timeout())?;
 
         info!(target: "async",
             "Started async job {} with timeout {:?}",
@@ -513,7 +769,7 @@ impl<'a> Task<'a> {
         Ok(job_id)
     }
 
-    fn poll_job(&self, job_id: u64, poll_interval: u64) -> Result<TaskExecResult> {
+    fn poll_job(&self, job_id: u64, poll_interval: u64, vars: &Value) -> Result<TaskExecResult> {
         let sleep_duration = Duration::from_secs(poll_interval);
 
         loop {
@@ -522,16 +778,33 @@ impl<'a> Task<'a> {
             })?;
 
             match info.status {
-                JobStatus::Finished => {
-                    let result = ModuleResult::new(info.changed, None, info.output.clone());
+                // jobs which failed without exit code, e.g. timed out, can't be checked
+                JobStatus::Finished | JobStatus::Failed if info.rc.is_some() => {
+                    let extended_vars = self.extend_vars(vars.clone())?;
+                    let result = ModuleResult::new(info.changed, None, info.output.clone())
+                        .with_rc(info.rc)
+                        .with_stdout(info.stdout)
+                        .with_stderr(info.stderr);
+                    let result = match info.status {
+                        JobStatus::Failed => result.with_failed(true).with_msg(format!(
+                            "Async job {job_id} failed: {}",
+                            info.error.unwrap_or_default()
+                        )),
+                        _ => result,
+                    };
+                    let (result, error) = self.check_failed_when(result, &extended_vars)?;
+                    if let Some(e) = error {
+                        return self.fail(e, Some(result));
+                    }
+                    let changed = self.is_changed(&result, &extended_vars)?;
                     let register_vars = self.register.clone().map(|register| {
                         [(register.clone(), Value::from_serialize(&result))]
                             .into_iter()
                             .collect::<Value>()
                     });
-                    return Ok(TaskExecResult::new(info.changed, register_vars));
+                    return Ok(TaskExecResult::new(changed, register_vars));
                 }
-                JobStatus::Failed => {
+                JobStatus::Finished | JobStatus::Failed => {
                     return Err(Error::new(
                         ErrorKind::SubprocessFail,
                         format!(
@@ -579,23 +852,24 @@ impl<'a> Task<'a> {
             Ok(_) => match setuid(user.uid) {
                 Ok(_) => {
                     // After changing user, call the inner module exec directly
-                    let module_name = self.module.get_name();
-
-                    let result = self.module.exec(
-                        self.global_params,
-                        rendered_params.clone(),
-                        &extended_vars,
-                        self.check_mode,
-                    );
+                    let result = self
+                        .module
+                        .exec(
+                            &self.get_children_global_params(),
+                            rendered_params.clone(),
+                            &extended_vars,
+                            self.check_mode,
+                        )
+                        .and_then(|(result, result_vars)| {
+                            Ok((self.check_failed_when(result, &extended_vars)?, result_vars))
+                        })
+                        .map_err(|e| self.censor_error(e));
 
                     match result {
-                        Ok((result, result_vars)) => {
+                        Ok(((result, None), result_vars)) => {
                             let changed = self.is_changed(&result, &extended_vars)?;
 
-                            if module_name != "include"
-                                && module_name != "block"
-                                && module_name != "meta"
-                            {
+                            if !self.is_control_flow_module() {
                                 log_module_result(changed, &result);
                             }
 
@@ -617,13 +891,8 @@ impl<'a> Task<'a> {
 
                             Ok(TaskExecResult::new(changed, new_vars))
                         }
-                        Err(e) => match self.ignore_errors {
-                            Some(is_true) if is_true => {
-                                info!(target: "ignoring", "{e}");
-                                Ok(TaskExecResult::new(false, None))
-                            }
-                            _ => Err(e),
-                        },
+                        Ok(((result, Some(e)), _)) => self.fail(e, Some(result)),
+                        Err(e) => self.fail(e, None),
                     }
                 }
                 Err(_) => Err(Error::new(
@@ -643,9 +912,22 @@ impl<'a> Task<'a> {
         rendered_params: &YamlValue,
         vars: &Value,
     ) -> Result<TaskExecResult> {
-        let temp_dir = std::env::temp_dir();
-        let task_file = temp_dir.join(format!("rash_task_{}.yaml", uuid::Uuid::new_v4()));
-        let result_file = temp_dir.join(format!("rash_result_{}.json", uuid::Uuid::new_v4()));
+        let result_file =
+            std::env::temp_dir().join(format!("rash_result_{}.json", uuid::Uuid::new_v4()));
+
+        // the child process registers the result and checks `changed_when` and `failed_when`
+        let mut task = serde_json::json!({
+            "name": self.name,
+            "no_log": self.no_log,
+            "register": self.register,
+            "changed_when": self.changed_when,
+            "failed_when": self.failed_when,
+            self.module.get_name(): rendered_params,
+        });
+        // safe unwrap: it is an object
+        task.as_object_mut()
+            .unwrap()
+            .retain(|key, value| !value.is_null() || key == self.module.get_name());
 
         let internal_data = InternalTaskData {
             original_path: vars
@@ -655,24 +937,11 @@ impl<'a> Task<'a> {
                 .and_then(|p| p.as_str().map(String::from)),
             args: None,
             vars: vars.clone(),
-            task: serde_norway::to_value(serde_json::json!({
-                "name": self.name,
-                self.module.get_name(): rendered_params,
-            }))
-            .map_err(|e| Error::new(ErrorKind::Other, e))?,
+            task: serde_norway::to_value(task).map_err(|e| Error::new(ErrorKind::Other, e))?,
+            secrets: get_secrets(),
         };
 
-        let task_content =
-            serde_yaml::to_string(&internal_data).map_err(|e| Error::new(ErrorKind::Other, e))?;
-
-        let mut file = File::create(&task_file).map_err(|e| {
-            Error::new(
-                ErrorKind::Other,
-                format!("Failed to create task file: {}", e),
-            )
-        })?;
-        file.write_all(task_content.as_bytes())
-            .map_err(|e| Error::new(ErrorKind::Other, e))?;
+        let task_file = write_task_file(&internal_data, &self.become_user)?;
 
         let rash_path = std::env::current_exe().map_err(|e| {
             Error::new(
@@ -696,7 +965,7 @@ impl<'a> Task<'a> {
                 .arg("--")
                 .arg(&rash_path)
                 .arg("--internal-task")
-                .arg(&task_file)
+                .arg(task_file.path())
                 .env(RASH_INTERNAL_RESULT_ENV, &result_file)
                 .env(RASH_INTERNAL_TASK_FLAG, "1")
                 .stdin(Stdio::piped())
@@ -745,7 +1014,7 @@ impl<'a> Task<'a> {
                 .arg("--")
                 .arg(&rash_path)
                 .arg("--internal-task")
-                .arg(&task_file)
+                .arg(task_file.path())
                 .env(RASH_INTERNAL_RESULT_ENV, &result_file)
                 .env(RASH_INTERNAL_TASK_FLAG, "1")
                 .status()
@@ -762,8 +1031,7 @@ impl<'a> Task<'a> {
             }
         };
 
-        // Cleanup temp files
-        let _ = fs::remove_file(&task_file);
+        drop(task_file);
 
         if !output.status.success() {
             let _ = fs::remove_file(&result_file);
@@ -797,6 +1065,90 @@ impl<'a> Task<'a> {
         Ok(result)
     }
 
+    /// Rendered connection of the task, if it is not local.
+    fn get_connection(&self, vars: &Value) -> Result<Option<Connection>> {
+        match &self.connection {
+            Some(connection_yaml) => {
+                let extended_vars = self.extend_vars(vars.clone())?;
+                let connection =
+                    Connection::from_yaml(&render(connection_yaml.clone(), &extended_vars)?)?;
+                Ok((!connection.is_local()).then_some(connection))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Vars to register the failed module result, if any, with the error in `msg`.
+    fn get_failed_register_vars(&self, e: &Error, result: Option<ModuleResult>) -> Option<Value> {
+        let result = match result {
+            Some(result) => result.with_changed(false).with_msg(e.to_string()),
+            None => ModuleResult::failed(e.to_string()),
+        };
+        self.register.clone().map(|register| {
+            [(register, Value::from_serialize(result))]
+                .into_iter()
+                .collect::<Value>()
+        })
+    }
+
+    /// Ignore the error of the task, registering it as a failed result.
+    fn ignore_error(&self, e: Error, result: Option<ModuleResult>) -> TaskExecResult {
+        info!(target: "ignoring", "{e}");
+        let register_vars = self.get_failed_register_vars(&e, result);
+        TaskExecResult::new(false, register_vars).with_ignored()
+    }
+
+    /// Modules which execute other tasks or control the execution, instead of acting on the
+    /// target.
+    fn is_control_flow_module(&self) -> bool {
+        matches!(
+            self.module.get_name(),
+            "include" | "block" | "parallel" | "meta"
+        )
+    }
+
+    fn exec_module_via_connection(
+        &self,
+        connection: &Connection,
+        rendered_params: &YamlValue,
+        vars: &Value,
+    ) -> Result<TaskExecResult> {
+        let environment = self
+            .render_environment(vars)?
+            .into_iter()
+            .collect::<HashMap<_, _>>();
+        let mut task = serde_json::json!({
+            "name": self.name,
+            "no_log": self.no_log,
+            "check_mode": self.check_mode,
+            "become": self.r#become,
+            "become_user": self.become_user,
+            "environment": environment,
+            "vars": self.vars,
+            "register": self.register,
+            "changed_when": self.changed_when,
+            "failed_when": self.failed_when,
+            self.module.get_name(): rendered_params,
+        });
+        // safe unwrap: it is an object
+        task.as_object_mut()
+            .unwrap()
+            .retain(|key, value| !value.is_null() || key == self.module.get_name());
+
+        let internal_data = InternalTaskData {
+            original_path: self.get_script_path(vars),
+            args: None,
+            vars: vars.clone(),
+            task: serde_norway::to_value(task).map_err(|e| Error::new(ErrorKind::Other, e))?,
+            secrets: get_secrets(),
+        };
+
+        debug!("executing in {connection}");
+        connection
+            .exec_internal_task(&internal_data)
+            .or_else(|e| self.fail(e, None))
+    }
+
     fn exec_module_rendered(
         &self,
         rendered_params: &YamlValue,
@@ -821,12 +1173,18 @@ impl<'a> Task<'a> {
             }
         }
 
-        let result = self.module.exec(
-            self.global_params,
-            rendered_params.clone(),
-            &extended_vars,
-            self.check_mode,
-        );
+        let result = self
+            .module
+            .exec(
+                &self.get_children_global_params(),
+                rendered_params.clone(),
+                &extended_vars,
+                self.check_mode,
+            )
+            .and_then(|(result, result_vars)| {
+                Ok((self.check_failed_when(result, &extended_vars)?, result_vars))
+            })
+            .map_err(|e| self.censor_error(e));
 
         // Restore original environment
         for (key, original_value) in original_env {
@@ -840,7 +1198,7 @@ impl<'a> Task<'a> {
         }
 
         match result {
-            Ok((result, result_vars)) => {
+            Ok(((result, None), result_vars)) => {
                 let changed = self.is_changed(&result, &extended_vars)?;
                 let is_meta_flush = module_name == "meta"
                     && result
@@ -849,7 +1207,7 @@ impl<'a> Task<'a> {
                         == Some("flush_handlers".to_string());
 
                 // Don't show output for control flow modules like include and block
-                if module_name != "include" && module_name != "block" && module_name != "meta" {
+                if !self.is_control_flow_module() {
                     log_module_result(changed, &result);
                 }
 
@@ -875,18 +1233,20 @@ impl<'a> Task<'a> {
                 }
                 Ok(exec_result)
             }
-            Err(e) => match self.ignore_errors {
-                Some(is_true) if is_true => {
-                    info!(target: "ignoring", "{e}");
-                    Ok(TaskExecResult::new(false, None))
-                }
-                _ => Err(e),
-            },
+            Ok(((result, Some(e)), _)) => self.fail(e, Some(result)),
+            Err(e) => self.fail(e, None),
         }
     }
 
     fn exec_module(&self, vars: Value) -> Result<TaskExecResult> {
         if self.is_exec(&vars)? {
+            if self.check_mode
+                && self.module.get_capabilities().check_mode == CheckModeSupport::None
+            {
+                info!(target: "skipped", "skipped (check mode unsupported)");
+                return Ok(TaskExecResult::new(false, None).with_skipped());
+            }
+
             let rendered_params = self.render_params(vars.clone())?;
 
             // Handle async execution
@@ -907,24 +1267,24 @@ impl<'a> Task<'a> {
                 }
 
                 // Poll for completion
-                let poll_result = self.poll_job(job_id, poll_interval);
-                return match poll_result {
-                    Ok(exec_result) => Ok(exec_result),
-                    Err(e) => match self.ignore_errors {
-                        Some(true) => {
-                            info!(target: "ignoring", "{e}");
-                            Ok(TaskExecResult::new(false, None))
-                        }
-                        _ => Err(e),
-                    },
-                };
+                return self
+                    .poll_job(job_id, poll_interval, &vars)
+                    .or_else(|e| self.fail(e, None));
+            }
+
+            if !self.is_control_flow_module()
+                && let Some(connection) = self.get_connection(&vars)?
+            {
+                return self.exec_module_via_connection(&connection, &rendered_params, &vars);
             }
 
-            match self.r#become && !self.check_mode {
+            match self.r#become && !self.check_mode && self.module.get_capabilities().r#become {
                 true => {
                     // Handle sudo method separately
                     if self.become_method == BecomeMethod::Sudo {
-                        return self.exec_module_via_sudo(&rendered_params, &vars);
+                        return self
+                            .exec_module_via_sudo(&rendered_params, &vars)
+                            .or_else(|e| self.fail(e, None));
                     }
 
                     // Syscall method (default)
@@ -1023,7 +1383,7 @@ impl<'a> Task<'a> {
             }
         } else {
             debug!("skipping");
-            Ok(TaskExecResult::new(false, None))
+            Ok(TaskExecResult::new(false, None).with_skipped())
         }
     }
 
@@ -1032,14 +1392,73 @@ impl<'a> Task<'a> {
     /// [`Module`]: ../modules/trait.Module.html
     /// [`Vars`]: ../vars/struct.Vars.html
     pub fn exec(&self, vars: Value) -> Result<TaskExecResult> {
+        let _no_log_guard = self.no_log.then(NoLogGuard::new);
+
         debug!("Module: {}", self.module.get_name());
-        debug!("Params: {:?}", self.params);
+        if !is_no_log() {
+            debug!("Params: {:?}", self.params);
+        }
+
+        let vars = self.get_file_vars(vars)?;
+        let result = if self.rescue.is_some() || self.always.is_some() {
+            self.exec_with_rescue_always(vars)
+        } else {
+            self.exec_main_task(vars)
+        };
+
+        if is_no_log()
+            && let Ok(Some(vars)) = result.as_ref().map(TaskExecResult::get_vars)
+        {
+            journal::add_no_log_vars(vars);
+        }
+        result
+    }
 
-        if self.rescue.is_some() || self.always.is_some() {
-            return self.exec_with_rescue_always(vars);
+    /// Execute module, with the vars of the loop item if any. With `debugger: on_failed`, the
+    /// debugger is opened each time it fails, until it succeeds or it is skipped or failed from
+    /// the debugger. Retries only execute the failed item again.
+    fn exec_module_with_debugger(&self, vars: Value) -> Result<TaskExecResult> {
+        if self.debugger != Debugger::OnFailed || self.is_control_flow_module() {
+            return self.exec_module(vars);
         }
 
-        self.exec_main_task(vars)
+        let mut retried_task: Option<Task> = None;
+        loop {
+            let task = retried_task.as_ref().unwrap_or(self);
+            let error = match task.exec_module(vars.clone()) {
+                Err(e) => e,
+                result => return result,
+            };
+
+            let task_vars = task.extend_vars(vars.clone()).unwrap_or(vars.clone());
+            match debugger::run(
+                io::stdin().lock(),
+                io::stderr(),
+                &task.get_display_name(&vars),
+                &error,
+                &task_vars,
+                &task.params,
+            )? {
+                debugger::Action::Retry(params) => {
+                    let mut task = self.clone();
+                    task.params = params;
+                    retried_task = Some(task);
+                }
+                debugger::Action::Skip => {
+                    return Ok(TaskExecResult::new(false, None).with_skipped());
+                }
+                debugger::Action::Quit => return Err(error),
+            }
+        }
+    }
+
+    /// Return vars with the `rash` builtins of the file the task was imported from, so `rash.dir`
+    /// and relative includes refer to it.
+    fn get_file_vars(&self, vars: Value) -> Result<Value> {
+        match &self.file_path {
+            Some(file_path) => set_script_path(vars, Path::new(file_path)),
+            None => Ok(vars),
+        }
     }
 
     /// Return name.
@@ -1060,6 +1479,59 @@ impl<'a> Task<'a> {
         )
     }
 
+    /// Return name to identify the task in logs: script path and rendered name (or module name).
+    pub fn get_display_name(&self, vars: &Value) -> String {
+        let name = self
+            .get_rendered_name(vars.clone())
+            .unwrap_or_else(|_| self.module.get_name().to_owned());
+        match self.get_script_path(vars) {
+            Some(path) => format!("{path}:{name}"),
+            None => name,
+        }
+    }
+
+    fn get_script_path(&self, vars: &Value) -> Option<String> {
+        if self.file_path.is_some() {
+            return self.file_path.clone();
+        }
+        match vars.get_attr("rash").and_then(|rash| rash.get_attr("path")) {
+            Ok(path) if !path.is_undefined() => Some(path.to_string()),
+            _ => None,
+        }
+    }
+
+    /// Return [`TaskInfo`] to identify the task in events and stats.
+    ///
+    /// [`TaskInfo`]: ../events/struct.TaskInfo.html
+    pub fn get_info(&self, vars: &Value) -> TaskInfo {
+        TaskInfo {
+            task: self.get_display_name(vars),
+            file: self.get_script_path(vars),
+            line: self.line,
+            module: self.module.get_name().to_owned(),
+            handler: false,
+        }
+    }
+
+    /// Emit `loop_item` event with the result of a loop iteration.
+    fn emit_loop_item(
+        &self,
+        index: usize,
+        vars: &Value,
+        result: &Result<TaskExecResult>,
+        duration: Duration,
+    ) {
+        if events::is_enabled() {
+            events::loop_item(
+                &self.get_display_name(vars),
+                index,
+                vars.get_attr(self.loop_control.get_loop_var())
+                    .unwrap_or_default(),
+                Outcome::new(result, duration),
+            );
+        }
+    }
+
     /// Return [`Module`].
     ///
     /// [`Module`]: ../modules/trait.Module.html
@@ -1072,6 +1544,60 @@ impl<'a> Task<'a> {
         self.notify.as_deref()
     }
 
+    /// Return tags, including the inherited ones.
+    pub fn get_tags(&self) -> &[String] {
+        &self.tags
+    }
+
+    /// Return true if task must be executed according to `--tags` and `--skip-tags`.
+    ///
+    /// `block`, `parallel` and `include` tasks are always selected because their tasks inherit
+    /// their tags and are filtered one by one.
+    pub fn is_selected_by_tags(&self) -> bool {
+        matches!(self.module.get_name(), "block" | "parallel" | "include")
+            || tags::is_selected(&self.tags, self.global_params)
+    }
+
+    /// Call `f` with each task created from this one, including the ones in its `rescue` and
+    /// `always` sections and in included files.
+    pub fn for_each_child(&self, vars: &Value, f: &mut children::Visit) -> Result<()> {
+        children::for_each_child(self, vars, f)
+    }
+
+    /// Return true if the task must be skipped until the task to start at with
+    /// `--start-at-task` is found.
+    pub fn is_before_start_at_task(&self, vars: &Value) -> Result<bool> {
+        match self.global_params.start_at_task {
+            Some(start_at_task) => start_at_task.is_before(self, vars),
+            None => Ok(false),
+        }
+    }
+
+    /// Return true if the task to start at with `--start-at-task` is not found yet.
+    fn is_start_at_task_pending(&self) -> bool {
+        self.global_params
+            .start_at_task
+            .is_some_and(|start_at_task| !start_at_task.is_started())
+    }
+
+    /// [`GlobalParams`] for tasks created from this one, which inherit its tags.
+    ///
+    /// [`GlobalParams`]: ../context/struct.GlobalParams.html
+    fn get_children_global_params(&self) -> GlobalParams<'_> {
+        GlobalParams {
+            r#become: self.r#become,
+            become_user: &self.become_user,
+            become_method: self.become_method,
+            become_exe: &self.become_exe,
+            become_password: self.become_password.as_deref(),
+            inherited_tags: &self.tags,
+            connection: self.connection.as_ref(),
+            throttle: self.throttle,
+            debugger: self.debugger,
+            ..*self.global_params
+        }
+    }
+
     /// Execute a task with comprehensive rescue and always handling.
     ///
     /// This method implements a try-catch-finally pattern similar to exception handling:
@@ -1083,16 +1609,31 @@ impl<'a> Task<'a> {
     /// and provides detailed error context for debugging.
     fn exec_with_rescue_always(&self, vars: Value) -> Result<TaskExecResult> {
         let initial_vars = vars;
-
-        // Stage 1: Execute main task and capture result
-        let (main_result, main_exec_result) = match self.exec_main_task(initial_vars.clone()) {
+        let failed_before = stats::get_recap().failed;
+
+        // Stage 1: Execute main task and capture result. It is skipped when the task to start
+        // at is in `rescue` or `always`.
+        let start_at_task = self
+            .global_params
+            .start_at_task
+            .filter(|start_at_task| !start_at_task.is_started());
+        let main_exec = match start_at_task {
+            Some(start_at_task) if !start_at_task.is_in_main_children(self, &initial_vars)? => {
+                debug!("skipping main task until start at task");
+                Ok(TaskExecResult::new(false, None))
+            }
+            _ => self.exec_main_task(initial_vars.clone()),
+        };
+        let (main_result, main_exec_result) = match main_exec {
             Ok(exec_result) => {
                 trace!("Main task execution succeeded");
                 (Ok(()), exec_result)
             }
             Err(task_error) => {
                 warn!("Main task execution failed: {task_error}");
-                (Err(task_error), TaskExecResult::new(false, None))
+                // the result registered by the failed task is available in `rescue`
+                let register_vars = get_registered_vars(&task_error);
+                (Err(task_error), TaskExecResult::new(false, register_vars))
             }
         };
 
@@ -1117,6 +1658,16 @@ impl<'a> Task<'a> {
                 trace!("Main task failed but no rescue tasks defined");
                 (Ok(()), TaskExecResult::new(main_changed, main_vars.clone())) // No rescue available, but continue to always
             }
+            (Ok(_), Some(rescue_tasks)) if self.is_start_at_task_pending() => {
+                debug!("Executing rescue tasks until start at task");
+                match self.execute_task_sequence(rescue_tasks, post_main_vars.clone()) {
+                    Ok(rescue_result) => (Ok(()), rescue_result),
+                    Err(rescue_error) => {
+                        error!("Rescue tasks failed: {rescue_error}");
+                        (Err(rescue_error), TaskExecResult::new(false, None))
+                    }
+                }
+            }
             (Ok(_), _) => {
                 trace!("Main task succeeded, skipping rescue tasks");
                 (Ok(()), TaskExecResult::new(main_changed, main_vars.clone())) // Task succeeded, no rescue needed
@@ -1167,10 +1718,13 @@ impl<'a> Task<'a> {
                 warn!("Unexpected state: main task succeeded but rescue reported failure");
                 Ok(TaskExecResult::new(main_changed, all_vars))
             }
-            (Err(_main_error), Ok(_)) => {
+            (Err(_main_error), Ok(_)) if self.rescue.is_some() => {
                 debug!("Task execution recovered through rescue tasks");
-                Ok(TaskExecResult::new(rescue_changed, all_vars))
+                // failures recovered by rescue tasks are not reported in the recap
+                stats::set_failed(failed_before);
+                Ok(TaskExecResult::new(rescue_changed, all_vars).with_rescued())
             }
+            (Err(_main_error), Ok(_)) => Ok(TaskExecResult::new(rescue_changed, all_vars)),
             (Err(main_error), Err(_)) => {
                 if self.rescue.is_some() {
                     Err(Error::new(
@@ -1205,16 +1759,20 @@ impl<'a> Task<'a> {
                 let mut current_vars = vars;
                 let mut current_new_vars = context! {};
                 let mut any_changed = false;
+                let children_global_params = self.get_children_global_params();
                 for (index, task_yaml) in tasks.iter().enumerate() {
-                    match Task::new(task_yaml, self.global_params) {
+                    match Task::new(task_yaml, &children_global_params) {
+                        Ok(task) if !task.is_selected_by_tags() => {
+                            debug!("skipping task {index} in sequence by tags");
+                        }
+                        Ok(task) if task.is_before_start_at_task(&current_vars)? => {
+                            debug!("skipping task {index} in sequence until start at task");
+                        }
                         Ok(task) => {
-                            info!(target: "task",
-                                "[{}:{}] - ",
-                                current_vars.get_attr("rash")?.get_attr("path")?,
-                                task.get_rendered_name(current_vars.clone())
-                                    .unwrap_or_else(|_| task.get_module().get_name().to_owned()),
-                            );
-                            match task.exec(current_vars.clone()) {
+                            let task_info = task.get_info(&current_vars);
+                            info!(target: "task", "[{}] - ", task_info.task);
+                            match stats::track_task(&task_info, || task.exec(current_vars.clone()))
+                            {
                                 Ok(exec_result) => {
                                     if exec_result.get_changed() {
                                         any_changed = true;
@@ -1307,7 +1865,7 @@ impl<'a> Task<'a> {
         } else if self.until.is_some() {
             self.exec_with_retry(vars)
         } else {
-            self.exec_module(vars)
+            self.exec_module_with_debugger(vars)
         }
     }
 
@@ -1315,17 +1873,24 @@ impl<'a> Task<'a> {
         let mut changed = false;
         let mut all_new_vars = context! {};
         let mut flush_handlers = false;
+        let mut all_skipped = true;
+        let mut ignored = false;
 
-        for item in self.render_iterator(vars.clone())?.into_iter() {
-            let ctx = context! {item => &item, ..vars.clone()};
+        for (index, ctx) in self.render_loop_vars(vars)?.into_iter().enumerate() {
+            self.start_loop_iteration(index, &ctx)?;
             trace!("pre execute loop: {:?}", ctx);
-            let exec_result = self.exec_module(ctx)?;
+            let start = Instant::now();
+            let exec_result = self.exec_module_with_debugger(ctx.clone());
+            self.emit_loop_item(index, &ctx, &exec_result, start.elapsed());
+            let exec_result = exec_result?;
             if exec_result.get_changed() {
                 changed = true;
             }
             if exec_result.is_flush_handlers() {
                 flush_handlers = true;
             }
+            all_skipped &= exec_result.skipped;
+            ignored |= exec_result.ignored;
             if let Some(v) = exec_result.take_vars() {
                 all_new_vars = context! {..all_new_vars, ..v};
             }
@@ -1342,6 +1907,12 @@ impl<'a> Task<'a> {
         if flush_handlers {
             result = result.with_flush_handlers();
         }
+        if all_skipped {
+            result = result.with_skipped();
+        }
+        if ignored {
+            result = result.with_ignored();
+        }
         Ok(result)
     }
 
@@ -1349,17 +1920,24 @@ impl<'a> Task<'a> {
         let mut changed = false;
         let mut all_new_vars = context! {};
         let mut flush_handlers = false;
+        let mut all_skipped = true;
+        let mut ignored = false;
 
-        for item in self.render_iterator(vars.clone())?.into_iter() {
-            let ctx = context! {item => &item, ..vars.clone()};
+        for (index, ctx) in self.render_loop_vars(vars)?.into_iter().enumerate() {
+            self.start_loop_iteration(index, &ctx)?;
             trace!("pre execute loop with retry: {:?}", ctx);
-            let exec_result = self.exec_with_retry(ctx)?;
+            let start = Instant::now();
+            let exec_result = self.exec_with_retry(ctx.clone());
+            self.emit_loop_item(index, &ctx, &exec_result, start.elapsed());
+            let exec_result = exec_result?;
             if exec_result.get_changed() {
                 changed = true;
             }
             if exec_result.is_flush_handlers() {
                 flush_handlers = true;
             }
+            all_skipped &= exec_result.skipped;
+            ignored |= exec_result.ignored;
             if let Some(v) = exec_result.take_vars() {
                 all_new_vars = context! {..all_new_vars, ..v};
             }
@@ -1376,22 +1954,44 @@ impl<'a> Task<'a> {
         if flush_handlers {
             result = result.with_flush_handlers();
         }
+        if all_skipped {
+            result = result.with_skipped();
+        }
+        if ignored {
+            result = result.with_ignored();
+        }
         Ok(result)
     }
 
+    /// Wait until less than `throttle` of the jobs are running.
+    fn wait_for_throttle(&self, job_ids: &[(u64, Value)]) {
+        let Some(throttle) = self.throttle else {
+            return;
+        };
+        let is_running = |job_id: u64| get_job_info(job_id).is_some_and(|info| !info.is_finished());
+        while job_ids
+            .iter()
+            .filter(|(job_id, _)| is_running(*job_id))
+            .count()
+            >= throttle
+        {
+            thread::sleep(THROTTLE_CHECK_INTERVAL);
+        }
+    }
+
     fn exec_parallel_loop(&self, vars: Value) -> Result<TaskExecResult> {
-        let items = self.render_iterator(vars.clone())?;
         let poll_interval = self.get_poll_interval();
 
-        let mut job_ids: Vec<(u64, YamlValue)> = Vec::new();
+        let mut job_ids: Vec<(u64, Value)> = Vec::new();
 
-        for item in items.into_iter() {
-            let ctx = context! {item => &item, ..vars.clone()};
+        for (index, ctx) in self.render_loop_vars(vars)?.into_iter().enumerate() {
+            self.start_loop_iteration(index, &ctx)?;
             let rendered_params = self.render_params(ctx.clone())?;
 
             if self.is_exec(&ctx)? {
+                self.wait_for_throttle(&job_ids);
                 let job_id = self.spawn_async_command(&rendered_params, &ctx)?;
-                job_ids.push((job_id, item));
+                job_ids.push((job_id, ctx));
             }
         }
 
@@ -1477,7 +2077,11 @@ impl<'a> Task<'a> {
             .collect::<Value>()
         });
 
-        Ok(TaskExecResult::new(any_changed, register_vars))
+        let result = TaskExecResult::new(any_changed, register_vars);
+        match has_failures {
+            true => Ok(result.with_ignored()),
+            false => Ok(result),
+        }
     }
 
     fn exec_async_single(&self, vars: Value) -> Result<TaskExecResult> {
@@ -1501,17 +2105,8 @@ impl<'a> Task<'a> {
             return Ok(TaskExecResult::new(true, register_vars));
         }
 
-        let poll_result = self.poll_job(job_id, poll_interval);
-        match poll_result {
-            Ok(exec_result) => Ok(exec_result),
-            Err(e) => match self.ignore_errors {
-                Some(true) => {
-                    info!(target: "ignoring", "{e}");
-                    Ok(TaskExecResult::new(false, None))
-                }
-                _ => Err(e),
-            },
-        }
+        self.poll_job(job_id, poll_interval, &vars)
+            .or_else(|e| self.fail(e, None))
     }
 }
 
@@ -1529,11 +2124,153 @@ impl From<YamlValue> for Task<'_> {
     }
 }
 
+/// Return vars with `rash` builtins updated to the script in `path`, if they are defined.
+fn set_script_path(vars: Value, path: &Path) -> Result<Value> {
+    let rash = vars.get_attr("rash")?;
+    if rash.is_undefined() {
+        return Ok(vars);
+    }
+    let builtins = Builtins::deserialize(rash)?.update(path)?;
+    Ok(context! {rash => &builtins, ..vars})
+}
+
+/// Read a file with a list of tasks, returning its content and the tasks.
+fn read_tasks_file(path: &Path) -> Result<(String, Vec<YamlValue>)> {
+    trace!("reading tasks from: {path:?}");
+    let content = fs::read_to_string(path).map_err(|e| {
+        Error::new(
+            ErrorKind::InvalidData,
+            format!("Error reading file {}: {e}", path.display()),
+        )
+    })?;
+    match serde_norway::from_str(&content)? {
+        YamlValue::Sequence(tasks) => Ok((content, tasks)),
+        yaml => Err(Error::new(
+            ErrorKind::InvalidData,
+            format!(
+                "Expected a YAML sequence of tasks in {}, got: {yaml:?}",
+                path.display()
+            ),
+        )),
+    }
+}
+
+/// Return params of an `import` task, which only accepts a `name` besides them.
+pub(crate) fn get_import_params(task_yaml: &YamlValue) -> Result<IncludeParams> {
+    if let Some(key) = task_yaml
+        .as_mapping()
+        .into_iter()
+        .flat_map(|task| task.keys())
+        .find(|key| !matches!(key.as_str(), Some("import" | "name")))
+    {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            format!(
+                "Invalid keyword {key:?} in import task, task keywords can be added with apply"
+            ),
+        ));
+    }
+
+    IncludeParams::from_yaml(task_yaml["import"].clone())
+}
+
+/// Parse tasks of an `import` task, with the `vars` and `apply` keywords of its params.
+fn parse_import<'a>(
+    task_yaml: &YamlValue,
+    file_path: &Path,
+    import_stack: &[PathBuf],
+    global_params: &'a GlobalParams,
+) -> Result<Tasks<'a>> {
+    let params = get_import_params(task_yaml)?;
+    let import_path = params.get_path(file_path);
+    let import_path = import_path.canonicalize().unwrap_or(import_path);
+    if import_stack.contains(&import_path) {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            format!("Circular import of {}", import_path.display()),
+        ));
+    }
+
+    let (content, mut tasks_yaml) = read_tasks_file(&import_path)?;
+    params.add_vars_to(&mut tasks_yaml);
+    params.apply_to(&mut tasks_yaml);
+    parse_tasks(
+        &tasks_yaml,
+        &[],
+        &content,
+        &import_path,
+        &[import_stack, std::slice::from_ref(&import_path)].concat(),
+        global_params,
+    )
+}
+
+/// Parse tasks of a sequence found in `path` of the file, setting their lines and expanding
+/// `import` tasks with the tasks of the imported files.
+///
+/// `import_stack` contains the files being parsed, starting with the script: tasks of files
+/// imported from it are tagged with their file path.
+fn parse_tasks<'a>(
+    tasks_yaml: &[YamlValue],
+    path: &[Segment],
+    file_content: &str,
+    file_path: &Path,
+    import_stack: &[PathBuf],
+    global_params: &'a GlobalParams,
+) -> Result<Tasks<'a>> {
+    let positions = Positions::new(file_content);
+    let is_imported = import_stack.len() > 1;
+    let mut tasks = Vec::new();
+    for (index, task_yaml) in tasks_yaml.iter().enumerate() {
+        if task_yaml.get("import").is_some() {
+            tasks.extend(parse_import(
+                task_yaml,
+                file_path,
+                import_stack,
+                global_params,
+            )?);
+            continue;
+        }
+
+        let task = Task::new(task_yaml, global_params)?;
+        tasks.push(Task {
+            line: positions.find(&[path, &[Segment::Index(index)]].concat()),
+            file_path: is_imported.then(|| file_path.display().to_string()),
+            ..task
+        });
+    }
+    Ok(tasks)
+}
+
+/// Return the files being parsed when the script in `path` is parsed.
+fn get_import_stack(path: &Path) -> Vec<PathBuf> {
+    vec![path.canonicalize().unwrap_or(path.to_path_buf())]
+}
+
+/// Parse tasks of the file of an `include` task, adding its `apply` keywords.
+pub fn parse_included_file<'a>(
+    path: &Path,
+    params: &IncludeParams,
+    global_params: &'a GlobalParams,
+) -> Result<Tasks<'a>> {
+    let (content, mut tasks_yaml) = read_tasks_file(path)?;
+    params.apply_to(&mut tasks_yaml);
+    parse_tasks(
+        &tasks_yaml,
+        &[],
+        &content,
+        path,
+        &get_import_stack(path),
+        global_params,
+    )
+}
+
 /// Parse a YAML file returning Tasks.
 ///
-/// Works with files that contain only a task list (no handlers section).
+/// Works with files that contain only a task list (no handlers section). `path` is used to
+/// resolve relative paths of `import` tasks.
 pub fn parse_file<'a>(
     file_content: &str,
+    path: &Path,
     global_params: &'a GlobalParams<'a>,
 ) -> Result<Tasks<'a>> {
     let yaml: YamlValue = serde_norway::from_str(file_content)?;
@@ -1541,10 +2278,14 @@ pub fn parse_file<'a>(
     match yaml {
         YamlValue::Sequence(tasks_yaml) => {
             trace!("Parsing {} tasks from file", tasks_yaml.len());
-            tasks_yaml
-                .iter()
-                .map(|task_yaml| Task::new(task_yaml, global_params))
-                .collect::<Result<Tasks>>()
+            parse_tasks(
+                &tasks_yaml,
+                &[],
+                file_content,
+                path,
+                &get_import_stack(path),
+                global_params,
+            )
         }
         _ => Err(Error::new(
             ErrorKind::InvalidData,
@@ -1553,17 +2294,25 @@ pub fn parse_file<'a>(
     }
 }
 
-/// Parsed result containing tasks and optional handlers.
+/// Parsed result containing tasks, optional handlers and the script header.
 #[derive(Debug)]
 pub struct ParsedFile<'a> {
     pub tasks: Tasks<'a>,
     pub handlers: Option<Handlers<'a>>,
+    pub header: Header,
+}
+
+/// Parse the header of a script, which must be applied to the `GlobalParams` used to parse it.
+pub fn parse_header(file_content: &str) -> Result<Header> {
+    Header::from_yaml(&serde_norway::from_str(file_content)?)
 }
 
 /// Parse a YAML file that may contain tasks and handlers sections.
 ///
 /// This function supports files with the following structure:
 /// ```yaml
+/// vars:
+///   foo: boo
 /// tasks:
 ///   - name: First task
 ///     ...
@@ -1571,8 +2320,13 @@ pub struct ParsedFile<'a> {
 ///   - name: Handler name
 ///     ...
 /// ```
+///
+/// Besides `tasks` and `handlers`, the mapping admits the keys of [`Header`].
+///
+/// `path` is used to resolve relative paths of `import` tasks.
 pub fn parse_file_with_handlers<'a>(
     file_content: &str,
+    path: &Path,
     global_params: &'a GlobalParams,
 ) -> Result<ParsedFile<'a>> {
     let yaml: YamlValue = serde_norway::from_str(file_content)?;
@@ -1583,10 +2337,14 @@ pub fn parse_file_with_handlers<'a>(
             let handlers_yaml = mapping.get(YamlValue::String("handlers".to_string()));
 
             let tasks = match tasks_yaml {
-                Some(YamlValue::Sequence(tasks_seq)) => tasks_seq
-                    .iter()
-                    .map(|task_yaml| Task::new(task_yaml, global_params))
-                    .collect::<Result<Tasks>>()?,
+                Some(YamlValue::Sequence(tasks_seq)) => parse_tasks(
+                    tasks_seq,
+                    &[Segment::Key("tasks".to_owned())],
+                    file_content,
+                    path,
+                    &get_import_stack(path),
+                    global_params,
+                )?,
                 Some(_) => {
                     return Err(Error::new(
                         ErrorKind::InvalidData,
@@ -1614,7 +2372,11 @@ pub fn parse_file_with_handlers<'a>(
                 None => None,
             };
 
-            Ok(ParsedFile { tasks, handlers })
+            Ok(ParsedFile {
+                tasks,
+                handlers,
+                header: Header::from_yaml(&yaml)?,
+            })
         }
         _ => Err(Error::new(
             ErrorKind::InvalidData,
@@ -1806,6 +2568,130 @@ mod tests {
         assert!(task.is_exec(&vars).unwrap());
     }
 
+    #[test]
+    fn test_is_failed() {
+        let s: String = r#"
+            failed_when: "'ERROR' in result.output"
+            register: result
+            command: 'example'
+            "#
+        .to_owned();
+        let yaml: YamlValue = serde_norway::from_str(&s).unwrap();
+        let task = Task::from(yaml);
+        let error_result = ModuleResult::new(true, None, Some("ERROR: foo".to_owned()));
+        assert!(task.is_failed(&error_result, &context! {}).unwrap());
+        let ok_result = ModuleResult::new(true, None, Some("foo".to_owned()));
+        assert!(!task.is_failed(&ok_result, &context! {}).unwrap());
+    }
+
+    #[test]
+    fn test_is_failed_array() {
+        let s: String = r#"
+            failed_when:
+              - result.changed
+              - boo == 'test'
+            register: result
+            command: 'example'
+            "#
+        .to_owned();
+        let yaml: YamlValue = serde_norway::from_str(&s).unwrap();
+        let task = Task::from(yaml);
+        let result = ModuleResult::new(true, None, None);
+        assert!(task.is_failed(&result, &context! {boo => "test"}).unwrap());
+        assert!(!task.is_failed(&result, &context! {boo => "other"}).unwrap());
+    }
+
+    #[test]
+    fn test_exec_failed_when() {
+        let s: String = r#"
+            failed_when: true
+            debug:
+              msg: foo
+            "#
+        .to_owned();
+        let yaml: YamlValue = serde_norway::from_str(&s).unwrap();
+        let task = Task::new(&yaml, &GLOBAL_PARAMS).unwrap();
+        let error = task.exec(context! {}).unwrap_err();
+        assert!(error.to_string().contains("failed_when"));
+    }
+
+    #[test]
+    fn test_exec_failed_when_ignore_errors() {
+        let s: String = r#"
+            failed_when: true
+            ignore_errors: true
+            debug:
+              msg: foo
+            "#
+        .to_owned();
+        let yaml: YamlValue = serde_norway::from_str(&s).unwrap();
+        let task = Task::new(&yaml, &GLOBAL_PARAMS).unwrap();
+        let result = task.exec(context! {}).unwrap();
+        assert!(!result.get_changed());
+    }
+
+    #[test]
+    fn test_exec_failed_when_rescue() {
+        let s: String = r#"
+            failed_when: "'foo' in result.output"
+            register: result
+            debug:
+              msg: foo
+            rescue:
+              - set_vars:
+                  rescued: true
+            "#
+        .to_owned();
+        let yaml: YamlValue = serde_norway::from_str(&s).unwrap();
+        let task = Task::new(&yaml, &GLOBAL_PARAMS).unwrap();
+        let vars = context! {rash => context! {path => "test"}};
+        let result = task.exec(vars).unwrap();
+        assert_eq!(
+            result.get_vars().unwrap().get_attr("rescued").unwrap(),
+            Value::from(true)
+        );
+    }
+
+    #[test]
+    fn test_write_task_file() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let internal_data = InternalTaskData {
+            original_path: None,
+            args: None,
+            vars: context! {},
+            task: serde_norway::from_str("debug: {}").unwrap(),
+            secrets: vec!["task-file-secret".to_owned()],
+        };
+        let task_file = write_task_file(&internal_data, "root").unwrap();
+        let path = task_file.path().to_path_buf();
+
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+        assert!(
+            fs::read_to_string(&path)
+                .unwrap()
+                .contains("task-file-secret")
+        );
+
+        drop(task_file);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_exec_no_log_censors_error() {
+        let s: String = r#"
+            no_log: true
+            command: sh -c 'echo secret-output >&2; false'
+            "#
+        .to_owned();
+        let yaml: YamlValue = serde_norway::from_str(&s).unwrap();
+        let task = Task::new(&yaml, &GLOBAL_PARAMS).unwrap();
+        let error = task.exec(context! {}).unwrap_err();
+        assert_eq!(error.to_string(), NO_LOG_MESSAGE);
+        assert!(!is_no_log());
+    }
+
     #[test]
     fn test_render_iterator() {
         let s: String = r#"
@@ -1823,6 +2709,47 @@ mod tests {
         assert_eq!(iterator.len(), 3);
     }
 
+    #[test]
+    fn test_render_loop_vars() {
+        let s: String = r#"
+            command: 'example'
+            vars:
+              upper: "{{ package | upper }}"
+            loop: "{{ packages }}"
+            loop_control:
+              loop_var: package
+              index_var: idx
+            "#
+        .to_owned();
+        let yaml: YamlValue = serde_norway::from_str(&s).unwrap();
+        let task = Task::from(yaml);
+        let vars = context! {item => "outer", packages => ["nginx", "curl"]};
+        let loop_vars = task.render_loop_vars(vars).unwrap();
+        assert_eq!(loop_vars.len(), 2);
+        assert_eq!(
+            loop_vars[1].get_attr("package").unwrap(),
+            Value::from("curl")
+        );
+        assert_eq!(loop_vars[1].get_attr("idx").unwrap(), Value::from(1));
+        assert_eq!(loop_vars[1].get_attr("item").unwrap(), Value::from("outer"));
+    }
+
+    #[test]
+    fn test_task_new_invalid_loop_control() {
+        let yaml: YamlValue = serde_norway::from_str(
+            r#"
+            debug:
+              msg: foo
+            loop: [1]
+            loop_control:
+              foo: bar
+            "#,
+        )
+        .unwrap();
+        let error = Task::new(&yaml, &GLOBAL_PARAMS).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+    }
+
     #[test]
     fn test_task_new() {
         let yaml_str = r#"
@@ -1845,7 +2772,7 @@ mod tests {
           debug:
             msg: "second"
         "#;
-        let tasks = parse_file(file_content, &GLOBAL_PARAMS).unwrap();
+        let tasks = parse_file(file_content, Path::new("main.rh"), &GLOBAL_PARAMS).unwrap();
         assert_eq!(tasks.len(), 2);
     }
 
@@ -1862,7 +2789,8 @@ mod tests {
             debug:
               msg: "handler"
         "#;
-        let parsed = parse_file_with_handlers(file_content, &GLOBAL_PARAMS).unwrap();
+        let parsed =
+            parse_file_with_handlers(file_content, Path::new("main.rh"), &GLOBAL_PARAMS).unwrap();
         assert_eq!(parsed.tasks.len(), 1);
         assert!(parsed.handlers.is_some());
         let handlers = parsed.handlers.unwrap();
@@ -1909,6 +2837,7 @@ mod tests {
             become_exe: "sudo",
             become_password: None,
             check_mode: true,
+            ..Default::default()
         };
         let yaml_str = r#"
         name: test check mode with become
@@ -1922,4 +2851,63 @@ mod tests {
         let result = task.exec(context! {}).unwrap();
         assert!(result.get_changed());
     }
+
+    #[test]
+    fn test_check_mode_skips_unsupported_module() {
+        let global_params = GlobalParams {
+            check_mode: true,
+            ..Default::default()
+        };
+        let yaml: YamlValue = serde_norway::from_str(
+            r#"
+        name: test check mode unsupported
+        uri:
+          url: http://localhost
+        "#,
+        )
+        .unwrap();
+        let task = Task::new(&yaml, &global_params).unwrap();
+
+        let result = task.exec(context! {}).unwrap();
+        assert!(!result.get_changed());
+        assert_eq!(result.get_status(), TaskStatus::Skipped);
+    }
+
+    #[test]
+    fn test_async_unsupported_module() {
+        let yaml: YamlValue = serde_norway::from_str(
+            r#"
+        debug:
+          msg: foo
+        async: 10
+        "#,
+        )
+        .unwrap();
+        let task = Task::new(&yaml, &GLOBAL_PARAMS).unwrap();
+
+        let error = task.exec(context! {}).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Async execution not supported by debug module"
+        );
+    }
+
+    #[test]
+    fn test_children_global_params_become() {
+        let yaml: YamlValue = serde_norway::from_str(
+            r#"
+        block:
+          - command: whoami
+        become: true
+        become_user: nobody
+        "#,
+        )
+        .unwrap();
+        let task = Task::new(&yaml, &GLOBAL_PARAMS).unwrap();
+        assert!(!task.get_module().get_capabilities().r#become);
+
+        let children_global_params = task.get_children_global_params();
+        assert!(children_global_params.r#become);
+        assert_eq!(children_global_params.become_user, "nobody");
+    }
 }
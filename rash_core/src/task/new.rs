@@ -40,6 +40,12 @@ impl TaskNew {
                 })
             })
             .collect::<Result<Vec<_>>>()?;
+        if attrs_seq.iter().any(|key| key == "import") {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "import is only supported in task lists of files, use include instead",
+            ));
+        }
         if !attrs_seq
             .into_iter()
             .all(|key| is_module(&key) || Task::is_attr(&key))
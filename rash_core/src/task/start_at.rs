@@ -0,0 +1,92 @@
+use crate::error::{Error, ErrorKind, Result};
+use crate::task::Task;
+use crate::task::children::{Visit, for_each_child, for_each_main_child};
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use glob::Pattern;
+use minijinja::Value;
+
+/// Task to start the execution at, set with `--start-at-task`. It is shared by all tasks,
+/// including the ones in blocks and included files, so tasks are skipped until it is found at
+/// any level.
+#[derive(Debug)]
+pub struct StartAtTask {
+    pattern: Pattern,
+    started: AtomicBool,
+}
+
+impl StartAtTask {
+    pub fn new(pattern: Pattern) -> Self {
+        StartAtTask {
+            pattern,
+            started: AtomicBool::new(false),
+        }
+    }
+
+    /// Return true when the task to start at has been found.
+    pub fn is_started(&self) -> bool {
+        self.started.load(Ordering::SeqCst)
+    }
+
+    /// Return an error if the task to start at has not been found.
+    pub fn check_started(&self) -> Result<()> {
+        match self.is_started() {
+            true => Ok(()),
+            false => Err(Error::new(
+                ErrorKind::NotFound,
+                format!("No task found matching '{}' to start at", self.pattern),
+            )),
+        }
+    }
+
+    /// Return true if the rendered name of the task, or its raw name if the rendering fails,
+    /// matches the pattern.
+    fn matches(&self, task: &Task, vars: &Value) -> bool {
+        task.get_rendered_name(vars.clone())
+            .ok()
+            .or_else(|| task.get_name())
+            .is_some_and(|name| self.pattern.matches(&name))
+    }
+
+    fn is_in_any(
+        &self,
+        for_each: fn(&Task, &Value, &mut Visit) -> Result<()>,
+        task: &Task,
+        vars: &Value,
+    ) -> Result<bool> {
+        let mut found = false;
+        for_each(task, vars, &mut |child, vars| {
+            if !found {
+                found = self.matches(child, vars) || self.is_in_children(child, vars)?;
+            }
+            Ok(())
+        })?;
+        Ok(found)
+    }
+
+    /// Return true if the task to start at is any of the tasks created from `task`.
+    fn is_in_children(&self, task: &Task, vars: &Value) -> Result<bool> {
+        self.is_in_any(for_each_child, task, vars)
+    }
+
+    /// Return true if the task to start at is any of the tasks executed by the module of
+    /// `task`, instead of its `rescue` or `always` sections.
+    pub fn is_in_main_children(&self, task: &Task, vars: &Value) -> Result<bool> {
+        self.is_in_any(for_each_main_child, task, vars)
+    }
+
+    /// Return true if `task` must be skipped because the task to start at is not found yet
+    /// and it is neither `task` nor any of its children. When `task` matches, the execution
+    /// starts.
+    pub fn is_before(&self, task: &Task, vars: &Value) -> Result<bool> {
+        if self.is_started() {
+            return Ok(false);
+        }
+        if self.matches(task, vars) {
+            self.started.store(true, Ordering::SeqCst);
+            return Ok(false);
+        }
+        Ok(!self.is_in_children(task, vars)?)
+    }
+}
@@ -0,0 +1,218 @@
+use crate::context::GlobalParams;
+use crate::error::{Error, ErrorKind, Result};
+use crate::task::children::for_each_child;
+use crate::task::{Task, Tasks};
+
+use std::collections::BTreeSet;
+
+use minijinja::Value;
+use serde_norway::Value as YamlValue;
+
+/// Tasks tagged with `always` are executed unless `always` is explicitly skipped.
+pub const ALWAYS_TAG: &str = "always";
+/// Tasks tagged with `never` are just executed when any of their tags is explicitly selected.
+pub const NEVER_TAG: &str = "never";
+
+/// Parse `tags` from a string (comma separated values allowed) or a list of strings.
+pub fn parse_tags_value(value: &YamlValue) -> Result<Vec<String>> {
+    let invalid_tags_error = || {
+        Error::new(
+            ErrorKind::InvalidData,
+            format!("Invalid tags value {value:?}: expected a string or a list of strings"),
+        )
+    };
+    match value {
+        YamlValue::Null => Ok(Vec::new()),
+        YamlValue::String(s) => Ok(s
+            .split(',')
+            .map(str::trim)
+            .filter(|tag| !tag.is_empty())
+            .map(String::from)
+            .collect()),
+        YamlValue::Sequence(seq) => seq
+            .iter()
+            .map(|v| v.as_str().map(String::from).ok_or_else(invalid_tags_error))
+            .collect(),
+        _ => Err(invalid_tags_error()),
+    }
+}
+
+/// Return true if a task with these tags must be executed with `--tags` and `--skip-tags`.
+pub fn is_selected(tags: &[String], global_params: &GlobalParams) -> bool {
+    let is_in = |selection: &[String]| tags.iter().any(|tag| selection.contains(tag));
+
+    if is_in(global_params.skip_tags) {
+        return false;
+    }
+
+    if tags.iter().any(|tag| tag == ALWAYS_TAG) {
+        return true;
+    }
+
+    if tags.iter().any(|tag| tag == NEVER_TAG) {
+        return is_in(global_params.tags);
+    }
+
+    global_params.tags.is_empty() || is_in(global_params.tags)
+}
+
+fn collect_task_tags(task: &Task, vars: &Value, tags: &mut BTreeSet<String>) -> Result<()> {
+    tags.extend(task.tags.iter().cloned());
+    for_each_child(task, vars, &mut |child, vars| {
+        collect_task_tags(child, vars, tags)
+    })
+}
+
+/// Return all tags defined in tasks, including the ones in blocks and included files.
+pub fn list_tags(tasks: &Tasks, vars: &Value) -> Result<BTreeSet<String>> {
+    let mut tags = BTreeSet::new();
+    for task in tasks {
+        collect_task_tags(task, vars, &mut tags)?;
+    }
+    Ok(tags)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::task::parse_file;
+
+    use std::fs;
+    use std::path::Path;
+
+    use minijinja::context;
+    use tempfile::tempdir;
+
+    fn to_vec(tags: &[&str]) -> Vec<String> {
+        tags.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_tags_value_string() {
+        let value = YamlValue::String("web, db".to_string());
+        assert_eq!(parse_tags_value(&value).unwrap(), to_vec(&["web", "db"]));
+    }
+
+    #[test]
+    fn test_parse_tags_value_sequence() {
+        let value: YamlValue = serde_norway::from_str("[web, db]").unwrap();
+        assert_eq!(parse_tags_value(&value).unwrap(), to_vec(&["web", "db"]));
+    }
+
+    #[test]
+    fn test_parse_tags_value_invalid() {
+        let value = YamlValue::Number(42.into());
+        let error = parse_tags_value(&value).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+
+        let value: YamlValue = serde_norway::from_str("[web, {db: true}]").unwrap();
+        assert_eq!(
+            parse_tags_value(&value).unwrap_err().kind(),
+            ErrorKind::InvalidData
+        );
+    }
+
+    #[test]
+    fn test_is_selected_without_selection() {
+        let global_params = GlobalParams::default();
+        assert!(is_selected(&[], &global_params));
+        assert!(is_selected(&to_vec(&["web"]), &global_params));
+        assert!(is_selected(&to_vec(&["always"]), &global_params));
+        assert!(!is_selected(&to_vec(&["never"]), &global_params));
+        assert!(!is_selected(&to_vec(&["never", "debug"]), &global_params));
+    }
+
+    #[test]
+    fn test_is_selected_tags() {
+        let selected = to_vec(&["web"]);
+        let global_params = GlobalParams {
+            tags: &selected,
+            ..Default::default()
+        };
+        assert!(!is_selected(&[], &global_params));
+        assert!(is_selected(&to_vec(&["web", "db"]), &global_params));
+        assert!(!is_selected(&to_vec(&["db"]), &global_params));
+        assert!(is_selected(&to_vec(&["always"]), &global_params));
+        assert!(is_selected(&to_vec(&["never", "web"]), &global_params));
+        assert!(!is_selected(&to_vec(&["never", "db"]), &global_params));
+    }
+
+    #[test]
+    fn test_is_selected_skip_tags() {
+        let skipped = to_vec(&["db", "always"]);
+        let global_params = GlobalParams {
+            skip_tags: &skipped,
+            ..Default::default()
+        };
+        assert!(is_selected(&[], &global_params));
+        assert!(is_selected(&to_vec(&["web"]), &global_params));
+        assert!(!is_selected(&to_vec(&["web", "db"]), &global_params));
+        assert!(!is_selected(&to_vec(&["always"]), &global_params));
+    }
+
+    #[test]
+    fn test_children_inherit_tags() {
+        let yaml: YamlValue = serde_norway::from_str(
+            r#"
+            block:
+              - debug:
+                  msg: foo
+                tags: child
+            tags: [parent, child]
+            "#,
+        )
+        .unwrap();
+        let global_params = GlobalParams::default();
+        let task = Task::new(&yaml, &global_params).unwrap();
+        assert_eq!(task.get_tags(), to_vec(&["parent", "child"]));
+
+        let children_global_params = task.get_children_global_params();
+        let child = Task::new(&task.params[0], &children_global_params).unwrap();
+        assert_eq!(child.get_tags(), to_vec(&["parent", "child"]));
+    }
+
+    #[test]
+    fn test_list_tags() {
+        let dir = tempdir().unwrap();
+        let include_path = dir.path().join("included.rh");
+        fs::write(
+            &include_path,
+            r#"
+            - debug:
+                msg: included
+              tags: included
+            "#,
+        )
+        .unwrap();
+
+        let file = r#"
+        - debug:
+            msg: foo
+          tags:
+            - web
+        - block:
+            - debug:
+                msg: bar
+              tags: db
+          tags: block
+          always:
+            - debug:
+                msg: always
+              tags: cleanup
+        - include: "{{ include_dir }}/included.rh"
+          tags: include
+        - debug:
+            msg: not tagged
+        "#;
+        let global_params = GlobalParams::default();
+        let tasks = parse_file(file, Path::new("main.rh"), &global_params).unwrap();
+        let vars = context! {include_dir => dir.path().to_str().unwrap()};
+
+        let tags = list_tags(&tasks, &vars).unwrap();
+        assert_eq!(
+            tags.into_iter().collect::<Vec<_>>(),
+            to_vec(&["block", "cleanup", "db", "include", "included", "web"])
+        );
+    }
+}
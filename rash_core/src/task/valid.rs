@@ -1,7 +1,9 @@
 use crate::context::{BecomeMethod, GlobalParams};
 use crate::error::{Error, ErrorKind, Result};
-use crate::modules::{MODULES, is_module};
-use crate::task::{Task, parse_notify_value};
+use crate::modules::{get_module, is_module};
+use crate::task::loop_control::LoopControl;
+use crate::task::tags::parse_tags_value;
+use crate::task::{Debugger, Task, parse_notify_value};
 
 use std::collections::HashSet;
 
@@ -85,13 +87,44 @@ impl TaskValid {
         Ok(())
     }
 
+    /// Task tags extended with the tags inherited from its parent.
+    fn get_tags(&self, global_params: &GlobalParams) -> Result<Vec<String>> {
+        Ok(parse_tags_value(&self.attrs["tags"])?.into_iter().fold(
+            global_params.inherited_tags.to_vec(),
+            |mut tags, tag| {
+                if !tags.contains(&tag) {
+                    tags.push(tag);
+                }
+                tags
+            },
+        ))
+    }
+
+    /// Max number of tasks running at once: a positive integer, also accepted as a string.
+    fn get_throttle(&self) -> Result<Option<usize>> {
+        let throttle = &self.attrs["throttle"];
+        let parsed = match throttle {
+            Value::Null => return Ok(None),
+            Value::Number(n) => n.as_u64(),
+            Value::String(s) => s.trim().parse::<u64>().ok(),
+            _ => None,
+        };
+        match parsed.filter(|throttle| *throttle > 0) {
+            Some(throttle) => Ok(Some(throttle as usize)),
+            None => Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("Invalid throttle value {throttle:?}: expected a positive integer"),
+            )),
+        }
+    }
+
     pub fn get_task<'a>(&self, global_params: &'a GlobalParams) -> Result<Task<'a>> {
         let module_name: &str = &self.get_module_name()?;
 
         // Validate that rescue and always attributes are only used with block modules
         self.validate_block_only_attributes()?;
 
-        Ok(Task {
+        let task = Task {
             r#become: match global_params.r#become {
                 true => true,
                 false => self.attrs["become"].as_bool().unwrap_or(false),
@@ -122,16 +155,13 @@ impl TaskValid {
                 false => self.attrs["check_mode"].as_bool().unwrap_or(false),
             },
             // &dyn Module from &Box<dyn Module>
-            module: &**MODULES.get::<str>(module_name).ok_or_else(|| {
-                Error::new(
-                    ErrorKind::NotFound,
-                    format!("Module not found in modules: {:?}", MODULES.keys()),
-                )
-            })?,
+            module: get_module(module_name)?,
             params: self.attrs[module_name].clone(),
             name: self.attrs["name"].as_str().map(String::from),
+            failed_when: self.parse_array(&self.attrs["failed_when"]),
             ignore_errors: self.attrs["ignore_errors"].as_bool(),
             r#loop: self.attrs.get("loop").map(|_| self.attrs["loop"].clone()),
+            loop_control: LoopControl::from_yaml(self.attrs.get("loop_control"))?,
             register: self.attrs["register"].as_str().map(String::from),
             vars: self.attrs.get("vars").map(|_| self.attrs["vars"].clone()),
             when: self.parse_array(&self.attrs["when"]),
@@ -153,8 +183,49 @@ impl TaskValid {
             until: self.parse_array(&self.attrs["until"]),
             r#async: self.attrs["async"].as_u64(),
             poll: self.attrs.get("poll").and_then(|p| p.as_u64()),
+            throttle: self.get_throttle()?,
+            no_log: self.attrs["no_log"].as_bool().unwrap_or(false),
+            connection: self
+                .attrs
+                .get("connection")
+                .or(global_params.connection)
+                .cloned(),
+            tags: self.get_tags(global_params)?,
+            debugger: match self.attrs.get("debugger") {
+                Some(debugger) => Debugger::from_yaml(debugger)?,
+                None => global_params.debugger,
+            },
+            line: None,
+            file_path: None,
             global_params,
-        })
+        };
+        self.validate_parallel_attributes(&task)?;
+        Ok(task)
+    }
+
+    /// Tasks in `parallel` are executed in threads of the same process, so they can't change
+    /// the whole process: its environment variables, its user with the `syscall` become method
+    /// or its stdin with the debugger.
+    fn validate_parallel_attributes(&self, task: &Task) -> Result<()> {
+        if !task.global_params.in_parallel {
+            return Ok(());
+        }
+        let invalid_attr = if task.environment.is_some() {
+            Some("environment")
+        } else if self.attrs.get("debugger").is_some() {
+            Some("debugger")
+        } else if task.r#become && task.become_method == BecomeMethod::Syscall {
+            Some("become with become_method: syscall")
+        } else {
+            None
+        };
+        match invalid_attr {
+            Some(attr) => Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("{attr} is not supported in tasks executed by parallel"),
+            )),
+            None => Ok(()),
+        }
     }
 }
 
@@ -572,4 +643,41 @@ mod tests {
         assert_eq!(task.r#async, Some(3600));
         assert_eq!(task.poll, Some(0));
     }
+
+    #[test]
+    fn test_invalid_tags_fails() {
+        let yaml: YamlValue = serde_norway::from_str(
+            r#"
+        command: ls
+        tags:
+          web: true
+        "#,
+        )
+        .unwrap();
+        let global_params = create_test_global_params();
+
+        let error = TaskValid::new(&yaml).get_task(&global_params).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+        assert!(error.to_string().contains("Invalid tags value"));
+    }
+
+    #[test]
+    fn test_throttle_parsing() {
+        let global_params = create_test_global_params();
+        let get_throttle = |throttle: &str| {
+            let yaml: YamlValue =
+                serde_norway::from_str(&format!("{{command: ls, throttle: {throttle}}}")).unwrap();
+            TaskValid::new(&yaml)
+                .get_task(&global_params)
+                .map(|task| task.throttle)
+        };
+
+        assert_eq!(get_throttle("2").unwrap(), Some(2));
+        assert_eq!(get_throttle("'2'").unwrap(), Some(2));
+        for invalid in ["0", "-1", "foo", "'{{ n }}'", "[1]"] {
+            let error = get_throttle(invalid).unwrap_err();
+            assert_eq!(error.kind(), ErrorKind::InvalidData, "{invalid}");
+            assert!(error.to_string().contains("Invalid throttle value"));
+        }
+    }
 }
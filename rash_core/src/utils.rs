@@ -1,5 +1,9 @@
 use crate::error::{Error, ErrorKind, Result};
 
+use std::path::PathBuf;
+
+use nix::unistd::{Uid, User};
+
 /// Get the width of the terminal.
 ///
 /// This function attempts to determine the terminal width using multiple approaches:
@@ -148,6 +152,37 @@ pub fn default_false() -> Option<bool> {
     Some(false)
 }
 
+/// Get directory where `rash` persists its state: `$XDG_STATE_HOME/rash`, or
+/// `~/.local/state/rash` if `XDG_STATE_HOME` is not set. The home directory is read from the
+/// user database when `HOME` is not set, as in systemd units.
+pub fn get_state_dir() -> Result<PathBuf> {
+    // relative paths are ignored, they would depend on the working directory
+    let get_absolute_var = |name| {
+        std::env::var_os(name)
+            .map(PathBuf::from)
+            .filter(|path| path.is_absolute())
+    };
+    let state_home = match get_absolute_var("XDG_STATE_HOME") {
+        Some(state_home) => state_home,
+        None => get_absolute_var("HOME")
+            .or_else(|| {
+                User::from_uid(Uid::current())
+                    .ok()
+                    .flatten()
+                    .map(|user| user.dir)
+                    .filter(|dir| dir.is_absolute())
+            })
+            .ok_or_else(|| {
+                Error::new(
+                    ErrorKind::NotFound,
+                    "State directory not found, set XDG_STATE_HOME or HOME to an absolute path",
+                )
+            })?
+            .join(".local/state"),
+    };
+    Ok(state_home.join("rash"))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -326,4 +361,22 @@ mod tests {
             std::env::remove_var("TERM_WIDTH");
         }
     }
+
+    #[test]
+    fn test_get_state_dir_ignores_relative_path() {
+        let state_home = std::env::var_os("XDG_STATE_HOME");
+        unsafe {
+            std::env::set_var("XDG_STATE_HOME", "relative/state");
+        }
+        let state_dir = get_state_dir();
+        unsafe {
+            match state_home {
+                Some(state_home) => std::env::set_var("XDG_STATE_HOME", state_home),
+                None => std::env::remove_var("XDG_STATE_HOME"),
+            }
+        }
+        let state_dir = state_dir.unwrap();
+        assert!(state_dir.is_absolute());
+        assert!(state_dir.ends_with(".local/state/rash"));
+    }
 }
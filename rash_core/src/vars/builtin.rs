@@ -95,6 +95,11 @@ impl Builtins {
         Ok(if dir.is_empty() { ".".to_owned() } else { dir })
     }
 
+    /// Return script absolute path.
+    pub fn get_path(&self) -> &Path {
+        Path::new(&self.path)
+    }
+
     pub fn update(&self, path: &Path) -> Result<Self> {
         Builtins::new(self.args.clone(), path, self.check_mode)
     }
@@ -0,0 +1,303 @@
+//! Vault
+//!
+//! Encrypted files to keep secrets at rest, e.g. vars files next to the scripts in git.
+//!
+//! Files start with the [`HEADER`] line, ending with the PBKDF2 iterations, followed by the
+//! base64 payload split in lines of 80 characters. The payload is the PBKDF2 salt, the
+//! AES-256-GCM nonce and the ciphertext with its authentication tag. The key is derived from
+//! the vault password with PBKDF2-HMAC-SHA256.
+//!
+//! Files encrypted by previous versions start with [`HEADER_V1`] and use 100,000 iterations.
+
+use crate::error::{Error, ErrorKind, Result};
+use crate::logger::add_secret;
+
+use std::fs::read_to_string;
+use std::path::Path;
+use std::sync::RwLock;
+
+use aes_gcm::aead::{Aead, KeyInit};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use base64::{Engine as _, engine::general_purpose};
+use rand::RngExt;
+use serde_norway::Value as YamlValue;
+use sha2::Sha256;
+
+/// First line of every encrypted file, followed by `;` and the PBKDF2 iterations.
+pub const HEADER: &str = "$RASH_VAULT;2;AES256-GCM";
+
+/// First line of files encrypted with [`V1_PBKDF2_ROUNDS`], before the iterations were saved.
+pub const HEADER_V1: &str = "$RASH_VAULT;1;AES256-GCM";
+
+/// Environment variable with the vault password.
+pub const PASSWORD_ENV: &str = "RASH_VAULT_PASSWORD";
+
+/// Environment variable with the path of a file containing the vault password.
+pub const PASSWORD_FILE_ENV: &str = "RASH_VAULT_PASSWORD_FILE";
+
+const LINE_WIDTH: usize = 80;
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+const KEY_LEN: usize = 32;
+/// PBKDF2 iterations of new files, as recommended by OWASP for PBKDF2-HMAC-SHA256.
+const PBKDF2_ROUNDS: u32 = 600_000;
+const V1_PBKDF2_ROUNDS: u32 = 100_000;
+
+static PASSWORD: RwLock<Option<String>> = RwLock::new(None);
+
+/// Set password used to decrypt files, overriding the environment variables.
+pub fn set_password(password: String) {
+    *PASSWORD.write().unwrap_or_else(|e| e.into_inner()) = Some(password);
+}
+
+/// Read vault password from file, removing the trailing new line.
+pub fn read_password_file(path: &Path) -> Result<String> {
+    read_to_string(path)
+        .map(|password| password.trim_end_matches(['\n', '\r']).to_owned())
+        .map_err(|e| {
+            Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "Failed to read vault password file '{}': {e}",
+                    path.display()
+                ),
+            )
+        })
+}
+
+/// Return vault password from [`set_password`], [`PASSWORD_ENV`] or [`PASSWORD_FILE_ENV`], in
+/// that order.
+pub fn get_password() -> Result<String> {
+    if let Some(password) = PASSWORD.read().unwrap_or_else(|e| e.into_inner()).clone() {
+        return Ok(password);
+    }
+    if let Ok(password) = std::env::var(PASSWORD_ENV) {
+        return Ok(password);
+    }
+    match std::env::var(PASSWORD_FILE_ENV) {
+        Ok(path) => read_password_file(Path::new(&path)),
+        Err(_) => Err(Error::new(
+            ErrorKind::NotFound,
+            format!(
+                "Vault password not found, use `--vault-password-file`, {PASSWORD_ENV} or {PASSWORD_FILE_ENV}"
+            ),
+        )),
+    }
+}
+
+/// Return true if the content is a vault encrypted file, of any version.
+pub fn is_encrypted(content: &str) -> bool {
+    content
+        .lines()
+        .next()
+        .is_some_and(|line| line.starts_with("$RASH_VAULT;"))
+}
+
+/// Return PBKDF2 iterations from the header line.
+fn parse_header(header: &str) -> Result<u32> {
+    if header == HEADER_V1 {
+        return Ok(V1_PBKDF2_ROUNDS);
+    }
+    header
+        .strip_prefix(HEADER)
+        .and_then(|rest| rest.strip_prefix(';'))
+        .and_then(|rounds| rounds.parse::<u32>().ok())
+        .filter(|rounds| *rounds > 0)
+        .ok_or_else(|| {
+            Error::new(
+                ErrorKind::InvalidData,
+                format!("Invalid vault header '{header}', expected '{HEADER};<ITERATIONS>'"),
+            )
+        })
+}
+
+fn derive_key(password: &str, salt: &[u8], rounds: u32) -> [u8; KEY_LEN] {
+    pbkdf2::pbkdf2_hmac_array::<Sha256, KEY_LEN>(password.as_bytes(), salt, rounds)
+}
+
+/// Encrypt content with the password, returning the vault file content.
+pub fn encrypt(plaintext: &str, password: &str) -> Result<String> {
+    let mut rng = rand::rng();
+    let mut salt = [0u8; SALT_LEN];
+    rng.fill(&mut salt[..]);
+    let mut nonce = [0u8; NONCE_LEN];
+    rng.fill(&mut nonce[..]);
+
+    let key = derive_key(password, &salt, PBKDF2_ROUNDS);
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&key));
+    let ciphertext = cipher
+        .encrypt(Nonce::from_slice(&nonce), plaintext.as_bytes())
+        .map_err(|_| Error::new(ErrorKind::Other, "Vault encryption failed"))?;
+
+    let payload = general_purpose::STANDARD.encode([&salt[..], &nonce[..], &ciphertext].concat());
+    let lines = payload
+        .as_bytes()
+        .chunks(LINE_WIDTH)
+        .map(|chunk| String::from_utf8_lossy(chunk).into_owned())
+        .collect::<Vec<_>>();
+    Ok(format!("{HEADER};{PBKDF2_ROUNDS}\n{}\n", lines.join("\n")))
+}
+
+/// Decrypt vault file content with the password.
+pub fn decrypt(content: &str, password: &str) -> Result<String> {
+    let mut lines = content.lines();
+    let rounds = parse_header(lines.next().map(str::trim_end).unwrap_or_default())?;
+
+    let payload = general_purpose::STANDARD
+        .decode(lines.map(str::trim).collect::<String>())
+        .map_err(|e| {
+            Error::new(
+                ErrorKind::InvalidData,
+                format!("Invalid vault payload: {e}"),
+            )
+        })?;
+    if payload.len() < SALT_LEN + NONCE_LEN {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            "Invalid vault payload: too short",
+        ));
+    }
+    let (salt, rest) = payload.split_at(SALT_LEN);
+    let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+
+    let key = derive_key(password, salt, rounds);
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&key));
+    let plaintext = cipher
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| {
+            Error::new(
+                ErrorKind::InvalidData,
+                "Vault decryption failed: wrong password or corrupted file",
+            )
+        })?;
+
+    String::from_utf8(plaintext).map_err(|e| Error::new(ErrorKind::InvalidData, e))
+}
+
+fn add_yaml_secrets(value: &YamlValue) {
+    match value {
+        YamlValue::String(s) => add_secret(s),
+        YamlValue::Number(n) => add_secret(&n.to_string()),
+        YamlValue::Sequence(seq) => seq.iter().for_each(add_yaml_secrets),
+        YamlValue::Mapping(map) => map.values().for_each(add_yaml_secrets),
+        YamlValue::Tagged(tagged) => add_yaml_secrets(&tagged.value),
+        YamlValue::Null | YamlValue::Bool(_) => (),
+    }
+}
+
+/// Return content decrypted with [`get_password`] if it is encrypted, or unchanged otherwise.
+/// Decrypted content is registered as secret to be masked in the output: its lines and, when it
+/// is YAML, like vars files, each of its values.
+pub fn decrypt_if_encrypted(content: String) -> Result<String> {
+    if !is_encrypted(&content) {
+        return Ok(content);
+    }
+    let decrypted = decrypt(&content, &get_password()?)?;
+    add_secret(&decrypted);
+    if let Ok(yaml) = serde_norway::from_str::<YamlValue>(&decrypted) {
+        add_yaml_secrets(&yaml);
+    }
+    Ok(decrypted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encrypt_decrypt() {
+        let plaintext = "password: foo\nusers:\n  - bar\n";
+        let encrypted = encrypt(plaintext, "secret").unwrap();
+
+        assert!(encrypted.starts_with(&format!("{HEADER};600000\n")));
+        assert!(encrypted.lines().all(|line| line.len() <= LINE_WIDTH));
+        assert!(!encrypted.contains("foo"));
+        assert!(is_encrypted(&encrypted));
+        assert_eq!(decrypt(&encrypted, "secret").unwrap(), plaintext);
+    }
+
+    #[test]
+    fn test_encrypt_random_salt() {
+        assert_ne!(
+            encrypt("foo", "secret").unwrap(),
+            encrypt("foo", "secret").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_decrypt_wrong_password() {
+        let encrypted = encrypt("foo", "secret").unwrap();
+        let error = decrypt(&encrypted, "wrong").unwrap_err();
+
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+        assert_eq!(
+            error.to_string(),
+            "Vault decryption failed: wrong password or corrupted file"
+        );
+    }
+
+    /// Encrypt `foo` with the `secret` password and a fixed salt and nonce.
+    fn encrypt_foo(header: &str, rounds: u32) -> String {
+        let salt = [1u8; SALT_LEN];
+        let nonce = [2u8; NONCE_LEN];
+        let key = derive_key("secret", &salt, rounds);
+        let ciphertext = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&key))
+            .encrypt(Nonce::from_slice(&nonce), "foo".as_bytes())
+            .unwrap();
+        let payload =
+            general_purpose::STANDARD.encode([&salt[..], &nonce[..], &ciphertext].concat());
+        format!("{header}\n{payload}\n")
+    }
+
+    #[test]
+    fn test_decrypt_v1() {
+        let encrypted = encrypt_foo(HEADER_V1, V1_PBKDF2_ROUNDS);
+
+        assert!(is_encrypted(&encrypted));
+        assert_eq!(decrypt(&encrypted, "secret").unwrap(), "foo");
+    }
+
+    #[test]
+    fn test_decrypt_iterations_from_header() {
+        let encrypted = encrypt_foo(&format!("{HEADER};1000"), 1000);
+        assert_eq!(decrypt(&encrypted, "secret").unwrap(), "foo");
+
+        let encrypted = encrypt_foo(&format!("{HEADER};2000"), 1000);
+        assert!(decrypt(&encrypted, "secret").is_err());
+    }
+
+    #[test]
+    fn test_decrypt_invalid() {
+        assert!(decrypt("foo: bar\n", "secret").is_err());
+        assert!(decrypt(&format!("{HEADER};1000\nnot base64!\n"), "secret").is_err());
+        assert!(decrypt(&format!("{HEADER};1000\nZm9v\n"), "secret").is_err());
+        for header in [
+            HEADER,
+            "$RASH_VAULT;2;AES256-GCM;0",
+            "$RASH_VAULT;3;AES256-GCM;1000",
+        ] {
+            assert_eq!(
+                decrypt(&format!("{header}\nZm9v\n"), "secret")
+                    .unwrap_err()
+                    .to_string(),
+                format!("Invalid vault header '{header}', expected '{HEADER};<ITERATIONS>'")
+            );
+        }
+    }
+
+    #[test]
+    fn test_is_encrypted() {
+        assert!(is_encrypted(&format!("{HEADER};600000\r\nZm9v\n")));
+        assert!(is_encrypted(&format!("{HEADER_V1}\nZm9v\n")));
+        assert!(!is_encrypted("foo: bar\n"));
+        assert!(!is_encrypted(""));
+    }
+
+    #[test]
+    fn test_decrypt_if_encrypted_plaintext() {
+        assert_eq!(
+            decrypt_if_encrypted("foo: bar\n".to_owned()).unwrap(),
+            "foo: bar\n"
+        );
+    }
+}
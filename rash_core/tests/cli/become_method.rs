@@ -190,3 +190,52 @@ fn test_become_password_task_parameter() {
         stdout
     );
 }
+
+#[test]
+fn test_become_method_sudo_register_and_failed_when() {
+    let script = r#"
+- command: echo boom
+  become: true
+  become_method: sudo
+  register: result
+  changed_when: false
+
+- debug:
+    msg: "stdout={{ result.stdout }}"
+
+- command: echo boom
+  become: true
+  become_method: sudo
+  register: failed_result
+  failed_when: "'boom' in failed_result.stdout"
+  ignore_errors: true
+
+- debug:
+    msg: "failed={{ failed_result.failed }}"
+"#;
+    let (stdout, _stderr) = execute_rash(&["--output", "raw", "-s", script]);
+
+    assert!(stdout.contains("stdout=boom\n"), "stdout: {stdout}");
+    assert!(stdout.contains("failed=True"), "stdout: {stdout}");
+}
+
+#[test]
+fn test_become_method_sudo_failed_when() {
+    let script = r#"
+- command: echo boom
+  become: true
+  become_method: sudo
+  register: result
+  failed_when: "'boom' in result.stdout"
+
+- debug:
+    msg: not executed
+"#;
+    let (stdout, stderr) = execute_rash(&["--output", "raw", "-s", script]);
+
+    assert!(!stdout.contains("not executed"), "stdout: {stdout}");
+    assert!(
+        stderr.contains("failed_when condition `'boom' in result.stdout` is true"),
+        "stderr: {stderr}"
+    );
+}
@@ -0,0 +1,103 @@
+use super::execute_rash;
+
+use std::fs;
+
+use tempfile::tempdir;
+
+#[test]
+fn test_connection_podman() {
+    let dir = tempdir().unwrap();
+    let dest = dir.path().join("greeting");
+    let script = format!(
+        r#"
+- name: write file in container
+  copy:
+    content: "hello {{{{ who }}}}\n"
+    dest: {dest}
+  connection: podman:web
+  register: written
+  vars:
+    who: world
+
+- command: printenv PODMAN_MOCK_CONTAINER
+  connection:
+    type: podman
+    container: "{{{{ container }}}}"
+    user: nobody
+  register: container_name
+  vars:
+    container: web
+
+- debug:
+    msg: "{{{{ written.changed }}}} {{{{ container_name.output }}}}"
+"#,
+        dest = dest.display()
+    );
+
+    let (stdout, stderr) = execute_rash(&["-s", &script]);
+
+    assert!(stderr.is_empty(), "stderr should be empty, got: {stderr}");
+    assert!(stdout.contains("ok: True web\n"), "stdout: {stdout}");
+    assert_eq!(fs::read_to_string(&dest).unwrap(), "hello world\n");
+}
+
+#[test]
+fn test_connection_inherited_by_block() {
+    let dir = tempdir().unwrap();
+    let dest = dir.path().join("file");
+    let script = format!(
+        r#"
+- block:
+    - command: printenv PODMAN_MOCK_CONTAINER
+      register: container_name
+
+    - file:
+        path: {dest}
+        state: touch
+      check_mode: true
+  connection: podman:db
+
+- debug:
+    msg: "{{{{ container_name.output }}}}"
+"#,
+        dest = dest.display()
+    );
+
+    let (stdout, stderr) = execute_rash(&["-s", &script]);
+
+    assert!(stderr.is_empty(), "stderr should be empty, got: {stderr}");
+    assert!(stdout.contains("ok: db\n"), "stdout: {stdout}");
+    assert!(!dest.exists());
+}
+
+#[test]
+fn test_connection_cli() {
+    let script = r#"
+- command: printenv PODMAN_MOCK_CONTAINER
+  register: container_name
+
+- debug:
+    msg: "{{ container_name.output }}"
+  connection: local
+"#;
+
+    let (stdout, stderr) = execute_rash(&["--connection", "podman:cache", "-s", script]);
+
+    assert!(stderr.is_empty(), "stderr should be empty, got: {stderr}");
+    assert!(stdout.contains("ok: cache\n"), "stdout: {stdout}");
+}
+
+#[test]
+fn test_connection_invalid() {
+    let script = r#"
+- command: echo foo
+  connection: ssh:host
+"#;
+
+    let (_stdout, stderr) = execute_rash(&["-s", script]);
+
+    assert!(
+        stderr.contains("Invalid connection 'ssh'"),
+        "stderr: {stderr}"
+    );
+}
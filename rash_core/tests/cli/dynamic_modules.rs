@@ -0,0 +1,125 @@
+use super::execute_rash;
+
+use std::fs;
+use std::path::Path;
+
+use tempfile::tempdir;
+
+fn create_module(dir: &Path) {
+    let module_dir = dir.join("modules/greet");
+    fs::create_dir_all(module_dir.join("templates")).unwrap();
+    fs::create_dir_all(module_dir.join("files")).unwrap();
+    fs::write(
+        module_dir.join("meta.yml"),
+        r#"
+name: greet
+description: Write a greeting
+params:
+  who:
+    type: string
+    required: true
+  times:
+    type: number
+    default: 1
+  dest:
+    type: string
+    required: true
+returns:
+  greeting:
+    type: string
+"#,
+    )
+    .unwrap();
+    fs::write(
+        module_dir.join("main.yml"),
+        r#"
+- template:
+    src: greeting.j2
+    dest: "{{ module.params.dest }}"
+  register: greeting_file
+
+- copy:
+    src: static.txt
+    dest: "{{ module.params.dest }}.static"
+
+- set_vars:
+    greeting: "hello {{ module.params.who }}"
+    __module_changed: "{{ greeting_file.changed }}"
+"#,
+    )
+    .unwrap();
+    fs::write(
+        module_dir.join("templates/greeting.j2"),
+        "hello {{ module.params.who }} x{{ module.params.times }}\n",
+    )
+    .unwrap();
+    fs::write(module_dir.join("files/static.txt"), "static\n").unwrap();
+}
+
+#[test]
+fn test_dynamic_module() {
+    let dir = tempdir().unwrap();
+    create_module(dir.path());
+    let dest = dir.path().join("out.txt");
+    let script_path = dir.path().join("script.rh");
+    fs::write(
+        &script_path,
+        format!(
+            r#"
+- greet:
+    who: world
+    times: 2
+    dest: {}
+  register: result
+
+- debug:
+    msg: "{{{{ result.extra.greeting }}}}"
+"#,
+            dest.display()
+        ),
+    )
+    .unwrap();
+
+    let (stdout, stderr) = execute_rash(&[script_path.to_str().unwrap()]);
+
+    assert!(stderr.is_empty());
+    assert!(stdout.contains("ok: hello world\n"));
+    assert_eq!(fs::read_to_string(&dest).unwrap(), "hello world x2\n");
+    assert_eq!(
+        fs::read_to_string(dir.path().join("out.txt.static")).unwrap(),
+        "static\n"
+    );
+}
+
+#[test]
+fn test_dynamic_module_invalid_params() {
+    let dir = tempdir().unwrap();
+    create_module(dir.path());
+    let script_path = dir.path().join("script.rh");
+
+    fs::write(
+        &script_path,
+        "- greet:\n    who: world\n    times: two\n    dest: /tmp/foo\n",
+    )
+    .unwrap();
+    let (_stdout, stderr) = execute_rash(&[script_path.to_str().unwrap()]);
+    assert!(stderr.contains("Parameter 'times' of module 'greet' must be a number"));
+
+    fs::write(
+        &script_path,
+        "- greet:\n    who: world\n    foo: bar\n    dest: /tmp/foo\n",
+    )
+    .unwrap();
+    let (_stdout, stderr) = execute_rash(&[script_path.to_str().unwrap()]);
+    assert!(stderr.contains("Unknown parameter 'foo' for module 'greet'"));
+}
+
+#[test]
+fn test_dynamic_module_not_found() {
+    let dir = tempdir().unwrap();
+    let script_path = dir.path().join("script.rh");
+    fs::write(&script_path, "tasks:\n  - greet:\n      who: world\n").unwrap();
+
+    let (_stdout, stderr) = execute_rash(&[script_path.to_str().unwrap()]);
+    assert!(stderr.contains("must be attr or module"));
+}
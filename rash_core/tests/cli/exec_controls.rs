@@ -0,0 +1,362 @@
+use super::execute_rash;
+
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use tempfile::tempdir;
+
+const SCRIPT: &str = r#"
+- name: first
+  debug:
+    msg: one
+
+- name: install {{ "pkg" }}
+  debug:
+    msg: two
+
+- debug:
+    msg: three
+
+- name: last task
+  debug:
+    msg: four
+  tags: last
+"#;
+
+fn execute_rash_with_stdin(args: &[&str], stdin: &str) -> (String, String) {
+    let mut child = Command::new(Path::new(env!("CARGO_BIN_EXE_rash")))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(stdin.as_bytes())
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+    (
+        String::from_utf8_lossy(&output.stdout).to_string(),
+        String::from_utf8_lossy(&output.stderr).to_string(),
+    )
+}
+
+#[test]
+fn test_list_tasks() {
+    let (stdout, _stderr) = execute_rash(&["--list-tasks", "-s", SCRIPT]);
+
+    let lines = stdout.lines().collect::<Vec<_>>();
+    assert_eq!(lines.len(), 4);
+    assert!(lines[0].starts_with("1\t"));
+    assert!(lines[0].ends_with("rash:2\tfirst"));
+    assert!(lines[1].ends_with("rash:6\tinstall pkg"));
+    assert!(lines[2].ends_with("rash:10\tdebug"));
+    assert!(lines[3].starts_with("4\t"));
+    assert!(!stdout.contains("ok: "));
+}
+
+#[test]
+fn test_list_tasks_with_tags() {
+    let (stdout, _stderr) = execute_rash(&["--list-tasks", "--tags", "last", "-s", SCRIPT]);
+
+    let lines = stdout.lines().collect::<Vec<_>>();
+    assert_eq!(lines.len(), 1);
+    assert!(lines[0].starts_with("4\t"));
+    assert!(lines[0].ends_with("\tlast task"));
+}
+
+#[test]
+fn test_start_at_task() {
+    let (stdout, _stderr) = execute_rash(&["--start-at-task", "install*", "-s", SCRIPT]);
+
+    assert!(!stdout.contains("ok: one"));
+    assert!(stdout.contains("ok: two"));
+    assert!(stdout.contains("ok: three"));
+    assert!(stdout.contains("ok: four"));
+}
+
+#[test]
+fn test_start_at_task_not_found() {
+    let (stdout, stderr) = execute_rash(&["--start-at-task", "missing", "-s", SCRIPT]);
+
+    assert!(!stdout.contains("ok: "));
+    assert!(stderr.contains("No task found matching 'missing' to start at"));
+}
+
+const NESTED_SCRIPT: &str = r#"
+- name: first
+  debug:
+    msg: one
+
+- name: setup
+  block:
+    - name: inner one
+      debug:
+        msg: inner-one
+    - name: inner two
+      debug:
+        msg: inner-two
+  rescue:
+    - name: recover
+      debug:
+        msg: recovered
+  always:
+    - name: cleanup
+      debug:
+        msg: cleaned
+
+- name: last
+  debug:
+    msg: four
+"#;
+
+#[test]
+fn test_list_tasks_nested() {
+    let (stdout, _stderr) = execute_rash(&["--list-tasks", "-s", NESTED_SCRIPT]);
+
+    let lines = stdout.lines().collect::<Vec<_>>();
+    assert_eq!(lines.len(), 7);
+    assert!(lines[1].starts_with("2\t"));
+    assert!(lines[1].ends_with("\tsetup"));
+    assert!(lines[2].starts_with("2.1\t"));
+    assert!(lines[2].ends_with("\tinner one"));
+    assert!(lines[3].starts_with("2.2\t"));
+    assert!(lines[4].starts_with("2.3\t"));
+    assert!(lines[4].ends_with("\trecover"));
+    assert!(lines[5].starts_with("2.4\t"));
+    assert!(lines[5].ends_with("\tcleanup"));
+    assert!(lines[6].starts_with("3\t"));
+}
+
+#[test]
+fn test_list_tasks_nested_start_at_task() {
+    let (stdout, _stderr) = execute_rash(&[
+        "--list-tasks",
+        "--start-at-task",
+        "inner two",
+        "-s",
+        NESTED_SCRIPT,
+    ]);
+
+    let lines = stdout.lines().collect::<Vec<_>>();
+    assert_eq!(lines.len(), 5);
+    assert!(lines[0].ends_with("\tsetup"));
+    assert!(lines[1].starts_with("2.2\t"));
+    assert!(lines[1].ends_with("\tinner two"));
+}
+
+#[test]
+fn test_start_at_task_in_block() {
+    let (stdout, _stderr) = execute_rash(&["--start-at-task", "inner two", "-s", NESTED_SCRIPT]);
+
+    assert!(!stdout.contains("ok: one"));
+    assert!(!stdout.contains("ok: inner-one"));
+    assert!(stdout.contains("ok: inner-two"));
+    assert!(!stdout.contains("ok: recovered"));
+    assert!(stdout.contains("ok: cleaned"));
+    assert!(stdout.contains("ok: four"));
+}
+
+#[test]
+fn test_start_at_task_in_rescue() {
+    let (stdout, _stderr) = execute_rash(&["--start-at-task", "recover", "-s", NESTED_SCRIPT]);
+
+    assert!(!stdout.contains("ok: inner-"));
+    assert!(stdout.contains("ok: recovered"));
+    assert!(stdout.contains("ok: cleaned"));
+    assert!(stdout.contains("ok: four"));
+}
+
+#[test]
+fn test_start_at_task_in_always() {
+    let (stdout, _stderr) = execute_rash(&["--start-at-task", "cleanup", "-s", NESTED_SCRIPT]);
+
+    assert!(!stdout.contains("ok: inner-"));
+    assert!(!stdout.contains("ok: recovered"));
+    assert!(stdout.contains("ok: cleaned"));
+    assert!(stdout.contains("ok: four"));
+}
+
+#[test]
+fn test_start_at_task_in_include() {
+    let dir = tempdir().unwrap();
+    fs::write(
+        dir.path().join("included.rh"),
+        r#"
+- name: included one
+  debug:
+    msg: included-one
+
+- name: included two
+  debug:
+    msg: included-two
+"#,
+    )
+    .unwrap();
+    let script_path = dir.path().join("main.rh");
+    fs::write(
+        &script_path,
+        r#"#!/usr/bin/env rash
+- name: first
+  debug:
+    msg: one
+
+- include: "{{ rash.dir }}/included.rh"
+
+- name: last
+  debug:
+    msg: four
+"#,
+    )
+    .unwrap();
+    let script_path = script_path.to_str().unwrap();
+
+    let (stdout, _stderr) = execute_rash(&["--list-tasks", script_path]);
+    let lines = stdout.lines().collect::<Vec<_>>();
+    assert_eq!(lines.len(), 5);
+    assert!(lines[2].starts_with("2.1\t"));
+    assert!(lines[2].ends_with("included.rh:2\tincluded one"));
+
+    let (stdout, _stderr) = execute_rash(&["--start-at-task", "included two", script_path]);
+    assert!(!stdout.contains("ok: one"));
+    assert!(!stdout.contains("ok: included-one"));
+    assert!(stdout.contains("ok: included-two"));
+    assert!(stdout.contains("ok: four"));
+}
+
+#[test]
+fn test_step() {
+    let (stdout, stderr) = execute_rash_with_stdin(&["--step", "-s", SCRIPT], "n\ny\nc\n");
+
+    assert!(!stdout.contains("ok: one"));
+    assert!(stdout.contains("ok: two"));
+    assert!(stdout.contains("ok: three"));
+    assert!(stdout.contains("ok: four"));
+    assert_eq!(stderr.matches("Perform task: ").count(), 3);
+}
+
+#[test]
+fn test_step_eof() {
+    let (stdout, stderr) = execute_rash_with_stdin(&["--step", "-s", SCRIPT], "y\n");
+
+    assert!(stdout.contains("ok: one"));
+    assert!(!stdout.contains("ok: two"));
+    assert_eq!(stderr.matches("Perform task: ").count(), 4);
+}
+
+const DEBUGGER_SCRIPT: &str = r#"
+- name: fail
+  debug:
+    msg: "{{ missing }}"
+
+- name: after
+  debug:
+    msg: after
+"#;
+
+#[test]
+fn test_debugger_retry() {
+    let (stdout, stderr) = execute_rash_with_stdin(
+        &["--debugger", "-s", DEBUGGER_SCRIPT],
+        "p 1 + 1\nset msg fixed\nr\n",
+    );
+
+    assert!(stderr.contains("fail] failed: undefined variable 'missing'"));
+    assert!(stderr.contains("(debug) 2\n"));
+    assert!(stdout.contains("ok: fixed\n"));
+    assert!(stdout.contains("ok: after\n"));
+}
+
+#[test]
+fn test_debugger_loop_item() {
+    let script = r#"
+- name: loop
+  debug:
+    msg: "{{ item.name }}"
+  loop:
+    - name: one
+    - other: two
+    - name: three
+"#;
+    let (stdout, stderr) = execute_rash_with_stdin(
+        &["--debugger", "-s", script],
+        "p item.other\nset msg fixed\nr\n",
+    );
+
+    assert!(stderr.contains("(debug) \"two\"\n"), "stderr: {stderr}");
+    assert_eq!(stdout.matches("ok: one\n").count(), 1, "stdout: {stdout}");
+    assert!(stdout.contains("ok: fixed\n"), "stdout: {stdout}");
+    assert!(stdout.contains("ok: three\n"), "stdout: {stdout}");
+}
+
+#[test]
+fn test_debugger_skip() {
+    let (stdout, stderr) =
+        execute_rash_with_stdin(&["--debugger", "-s", DEBUGGER_SCRIPT], "skip\n");
+
+    assert_eq!(stderr.matches("(debug) ").count(), 1);
+    assert!(stdout.contains("ok: after\n"));
+}
+
+#[test]
+fn test_debugger_keyword_quit() {
+    let script = r#"
+- name: fail
+  debug:
+    msg: "{{ missing }}"
+  debugger: on_failed
+
+- name: after
+  debug:
+    msg: after
+"#;
+    let (stdout, stderr) = execute_rash_with_stdin(&["-s", script], "q\n");
+
+    assert!(stderr.contains("(debug) "));
+    assert!(stderr.ends_with("[ERROR] undefined variable 'missing' in template: {{ missing }}\n"));
+    assert!(!stdout.contains("ok: after\n"));
+}
+
+#[test]
+fn test_debugger_keyword_never() {
+    let script = r#"
+- block:
+    - debug:
+        msg: "{{ missing }}"
+  debugger: never
+"#;
+    let (_stdout, stderr) = execute_rash_with_stdin(&["--debugger", "-s", script], "s\n");
+
+    assert!(!stderr.contains("(debug) "));
+    assert!(stderr.contains("[ERROR] undefined variable 'missing'"));
+}
+
+#[test]
+fn test_check_mode_skips_unsupported_modules() {
+    let dir = tempfile::tempdir().unwrap();
+    let file_path = dir.path().join("created");
+    let script_path = dir.path().join("create.sh");
+    std::fs::write(&script_path, format!("touch {}\n", file_path.display())).unwrap();
+
+    let script = format!(
+        r#"
+- name: create file
+  script: {}
+
+- debug:
+    msg: still running
+"#,
+        script_path.display()
+    );
+    let (stdout, stderr) = execute_rash(&["--check", "-s", &script]);
+
+    assert!(stderr.is_empty(), "stderr: {stderr}");
+    assert!(stdout.contains("skipped (check mode unsupported)\n"));
+    assert!(stdout.contains("ok: still running\n"));
+    assert!(!file_path.exists());
+}
@@ -0,0 +1,88 @@
+use super::{execute_rash, execute_rash_with_env};
+
+use std::fs;
+
+use tempfile::tempdir;
+
+const SCRIPT: &str = r#"#!/usr/bin/env rash
+#
+# Usage:
+#   main.rh [<name>]
+
+vars:
+  name: header
+  greeting: "hello from {{ rash.path | basename }}"
+vars_files:
+  - vars.yml
+environment:
+  COLOR: blue
+  SIZE: big
+tasks:
+  - debug:
+      msg: "name={{ name }}"
+
+  - debug:
+      msg: "{{ greeting }}, file={{ file_var }}"
+
+  - debug:
+      msg: "color={{ env.COLOR }} size={{ env.SIZE }}"
+
+  - command: sh -c 'echo "shell color=$COLOR"'
+
+  - set_vars:
+      name: set_vars
+
+  - debug:
+      msg: "name={{ name }} check_mode={{ rash.check_mode }}"
+"#;
+
+#[test]
+fn test_header() {
+    let dir = tempdir().unwrap();
+    let script_path = dir.path().join("main.rh");
+    fs::write(&script_path, SCRIPT).unwrap();
+    fs::write(dir.path().join("vars.yml"), "file_var: file\n").unwrap();
+    let script_path = script_path.to_str().unwrap();
+
+    let (stdout, stderr) = execute_rash(&[script_path]);
+    assert!(stderr.is_empty(), "stderr should be empty, got: {stderr}");
+    assert!(stdout.contains("ok: name=header\n"));
+    assert!(stdout.contains("ok: hello from main.rh, file=file\n"));
+    assert!(stdout.contains("ok: color=blue size=big\n"));
+    assert!(stdout.contains("shell color=blue\n"));
+    assert!(stdout.contains("ok: name=set_vars check_mode=False\n"));
+
+    let (stdout, stderr) = execute_rash_with_env(
+        &["--environment", "COLOR=red", script_path, "arg"],
+        &[("SIZE", "small")],
+    );
+    assert!(stderr.is_empty(), "stderr should be empty, got: {stderr}");
+    assert!(stdout.contains("ok: name=arg\n"));
+    assert!(stdout.contains("ok: color=red size=big\n"));
+}
+
+#[test]
+fn test_header_check_mode() {
+    let script = r#"
+check_mode: true
+tasks:
+  - debug:
+      msg: "check_mode={{ rash.check_mode }}"
+"#;
+    let (stdout, stderr) = execute_rash(&["-s", script]);
+    assert!(stderr.is_empty(), "stderr should be empty, got: {stderr}");
+    assert!(stdout.contains("ok: check_mode=True\n"));
+}
+
+#[test]
+fn test_header_invalid_key() {
+    let script = r#"
+become_user: root
+tasks:
+  - debug:
+      msg: foo
+"#;
+    let (stdout, stderr) = execute_rash(&["-s", script]);
+    assert!(stdout.is_empty());
+    assert!(stderr.starts_with("[ERROR] Invalid script header: unknown field `become_user`"));
+}
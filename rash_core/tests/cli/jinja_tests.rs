@@ -0,0 +1,59 @@
+use super::execute_rash;
+
+use std::fs;
+
+use tempfile::tempdir;
+
+#[test]
+fn test_jinja_tests_in_task_conditions() {
+    let dir = tempdir().unwrap();
+    let file_path = dir.path().join("config.yml");
+    fs::write(&file_path, "").unwrap();
+
+    let script = format!(
+        r#"
+- command: echo rash 2.21.0
+  register: result
+  until: result.stdout is search('\d+\.\d+')
+  changed_when: "'rash 2.21.0' is match('rash')"
+
+- assert:
+    that:
+      - "'{}' is file"
+      - "'{}' is directory"
+      - "'{}' is abs"
+      - "'{}/missing' is not exists"
+      - "['a'] is subset(['a', 'b'])"
+
+- debug:
+    msg: new version
+  when: (result.stdout | regex_search('[0-9.]+')) is version('2.9', '>')
+"#,
+        file_path.display(),
+        dir.path().display(),
+        dir.path().display(),
+        dir.path().display(),
+    );
+    let (stdout, stderr) = execute_rash(&["-s", &script]);
+
+    assert!(stderr.is_empty(), "stderr: {stderr}");
+    assert!(
+        stdout.contains("changed: rash 2.21.0\n"),
+        "stdout: {stdout}"
+    );
+    assert!(stdout.contains("\nok\nTASK"), "stdout: {stdout}");
+    assert!(stdout.contains("ok: new version\n"), "stdout: {stdout}");
+}
+
+#[test]
+fn test_jinja_tests_version_skip() {
+    let script = r#"
+- debug:
+    msg: old version
+  when: "'1.0.0-rc.1' is version('1.0.0', '>=', version_type='semver')"
+"#;
+    let (stdout, stderr) = execute_rash(&["-s", script]);
+
+    assert!(stderr.is_empty(), "stderr: {stderr}");
+    assert!(!stdout.contains("old version"), "stdout: {stdout}");
+}
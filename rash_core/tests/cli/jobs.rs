@@ -0,0 +1,188 @@
+use super::execute_rash_with_env;
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use tempfile::tempdir;
+
+#[test]
+fn test_async_status_across_runs() {
+    let state_dir = tempdir().unwrap();
+    let env = [("XDG_STATE_HOME", state_dir.path().to_str().unwrap())];
+
+    let script = r#"
+- command: sh -c "sleep 0.5; echo hello"
+  async: 10
+  poll: 0
+  register: job
+
+- debug:
+    msg: "{{ job.rash_job_id }}"
+"#;
+    let (stdout, stderr) = execute_rash_with_env(&["--script", script], &env);
+    assert!(stderr.is_empty(), "stderr should be empty, got: {stderr}");
+    assert!(stdout.contains("ok: 1\n"));
+
+    let script = r#"
+- async_status:
+    jid: 1
+  register: result
+  until: result.extra.finished
+  retries: 10
+  delay: 1
+
+- debug:
+    msg: "{{ result.stdout | trim }} rc={{ result.rc }}"
+"#;
+    let (stdout, stderr) = execute_rash_with_env(&["--script", script], &env);
+    assert!(stderr.is_empty(), "stderr should be empty, got: {stderr}");
+    assert!(stdout.contains("ok: hello rc=0\n"));
+
+    let (stdout, _) = execute_rash_with_env(&["jobs", "list"], &env);
+    assert!(
+        stdout.starts_with("1\tfinished\t"),
+        "unexpected list: {stdout}"
+    );
+    assert!(stdout.ends_with("\tsh -c \"sleep 0.5; echo hello\"\n"));
+}
+
+#[test]
+fn test_jobs_status_and_kill() {
+    let state_dir = tempdir().unwrap();
+    let env = [("XDG_STATE_HOME", state_dir.path().to_str().unwrap())];
+
+    let script = r#"
+- command: sleep 30
+  async: 60
+  poll: 0
+"#;
+    execute_rash_with_env(&["--script", script], &env);
+
+    let (stdout, _) = execute_rash_with_env(&["jobs", "status", "1"], &env);
+    let status: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(status["status"], "running");
+    assert_eq!(status["command"], "sleep 30");
+
+    let (stdout, _) = execute_rash_with_env(&["jobs", "kill", "1"], &env);
+    let status: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(status["status"], "failed");
+    assert_eq!(status["error"], "Job killed");
+
+    let (_, stderr) = execute_rash_with_env(&["jobs", "kill", "1"], &env);
+    assert_eq!(stderr, "[ERROR] Job 1 is not running\n");
+
+    let (_, stderr) = execute_rash_with_env(&["jobs", "status", "2"], &env);
+    assert_eq!(stderr, "[ERROR] Job with ID 2 not found\n");
+}
+
+#[test]
+fn test_jobs_ttl() {
+    let state_dir = tempdir().unwrap();
+    let env = [("XDG_STATE_HOME", state_dir.path().to_str().unwrap())];
+
+    let script = r#"
+- command: "true"
+  async: 10
+  poll: 1
+"#;
+    execute_rash_with_env(&["--script", script], &env);
+
+    let (stdout, _) = execute_rash_with_env(&["jobs", "list"], &env);
+    assert!(
+        stdout.starts_with("1\tfinished\t"),
+        "unexpected list: {stdout}"
+    );
+
+    let (stdout, _) = execute_rash_with_env(&["jobs", "list"], &[env[0], ("RASH_JOBS_TTL", "0")]);
+    assert!(stdout.is_empty(), "unexpected list: {stdout}");
+}
+
+#[test]
+fn test_async_poll_failed_when() {
+    let state_dir = tempdir().unwrap();
+    let env = [("XDG_STATE_HOME", state_dir.path().to_str().unwrap())];
+
+    let script = r#"
+- command: echo boom
+  async: 10
+  poll: 1
+  register: result
+  failed_when: "'boom' in result.output"
+  ignore_errors: true
+
+- debug:
+    msg: "failed={{ result.failed }}"
+
+- command: echo boom
+  async: 10
+  poll: 1
+  failed_when: "'boom' in result.output"
+  register: result
+
+- debug:
+    msg: not executed
+"#;
+    let (stdout, stderr) = execute_rash_with_env(&["--script", script], &env);
+
+    assert!(stdout.contains("ok: failed=True\n"), "stdout: {stdout}");
+    assert!(!stdout.contains("not executed"), "stdout: {stdout}");
+    assert!(
+        stderr.contains("failed_when condition `'boom' in result.output` is true"),
+        "stderr: {stderr}"
+    );
+}
+
+#[test]
+fn test_async_poll_register_failed_command() {
+    let state_dir = tempdir().unwrap();
+    let env = [("XDG_STATE_HOME", state_dir.path().to_str().unwrap())];
+
+    let script = r#"
+- shell: echo out; exit 3
+  async: 10
+  poll: 1
+  register: result
+  ignore_errors: true
+
+- debug:
+    msg: "failed={{ result.failed }} rc={{ result.rc }} stdout={{ result.stdout | trim }}"
+"#;
+    let (stdout, stderr) = execute_rash_with_env(&["--script", script], &env);
+
+    assert!(stderr.is_empty(), "stderr: {stderr}");
+    assert!(
+        stdout.contains("ok: failed=True rc=3 stdout=out\n"),
+        "stdout: {stdout}"
+    );
+}
+
+#[test]
+fn test_script_named_jobs() {
+    let dir = tempdir().unwrap();
+    fs::write(
+        dir.path().join("jobs"),
+        r#"
+- debug:
+    msg: "script {{ rash.args | join(',') }} executed"
+"#,
+    )
+    .unwrap();
+
+    let output = Command::new(Path::new(env!("CARGO_BIN_EXE_rash")))
+        .arg("jobs")
+        .current_dir(dir.path())
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("script  executed"));
+
+    let output = Command::new(Path::new(env!("CARGO_BIN_EXE_rash")))
+        .args(["jobs", "list"])
+        .current_dir(dir.path())
+        .env("XDG_STATE_HOME", dir.path())
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert!(!String::from_utf8_lossy(&output.stdout).contains("executed"));
+}
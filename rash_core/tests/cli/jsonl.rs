@@ -0,0 +1,172 @@
+use super::execute_rash;
+
+use std::fs;
+
+use serde_json::Value;
+use tempfile::tempdir;
+
+fn parse_events(stdout: &str) -> Vec<Value> {
+    stdout
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect()
+}
+
+fn get_events<'a>(events: &'a [Value], event: &str) -> Vec<&'a Value> {
+    events.iter().filter(|e| e["event"] == event).collect()
+}
+
+#[test]
+fn test_jsonl() {
+    let script = r#"
+tasks:
+  - name: hello
+    command: echo hello
+    notify: handler
+
+  - name: loop
+    debug:
+      msg: "{{ item }}"
+    loop:
+      - foo
+      - bar
+
+  - name: skipped
+    debug:
+      msg: skipped
+    when: false
+
+handlers:
+  - name: handler
+    debug:
+      msg: handled
+"#;
+    let (stdout, _stderr) = execute_rash(&["--output", "jsonl", "-s", script]);
+    let events = parse_events(&stdout);
+
+    assert!(events.iter().all(|e| e["version"] == 1));
+    assert!(events.iter().all(|e| e["timestamp"].is_f64()));
+    assert_eq!(events.first().unwrap()["event"], "run_start");
+    let run_end = events.last().unwrap();
+    assert_eq!(run_end["event"], "run_end");
+    assert_eq!(run_end["recap"]["ok"], 3);
+    assert_eq!(run_end["recap"]["changed"], 1);
+    assert_eq!(run_end["recap"]["skipped"], 1);
+
+    let task_starts = get_events(&events, "task_start");
+    assert_eq!(task_starts.len(), 3);
+    assert!(task_starts[0]["task"].as_str().unwrap().ends_with(":hello"));
+    assert_eq!(task_starts[0]["module"], "command");
+    assert_eq!(task_starts[0]["line"], 3);
+    assert_eq!(task_starts[1]["line"], 7);
+    assert_eq!(task_starts[2]["line"], 14);
+
+    let task_ends = get_events(&events, "task_end");
+    assert_eq!(task_ends.len(), 4);
+    assert_eq!(task_ends[0]["status"], "changed");
+    assert_eq!(task_ends[0]["changed"], true);
+    assert_eq!(task_ends[0]["output"], "hello\n");
+    assert!(task_ends[0]["duration"].is_f64());
+    assert_eq!(task_ends[2]["status"], "skipped");
+    assert_eq!(task_ends[2]["skipped"], true);
+    assert_eq!(task_ends[3]["handler"], true);
+    assert_eq!(task_ends[3]["output"], "handled");
+
+    let loop_items = get_events(&events, "loop_item");
+    assert_eq!(loop_items.len(), 2);
+    assert_eq!(loop_items[0]["index"], 0);
+    assert_eq!(loop_items[0]["item"], "foo");
+    assert_eq!(loop_items[1]["item"], "bar");
+    assert_eq!(loop_items[1]["output"], "bar");
+
+    let handler_starts = get_events(&events, "handler_start");
+    assert_eq!(handler_starts.len(), 1);
+    assert_eq!(handler_starts[0]["task"], "handler:handler");
+}
+
+#[test]
+fn test_jsonl_failed() {
+    let script = r#"
+- name: retry
+  command: echo retry
+  register: result
+  until: false
+  retries: 2
+"#;
+    let (stdout, stderr) = execute_rash(&["--output", "jsonl", "-s", script]);
+    let events = parse_events(&stdout);
+
+    let retries = get_events(&events, "task_retry");
+    assert_eq!(retries.len(), 2);
+    assert_eq!(retries[1]["attempt"], 2);
+    assert_eq!(retries[1]["retries"], 2);
+
+    let task_end = get_events(&events, "task_end")[0];
+    assert_eq!(task_end["status"], "failed");
+    assert_eq!(task_end["failed"], true);
+    assert_eq!(
+        task_end["error"],
+        "Task failed: until condition not satisfied after 2 retries"
+    );
+    assert_eq!(events.last().unwrap()["recap"]["failed"], 1);
+    assert!(stderr.contains("until condition not satisfied"));
+}
+
+#[test]
+fn test_jsonl_diff() {
+    let dir = tempdir().unwrap();
+    let file_path = dir.path().join("file.txt");
+    fs::write(&file_path, "foo\n").unwrap();
+    let script = format!(
+        r#"
+- copy:
+    content: "bar\n"
+    dest: {}
+"#,
+        file_path.display()
+    );
+
+    let (stdout, _stderr) = execute_rash(&["--output", "jsonl", "--diff", "-s", &script]);
+    let task_end = parse_events(&stdout)
+        .into_iter()
+        .find(|e| e["event"] == "task_end")
+        .unwrap();
+    assert_eq!(
+        task_end["diff"],
+        format!(
+            "--- before: {path}\n+++ after: {path}\n@@ -1 +1 @@\n-foo\n+bar\n",
+            path = file_path.display()
+        )
+    );
+
+    fs::write(&file_path, "foo\n").unwrap();
+    let (stdout, _stderr) = execute_rash(&["--output", "jsonl", "-s", &script]);
+    let task_end = parse_events(&stdout)
+        .into_iter()
+        .find(|e| e["event"] == "task_end")
+        .unwrap();
+    assert_eq!(task_end["diff"], Value::Null);
+}
+
+#[test]
+fn test_jsonl_no_log() {
+    let script = r#"
+- name: secret loop
+  debug:
+    msg: "{{ item }}"
+  loop:
+    - my-secret-item
+  no_log: true
+"#;
+    let (stdout, _stderr) = execute_rash(&["--output", "jsonl", "-s", script]);
+
+    assert!(!stdout.contains("my-secret-item"));
+    let events = parse_events(&stdout);
+    let loop_item = get_events(&events, "loop_item")[0];
+    assert!(
+        loop_item["item"]
+            .as_str()
+            .unwrap()
+            .contains("the output has been hidden")
+    );
+}
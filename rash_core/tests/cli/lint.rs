@@ -0,0 +1,151 @@
+use super::execute_rash;
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use tempfile::tempdir;
+
+fn rash_status(args: &[&str]) -> i32 {
+    Command::new(Path::new(env!("CARGO_BIN_EXE_rash")))
+        .args(args)
+        .output()
+        .unwrap()
+        .status
+        .code()
+        .unwrap()
+}
+
+#[test]
+fn test_syntax_check_ok() {
+    let script = r#"
+- name: not executed
+  debug:
+    msg: "{{ rash.path }} executed"
+  when: rash.check_mode == false
+"#;
+    let (stdout, stderr) = execute_rash(&["--syntax-check", "-s", script]);
+
+    assert!(stderr.is_empty(), "stderr should be empty, got: {stderr}");
+    assert!(!stdout.contains("executed"));
+    assert_eq!(rash_status(&["--syntax-check", "-s", script]), 0);
+}
+
+#[test]
+fn test_syntax_check_problems() {
+    let script = r#"
+- name: unknown param
+  command:
+    cmd: ls
+    foo: bar
+  notify: restart
+
+- name: invalid expression
+  debug:
+    msg: "{{ foo }}"
+  when: foo ==
+"#;
+    let (stdout, stderr) = execute_rash(&["--syntax-check", "-s", script]);
+
+    assert!(stdout.is_empty(), "stdout should be empty, got: {stdout}");
+    assert!(stderr.contains("rash:3: Invalid params for module 'command': unknown field `foo`"));
+    assert!(stderr.contains("rash:6: Handler 'restart' not found"));
+    assert!(stderr.contains("rash:11: syntax error"));
+    assert_eq!(rash_status(&["--syntax-check", "-s", script]), 1);
+}
+
+#[test]
+fn test_lint_include() {
+    let dir = tempdir().unwrap();
+    let included_path = dir.path().join("included.rh");
+    fs::write(
+        &included_path,
+        r#"
+- name: included task
+  file:
+    path: /tmp/foo
+    state: unknown
+"#,
+    )
+    .unwrap();
+    let script_path = dir.path().join("main.rh");
+    fs::write(
+        &script_path,
+        r#"#!/usr/bin/env rash
+tasks:
+  - include: "{{ rash.dir }}/included.rh"
+    notify: restart
+
+handlers:
+  - name: restart
+    debug:
+      msg: restarted
+"#,
+    )
+    .unwrap();
+
+    let script_path = script_path.to_str().unwrap();
+    let (_, stderr) = execute_rash(&["lint", script_path]);
+
+    assert!(stderr.contains(&format!(
+        "{}:3: Invalid params for module 'file': unknown variant `unknown`",
+        included_path.display()
+    )));
+    assert!(!stderr.contains("main.rh"));
+    assert_eq!(rash_status(&["lint", script_path]), 1);
+}
+
+#[test]
+fn test_lint_multiple_files() {
+    let dir = tempdir().unwrap();
+    let valid_path = dir.path().join("valid.rh");
+    fs::write(&valid_path, "- debug:\n    msg: hi\n").unwrap();
+    let invalid_path = dir.path().join("invalid.rh");
+    fs::write(&invalid_path, "- debug:\n    msg: hi\n  foo: bar\n").unwrap();
+
+    let valid_path = valid_path.to_str().unwrap();
+    let invalid_path = invalid_path.to_str().unwrap();
+    let (_, stderr) = execute_rash(&["lint", valid_path, invalid_path]);
+
+    assert!(!stderr.contains(valid_path));
+    assert!(stderr.contains(&format!("{invalid_path}:1: ")));
+    assert_eq!(rash_status(&["lint", valid_path]), 0);
+    assert_eq!(rash_status(&["lint", valid_path, invalid_path]), 1);
+}
+
+#[test]
+fn test_script_named_lint() {
+    let dir = tempdir().unwrap();
+    fs::write(
+        dir.path().join("lint"),
+        r#"
+- debug:
+    msg: "script {{ rash.args | join(',') }} executed"
+"#,
+    )
+    .unwrap();
+
+    let output = Command::new(Path::new(env!("CARGO_BIN_EXE_rash")))
+        .arg("lint")
+        .current_dir(dir.path())
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("script  executed"));
+
+    let output = Command::new(Path::new(env!("CARGO_BIN_EXE_rash")))
+        .args(["./lint", "foo"])
+        .current_dir(dir.path())
+        .output()
+        .unwrap();
+    assert!(String::from_utf8_lossy(&output.stdout).contains("script foo executed"));
+
+    // valid command arguments run the command
+    let output = Command::new(Path::new(env!("CARGO_BIN_EXE_rash")))
+        .args(["lint", "lint"])
+        .current_dir(dir.path())
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert!(!String::from_utf8_lossy(&output.stdout).contains("executed"));
+}
@@ -0,0 +1,132 @@
+use super::execute_rash;
+
+use std::fs;
+
+use tempfile::tempdir;
+
+#[test]
+fn test_loop_control_loop_var_and_index_var() {
+    let script = r#"
+- debug:
+    msg: "{{ idx }}:{{ package }}"
+  loop:
+    - nginx
+    - curl
+  loop_control:
+    loop_var: package
+    index_var: idx
+"#;
+    let (stdout, stderr) = execute_rash(&["-s", script]);
+
+    assert!(stderr.is_empty(), "stderr should be empty, got: {stderr}");
+    assert!(stdout.contains("ok: 0:nginx"));
+    assert!(stdout.contains("ok: 1:curl"));
+}
+
+#[test]
+fn test_loop_control_nested_include() {
+    let dir = tempdir().unwrap();
+    let include_path = dir.path().join("inner.rh");
+    fs::write(
+        &include_path,
+        r#"
+- debug:
+    msg: "{{ outer }}-{{ item }}"
+  loop: [x, y]
+"#,
+    )
+    .unwrap();
+
+    let script = format!(
+        r#"
+- include: {}
+  loop: [a, b]
+  loop_control:
+    loop_var: outer
+"#,
+        include_path.display()
+    );
+    let (stdout, stderr) = execute_rash(&["-s", &script]);
+
+    assert!(stderr.is_empty(), "stderr should be empty, got: {stderr}");
+    for expected in ["a-x", "a-y", "b-x", "b-y"] {
+        assert!(
+            stdout.contains(&format!("ok: {expected}")),
+            "missing {expected}"
+        );
+    }
+}
+
+#[test]
+fn test_loop_control_label() {
+    let script = r#"
+- name: install
+  debug:
+    msg: "{{ item.version }}"
+  loop:
+    - name: nginx
+      version: "1.25"
+  loop_control:
+    label: "{{ item.name }}"
+"#;
+    let (stdout, stderr) = execute_rash(&["-s", script]);
+
+    assert!(stderr.is_empty(), "stderr should be empty, got: {stderr}");
+    assert!(stdout.contains("TASK [install] - (nginx) - "));
+    assert!(stdout.contains("ok: 1.25"));
+}
+
+#[test]
+fn test_loop_control_extended() {
+    let script = r#"
+- debug:
+    msg: "{{ rash_loop.index }}/{{ rash_loop.length }} first={{ rash_loop.first }} last={{ rash_loop.last }} prev={{ rash_loop.previtem | default('-') }} next={{ rash_loop.nextitem | default('-') }}"
+  loop: [a, b, c]
+  loop_control:
+    extended: true
+"#;
+    let (stdout, stderr) = execute_rash(&["-s", script]);
+
+    assert!(stderr.is_empty(), "stderr should be empty, got: {stderr}");
+    assert!(stdout.contains("ok: 1/3 first=True last=False prev=- next=b"));
+    assert!(stdout.contains("ok: 2/3 first=False last=False prev=a next=c"));
+    assert!(stdout.contains("ok: 3/3 first=False last=True prev=b next=-"));
+}
+
+#[test]
+fn test_loop_control_until() {
+    let script = r#"
+- debug:
+    msg: "retried {{ number }}"
+  loop: [1, 2]
+  loop_control:
+    loop_var: number
+  until: true
+"#;
+    let (stdout, stderr) = execute_rash(&["-s", script]);
+
+    assert!(stderr.is_empty(), "stderr should be empty, got: {stderr}");
+    assert!(stdout.contains("ok: retried 1"));
+    assert!(stdout.contains("ok: retried 2"));
+}
+
+#[test]
+fn test_loop_control_async() {
+    let dir = tempdir().unwrap();
+    let script = format!(
+        r#"
+- command: "touch {}/{{{{ file }}}}"
+  loop: [foo, boo]
+  loop_control:
+    loop_var: file
+  async: 10
+  poll: 1
+"#,
+        dir.path().display()
+    );
+    let (_, stderr) = execute_rash(&["-s", &script]);
+
+    assert!(stderr.is_empty(), "stderr should be empty, got: {stderr}");
+    assert!(dir.path().join("foo").exists());
+    assert!(dir.path().join("boo").exists());
+}
@@ -8,9 +8,43 @@ mod args;
 #[cfg(not(all(target_arch = "aarch64", target_os = "linux")))]
 mod become_method;
 #[cfg(not(all(target_arch = "aarch64", target_os = "linux")))]
+mod connection;
+#[cfg(not(all(target_arch = "aarch64", target_os = "linux")))]
+mod dynamic_modules;
+#[cfg(not(all(target_arch = "aarch64", target_os = "linux")))]
 mod environment;
 #[cfg(not(all(target_arch = "aarch64", target_os = "linux")))]
+mod exec_controls;
+#[cfg(not(all(target_arch = "aarch64", target_os = "linux")))]
+mod header;
+#[cfg(not(all(target_arch = "aarch64", target_os = "linux")))]
+mod jinja_tests;
+#[cfg(not(all(target_arch = "aarch64", target_os = "linux")))]
+mod jobs;
+#[cfg(not(all(target_arch = "aarch64", target_os = "linux")))]
+mod jsonl;
+#[cfg(not(all(target_arch = "aarch64", target_os = "linux")))]
+mod lint;
+#[cfg(not(all(target_arch = "aarch64", target_os = "linux")))]
+mod loop_control;
+#[cfg(not(all(target_arch = "aarch64", target_os = "linux")))]
 mod modules;
+#[cfg(not(all(target_arch = "aarch64", target_os = "linux")))]
+mod no_log;
+#[cfg(not(all(target_arch = "aarch64", target_os = "linux")))]
+mod parallel;
+#[cfg(not(all(target_arch = "aarch64", target_os = "linux")))]
+mod push;
+#[cfg(not(all(target_arch = "aarch64", target_os = "linux")))]
+mod recap;
+#[cfg(not(all(target_arch = "aarch64", target_os = "linux")))]
+mod register;
+#[cfg(not(all(target_arch = "aarch64", target_os = "linux")))]
+mod resume;
+#[cfg(not(all(target_arch = "aarch64", target_os = "linux")))]
+mod tags;
+#[cfg(not(all(target_arch = "aarch64", target_os = "linux")))]
+mod vault;
 
 use std::env;
 use std::iter;
@@ -0,0 +1,55 @@
+use crate::cli::modules::run_test;
+
+#[test]
+fn test_gather_facts() {
+    let script_text = r#"
+#!/usr/bin/env rash
+- gather_facts:
+
+- debug:
+    msg: "{{ facts.system }} {{ facts.processor_count > 0 }} {{ facts.mounts | length > 0 }}"
+"#;
+
+    let args = [];
+    let (stdout, stderr) = run_test(script_text, &args);
+
+    assert!(stderr.is_empty(), "stderr should be empty: {stderr}");
+    assert!(stdout.contains("Linux True True"), "stdout: {stdout}");
+}
+
+#[test]
+fn test_gather_facts_subset() {
+    let script_text = r#"
+#!/usr/bin/env rash
+- gather_facts:
+    gather_subset:
+      - min
+      - "!user"
+
+- debug:
+    msg: "{{ facts.architecture is defined }} {{ facts.user_uid is defined }} {{ facts.mounts is defined }}"
+"#;
+
+    let args = [];
+    let (stdout, stderr) = run_test(script_text, &args);
+
+    assert!(stderr.is_empty(), "stderr should be empty: {stderr}");
+    assert!(stdout.contains("True False False"), "stdout: {stdout}");
+}
+
+#[test]
+fn test_gather_facts_invalid_subset() {
+    let script_text = r#"
+#!/usr/bin/env rash
+- gather_facts:
+    gather_subset: foo
+"#;
+
+    let args = [];
+    let (_, stderr) = run_test(script_text, &args);
+
+    assert!(
+        stderr.contains("Invalid gather_subset `foo`"),
+        "stderr: {stderr}"
+    );
+}
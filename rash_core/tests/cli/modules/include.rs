@@ -13,7 +13,7 @@ fn test_include_not_exists() {
     let (stdout, stderr) = run_test(script_text, &[]);
 
     assert!(stdout.contains("- 1 to go - "));
-    assert!(stderr.contains("[ERROR] Error reading file: Os { code: 2, kind: NotFound, message: \"No such file or directory\" }\n"));
+    assert!(stderr.contains("/file_not_exists.rh: No such file or directory (os error 2)\n"));
 }
 
 #[test]
@@ -44,3 +44,112 @@ fn test_include() {
     assert!(stdout.contains("lib.rh:assert] - 1 to go - "));
     assert!(stderr.is_empty());
 }
+
+#[test]
+fn test_include_vars_apply_loop() {
+    let script_content = r#"#!/usr/bin/env rash
+- name: Add users
+  include:
+    file: user.rh
+    vars:
+      shell: /bin/bash
+    apply:
+      tags: users
+      when: item != "skipped"
+  loop:
+    - alice
+    - skipped
+    - bob
+
+- debug:
+    msg: "shell is {{ shell | default('undefined') }}"
+    "#;
+
+    let user_content = r#"
+- debug:
+    msg: "{{ item }} uses {{ shell }}"
+    "#;
+
+    let scripts = HashMap::from([("script.rh", script_content), ("user.rh", user_content)]);
+    let (stdout, stderr) = run_tests(scripts.clone(), "script.rh", &[]);
+
+    assert!(stdout.contains("alice uses /bin/bash"));
+    assert!(stdout.contains("bob uses /bin/bash"));
+    assert!(!stdout.contains("skipped uses"));
+    assert!(stdout.contains("shell is undefined"));
+    assert!(stderr.is_empty());
+
+    let (stdout, stderr) = run_tests(scripts, "script.rh", &["--skip-tags", "users"]);
+    assert!(!stdout.contains("uses /bin/bash"));
+    assert!(stdout.contains("shell is undefined"));
+    assert!(stderr.is_empty());
+}
+
+#[test]
+fn test_import() {
+    let script_content = r#"#!/usr/bin/env rash
+- import:
+    file: lib.rh
+    vars:
+      greeting: hello
+    apply:
+      tags: lib
+
+- debug:
+    msg: main
+    "#;
+
+    let lib_content = r#"
+- name: Greet
+  debug:
+    msg: "{{ greeting }} from {{ rash.path | basename }}"
+    "#;
+
+    let scripts = HashMap::from([("script.rh", script_content), ("lib.rh", lib_content)]);
+    let (stdout, stderr) = run_tests(scripts.clone(), "script.rh", &[]);
+
+    assert!(stdout.contains("lib.rh:Greet] - 2 to go - "));
+    assert!(stdout.contains("hello from lib.rh"));
+    assert!(stderr.is_empty());
+
+    let (stdout, _) = run_tests(scripts.clone(), "script.rh", &["--list-tasks"]);
+    let lines = stdout.lines().collect::<Vec<_>>();
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].ends_with("lib.rh:2\tGreet"));
+    assert!(lines[1].ends_with("script.rh:9\tdebug"));
+
+    let (stdout, _) = run_tests(scripts, "script.rh", &["--list-tasks", "--tags", "lib"]);
+    assert_eq!(stdout.lines().count(), 1);
+}
+
+#[test]
+fn test_import_circular() {
+    let script_content = r#"#!/usr/bin/env rash
+- import: lib.rh
+    "#;
+
+    let lib_content = r#"
+- import: script.rh
+    "#;
+
+    let scripts = HashMap::from([("script.rh", script_content), ("lib.rh", lib_content)]);
+    let (stdout, stderr) = run_tests(scripts, "script.rh", &[]);
+
+    assert!(stdout.is_empty());
+    assert!(stderr.contains("[ERROR] Circular import of "));
+    assert!(stderr.ends_with("/script.rh\n"));
+}
+
+#[test]
+fn test_import_in_block() {
+    let script_content = r#"#!/usr/bin/env rash
+- block:
+    - import: lib.rh
+    "#;
+
+    let (_, stderr) = run_test(script_content, &[]);
+
+    assert!(
+        stderr.contains("import is only supported in task lists of files, use include instead\n")
+    );
+}
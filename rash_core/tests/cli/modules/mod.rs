@@ -24,6 +24,7 @@ mod fail;
 mod fail2ban;
 mod firewalld;
 mod flatpak;
+mod gather_facts;
 mod gem;
 mod git;
 mod group;
@@ -0,0 +1,63 @@
+use super::execute_rash;
+
+const SCRIPT: &str = r#"
+- name: hidden task
+  debug:
+    msg: hidden output
+  no_log: true
+
+- name: hidden block
+  block:
+    - debug:
+        msg: hidden block output
+  no_log: true
+
+- name: hidden parallel
+  parallel:
+    - debug:
+        msg: hidden parallel output
+  no_log: true
+
+- name: visible task
+  debug:
+    msg: visible output
+"#;
+
+#[test]
+fn test_no_log() {
+    let (stdout, _stderr) = execute_rash(&["-s", SCRIPT]);
+
+    assert!(!stdout.contains("hidden output"));
+    assert!(!stdout.contains("hidden block output"));
+    assert!(!stdout.contains("hidden parallel output"));
+    assert!(stdout.contains("visible output"));
+    assert!(stdout.contains("'no_log: true'"));
+}
+
+#[test]
+fn test_no_log_json() {
+    let (stdout, _stderr) = execute_rash(&["--output", "json", "-s", SCRIPT]);
+
+    assert!(!stdout.contains("hidden output"));
+    assert!(!stdout.contains("hidden block output"));
+    assert!(!stdout.contains("hidden parallel output"));
+    assert!(stdout.contains(r#""output":"visible output""#));
+}
+
+#[test]
+fn test_no_log_diff() {
+    let dir = tempfile::tempdir().unwrap();
+    let dest = dir.path().join("file.txt");
+    let script = format!(
+        r#"
+- copy:
+    content: hidden content
+    dest: {}
+  no_log: true
+"#,
+        dest.display()
+    );
+    let (stdout, _stderr) = execute_rash(&["--diff", "-s", &script]);
+
+    assert!(!stdout.contains("hidden content"));
+}
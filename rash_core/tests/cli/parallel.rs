@@ -0,0 +1,133 @@
+use super::execute_rash;
+
+use std::time::{Duration, Instant};
+
+#[test]
+fn test_parallel() {
+    let script = r#"
+- name: parallel
+  parallel:
+    - name: slow
+      command: sh -c "sleep 0.5; echo slow"
+      register: slow
+    - name: fast
+      command: echo fast
+      register: fast
+
+- debug:
+    msg: "{{ slow.output | trim }} {{ fast.output | trim }}"
+"#;
+    let start = Instant::now();
+    let (stdout, stderr) = execute_rash(&["--script", script]);
+    assert!(stderr.is_empty(), "stderr should be empty, got: {stderr}");
+    assert!(stdout.contains("ok: slow fast\n"));
+
+    let fast_position = stdout.find("changed: fast").unwrap();
+    let slow_position = stdout.find("changed: slow").unwrap();
+    assert!(fast_position < slow_position);
+    assert!(start.elapsed() < Duration::from_secs(5));
+}
+
+#[test]
+fn test_parallel_throttle() {
+    let script = r#"
+- name: parallel
+  throttle: 1
+  parallel:
+    - name: slow
+      command: sh -c "sleep 0.5; echo slow"
+    - name: fast
+      command: echo fast
+"#;
+    let (stdout, stderr) = execute_rash(&["--script", script]);
+    assert!(stderr.is_empty(), "stderr should be empty, got: {stderr}");
+
+    let fast_position = stdout.find("changed: fast").unwrap();
+    let slow_position = stdout.find("changed: slow").unwrap();
+    assert!(slow_position < fast_position);
+}
+
+#[test]
+fn test_parallel_failed() {
+    let script = r#"
+- parallel:
+    - name: fail
+      fail:
+        msg: boom
+    - name: slow
+      command: sh -c "sleep 0.3; echo slow"
+
+- debug:
+    msg: not executed
+"#;
+    let (stdout, stderr) = execute_rash(&["--script", script, "--recap"]);
+    assert_eq!(stderr, "[ERROR] boom\n");
+    assert!(stdout.contains("changed: slow\n"));
+    assert!(stdout.contains("changed=1    failed=1"));
+    assert!(!stdout.contains("not executed"));
+}
+
+#[test]
+fn test_async_loop_throttle() {
+    let script = r#"
+- command: sh -c "sleep 0.3; echo {{ item }}"
+  loop: [a, b, c]
+  async: 10
+  poll: 1
+  throttle: 1
+"#;
+    let start = Instant::now();
+    let (_, stderr) = execute_rash(&["--script", script]);
+    assert!(stderr.is_empty(), "stderr should be empty, got: {stderr}");
+    assert!(start.elapsed() >= Duration::from_millis(900));
+}
+
+#[test]
+fn test_parallel_environment_rejected() {
+    let script = r#"
+- parallel:
+    - name: first
+      command: sh -c "sleep 0.2; echo $PARALLEL_VAR"
+      environment:
+        PARALLEL_VAR: first
+    - name: second
+      command: sh -c "sleep 0.2; echo $PARALLEL_VAR"
+      environment:
+        PARALLEL_VAR: second
+"#;
+    let (stdout, stderr) = execute_rash(&["--script", script]);
+    assert!(stderr.contains("environment is not supported in tasks executed by parallel"));
+    assert!(!stdout.contains("changed: first"));
+    assert!(!stdout.contains("changed: second"));
+}
+
+#[test]
+fn test_parallel_syscall_become_and_debugger_rejected() {
+    let script = r#"
+- parallel:
+    - block:
+        - command: "true"
+          become: true
+"#;
+    let (_, stderr) = execute_rash(&["--script", script]);
+    assert!(stderr.contains(
+        "become with become_method: syscall is not supported in tasks executed by parallel"
+    ));
+
+    let script = r#"
+- parallel:
+    - command: "true"
+      debugger: on_failed
+"#;
+    let (_, stderr) = execute_rash(&["--script", script]);
+    assert!(stderr.contains("debugger is not supported in tasks executed by parallel"));
+
+    let script = r#"
+- parallel:
+    - command: "true"
+      become: true
+      become_method: sudo
+"#;
+    let (_, stderr) = execute_rash(&["--debugger", "--script", script]);
+    assert!(!stderr.contains("not supported"), "stderr: {stderr}");
+}
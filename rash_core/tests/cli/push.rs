@@ -0,0 +1,346 @@
+use super::execute_rash_with_env;
+
+use std::env;
+use std::fs;
+use std::net::{TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use tempfile::{TempDir, tempdir};
+
+const INVENTORY: &str = r#"
+vars:
+  greeting: hello
+hosts:
+  web1:
+    host: 127.0.0.1
+    port: 22
+  web2:
+    host: localhost
+    user: root
+    vars:
+      greeting: hi
+  db1:
+    host: 192.0.2.1
+"#;
+
+const SCRIPT: &str = r#"#!/usr/bin/env rash
+- name: greet
+  debug:
+    msg: "{{ greeting }} from {{ inventory_hostname }}: {{ file('files/data.txt') }}"
+
+- name: args
+  command: echo {{ rash.args | join(' ') }}
+  check_mode: false
+"#;
+
+/// Create script dir with the inventory, the script and a file used by it.
+fn create_script_dir() -> (TempDir, PathBuf, PathBuf) {
+    let dir = tempdir().unwrap();
+    let inventory_path = dir.path().join("hosts.yml");
+    fs::write(&inventory_path, INVENTORY).unwrap();
+    let script_path = dir.path().join("main.rh");
+    fs::write(&script_path, SCRIPT).unwrap();
+    fs::create_dir(dir.path().join("files")).unwrap();
+    fs::write(dir.path().join("files/data.txt"), "data").unwrap();
+    (dir, inventory_path, script_path)
+}
+
+/// Shared cache dir to upload the binary just once.
+fn get_cache_dir() -> PathBuf {
+    Path::new(env!("CARGO_TARGET_TMPDIR")).join("push_cache")
+}
+
+fn execute_rash_push(args: &[&str]) -> (String, String) {
+    execute_rash_with_env(
+        args,
+        &[("XDG_CACHE_HOME", get_cache_dir().to_str().unwrap())],
+    )
+}
+
+#[test]
+fn test_push() {
+    let (_dir, inventory_path, script_path) = create_script_dir();
+
+    let (stdout, stderr) = execute_rash_push(&[
+        "--inventory",
+        inventory_path.to_str().unwrap(),
+        "--hosts",
+        "web*",
+        script_path.to_str().unwrap(),
+        "foo",
+    ]);
+
+    assert!(stderr.is_empty(), "stderr should be empty, got: {stderr}");
+    assert!(stdout.contains("ok: [web1] hello from web1: data\n"));
+    assert!(stdout.contains("ok: [web2] hi from web2: data\n"));
+    assert!(stdout.contains("changed: [web1] foo\n"));
+    assert!(stdout.contains("PLAY RECAP"));
+    assert!(stdout.contains(
+        "web1 : unreachable=0    ok=2    changed=1    failed=0    skipped=0    rescued=0    ignored=0\n"
+    ));
+    assert!(stdout.contains("web2 : unreachable=0"));
+    assert!(!stdout.contains("db1"));
+
+    let cached_binaries = fs::read_dir(get_cache_dir().join("rash"))
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+        .collect::<Vec<_>>();
+    assert!(
+        cached_binaries.iter().any(|name| name.starts_with("rash-")),
+        "cached: {cached_binaries:?}"
+    );
+}
+
+#[test]
+fn test_push_unreachable() {
+    let (_dir, inventory_path, script_path) = create_script_dir();
+
+    let (stdout, stderr) = execute_rash_push(&[
+        "--inventory",
+        inventory_path.to_str().unwrap(),
+        "--check",
+        script_path.to_str().unwrap(),
+    ]);
+
+    assert_eq!(
+        stderr,
+        "[ERROR] [db1] ssh: connect to host 192.0.2.1 port 22: Connection refused\n"
+    );
+    assert!(stdout.contains("db1  : unreachable=1    ok=0"));
+    assert!(stdout.contains("web1 : unreachable=0    ok=2"));
+    assert!(stdout.contains("web2 : unreachable=0    ok=2"));
+}
+
+#[test]
+fn test_push_jsonl() {
+    let (_dir, inventory_path, script_path) = create_script_dir();
+
+    let (stdout, _) = execute_rash_push(&[
+        "--inventory",
+        inventory_path.to_str().unwrap(),
+        "--output",
+        "jsonl",
+        script_path.to_str().unwrap(),
+    ]);
+
+    let events = stdout
+        .lines()
+        .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+        .collect::<Vec<_>>();
+    let get_events = |host: &str, event: &str| {
+        events
+            .iter()
+            .filter(|e| e["host"] == host && e["event"] == event)
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(get_events("web1", "task_end").len(), 2);
+    assert_eq!(get_events("web2", "run_end")[0]["recap"]["ok"], 2);
+    assert_eq!(
+        get_events("db1", "host_unreachable")[0]["error"],
+        "ssh: connect to host 192.0.2.1 port 22: Connection refused"
+    );
+}
+
+#[test]
+fn test_push_profile() {
+    let (_dir, inventory_path, script_path) = create_script_dir();
+
+    let (stdout, _) = execute_rash_push(&[
+        "--inventory",
+        inventory_path.to_str().unwrap(),
+        "--hosts",
+        "web1",
+        "--profile",
+        script_path.to_str().unwrap(),
+    ]);
+
+    let profile = stdout.split("TASKS PROFILE").nth(1).unwrap();
+    let lines = profile.lines().skip(1).collect::<Vec<_>>();
+    assert_eq!(lines.len(), 2, "{profile}");
+    assert!(lines.iter().all(|line| line.starts_with("[web1] ")));
+    assert!(lines.iter().any(|line| line.contains("main.rh:greet ---")));
+}
+
+#[test]
+fn test_push_json_tasks() {
+    let (_dir, inventory_path, script_path) = create_script_dir();
+
+    let (stdout, _) = execute_rash_push(&[
+        "--inventory",
+        inventory_path.to_str().unwrap(),
+        "--hosts",
+        "web1",
+        "--output",
+        "json",
+        script_path.to_str().unwrap(),
+    ]);
+
+    let recap = serde_json::from_str::<serde_json::Value>(stdout.lines().last().unwrap()).unwrap();
+    let tasks = recap["hosts"]["web1"]["tasks"].as_array().unwrap();
+    assert_eq!(tasks.len(), 2);
+    assert_eq!(tasks[1]["status"], "changed");
+}
+
+#[test]
+fn test_push_no_hosts_matched() {
+    let (_dir, inventory_path, script_path) = create_script_dir();
+
+    let (stdout, stderr) = execute_rash_push(&[
+        "--inventory",
+        inventory_path.to_str().unwrap(),
+        "--hosts",
+        "cache*",
+        script_path.to_str().unwrap(),
+    ]);
+
+    assert!(stdout.is_empty());
+    assert_eq!(stderr, "[ERROR] No hosts in inventory matching: cache*\n");
+}
+
+/// `sshd` listening in a free port of 127.0.0.1, executed by the current user, who is authorized
+/// with a key generated for the test.
+struct Sshd {
+    process: Child,
+    port: u16,
+    dir: TempDir,
+}
+
+impl Sshd {
+    fn start() -> Self {
+        let dir = tempdir().unwrap();
+        for key in ["host_key", "client_key"] {
+            let status = Command::new("ssh-keygen")
+                .args(["-q", "-t", "ed25519", "-N", "", "-f"])
+                .arg(dir.path().join(key))
+                .status()
+                .unwrap();
+            assert!(status.success());
+        }
+
+        let port = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let config_path = dir.path().join("sshd_config");
+        fs::write(
+            &config_path,
+            format!(
+                r#"ListenAddress 127.0.0.1
+Port {port}
+HostKey {dir}/host_key
+AuthorizedKeysFile {dir}/client_key.pub
+PidFile {dir}/sshd.pid
+SetEnv XDG_CACHE_HOME={dir}/remote_cache
+UsePAM no
+StrictModes no
+PasswordAuthentication no
+"#,
+                dir = dir.path().display()
+            ),
+        )
+        .unwrap();
+
+        // sshd must be executed with its absolute path
+        let sshd_path = ["/usr/sbin/sshd", "/usr/bin/sshd"]
+            .into_iter()
+            .map(Path::new)
+            .find(|path| path.exists())
+            .expect("sshd not found");
+        let process = Command::new(sshd_path)
+            .args(["-D", "-e", "-f"])
+            .arg(&config_path)
+            .stderr(Stdio::null())
+            .spawn()
+            .unwrap();
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while TcpStream::connect(("127.0.0.1", port)).is_err() {
+            assert!(
+                Instant::now() < deadline,
+                "sshd is not listening in port {port}"
+            );
+            thread::sleep(Duration::from_millis(50));
+        }
+
+        Sshd { process, port, dir }
+    }
+}
+
+impl Drop for Sshd {
+    fn drop(&mut self) {
+        let _ = self.process.kill();
+        let _ = self.process.wait();
+    }
+}
+
+#[test]
+#[ignore = "requires sshd and ssh-keygen, run with `--ignored`"]
+fn test_push_sshd() {
+    let sshd = Sshd::start();
+    let (dir, _, _) = create_script_dir();
+    let inventory_path = dir.path().join("sshd.yml");
+    fs::write(
+        &inventory_path,
+        format!(
+            r#"
+vars:
+  greeting: hello
+hosts:
+  local:
+    host: 127.0.0.1
+    port: {port}
+    identity_file: {identity_file}
+    ssh_args:
+      - -o
+      - StrictHostKeyChecking=no
+      - -o
+      - UserKnownHostsFile=/dev/null
+      - -o
+      - LogLevel=ERROR
+"#,
+            port = sshd.port,
+            identity_file = sshd.dir.path().join("client_key").display(),
+        ),
+    )
+    .unwrap();
+    let script_path = dir.path().join("sshd.rh");
+    fs::write(
+        &script_path,
+        r#"#!/usr/bin/env rash
+- name: greet
+  debug:
+    msg: "{{ greeting }} from {{ inventory_hostname }}: {{ file('files/data.txt') }}"
+
+- name: args
+  debug:
+    msg: "{{ rash.args | join('|') }}"
+"#,
+    )
+    .unwrap();
+
+    // use the real `ssh` instead of the mock
+    let path = env::var_os("PATH").unwrap();
+    let path =
+        env::join_paths(env::split_paths(&path).filter(|p| !p.ends_with("tests/mocks"))).unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_rash"))
+        .args(["--inventory", inventory_path.to_str().unwrap()])
+        .arg(&script_path)
+        .args(["foo bar", "it's", "$HOME"])
+        .env("PATH", path)
+        .env("XDG_CACHE_HOME", get_cache_dir())
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    assert!(stderr.is_empty(), "stderr should be empty, got: {stderr}");
+    assert!(stdout.contains("ok: [local] hello from local: data\n"));
+    assert!(stdout.contains("ok: [local] foo bar|it's|$HOME\n"));
+    assert!(stdout.contains("local : unreachable=0    ok=2"));
+    assert!(sshd.dir.path().join("remote_cache/rash").is_dir());
+}
@@ -0,0 +1,122 @@
+use super::execute_rash;
+
+use serde_json::Value;
+
+const SCRIPT: &str = r#"
+tasks:
+  - name: ok task
+    command: "true"
+    changed_when: false
+
+  - name: changed task
+    command: "true"
+    notify: handler
+
+  - name: skipped task
+    debug:
+      msg: skipped
+    when: false
+
+  - name: ignored task
+    command: "false"
+    ignore_errors: true
+
+  - name: rescued block
+    block:
+      - name: failed task
+        command: "false"
+    rescue:
+      - name: rescue task
+        debug:
+          msg: rescued
+
+handlers:
+  - name: handler
+    debug:
+      msg: handled
+"#;
+
+#[test]
+fn test_recap() {
+    let (stdout, _stderr) = execute_rash(&["--recap", "-s", SCRIPT]);
+
+    assert!(stdout.contains("PLAY RECAP"));
+    assert!(
+        stdout.contains("ok=4    changed=1    failed=0    skipped=1    rescued=1    ignored=1")
+    );
+    assert!(!stdout.contains("TASKS PROFILE"));
+}
+
+#[test]
+fn test_no_recap() {
+    let (stdout, _stderr) = execute_rash(&["-s", SCRIPT]);
+
+    assert!(!stdout.contains("PLAY RECAP"));
+}
+
+#[test]
+fn test_recap_failed() {
+    let script = r#"
+- name: ok task
+  debug:
+    msg: ok
+- name: failed task
+  command: "false"
+- name: not executed
+  debug:
+    msg: not executed
+"#;
+    let (stdout, stderr) = execute_rash(&["--recap", "-s", script]);
+
+    assert!(
+        stdout.contains("ok=1    changed=0    failed=1    skipped=0    rescued=0    ignored=0")
+    );
+    assert!(!stderr.is_empty());
+}
+
+#[test]
+fn test_profile() {
+    let (stdout, _stderr) = execute_rash(&["--profile", "-s", SCRIPT]);
+
+    let profile = stdout.split("TASKS PROFILE").nth(1).unwrap();
+    let lines = profile.lines().skip(1).collect::<Vec<_>>();
+    assert_eq!(lines.len(), 8);
+    assert!(lines.iter().all(|line| line.ends_with('s')));
+    assert!(lines.iter().any(|line| line.contains("rash:ok task ---")));
+    assert!(
+        lines
+            .iter()
+            .any(|line| line.starts_with("handler:handler ---"))
+    );
+}
+
+#[test]
+fn test_recap_json() {
+    let (stdout, _stderr) = execute_rash(&["--recap", "--output", "json", "-s", SCRIPT]);
+
+    let recap: Value = serde_json::from_str(stdout.lines().last().unwrap()).unwrap();
+    assert_eq!(
+        recap["recap"],
+        serde_json::json!({
+            "ok": 4,
+            "changed": 1,
+            "failed": 0,
+            "skipped": 1,
+            "rescued": 1,
+            "ignored": 1,
+        })
+    );
+    let tasks = recap["tasks"].as_array().unwrap();
+    assert!(tasks[0]["name"].as_str().unwrap().ends_with("rash:ok task"));
+    assert_eq!(tasks[0]["status"], "ok");
+    assert!(tasks[0]["duration"].as_f64().unwrap() >= 0.0);
+    assert!(tasks.iter().any(|task| task["status"] == "rescued"));
+}
+
+#[test]
+fn test_recap_raw() {
+    let (stdout, _stderr) = execute_rash(&["--recap", "--output", "raw", "-s", SCRIPT]);
+
+    assert!(!stdout.contains("PLAY RECAP"));
+    assert!(!stdout.contains("recap"));
+}
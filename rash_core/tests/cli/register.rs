@@ -0,0 +1,135 @@
+use super::execute_rash;
+
+use std::fs;
+
+use serde_json::Value;
+use tempfile::tempdir;
+
+#[test]
+fn test_register_command_result() {
+    let script = r#"
+- command: printf 'foo\nbar\n'
+  register: result
+
+- debug:
+    msg: "rc={{ result.rc }} lines={{ result.stdout_lines | join(',') }} stderr={{ result.stderr }}"
+"#;
+    let (stdout, stderr) = execute_rash(&["-s", script]);
+
+    assert!(stderr.is_empty(), "stderr: {stderr}");
+    assert!(stdout.contains("ok: rc=0 lines=foo,bar stderr=\n"));
+}
+
+#[test]
+fn test_register_ignored_error() {
+    let script = r#"
+- command: ls /nonexistent
+  register: result
+  ignore_errors: true
+
+- debug:
+    msg: "failed={{ result.failed }} changed={{ result.changed }}"
+
+- debug:
+    msg: "{{ result.msg }}"
+  when: result.failed
+"#;
+    let (stdout, stderr) = execute_rash(&["-s", script]);
+
+    assert!(stderr.is_empty(), "stderr: {stderr}");
+    assert!(stdout.contains("ok: failed=True changed=False\n"));
+    assert!(stdout.contains("ok: ls: cannot access '/nonexistent': No such file or directory"));
+}
+
+#[test]
+fn test_register_failed_command_result() {
+    let script = r#"
+- shell: echo out; echo err >&2; exit 3
+  register: result
+  ignore_errors: true
+
+- debug:
+    msg: "failed={{ result.failed }} rc={{ result.rc }} stdout={{ result.stdout | trim }} stderr={{ result.stderr | trim }}"
+
+- shell: exit 1
+  register: result
+  failed_when: result.rc > 1
+
+- debug:
+    msg: "failed={{ result.failed }} rc={{ result.rc }}"
+"#;
+    let (stdout, stderr) = execute_rash(&["-s", script]);
+
+    assert!(stderr.is_empty(), "stderr: {stderr}");
+    assert!(stdout.contains("ok: failed=True rc=3 stdout=out stderr=err\n"));
+    assert!(stdout.contains("ok: failed=False rc=1\n"));
+}
+
+#[test]
+fn test_register_failed_result_in_rescue() {
+    let script = r#"
+- block:
+    - shell: echo out; exit 2
+      register: result
+      failed_when: result.rc > 1
+  rescue:
+    - debug:
+        msg: "failed={{ result.failed }} rc={{ result.rc }} stdout={{ result.stdout | trim }} msg={{ result.msg }}"
+
+- block:
+    - command: ls /nonexistent
+      register: result
+  rescue:
+    - debug:
+        msg: "rc={{ result.rc }} stderr={{ result.stderr | trim }}"
+"#;
+    let (stdout, stderr) = execute_rash(&["-s", script]);
+
+    assert!(!stderr.contains("[ERROR]"), "stderr: {stderr}");
+    assert!(stdout.contains(
+        "ok: failed=True rc=2 stdout=out msg=failed_when condition `result.rc > 1` is true: out\n"
+    ));
+    assert!(
+        stdout.contains(
+            "ok: rc=2 stderr=ls: cannot access '/nonexistent': No such file or directory\n"
+        )
+    );
+}
+
+#[test]
+fn test_json_output_command_result() {
+    let (stdout, stderr) = execute_rash(&["--output", "json", "-s", "\n- command: echo foo"]);
+
+    assert!(stderr.is_empty(), "stderr: {stderr}");
+    let result: Value = serde_json::from_str(stdout.lines().next().unwrap()).unwrap();
+    assert_eq!(result["changed"], true);
+    assert_eq!(result["failed"], false);
+    assert_eq!(result["rc"], 0);
+    assert_eq!(result["stdout"], "foo\n");
+    assert_eq!(result["stdout_lines"], serde_json::json!(["foo"]));
+}
+
+#[test]
+fn test_diff_with_headers() {
+    let dir = tempdir().unwrap();
+    let file_path = dir.path().join("file.txt");
+    fs::write(&file_path, "foo\n").unwrap();
+    let script = format!(
+        r#"
+- lineinfile:
+    path: {}
+    line: bar
+  register: result
+
+- debug:
+    msg: "{{{{ result.diff.before | trim }}}} -> {{{{ result.diff.after | replace('\n', ' ') | trim }}}}"
+"#,
+        file_path.display()
+    );
+    let (stdout, stderr) = execute_rash(&["--diff", "-s", &script]);
+
+    assert!(stderr.is_empty(), "stderr: {stderr}");
+    assert!(stdout.contains(&format!("--- before: {}\n", file_path.display())));
+    assert!(stdout.contains(&format!("+++ after: {}\n", file_path.display())));
+    assert!(stdout.contains("ok: foo -> foo bar\n"));
+}
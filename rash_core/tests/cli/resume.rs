@@ -0,0 +1,153 @@
+use super::execute_rash_with_env;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use tempfile::{TempDir, tempdir};
+
+const SCRIPT: &str = r#"#!/usr/bin/env rash
+tasks:
+  - name: first
+    command: echo one
+    register: first
+    notify: handler
+
+  - name: fail
+    debug:
+      msg: "{{ file(rash.dir ~ '/flag') }}"
+
+  - name: after
+    debug:
+      msg: "{{ first.output | trim }}"
+
+handlers:
+  - name: handler
+    debug:
+      msg: handled
+"#;
+
+fn create_script_dir() -> (TempDir, PathBuf) {
+    let dir = tempdir().unwrap();
+    let script_path = dir.path().join("main.rh");
+    fs::write(&script_path, SCRIPT).unwrap();
+    (dir, script_path)
+}
+
+fn execute_rash_journaled(dir: &Path, args: &[&str]) -> (String, String) {
+    execute_rash_with_env(
+        args,
+        &[("XDG_STATE_HOME", dir.join("state").to_str().unwrap())],
+    )
+}
+
+#[test]
+fn test_resume() {
+    let (dir, script_path) = create_script_dir();
+    let script_path = script_path.to_str().unwrap();
+
+    let (stdout, stderr) = execute_rash_journaled(dir.path(), &[script_path]);
+    assert!(stdout.contains("changed: one\n"));
+    assert!(stdout.contains("to resume from the failed task, use: --resume\n"));
+    assert!(!stdout.contains("handled"));
+    assert!(stderr.starts_with("[ERROR]"));
+
+    fs::write(dir.path().join("flag"), "").unwrap();
+    let (stdout, stderr) = execute_rash_journaled(dir.path(), &["--resume", script_path]);
+    assert!(stderr.is_empty(), "stderr should be empty, got: {stderr}");
+    assert!(!stdout.contains(":first]"));
+    assert!(stdout.contains(":fail]"));
+    assert!(stdout.contains("ok: one\n"));
+    assert!(stdout.contains("ok: handled\n"));
+
+    let (stdout, stderr) = execute_rash_journaled(dir.path(), &["--resume", script_path]);
+    assert!(stdout.is_empty());
+    assert_eq!(
+        stderr,
+        "[ERROR] No journal found to resume, previous run finished or never started\n"
+    );
+}
+
+#[test]
+fn test_resume_script_changed() {
+    let (dir, script_path) = create_script_dir();
+    let script_path_str = script_path.to_str().unwrap();
+
+    execute_rash_journaled(dir.path(), &[script_path_str]);
+    fs::write(&script_path, SCRIPT.replace("echo one", "echo two")).unwrap();
+
+    let (stdout, stderr) = execute_rash_journaled(dir.path(), &["--resume", script_path_str]);
+    assert!(stdout.is_empty());
+    assert_eq!(
+        stderr,
+        "[ERROR] Script changed since the journal was written, refusing to resume\n"
+    );
+}
+
+#[test]
+fn test_resume_args_changed() {
+    let (dir, script_path) = create_script_dir();
+    let script_path = script_path.to_str().unwrap();
+
+    execute_rash_journaled(dir.path(), &["-e", "FOO=one", script_path]);
+
+    let (stdout, stderr) =
+        execute_rash_journaled(dir.path(), &["--resume", "-e", "FOO=two", script_path]);
+    assert!(stdout.is_empty());
+    assert_eq!(
+        stderr,
+        "[ERROR] Script arguments changed since the journal was written, refusing to resume\n"
+    );
+}
+
+#[test]
+fn test_resume_check_mode_not_journaled() {
+    let (dir, script_path) = create_script_dir();
+    let script_path = script_path.to_str().unwrap();
+
+    let (stdout, stderr) = execute_rash_journaled(dir.path(), &["--check", script_path]);
+    assert!(!stdout.contains("--resume"));
+    assert!(stderr.starts_with("[ERROR]"));
+
+    let (_, stderr) = execute_rash_journaled(dir.path(), &["--resume", script_path]);
+    assert_eq!(
+        stderr,
+        "[ERROR] No journal found to resume, previous run finished or never started\n"
+    );
+}
+
+#[test]
+fn test_resume_no_log_vars_not_journaled() {
+    let dir = tempdir().unwrap();
+    let script_path = dir.path().join("main.rh");
+    fs::write(
+        &script_path,
+        r#"#!/usr/bin/env rash
+- name: secret
+  command: echo journal-no-log-output
+  register: secret
+  no_log: true
+
+- name: fail
+  debug:
+    msg: "{{ file(rash.dir ~ '/flag') }}"
+
+- name: after
+  debug:
+    msg: "{{ secret is defined }}"
+"#,
+    )
+    .unwrap();
+    let script_path = script_path.to_str().unwrap();
+
+    let (stdout, _) = execute_rash_journaled(dir.path(), &[script_path]);
+    assert!(stdout.contains("--resume"));
+    for entry in fs::read_dir(dir.path().join("state/rash/journals")).unwrap() {
+        let content = fs::read_to_string(entry.unwrap().path()).unwrap();
+        assert!(!content.contains("journal-no-log-output"));
+    }
+
+    fs::write(dir.path().join("flag"), "").unwrap();
+    let (stdout, stderr) = execute_rash_journaled(dir.path(), &["--resume", script_path]);
+    assert!(stderr.contains("Vars not saved in the journal, they could contain secrets: secret"));
+    assert!(stdout.contains("ok: False\n"));
+}
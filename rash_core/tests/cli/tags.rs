@@ -0,0 +1,87 @@
+use super::execute_rash;
+
+const SCRIPT: &str = r#"
+- name: web task
+  debug:
+    msg: web output
+  tags: web
+
+- name: db block
+  block:
+    - name: db child
+      debug:
+        msg: db output
+  tags:
+    - db
+  always:
+    - name: db cleanup
+      debug:
+        msg: cleanup output
+
+- name: never task
+  debug:
+    msg: never output
+  tags: [never, debug]
+
+- name: always task
+  debug:
+    msg: always output
+  tags: always
+
+- name: untagged task
+  debug:
+    msg: untagged output
+"#;
+
+#[test]
+fn test_no_tags() {
+    let (stdout, stderr) = execute_rash(&["-s", SCRIPT]);
+
+    assert!(stderr.is_empty(), "stderr should be empty, got: {stderr}");
+    assert!(stdout.contains("web output"));
+    assert!(stdout.contains("db output"));
+    assert!(stdout.contains("cleanup output"));
+    assert!(stdout.contains("always output"));
+    assert!(stdout.contains("untagged output"));
+    assert!(!stdout.contains("never output"));
+}
+
+#[test]
+fn test_tags() {
+    let (stdout, _stderr) = execute_rash(&["--tags", "db", "-s", SCRIPT]);
+
+    assert!(!stdout.contains("web output"));
+    assert!(stdout.contains("db output"));
+    assert!(stdout.contains("cleanup output"));
+    assert!(stdout.contains("always output"));
+    assert!(!stdout.contains("untagged output"));
+    assert!(!stdout.contains("never output"));
+}
+
+#[test]
+fn test_tags_never() {
+    let (stdout, _stderr) = execute_rash(&["-t", "web,debug", "-s", SCRIPT]);
+
+    assert!(stdout.contains("web output"));
+    assert!(stdout.contains("never output"));
+    assert!(!stdout.contains("db output"));
+}
+
+#[test]
+fn test_skip_tags() {
+    let (stdout, _stderr) =
+        execute_rash(&["--skip-tags", "db", "--skip-tags", "always", "-s", SCRIPT]);
+
+    assert!(stdout.contains("web output"));
+    assert!(!stdout.contains("db output"));
+    assert!(!stdout.contains("cleanup output"));
+    assert!(!stdout.contains("always output"));
+    assert!(stdout.contains("untagged output"));
+}
+
+#[test]
+fn test_list_tags() {
+    let (stdout, _stderr) = execute_rash(&["--list-tags", "-s", SCRIPT]);
+
+    assert_eq!(stdout, "always\ndb\ndebug\nnever\nweb\n");
+}
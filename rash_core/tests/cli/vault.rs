@@ -0,0 +1,214 @@
+use super::{execute_rash, execute_rash_with_env};
+
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+use std::process::Command;
+
+use tempfile::tempdir;
+
+#[test]
+fn test_vault_encrypt_view_decrypt() {
+    let dir = tempdir().unwrap();
+    let password_file = dir.path().join("password");
+    fs::write(&password_file, "secret\n").unwrap();
+    let vars_file = dir.path().join("secrets.yml");
+    fs::write(&vars_file, "db_password: hunter2\n").unwrap();
+    let password_file = password_file.to_str().unwrap();
+    let vars_file_str = vars_file.to_str().unwrap();
+
+    let (_stdout, stderr) = execute_rash(&[
+        "vault",
+        "encrypt",
+        "--vault-password-file",
+        password_file,
+        vars_file_str,
+    ]);
+    assert!(stderr.is_empty(), "stderr should be empty, got: {stderr}");
+    let encrypted = fs::read_to_string(&vars_file).unwrap();
+    assert!(encrypted.starts_with("$RASH_VAULT;2;AES256-GCM;600000\n"));
+    assert!(!encrypted.contains("hunter2"));
+
+    let (_stdout, stderr) = execute_rash(&[
+        "vault",
+        "encrypt",
+        "--vault-password-file",
+        password_file,
+        vars_file_str,
+    ]);
+    assert!(stderr.contains("file is already encrypted"));
+
+    let (stdout, _stderr) = execute_rash(&[
+        "vault",
+        "view",
+        "--vault-password-file",
+        password_file,
+        vars_file_str,
+    ]);
+    assert_eq!(stdout, "db_password: hunter2\n");
+
+    let (_stdout, stderr) = execute_rash(&[
+        "vault",
+        "decrypt",
+        "--vault-password-file",
+        password_file,
+        vars_file_str,
+    ]);
+    assert!(stderr.is_empty(), "stderr should be empty, got: {stderr}");
+    assert_eq!(
+        fs::read_to_string(&vars_file).unwrap(),
+        "db_password: hunter2\n"
+    );
+}
+
+#[test]
+fn test_vault_rekey_and_edit() {
+    let dir = tempdir().unwrap();
+    let vars_file = dir.path().join("secrets.yml");
+    fs::write(&vars_file, "db_password: hunter2\n").unwrap();
+    let new_password_file = dir.path().join("new_password");
+    fs::write(&new_password_file, "new_secret").unwrap();
+    let vars_file_str = vars_file.to_str().unwrap();
+
+    let env = [("RASH_VAULT_PASSWORD", "secret")];
+    execute_rash_with_env(&["vault", "encrypt", vars_file_str], &env);
+    execute_rash_with_env(
+        &[
+            "vault",
+            "rekey",
+            "--new-vault-password-file",
+            new_password_file.to_str().unwrap(),
+            vars_file_str,
+        ],
+        &env,
+    );
+
+    let (_stdout, stderr) = execute_rash_with_env(&["vault", "view", vars_file_str], &env);
+    assert!(stderr.contains("wrong password or corrupted file"));
+
+    let env = [
+        ("RASH_VAULT_PASSWORD", "new_secret"),
+        ("EDITOR", "sed -i s/hunter2/hunter3/"),
+    ];
+    let (_stdout, stderr) = execute_rash_with_env(&["vault", "edit", vars_file_str], &env);
+    assert!(stderr.is_empty(), "stderr should be empty, got: {stderr}");
+    assert!(!fs::read_to_string(&vars_file).unwrap().contains("hunter"));
+
+    let (stdout, _stderr) = execute_rash_with_env(&["vault", "view", vars_file_str], &env);
+    assert_eq!(stdout, "db_password: hunter3\n");
+}
+
+#[test]
+fn test_vault_setup_and_file_lookup() {
+    let dir = tempdir().unwrap();
+    let password_file = dir.path().join("password");
+    fs::write(&password_file, "secret\n").unwrap();
+    let vars_file = dir.path().join("secrets.yml");
+    fs::write(&vars_file, "db_password: hunter2\n").unwrap();
+    let password_file = password_file.to_str().unwrap();
+    let vars_file_str = vars_file.to_str().unwrap();
+    execute_rash(&[
+        "vault",
+        "encrypt",
+        "--vault-password-file",
+        password_file,
+        vars_file_str,
+    ]);
+
+    let script = format!(
+        r#"
+- setup:
+    from:
+      - {vars_file_str}
+
+- debug:
+    msg: "{{{{ db_password }}}} {{{{ file('{vars_file_str}') }}}}"
+"#
+    );
+    let (stdout, stderr) = execute_rash(&["--vault-password-file", password_file, "-s", &script]);
+    assert!(stderr.is_empty(), "stderr should be empty, got: {stderr}");
+    assert!(stdout.contains("ok: ******** ********\n"));
+    assert!(!stdout.contains("hunter2"));
+
+    let (_stdout, stderr) = execute_rash(&["-s", &script]);
+    assert!(stderr.contains("Vault password not found"));
+}
+
+#[test]
+fn test_vault_vars_files_masked() {
+    let dir = tempdir().unwrap();
+    fs::write(
+        dir.path().join("secrets.yml"),
+        "api_token: vars-file-token\n",
+    )
+    .unwrap();
+    let env = [("RASH_VAULT_PASSWORD", "secret")];
+    execute_rash_with_env(
+        &[
+            "vault",
+            "encrypt",
+            dir.path().join("secrets.yml").to_str().unwrap(),
+        ],
+        &env,
+    );
+    let script_path = dir.path().join("main.rh");
+    fs::write(
+        &script_path,
+        r#"#!/usr/bin/env rash
+vars_files:
+  - secrets.yml
+tasks:
+  - debug:
+      msg: "token: {{ api_token }}"
+"#,
+    )
+    .unwrap();
+
+    let (stdout, stderr) = execute_rash_with_env(&[script_path.to_str().unwrap()], &env);
+    assert!(stderr.is_empty(), "stderr should be empty, got: {stderr}");
+    assert!(stdout.contains("ok: token: ********\n"));
+    assert!(!stdout.contains("vars-file-token"));
+}
+
+#[test]
+fn test_vault_encrypt_keeps_permissions() {
+    let dir = tempdir().unwrap();
+    let vars_file = dir.path().join("secrets.yml");
+    fs::write(&vars_file, "db_password: hunter2\n").unwrap();
+    fs::set_permissions(&vars_file, fs::Permissions::from_mode(0o640)).unwrap();
+
+    let (_stdout, stderr) = execute_rash_with_env(
+        &["vault", "encrypt", vars_file.to_str().unwrap()],
+        &[("RASH_VAULT_PASSWORD", "secret")],
+    );
+    assert!(stderr.is_empty(), "stderr should be empty, got: {stderr}");
+    assert!(
+        fs::read_to_string(&vars_file)
+            .unwrap()
+            .starts_with("$RASH_VAULT;2;AES256-GCM;600000\n")
+    );
+    let mode = fs::metadata(&vars_file).unwrap().permissions().mode();
+    assert_eq!(mode & 0o777, 0o640);
+    assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+}
+
+#[test]
+fn test_script_named_vault() {
+    let dir = tempdir().unwrap();
+    fs::write(
+        dir.path().join("vault"),
+        r#"
+- debug:
+    msg: "script {{ rash.args | join(',') }} executed"
+"#,
+    )
+    .unwrap();
+
+    let output = Command::new(Path::new(env!("CARGO_BIN_EXE_rash")))
+        .args(["vault", "foo"])
+        .current_dir(dir.path())
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("script foo executed"));
+}